@@ -129,6 +129,10 @@ client_request_definitions! {
         params: v2::ThreadLoadedListParams,
         response: v2::ThreadLoadedListResponse,
     },
+    ThreadSearch => "thread/search" {
+        params: v2::ThreadSearchParams,
+        response: v2::ThreadSearchResponse,
+    },
     SkillsList => "skills/list" {
         params: v2::SkillsListParams,
         response: v2::SkillsListResponse,
@@ -166,6 +170,16 @@ client_request_definitions! {
         response: v2::ListMcpServerStatusResponse,
     },
 
+    McpPromptList => "mcpPrompt/list" {
+        params: v2::ListMcpPromptsParams,
+        response: v2::ListMcpPromptsResponse,
+    },
+
+    McpPromptGet => "mcpPrompt/get" {
+        params: v2::GetMcpPromptParams,
+        response: v2::GetMcpPromptResponse,
+    },
+
     LoginAccount => "account/login/start" {
         params: v2::LoginAccountParams,
         response: v2::LoginAccountResponse,
@@ -560,6 +574,7 @@ server_notification_definitions! {
     FileChangeOutputDelta => "item/fileChange/outputDelta" (v2::FileChangeOutputDeltaNotification),
     McpToolCallProgress => "item/mcpToolCall/progress" (v2::McpToolCallProgressNotification),
     McpServerOauthLoginCompleted => "mcpServer/oauthLogin/completed" (v2::McpServerOauthLoginCompletedNotification),
+    McpServerToolsChanged => "mcpServer/toolsChanged" (v2::McpServerToolsChangedNotification),
     AccountUpdated => "account/updated" (v2::AccountUpdatedNotification),
     AccountRateLimitsUpdated => "account/rateLimits/updated" (v2::AccountRateLimitsUpdatedNotification),
     ReasoningSummaryTextDelta => "item/reasoning/summaryTextDelta" (v2::ReasoningSummaryTextDeltaNotification),
@@ -30,6 +30,8 @@ use codex_protocol::protocol::TokenUsageInfo as CoreTokenUsageInfo;
 use codex_protocol::user_input::UserInput as CoreUserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use mcp_types::ContentBlock as McpContentBlock;
+use mcp_types::Prompt as McpPrompt;
+use mcp_types::PromptMessage as McpPromptMessage;
 use mcp_types::Resource as McpResource;
 use mcp_types::ResourceTemplate as McpResourceTemplate;
 use mcp_types::Tool as McpTool;
@@ -103,6 +105,8 @@ pub enum CodexErrorInfo {
         #[ts(rename = "httpStatusCode")]
         http_status_code: Option<u16>,
     },
+    /// A configured session or turn token/cost budget was exceeded.
+    BudgetExceeded,
     Other,
 }
 
@@ -128,6 +132,7 @@ impl From<CoreCodexErrorInfo> for CodexErrorInfo {
             CoreCodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code } => {
                 CodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code }
             }
+            CoreCodexErrorInfo::BudgetExceeded => CodexErrorInfo::BudgetExceeded,
             CoreCodexErrorInfo::Other => CodexErrorInfo::Other,
         }
     }
@@ -174,6 +179,7 @@ pub enum SandboxMode {
     ReadOnly,
     WorkspaceWrite,
     DangerFullAccess,
+    RestrictedRead,
 }
 
 impl SandboxMode {
@@ -182,6 +188,7 @@ impl SandboxMode {
             SandboxMode::ReadOnly => CoreSandboxMode::ReadOnly,
             SandboxMode::WorkspaceWrite => CoreSandboxMode::WorkspaceWrite,
             SandboxMode::DangerFullAccess => CoreSandboxMode::DangerFullAccess,
+            SandboxMode::RestrictedRead => CoreSandboxMode::RestrictedRead,
         }
     }
 }
@@ -192,6 +199,7 @@ impl From<CoreSandboxMode> for SandboxMode {
             CoreSandboxMode::ReadOnly => SandboxMode::ReadOnly,
             CoreSandboxMode::WorkspaceWrite => SandboxMode::WorkspaceWrite,
             CoreSandboxMode::DangerFullAccess => SandboxMode::DangerFullAccess,
+            CoreSandboxMode::RestrictedRead => SandboxMode::RestrictedRead,
         }
     }
 }
@@ -567,6 +575,20 @@ pub enum SandboxPolicy {
         #[serde(default)]
         exclude_slash_tmp: bool,
     },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    RestrictedRead {
+        #[serde(default)]
+        readable_roots: Vec<AbsolutePathBuf>,
+        #[serde(default)]
+        writable_roots: Vec<AbsolutePathBuf>,
+        #[serde(default)]
+        network_access: bool,
+        #[serde(default)]
+        exclude_tmpdir_env_var: bool,
+        #[serde(default)]
+        exclude_slash_tmp: bool,
+    },
 }
 
 impl SandboxPolicy {
@@ -595,6 +617,19 @@ impl SandboxPolicy {
                 exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                 exclude_slash_tmp: *exclude_slash_tmp,
             },
+            SandboxPolicy::RestrictedRead {
+                readable_roots,
+                writable_roots,
+                network_access,
+                exclude_tmpdir_env_var,
+                exclude_slash_tmp,
+            } => codex_protocol::protocol::SandboxPolicy::RestrictedRead {
+                readable_roots: readable_roots.clone(),
+                writable_roots: writable_roots.clone(),
+                network_access: *network_access,
+                exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
+                exclude_slash_tmp: *exclude_slash_tmp,
+            },
         }
     }
 }
@@ -625,6 +660,19 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
             },
+            codex_protocol::protocol::SandboxPolicy::RestrictedRead {
+                readable_roots,
+                writable_roots,
+                network_access,
+                exclude_tmpdir_env_var,
+                exclude_slash_tmp,
+            } => SandboxPolicy::RestrictedRead {
+                readable_roots,
+                writable_roots,
+                network_access,
+                exclude_tmpdir_env_var,
+                exclude_slash_tmp,
+            },
         }
     }
 }
@@ -940,6 +988,54 @@ pub struct ListMcpServerStatusResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ListMcpPromptsParams {
+    /// Only list prompts published by this server. When omitted, every
+    /// enabled server is queried.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub server: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerPrompts {
+    pub server: String,
+    pub prompts: Vec<McpPrompt>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ListMcpPromptsResponse {
+    /// Prompts grouped by server, sorted by server name. Servers that do not
+    /// publish prompts are omitted.
+    pub data: Vec<McpServerPrompts>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GetMcpPromptParams {
+    pub server: String,
+    pub name: String,
+    #[serde(default)]
+    pub arguments: HashMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GetMcpPromptResponse {
+    pub description: Option<String>,
+    /// Rendered messages. Pass a `mcpPrompt` user input to `turn/start` to
+    /// have the server inject them into a turn.
+    pub messages: Vec<McpPromptMessage>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1211,6 +1307,56 @@ pub struct ThreadLoadedListResponse {
     pub next_cursor: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchParams {
+    /// Whitespace-separated terms; a thread matches when every term appears in
+    /// its user messages, agent messages, commands or touched files.
+    pub query: String,
+    /// Optional maximum number of threads; defaults to a reasonable server-side value.
+    pub limit: Option<u32>,
+    /// When true, cache extracted rollout text in an index under `CODEX_HOME`
+    /// so later searches only re-read rollouts that changed.
+    #[serde(default)]
+    pub use_index: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResponse {
+    /// Matching threads ordered newest first.
+    pub data: Vec<ThreadSearchResult>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchResult {
+    pub thread: Thread,
+    /// Excerpts around the matched terms, in rollout order.
+    pub matches: Vec<ThreadSearchMatch>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSearchMatch {
+    pub kind: ThreadSearchMatchKind,
+    pub snippet: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ThreadSearchMatchKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    File,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1478,6 +1624,11 @@ pub struct ReviewStartParams {
     /// detached on a new thread (returned in `reviewThreadId`).
     #[serde(default)]
     pub delivery: Option<ReviewDelivery>,
+
+    /// Restrict the review to these paths, relative to the thread's working
+    /// directory. When empty, all changes are reviewed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1513,6 +1664,11 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the commits in the range `from..to`.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { from: String, to: String },
+
     /// Arbitrary instructions, equivalent to the old free-form prompt.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -1545,10 +1701,25 @@ pub struct TurnInterruptResponse {}
 #[ts(tag = "type")]
 #[ts(export_to = "v2/")]
 pub enum UserInput {
-    Text { text: String },
-    Image { url: String },
-    LocalImage { path: PathBuf },
-    Skill { name: String, path: PathBuf },
+    Text {
+        text: String,
+    },
+    Image {
+        url: String,
+    },
+    LocalImage {
+        path: PathBuf,
+    },
+    Skill {
+        name: String,
+        path: PathBuf,
+    },
+    McpPrompt {
+        server: String,
+        name: String,
+        #[serde(default)]
+        arguments: HashMap<String, String>,
+    },
 }
 
 impl UserInput {
@@ -1558,6 +1729,15 @@ impl UserInput {
             UserInput::Image { url } => CoreUserInput::Image { image_url: url },
             UserInput::LocalImage { path } => CoreUserInput::LocalImage { path },
             UserInput::Skill { name, path } => CoreUserInput::Skill { name, path },
+            UserInput::McpPrompt {
+                server,
+                name,
+                arguments,
+            } => CoreUserInput::McpPrompt {
+                server,
+                name,
+                arguments,
+            },
         }
     }
 }
@@ -1569,6 +1749,15 @@ impl From<CoreUserInput> for UserInput {
             CoreUserInput::Image { image_url } => UserInput::Image { url: image_url },
             CoreUserInput::LocalImage { path } => UserInput::LocalImage { path },
             CoreUserInput::Skill { name, path } => UserInput::Skill { name, path },
+            CoreUserInput::McpPrompt {
+                server,
+                name,
+                arguments,
+            } => UserInput::McpPrompt {
+                server,
+                name,
+                arguments,
+            },
             _ => unreachable!("unsupported user input variant"),
         }
     }
@@ -1948,6 +2137,13 @@ pub struct McpToolCallProgressNotification {
     pub turn_id: String,
     pub item_id: String,
     pub message: String,
+    /// Progress so far; absent when the update is a log message from the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub progress: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub total: Option<f64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1961,6 +2157,16 @@ pub struct McpServerOauthLoginCompletedNotification {
     pub error: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerToolsChangedNotification {
+    pub thread_id: String,
+    pub server: String,
+    /// Tools now available from the server, after `enabled_tools`/`disabled_tools` filtering.
+    pub tools: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -35,7 +35,9 @@ use codex_app_server_protocol::InterruptConversationResponse;
 use codex_app_server_protocol::ItemCompletedNotification;
 use codex_app_server_protocol::ItemStartedNotification;
 use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::McpServerToolsChangedNotification;
 use codex_app_server_protocol::McpToolCallError;
+use codex_app_server_protocol::McpToolCallProgressNotification;
 use codex_app_server_protocol::McpToolCallResult;
 use codex_app_server_protocol::McpToolCallStatus;
 use codex_app_server_protocol::PatchApplyStatus;
@@ -267,6 +269,19 @@ pub(crate) async fn apply_bespoke_event_handling(
                 .send_server_notification(ServerNotification::ItemStarted(notification))
                 .await;
         }
+        EventMsg::McpToolCallProgress(progress_event) => {
+            let notification = McpToolCallProgressNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_turn_id.clone(),
+                item_id: progress_event.call_id,
+                message: progress_event.message.unwrap_or_default(),
+                progress: progress_event.progress,
+                total: progress_event.total,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::McpToolCallProgress(notification))
+                .await;
+        }
         EventMsg::McpToolCallEnd(end_event) => {
             let notification = construct_mcp_tool_call_end_notification(
                 end_event,
@@ -307,6 +322,16 @@ pub(crate) async fn apply_bespoke_event_handling(
                 .send_server_notification(ServerNotification::DeprecationNotice(notification))
                 .await;
         }
+        EventMsg::McpToolsChanged(event) => {
+            let notification = McpServerToolsChangedNotification {
+                thread_id: conversation_id.to_string(),
+                server: event.server,
+                tools: event.tools,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::McpServerToolsChanged(notification))
+                .await;
+        }
         EventMsg::ReasoningContentDelta(event) => {
             let notification = ReasoningSummaryTextDeltaNotification {
                 thread_id: conversation_id.to_string(),
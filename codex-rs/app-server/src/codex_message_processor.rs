@@ -39,6 +39,8 @@ use codex_app_server_protocol::GetAuthStatusParams;
 use codex_app_server_protocol::GetAuthStatusResponse;
 use codex_app_server_protocol::GetConversationSummaryParams;
 use codex_app_server_protocol::GetConversationSummaryResponse;
+use codex_app_server_protocol::GetMcpPromptParams;
+use codex_app_server_protocol::GetMcpPromptResponse;
 use codex_app_server_protocol::GetUserAgentResponse;
 use codex_app_server_protocol::GetUserSavedConfigResponse;
 use codex_app_server_protocol::GitDiffToRemoteResponse;
@@ -48,6 +50,8 @@ use codex_app_server_protocol::InterruptConversationParams;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::ListConversationsParams;
 use codex_app_server_protocol::ListConversationsResponse;
+use codex_app_server_protocol::ListMcpPromptsParams;
+use codex_app_server_protocol::ListMcpPromptsResponse;
 use codex_app_server_protocol::ListMcpServerStatusParams;
 use codex_app_server_protocol::ListMcpServerStatusResponse;
 use codex_app_server_protocol::LoginAccountParams;
@@ -60,6 +64,7 @@ use codex_app_server_protocol::LogoutChatGptResponse;
 use codex_app_server_protocol::McpServerOauthLoginCompletedNotification;
 use codex_app_server_protocol::McpServerOauthLoginParams;
 use codex_app_server_protocol::McpServerOauthLoginResponse;
+use codex_app_server_protocol::McpServerPrompts;
 use codex_app_server_protocol::McpServerRefreshResponse;
 use codex_app_server_protocol::McpServerStatus;
 use codex_app_server_protocol::ModelListParams;
@@ -99,6 +104,11 @@ use codex_app_server_protocol::ThreadLoadedListResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
 use codex_app_server_protocol::ThreadRollbackParams;
+use codex_app_server_protocol::ThreadSearchMatch;
+use codex_app_server_protocol::ThreadSearchMatchKind;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use codex_app_server_protocol::ThreadSearchResult;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadStartResponse;
 use codex_app_server_protocol::ThreadStartedNotification;
@@ -121,8 +131,10 @@ use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::InitialHistory;
 use codex_core::NewThread;
 use codex_core::RolloutRecorder;
+use codex_core::SearchMatchKind;
 use codex_core::SessionMeta;
 use codex_core::ThreadManager;
+use codex_core::ThreadSearchOptions;
 use codex_core::auth::CLIENT_ID;
 use codex_core::auth::login_with_api_key;
 use codex_core::config::Config;
@@ -137,6 +149,7 @@ use codex_core::exec_env::create_env;
 use codex_core::features::Feature;
 use codex_core::find_thread_path_by_id_str;
 use codex_core::git_info::git_diff_to_remote;
+use codex_core::mcp::collect_mcp_prompts;
 use codex_core::mcp::collect_mcp_snapshot;
 use codex_core::mcp::group_tools_by_server;
 use codex_core::parse_cursor;
@@ -148,6 +161,7 @@ use codex_core::protocol::ReviewTarget as CoreReviewTarget;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::read_head_for_summary;
 use codex_core::sandboxing::SandboxPermissions;
+use codex_core::search_threads;
 use codex_feedback::CodexFeedback;
 use codex_login::ServerOptions as LoginServerOptions;
 use codex_login::ShutdownHandle;
@@ -307,6 +321,7 @@ impl CodexMessageProcessor {
 
     fn review_request_from_target(
         target: ApiReviewTarget,
+        paths: Vec<String>,
     ) -> Result<(ReviewRequest, String), JSONRPCErrorError> {
         fn invalid_request(message: String) -> JSONRPCErrorError {
             JSONRPCErrorError {
@@ -335,6 +350,14 @@ impl CodexMessageProcessor {
                     .filter(|t| !t.is_empty());
                 ApiReviewTarget::Commit { sha, title }
             }
+            ApiReviewTarget::CommitRange { from, to } => {
+                let from = from.trim().to_string();
+                let to = to.trim().to_string();
+                if from.is_empty() || to.is_empty() {
+                    return Err(invalid_request("from and to must not be empty".to_string()));
+                }
+                ApiReviewTarget::CommitRange { from, to }
+            }
             ApiReviewTarget::Custom { instructions } => {
                 let trimmed = instructions.trim().to_string();
                 if trimmed.is_empty() {
@@ -352,13 +375,20 @@ impl CodexMessageProcessor {
             ApiReviewTarget::UncommittedChanges => CoreReviewTarget::UncommittedChanges,
             ApiReviewTarget::BaseBranch { branch } => CoreReviewTarget::BaseBranch { branch },
             ApiReviewTarget::Commit { sha, title } => CoreReviewTarget::Commit { sha, title },
+            ApiReviewTarget::CommitRange { from, to } => CoreReviewTarget::CommitRange { from, to },
             ApiReviewTarget::Custom { instructions } => CoreReviewTarget::Custom { instructions },
         };
 
-        let hint = codex_core::review_prompts::user_facing_hint(&core_target);
+        let paths: Vec<String> = paths
+            .into_iter()
+            .map(|path| path.trim().to_string())
+            .filter(|path| !path.is_empty())
+            .collect();
+        let hint = codex_core::review_prompts::user_facing_hint_with_paths(&core_target, &paths);
         let review_request = ReviewRequest {
             target: core_target,
             user_facing_hint: Some(hint.clone()),
+            paths,
         };
 
         Ok((review_request, hint))
@@ -391,6 +421,9 @@ impl CodexMessageProcessor {
             ClientRequest::ThreadLoadedList { request_id, params } => {
                 self.thread_loaded_list(request_id, params).await;
             }
+            ClientRequest::ThreadSearch { request_id, params } => {
+                self.thread_search(request_id, params).await;
+            }
             ClientRequest::SkillsList { request_id, params } => {
                 self.skills_list(request_id, params).await;
             }
@@ -433,6 +466,12 @@ impl CodexMessageProcessor {
             ClientRequest::McpServerStatusList { request_id, params } => {
                 self.list_mcp_server_status(request_id, params).await;
             }
+            ClientRequest::McpPromptList { request_id, params } => {
+                self.list_mcp_prompts(request_id, params).await;
+            }
+            ClientRequest::McpPromptGet { request_id, params } => {
+                self.get_mcp_prompt(request_id, params).await;
+            }
             ClientRequest::LoginAccount { request_id, params } => {
                 self.login_v2(request_id, params).await;
             }
@@ -1669,6 +1708,75 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn thread_search(&self, request_id: RequestId, params: ThreadSearchParams) {
+        let ThreadSearchParams {
+            query,
+            limit,
+            use_index,
+        } = params;
+
+        if query.trim().is_empty() {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "query must not be empty".to_string(),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        let limit = limit
+            .map(|value| value as usize)
+            .unwrap_or(THREAD_LIST_DEFAULT_LIMIT)
+            .clamp(1, THREAD_LIST_MAX_LIMIT);
+        let options = ThreadSearchOptions {
+            limit,
+            allowed_sources: INTERACTIVE_SESSION_SOURCES,
+            use_index,
+        };
+        let results = match search_threads(&self.config.codex_home, &query, options).await {
+            Ok(results) => results,
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to search threads: {err}"))
+                    .await;
+                return;
+            }
+        };
+
+        let fallback_provider = self.config.model_provider_id.as_str();
+        let mut data = Vec::with_capacity(results.hits.len());
+        for hit in results.hits {
+            let summary = match read_summary_from_rollout(&hit.path, fallback_provider).await {
+                Ok(summary) => summary,
+                Err(err) => {
+                    warn!("skipping search hit {}: {err}", hit.path.display());
+                    continue;
+                }
+            };
+            let matches = hit
+                .matches
+                .into_iter()
+                .map(|m| ThreadSearchMatch {
+                    kind: match m.kind {
+                        SearchMatchKind::UserMessage => ThreadSearchMatchKind::UserMessage,
+                        SearchMatchKind::AgentMessage => ThreadSearchMatchKind::AgentMessage,
+                        SearchMatchKind::Command => ThreadSearchMatchKind::Command,
+                        SearchMatchKind::File => ThreadSearchMatchKind::File,
+                    },
+                    snippet: m.snippet,
+                })
+                .collect();
+            data.push(ThreadSearchResult {
+                thread: summary_to_thread(summary),
+                matches,
+            });
+        }
+
+        self.outgoing
+            .send_response(request_id, ThreadSearchResponse { data })
+            .await;
+    }
+
     async fn thread_resume(&mut self, request_id: RequestId, params: ThreadResumeParams) {
         let ThreadResumeParams {
             thread_id,
@@ -2555,6 +2663,90 @@ impl CodexMessageProcessor {
         outgoing.send_response(request_id, response).await;
     }
 
+    async fn list_mcp_prompts(&self, request_id: RequestId, params: ListMcpPromptsParams) {
+        let outgoing = Arc::clone(&self.outgoing);
+        let config = match self.load_latest_config().await {
+            Ok(config) => config,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        if let Some(server) = params.server.as_deref()
+            && !config.mcp_servers.contains_key(server)
+        {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("unknown MCP server: {server}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        tokio::spawn(async move {
+            let prompts = collect_mcp_prompts(&config, params.server.as_deref()).await;
+            let mut data: Vec<McpServerPrompts> = prompts
+                .into_iter()
+                .map(|(server, mut prompts)| {
+                    prompts.sort_by(|a, b| a.name.cmp(&b.name));
+                    McpServerPrompts { server, prompts }
+                })
+                .collect();
+            data.sort_by(|a, b| a.server.cmp(&b.server));
+            outgoing
+                .send_response(request_id, ListMcpPromptsResponse { data })
+                .await;
+        });
+    }
+
+    async fn get_mcp_prompt(&self, request_id: RequestId, params: GetMcpPromptParams) {
+        let outgoing = Arc::clone(&self.outgoing);
+        let config = match self.load_latest_config().await {
+            Ok(config) => config,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        if !config.mcp_servers.contains_key(&params.server) {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("unknown MCP server: {}", params.server),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        tokio::spawn(async move {
+            let GetMcpPromptParams {
+                server,
+                name,
+                arguments,
+            } = params;
+            match codex_core::mcp::get_mcp_prompt(&config, &server, &name, &arguments).await {
+                Ok(result) => {
+                    let response = GetMcpPromptResponse {
+                        description: result.description,
+                        messages: result.messages,
+                    };
+                    outgoing.send_response(request_id, response).await;
+                }
+                Err(err) => {
+                    let error = JSONRPCErrorError {
+                        code: INTERNAL_ERROR_CODE,
+                        message: format!("failed to get MCP prompt {server}:{name}: {err:#}"),
+                        data: None,
+                    };
+                    outgoing.send_error(request_id, error).await;
+                }
+            }
+        });
+    }
+
     async fn handle_resume_conversation(
         &self,
         request_id: RequestId,
@@ -3458,6 +3650,7 @@ impl CodexMessageProcessor {
             thread_id,
             target,
             delivery,
+            paths,
         } = params;
         let (parent_thread_id, parent_thread) = match self.load_thread(&thread_id).await {
             Ok(v) => v,
@@ -3467,7 +3660,7 @@ impl CodexMessageProcessor {
             }
         };
 
-        let (review_request, display_text) = match Self::review_request_from_target(target) {
+        let (review_request, display_text) = match Self::review_request_from_target(target, paths) {
             Ok(value) => value,
             Err(err) => {
                 self.outgoing.send_error(request_id, err).await;
@@ -93,6 +93,7 @@ fn map_sandbox_mode_requirement_to_api(mode: CoreSandboxModeRequirement) -> Opti
         CoreSandboxModeRequirement::WorkspaceWrite => Some(SandboxMode::WorkspaceWrite),
         CoreSandboxModeRequirement::DangerFullAccess => Some(SandboxMode::DangerFullAccess),
         CoreSandboxModeRequirement::ExternalSandbox => None,
+        CoreSandboxModeRequirement::RestrictedRead => Some(SandboxMode::RestrictedRead),
     }
 }
 
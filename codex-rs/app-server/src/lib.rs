@@ -15,6 +15,8 @@ use codex_app_server_protocol::ConfigWarningNotification;
 use codex_app_server_protocol::JSONRPCMessage;
 use codex_core::check_execpolicy_for_warnings;
 use codex_feedback::CodexFeedback;
+use codex_otel::metrics::PrometheusRegistry;
+use codex_otel::metrics::PrometheusServer;
 use tokio::io::AsyncBufReadExt;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
@@ -154,6 +156,27 @@ pub async fn run_main(
         .with(otel_logger_layer)
         .with(otel_tracing_layer)
         .try_init();
+    // Optional Prometheus scrape endpoint. Keep the server alive until the
+    // app server shuts down.
+    let _prometheus_server = match config.otel.prometheus_listen_addr {
+        Some(addr) => {
+            let registry = PrometheusRegistry::new();
+            registry.install_global();
+            let server = PrometheusServer::start(addr, registry).map_err(|e| {
+                std::io::Error::new(
+                    e.kind(),
+                    format!("error starting prometheus endpoint on {addr}: {e}"),
+                )
+            })?;
+            info!(
+                "serving prometheus metrics on http://{}/metrics",
+                server.local_addr()
+            );
+            Some(server)
+        }
+        None => None,
+    };
+
     for warning in &config_warnings {
         match &warning.details {
             Some(details) => error!("{} {}", warning.summary, details),
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::GetMcpPromptParams;
+use codex_app_server_protocol::GetMcpPromptResponse;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::ListMcpPromptsParams;
+use codex_app_server_protocol::ListMcpPromptsResponse;
+use codex_app_server_protocol::McpServerPrompts;
+use codex_app_server_protocol::RequestId;
+use core_test_support::stdio_server_bin;
+use mcp_types::ContentBlock;
+use mcp_types::Prompt;
+use mcp_types::PromptArgument;
+use mcp_types::PromptMessage;
+use mcp_types::Role;
+use mcp_types::TextContent;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+const INVALID_REQUEST_ERROR_CODE: i64 = -32600;
+
+fn write_mcp_config(codex_home: &Path) -> Result<()> {
+    let command = stdio_server_bin()?;
+    std::fs::write(
+        codex_home.join("config.toml"),
+        format!(
+            r#"
+[mcp_servers.docs]
+command = {command:?}
+"#
+        ),
+    )?;
+    Ok(())
+}
+
+fn text_message(role: Role, text: &str) -> PromptMessage {
+    PromptMessage {
+        content: ContentBlock::TextContent(TextContent {
+            annotations: None,
+            text: text.to_string(),
+            r#type: "text".to_string(),
+        }),
+        role,
+    }
+}
+
+#[tokio::test]
+async fn mcp_prompt_list_returns_prompts_grouped_by_server() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_mcp_config(codex_home.path())?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_mcp_prompt_list_request(ListMcpPromptsParams { server: None })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(
+        to_response::<ListMcpPromptsResponse>(response)?,
+        ListMcpPromptsResponse {
+            data: vec![McpServerPrompts {
+                server: "docs".to_string(),
+                prompts: vec![Prompt {
+                    arguments: Some(vec![PromptArgument {
+                        description: Some("What to review.".to_string()),
+                        name: "topic".to_string(),
+                        required: Some(true),
+                        title: None,
+                    }]),
+                    description: Some("Ask for a focused review of the given topic.".to_string()),
+                    name: "review".to_string(),
+                    title: None,
+                }],
+            }],
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn mcp_prompt_get_renders_messages() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_mcp_config(codex_home.path())?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_mcp_prompt_get_request(GetMcpPromptParams {
+            server: "docs".to_string(),
+            name: "review".to_string(),
+            arguments: HashMap::from([("topic".to_string(), "the parser".to_string())]),
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(
+        to_response::<GetMcpPromptResponse>(response)?,
+        GetMcpPromptResponse {
+            description: Some("Focused review".to_string()),
+            messages: vec![
+                text_message(
+                    Role::User,
+                    "Please review the parser and list concrete problems."
+                ),
+                text_message(Role::Assistant, "I will review the parser."),
+            ],
+        }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn mcp_prompt_get_rejects_unknown_server() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_mcp_config(codex_home.path())?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_mcp_prompt_get_request(GetMcpPromptParams {
+            server: "missing".to_string(),
+            name: "review".to_string(),
+            arguments: HashMap::new(),
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+
+    assert_eq!(error.error.code, INVALID_REQUEST_ERROR_CODE);
+    assert_eq!(error.error.message, "unknown MCP server: missing");
+    Ok(())
+}
@@ -2,6 +2,7 @@ mod account;
 mod analytics;
 mod config_rpc;
 mod initialize;
+mod mcp_prompt;
 mod model_list;
 mod output_schema;
 mod rate_limits;
@@ -12,6 +13,7 @@ mod thread_list;
 mod thread_loaded_list;
 mod thread_resume;
 mod thread_rollback;
+mod thread_search;
 mod thread_start;
 mod turn_interrupt;
 mod turn_start;
@@ -57,6 +57,7 @@ async fn review_start_runs_review_turn_and_emits_code_review_item() -> Result<()
         .send_review_start_request(ReviewStartParams {
             thread_id: thread_id.clone(),
             delivery: Some(ReviewDelivery::Inline),
+            paths: Vec::new(),
             target: ReviewTarget::Commit {
                 sha: "1234567deadbeef".to_string(),
                 title: Some("Tidy UI colors".to_string()),
@@ -143,6 +144,7 @@ async fn review_start_rejects_empty_base_branch() -> Result<()> {
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: Vec::new(),
             target: ReviewTarget::BaseBranch {
                 branch: "   ".to_string(),
             },
@@ -186,6 +188,7 @@ async fn review_start_with_detached_delivery_returns_new_thread_id() -> Result<(
         .send_review_start_request(ReviewStartParams {
             thread_id: thread_id.clone(),
             delivery: Some(ReviewDelivery::Detached),
+            paths: Vec::new(),
             target: ReviewTarget::Custom {
                 instructions: "detached review".to_string(),
             },
@@ -224,6 +227,7 @@ async fn review_start_rejects_empty_commit_sha() -> Result<()> {
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: Vec::new(),
             target: ReviewTarget::Commit {
                 sha: "\t".to_string(),
                 title: None,
@@ -259,6 +263,7 @@ async fn review_start_rejects_empty_custom_instructions() -> Result<()> {
         .send_review_start_request(ReviewStartParams {
             thread_id,
             delivery: Some(ReviewDelivery::Inline),
+            paths: Vec::new(),
             target: ReviewTarget::Custom {
                 instructions: "\n\n".to_string(),
             },
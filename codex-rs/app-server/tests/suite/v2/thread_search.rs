@@ -0,0 +1,96 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadSearchMatch;
+use codex_app_server_protocol::ThreadSearchMatchKind;
+use codex_app_server_protocol::ThreadSearchParams;
+use codex_app_server_protocol::ThreadSearchResponse;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn thread_search_returns_matching_threads_with_snippets() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let migration_id = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-02T12-00-00",
+        "2025-01-02T12:00:00Z",
+        "Fix the flaky migration test",
+        Some("mock_provider"),
+        None,
+    )?;
+    create_fake_rollout(
+        codex_home.path(),
+        "2025-01-03T12-00-00",
+        "2025-01-03T12:00:00Z",
+        "Write a README",
+        Some("mock_provider"),
+        None,
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_thread_search_request(ThreadSearchParams {
+            query: "flaky MIGRATION".to_string(),
+            limit: None,
+            use_index: true,
+        })
+        .await?;
+    let resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadSearchResponse { data } = to_response::<ThreadSearchResponse>(resp)?;
+
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0].thread.id, migration_id);
+    assert_eq!(data[0].thread.preview, "Fix the flaky migration test");
+    assert_eq!(
+        data[0].matches,
+        vec![ThreadSearchMatch {
+            kind: ThreadSearchMatchKind::UserMessage,
+            snippet: "Fix the flaky migration test".to_string(),
+        }]
+    );
+    assert!(
+        codex_home
+            .path()
+            .join(codex_core::SESSION_SEARCH_INDEX_FILE)
+            .exists()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn thread_search_rejects_empty_query() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_thread_search_request(ThreadSearchParams {
+            query: "   ".to_string(),
+            limit: None,
+            use_index: false,
+        })
+        .await?;
+    let err: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(err.error.message, "query must not be empty");
+
+    Ok(())
+}
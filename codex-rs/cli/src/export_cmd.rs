@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_common::CliConfigOverrides;
+use codex_core::TranscriptFormat;
+use codex_core::config::Config;
+use codex_core::find_thread_path_by_id_str;
+use codex_core::load_transcript;
+
+/// Export a recorded session as a shareable transcript.
+#[derive(Debug, clap::Parser)]
+pub struct ExportCommand {
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Session id (UUID) or path to a rollout `.jsonl` file.
+    #[arg(value_name = "SESSION_ID")]
+    pub session_id: String,
+
+    /// Transcript format.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Md)]
+    pub format: ExportFormat,
+
+    /// Write the transcript to this file instead of stdout.
+    #[arg(short = 'o', long = "output", value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Markdown, suitable for pasting into PRs and issues.
+    #[value(alias = "markdown")]
+    Md,
+    /// Self-contained HTML page.
+    Html,
+    /// Structured JSON.
+    Json,
+}
+
+impl From<ExportFormat> for TranscriptFormat {
+    fn from(format: ExportFormat) -> Self {
+        match format {
+            ExportFormat::Md => TranscriptFormat::Markdown,
+            ExportFormat::Html => TranscriptFormat::Html,
+            ExportFormat::Json => TranscriptFormat::Json,
+        }
+    }
+}
+
+impl ExportCommand {
+    pub async fn run(self) -> Result<()> {
+        let path = PathBuf::from(&self.session_id);
+        let rollout_path = if path.extension().is_some_and(|ext| ext == "jsonl") && path.is_file() {
+            path
+        } else {
+            let overrides = self
+                .config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?;
+            let config = Config::load_with_cli_overrides(overrides)
+                .await
+                .context("failed to load configuration")?;
+            match find_thread_path_by_id_str(&config.codex_home, &self.session_id).await? {
+                Some(path) => path,
+                None => bail!("No saved session found with id {}", self.session_id),
+            }
+        };
+
+        let transcript = load_transcript(&rollout_path)
+            .await
+            .with_context(|| format!("failed to read {}", rollout_path.display()))?;
+        let rendered = transcript.render(self.format.into());
+
+        match self.output {
+            Some(output) => {
+                std::fs::write(&output, rendered)
+                    .with_context(|| format!("failed to write {}", output.display()))?;
+            }
+            None => print!("{rendered}"),
+        }
+        Ok(())
+    }
+}
@@ -33,11 +33,17 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod export_cmd;
 mod mcp_cmd;
+mod memory_cmd;
+mod sessions_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::export_cmd::ExportCommand;
 use crate::mcp_cmd::McpCli;
+use crate::memory_cmd::MemoryCli;
+use crate::sessions_cmd::SessionsCli;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -123,6 +129,15 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// Search saved sessions.
+    Sessions(SessionsCli),
+
+    /// Export a saved session as a Markdown, HTML or JSON transcript.
+    Export(ExportCommand),
+
+    /// [experimental] List, add, edit and delete project memories.
+    Memory(MemoryCli),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -575,6 +590,27 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             let exit_info = run_interactive_tui(interactive, codex_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::Sessions(mut sessions_cli)) => {
+            prepend_config_flags(
+                &mut sessions_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            sessions_cli.run().await?;
+        }
+        Some(Subcommand::Export(mut export_cli)) => {
+            prepend_config_flags(
+                &mut export_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            export_cli.run().await?;
+        }
+        Some(Subcommand::Memory(mut memory_cli)) => {
+            prepend_config_flags(
+                &mut memory_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            memory_cli.run().await?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -0,0 +1,141 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::memory::MemoryStore;
+
+/// Subcommands:
+/// - `list`   — show the memories saved for a project
+/// - `add`    — save a new memory
+/// - `edit`   — replace the text of a memory
+/// - `delete` — remove a memory
+#[derive(Debug, clap::Parser)]
+pub struct MemoryCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    /// Project directory whose memories to manage. Defaults to the current
+    /// directory; memories are shared by the whole Git repository.
+    #[arg(long = "project", value_name = "DIR", global = true)]
+    pub project: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub subcommand: MemorySubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum MemorySubcommand {
+    /// List the memories saved for the project.
+    List(ListArgs),
+
+    /// Save a new memory.
+    Add(AddArgs),
+
+    /// Replace the text of a memory.
+    Edit(EditArgs),
+
+    /// Delete a memory.
+    #[clap(visible_alias = "rm")]
+    Delete(DeleteArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Output the memories as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct AddArgs {
+    /// Text of the memory.
+    #[arg(value_name = "TEXT", required = true, num_args = 1..)]
+    pub text: Vec<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct EditArgs {
+    /// Id of the memory, as shown by `codex memory list`.
+    #[arg(value_name = "ID")]
+    pub id: String,
+
+    /// New text of the memory.
+    #[arg(value_name = "TEXT", required = true, num_args = 1..)]
+    pub text: Vec<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct DeleteArgs {
+    /// Id of the memory, as shown by `codex memory list`.
+    #[arg(value_name = "ID")]
+    pub id: String,
+}
+
+impl MemoryCli {
+    pub async fn run(self) -> Result<()> {
+        let MemoryCli {
+            config_overrides,
+            project,
+            subcommand,
+        } = self;
+
+        let overrides = config_overrides
+            .parse_overrides()
+            .map_err(anyhow::Error::msg)?;
+        let config = Config::load_with_cli_overrides(overrides)
+            .await
+            .context("failed to load configuration")?;
+        let project = match project {
+            Some(dir) => std::path::absolute(&dir)
+                .with_context(|| format!("failed to resolve {}", dir.display()))?,
+            None => config.cwd.clone(),
+        };
+        let store = MemoryStore::for_project(&config.codex_home, &project);
+
+        match subcommand {
+            MemorySubcommand::List(args) => run_list(&store, args),
+            MemorySubcommand::Add(args) => {
+                let memory = store.add(&args.text.join(" "))?;
+                println!("Saved memory {}.", memory.id);
+                Ok(())
+            }
+            MemorySubcommand::Edit(args) => {
+                match store.update(&args.id, &args.text.join(" "))? {
+                    Some(memory) => println!("Updated memory {}.", memory.id),
+                    None => anyhow::bail!("no memory with id `{}`", args.id),
+                }
+                Ok(())
+            }
+            MemorySubcommand::Delete(args) => {
+                if !store.delete(&args.id)? {
+                    anyhow::bail!("no memory with id `{}`", args.id);
+                }
+                println!("Deleted memory {}.", args.id);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn run_list(store: &MemoryStore, args: ListArgs) -> Result<()> {
+    let memories = store.list()?;
+
+    if args.json {
+        let output = serde_json::to_string_pretty(&memories)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if memories.is_empty() {
+        println!("No memories saved for {}.", store.project().display());
+        return Ok(());
+    }
+
+    for memory in &memories {
+        println!("{}  {}  {}", memory.id, memory.updated_at, memory.text);
+    }
+
+    Ok(())
+}
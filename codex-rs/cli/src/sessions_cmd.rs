@@ -0,0 +1,154 @@
+use anyhow::Context;
+use anyhow::Result;
+use codex_common::CliConfigOverrides;
+use codex_core::SearchMatchKind;
+use codex_core::ThreadSearchOptions;
+use codex_core::config::Config;
+use codex_core::search_threads;
+
+/// Subcommands:
+/// - `search` — full-text search across recorded session rollouts
+#[derive(Debug, clap::Parser)]
+pub struct SessionsCli {
+    #[clap(flatten)]
+    pub config_overrides: CliConfigOverrides,
+
+    #[command(subcommand)]
+    pub subcommand: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionsSubcommand {
+    /// Search saved sessions for messages, commands and files that match every term.
+    Search(SearchArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct SearchArgs {
+    /// Search terms; a session matches when it contains all of them (case-insensitive).
+    #[arg(value_name = "QUERY", required = true, num_args = 1..)]
+    pub query: Vec<String>,
+
+    /// Maximum number of sessions to show.
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Cache extracted session text in `$CODEX_HOME` to speed up later searches.
+    #[arg(long)]
+    pub index: bool,
+
+    /// Output the results as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl SessionsCli {
+    pub async fn run(self) -> Result<()> {
+        let SessionsCli {
+            config_overrides,
+            subcommand,
+        } = self;
+
+        match subcommand {
+            SessionsSubcommand::Search(args) => run_search(&config_overrides, args).await,
+        }
+    }
+}
+
+async fn run_search(config_overrides: &CliConfigOverrides, args: SearchArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+
+    let query = args.query.join(" ");
+    let options = ThreadSearchOptions {
+        limit: args.limit,
+        use_index: args.index,
+        ..Default::default()
+    };
+    let results = search_threads(&config.codex_home, &query, options)
+        .await
+        .context("failed to search sessions")?;
+
+    if args.json {
+        let json_hits: Vec<_> = results
+            .hits
+            .iter()
+            .map(|hit| {
+                let matches: Vec<_> = hit
+                    .matches
+                    .iter()
+                    .map(|m| {
+                        serde_json::json!({
+                            "kind": match_kind_label(m.kind),
+                            "snippet": m.snippet,
+                        })
+                    })
+                    .collect();
+                serde_json::json!({
+                    "id": hit.thread_id.map(|id| id.to_string()),
+                    "path": hit.path,
+                    "created_at": hit.created_at,
+                    "updated_at": hit.updated_at,
+                    "preview": hit.preview,
+                    "matches": matches,
+                })
+            })
+            .collect();
+        let output = serde_json::to_string_pretty(&json_hits)?;
+        println!("{output}");
+        return Ok(());
+    }
+
+    if results.hits.is_empty() {
+        println!("No sessions match `{query}`.");
+        return Ok(());
+    }
+
+    for (i, hit) in results.hits.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let id = hit
+            .thread_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| hit.path.display().to_string());
+        let when = hit
+            .updated_at
+            .as_deref()
+            .or(hit.created_at.as_deref())
+            .unwrap_or("-");
+        println!("{id}  {when}");
+        if let Some(preview) = &hit.preview {
+            println!("  {}", first_line(preview));
+        }
+        for m in &hit.matches {
+            println!("    [{}] {}", match_kind_label(m.kind), m.snippet);
+        }
+    }
+    if results.reached_scan_cap {
+        println!();
+        println!(
+            "Stopped after scanning {} sessions; narrow the query to see older results.",
+            results.num_scanned_files
+        );
+    }
+
+    Ok(())
+}
+
+fn match_kind_label(kind: SearchMatchKind) -> &'static str {
+    match kind {
+        SearchMatchKind::UserMessage => "user",
+        SearchMatchKind::AgentMessage => "agent",
+        SearchMatchKind::Command => "command",
+        SearchMatchKind::File => "file",
+    }
+}
+
+fn first_line(text: &str) -> &str {
+    text.lines().next().unwrap_or_default()
+}
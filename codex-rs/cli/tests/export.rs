@@ -0,0 +1,123 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tempfile::TempDir;
+
+const THREAD_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn write_rollout(codex_home: &Path) -> Result<PathBuf> {
+    let day_dir = codex_home.join("sessions/2025/01/02");
+    std::fs::create_dir_all(&day_dir)?;
+    let lines = [
+        json!({
+            "timestamp": "2025-01-02T12:00:00Z",
+            "type": "session_meta",
+            "payload": {
+                "id": THREAD_ID,
+                "timestamp": "2025-01-02T12:00:00Z",
+                "cwd": "/repo",
+                "originator": "codex",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "source": "cli",
+                "model_provider": "openai",
+            },
+        }),
+        json!({
+            "timestamp": "2025-01-02T12:00:01Z",
+            "type": "event_msg",
+            "payload": {"type": "user_message", "message": "Fix the flaky migration test"},
+        }),
+        json!({
+            "timestamp": "2025-01-02T12:00:02Z",
+            "type": "event_msg",
+            "payload": {"type": "agent_message", "message": "Fixed <it>."},
+        }),
+    ];
+    let contents = lines
+        .iter()
+        .map(JsonValue::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let path = day_dir.join(format!("rollout-2025-01-02T12-00-00-{THREAD_ID}.jsonl"));
+    std::fs::write(&path, contents + "\n")?;
+    Ok(path)
+}
+
+#[test]
+fn export_markdown_by_session_id() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["export", THREAD_ID]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.starts_with(&format!("# Codex session {THREAD_ID}\n")));
+    assert!(stdout.contains("## User\n\nFix the flaky migration test\n"));
+    assert!(stdout.contains("## Codex\n\nFixed <it>.\n"));
+
+    Ok(())
+}
+
+#[test]
+fn export_html_and_json_to_file() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let rollout = write_rollout(codex_home.path())?;
+    let out_dir = TempDir::new()?;
+    let html_path = out_dir.path().join("session.html");
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["export", THREAD_ID, "--format", "html", "-o"])
+        .arg(&html_path)
+        .output()?;
+    assert!(output.status.success());
+    let html = std::fs::read_to_string(&html_path)?;
+    assert!(html.contains("Fixed &lt;it&gt;."));
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .arg("export")
+        .arg(&rollout)
+        .args(["--format", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(parsed["thread_id"], json!(THREAD_ID));
+    assert_eq!(
+        parsed["entries"],
+        json!([
+            {"type": "user_message", "message": "Fix the flaky migration test"},
+            {"type": "agent_message", "message": "Fixed <it>."},
+        ])
+    );
+
+    Ok(())
+}
+
+#[test]
+fn export_unknown_session_fails() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args(["export", "00000000-0000-0000-0000-000000000000"])
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("No saved session found"));
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use std::path::Path;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use tempfile::TempDir;
+
+fn codex_command(codex_home: &Path, project: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    cmd.current_dir(project);
+    Ok(cmd)
+}
+
+fn list_json(codex_home: &Path, project: &Path) -> Result<Vec<JsonValue>> {
+    let mut cmd = codex_command(codex_home, project)?;
+    let output = cmd.args(["memory", "list", "--json"]).output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    Ok(parsed.as_array().cloned().unwrap_or_default())
+}
+
+#[test]
+fn add_edit_and_delete_memories() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let project = TempDir::new()?;
+
+    let mut cmd = codex_command(codex_home.path(), project.path())?;
+    let output = cmd
+        .args([
+            "memory",
+            "add",
+            "Run",
+            "just",
+            "fmt",
+            "before",
+            "committing",
+        ])
+        .output()?;
+    assert!(output.status.success());
+
+    let memories = list_json(codex_home.path(), project.path())?;
+    assert_eq!(memories.len(), 1);
+    assert_eq!(memories[0]["text"], "Run just fmt before committing");
+    let id = memories[0]["id"].as_str().expect("id").to_string();
+
+    let mut cmd = codex_command(codex_home.path(), project.path())?;
+    let output = cmd
+        .args(["memory", "edit", &id, "Run just fmt in codex-rs"])
+        .output()?;
+    assert!(output.status.success());
+    let memories = list_json(codex_home.path(), project.path())?;
+    assert_eq!(memories[0]["text"], "Run just fmt in codex-rs");
+
+    let other_project = TempDir::new()?;
+    assert_eq!(
+        list_json(codex_home.path(), other_project.path())?,
+        Vec::new()
+    );
+
+    let mut cmd = codex_command(codex_home.path(), project.path())?;
+    let output = cmd.args(["memory", "delete", &id]).output()?;
+    assert!(output.status.success());
+    assert_eq!(list_json(codex_home.path(), project.path())?, Vec::new());
+
+    let mut cmd = codex_command(codex_home.path(), project.path())?;
+    let output = cmd.args(["memory", "delete", &id]).output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains(&format!("no memory with id `{id}`")));
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tempfile::TempDir;
+
+const THREAD_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+fn codex_command(codex_home: &Path) -> Result<assert_cmd::Command> {
+    let mut cmd = assert_cmd::Command::new(codex_utils_cargo_bin::cargo_bin("codex")?);
+    cmd.env("CODEX_HOME", codex_home);
+    Ok(cmd)
+}
+
+fn write_rollout(codex_home: &Path) -> Result<()> {
+    let day_dir = codex_home.join("sessions/2025/01/02");
+    std::fs::create_dir_all(&day_dir)?;
+    let lines = [
+        json!({
+            "timestamp": "2025-01-02T12:00:00Z",
+            "type": "session_meta",
+            "payload": {
+                "id": THREAD_ID,
+                "timestamp": "2025-01-02T12:00:00Z",
+                "cwd": "/repo",
+                "originator": "codex",
+                "cli_version": "0.0.0",
+                "instructions": null,
+                "source": "cli",
+                "model_provider": "openai",
+            },
+        }),
+        json!({
+            "timestamp": "2025-01-02T12:00:01Z",
+            "type": "event_msg",
+            "payload": {"type": "user_message", "message": "Fix the flaky migration test"},
+        }),
+    ];
+    let contents = lines
+        .iter()
+        .map(JsonValue::to_string)
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(
+        day_dir.join(format!("rollout-2025-01-02T12-00-00-{THREAD_ID}.jsonl")),
+        contents + "\n",
+    )?;
+    Ok(())
+}
+
+#[test]
+fn search_prints_matching_sessions() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["sessions", "search", "flaky"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains(THREAD_ID));
+    assert!(stdout.contains("[user] Fix the flaky migration test"));
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd.args(["sessions", "search", "no-such-term"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("No sessions match `no-such-term`."));
+
+    Ok(())
+}
+
+#[test]
+fn search_json_output() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_rollout(codex_home.path())?;
+
+    let mut cmd = codex_command(codex_home.path())?;
+    let output = cmd
+        .args([
+            "sessions",
+            "search",
+            "--json",
+            "--index",
+            "migration",
+            "test",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let parsed: JsonValue = serde_json::from_slice(&output.stdout)?;
+    let hits = parsed.as_array().expect("array output");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["id"], json!(THREAD_ID));
+    assert_eq!(
+        hits[0]["matches"],
+        json!([{"kind": "user", "snippet": "Fix the flaky migration test"}])
+    );
+    assert!(
+        codex_home
+            .path()
+            .join(codex_core::SESSION_SEARCH_INDEX_FILE)
+            .exists()
+    );
+
+    Ok(())
+}
@@ -25,3 +25,14 @@ pub(crate) fn add_auth_headers<A: AuthProvider>(auth: &A, mut req: Request) -> R
     }
     req
 }
+
+/// Anthropic-style authentication: the token goes in `x-api-key` instead of
+/// an `Authorization: Bearer` header.
+pub(crate) fn add_api_key_header<A: AuthProvider>(auth: &A, mut req: Request) -> Request {
+    if let Some(token) = auth.bearer_token()
+        && let Ok(header) = token.parse()
+    {
+        let _ = req.headers.insert("x-api-key", header);
+    }
+    req
+}
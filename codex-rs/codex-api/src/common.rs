@@ -27,6 +27,10 @@ pub struct Prompt {
     pub parallel_tool_calls: bool,
     /// Optional output schema used to build the `text.format` controls.
     pub output_schema: Option<Value>,
+    /// Upper bound on generated tokens; `None` leaves the provider default.
+    pub max_output_tokens: Option<u64>,
+    /// Sampling temperature; `None` leaves the provider default.
+    pub temperature: Option<f64>,
 }
 
 /// Canonical input payload for the compaction endpoint.
@@ -134,6 +138,10 @@ pub struct ResponsesApiRequest<'a> {
     pub prompt_cache_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<TextControls>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -152,6 +160,10 @@ pub struct ResponseCreateWsRequest {
     pub prompt_cache_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<TextControls>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -61,6 +61,8 @@ impl<T: HttpTransport, A: AuthProvider> ChatClient<T, A> {
 
         let request =
             ChatRequestBuilder::new(model, &prompt.instructions, &prompt.input, &prompt.tools)
+                .max_tokens(prompt.max_output_tokens)
+                .temperature(prompt.temperature)
                 .conversation_id(conversation_id)
                 .session_source(session_source)
                 .build(self.streaming.provider())?;
@@ -1,5 +1,6 @@
 pub mod chat;
 pub mod compact;
+pub mod messages;
 pub mod models;
 pub mod responses;
 pub mod responses_websocket;
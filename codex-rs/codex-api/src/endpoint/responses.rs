@@ -89,6 +89,8 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
             .include(include)
             .prompt_cache_key(prompt_cache_key)
             .text(text)
+            .max_output_tokens(prompt.max_output_tokens)
+            .temperature(prompt.temperature)
             .conversation(conversation_id)
             .session_source(session_source)
             .store_override(store_override)
@@ -103,6 +105,7 @@ impl<T: HttpTransport, A: AuthProvider> ResponsesClient<T, A> {
         match self.streaming.provider().wire {
             WireApi::Responses | WireApi::Compact => "responses",
             WireApi::Chat => "chat/completions",
+            WireApi::Messages => "messages",
         }
     }
 
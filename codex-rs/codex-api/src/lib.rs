@@ -25,6 +25,8 @@ pub use crate::common::create_text_param_for_request;
 pub use crate::endpoint::chat::AggregateStreamExt;
 pub use crate::endpoint::chat::ChatClient;
 pub use crate::endpoint::compact::CompactClient;
+pub use crate::endpoint::messages::MessagesClient;
+pub use crate::endpoint::messages::MessagesOptions;
 pub use crate::endpoint::models::ModelsClient;
 pub use crate::endpoint::responses::ResponsesClient;
 pub use crate::endpoint::responses::ResponsesOptions;
@@ -35,6 +37,8 @@ pub use crate::provider::Provider;
 pub use crate::provider::WireApi;
 pub use crate::requests::ChatRequest;
 pub use crate::requests::ChatRequestBuilder;
+pub use crate::requests::MessagesRequest;
+pub use crate::requests::MessagesRequestBuilder;
 pub use crate::requests::ResponsesRequest;
 pub use crate::requests::ResponsesRequestBuilder;
 pub use crate::sse::stream_from_fixture;
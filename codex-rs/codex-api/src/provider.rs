@@ -13,6 +13,8 @@ pub enum WireApi {
     Responses,
     Chat,
     Compact,
+    /// Anthropic Messages API (`/v1/messages`).
+    Messages,
 }
 
 /// High-level retry configuration for a provider.
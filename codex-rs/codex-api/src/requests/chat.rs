@@ -24,6 +24,8 @@ pub struct ChatRequestBuilder<'a> {
     instructions: &'a str,
     input: &'a [ResponseItem],
     tools: &'a [Value],
+    max_tokens: Option<u64>,
+    temperature: Option<f64>,
     conversation_id: Option<String>,
     session_source: Option<SessionSource>,
 }
@@ -40,11 +42,23 @@ impl<'a> ChatRequestBuilder<'a> {
             instructions,
             input,
             tools,
+            max_tokens: None,
+            temperature: None,
             conversation_id: None,
             session_source: None,
         }
     }
 
+    pub fn max_tokens(mut self, max_tokens: Option<u64>) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn temperature(mut self, temperature: Option<f64>) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
     pub fn conversation_id(mut self, id: Option<String>) -> Self {
         self.conversation_id = id;
         self
@@ -291,12 +305,20 @@ impl<'a> ChatRequestBuilder<'a> {
             }
         }
 
-        let payload = json!({
+        let mut payload = json!({
             "model": self.model,
             "messages": messages,
             "stream": true,
             "tools": self.tools,
         });
+        if let Some(obj) = payload.as_object_mut() {
+            if let Some(max_tokens) = self.max_tokens {
+                obj.insert("max_tokens".to_string(), json!(max_tokens));
+            }
+            if let Some(temperature) = self.temperature {
+                obj.insert("temperature".to_string(), json!(temperature));
+            }
+        }
 
         let mut headers = build_conversation_headers(self.conversation_id);
         if let Some(subagent) = subagent_header(&self.session_source) {
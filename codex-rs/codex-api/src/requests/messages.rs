@@ -0,0 +1,518 @@
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::requests::headers::build_conversation_headers;
+use crate::requests::headers::insert_header;
+use crate::sse::messages::REDACTED_THINKING_ID;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputContentItem;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use http::HeaderMap;
+use serde_json::Value;
+use serde_json::json;
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Assembled request body plus headers for Anthropic Messages streaming calls.
+pub struct MessagesRequest {
+    pub body: Value,
+    pub headers: HeaderMap,
+}
+
+pub struct MessagesRequestBuilder<'a> {
+    model: &'a str,
+    instructions: &'a str,
+    input: &'a [ResponseItem],
+    tools: &'a [Value],
+    parallel_tool_calls: bool,
+    max_tokens: u64,
+    thinking_budget: Option<u64>,
+    temperature: Option<f64>,
+    conversation_id: Option<String>,
+}
+
+impl<'a> MessagesRequestBuilder<'a> {
+    /// `max_tokens` is mandatory for the Messages API, so it has no default.
+    pub fn new(
+        model: &'a str,
+        instructions: &'a str,
+        input: &'a [ResponseItem],
+        tools: &'a [Value],
+        max_tokens: u64,
+    ) -> Self {
+        Self {
+            model,
+            instructions,
+            input,
+            tools,
+            parallel_tool_calls: true,
+            max_tokens,
+            thinking_budget: None,
+            temperature: None,
+            conversation_id: None,
+        }
+    }
+
+    pub fn parallel_tool_calls(mut self, enabled: bool) -> Self {
+        self.parallel_tool_calls = enabled;
+        self
+    }
+
+    /// Enables extended thinking with the given token budget.
+    pub fn thinking_budget(mut self, budget: Option<u64>) -> Self {
+        self.thinking_budget = budget;
+        self
+    }
+
+    /// Ignored when extended thinking is enabled, which only supports the
+    /// default temperature.
+    pub fn temperature(mut self, temperature: Option<f64>) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
+    pub fn conversation_id(mut self, id: Option<String>) -> Self {
+        self.conversation_id = id;
+        self
+    }
+
+    pub fn build(self, _provider: &Provider) -> Result<MessagesRequest, ApiError> {
+        let mut messages = Vec::<Value>::new();
+
+        for item in self.input {
+            match item {
+                ResponseItem::Message { role, content, .. } => {
+                    let blocks: Vec<Value> = content.iter().filter_map(content_block).collect();
+                    // The Messages API only knows `user` and `assistant`;
+                    // developer and system messages are sent as user turns.
+                    let role = if role == "assistant" {
+                        "assistant"
+                    } else {
+                        "user"
+                    };
+                    for block in blocks {
+                        push_block(&mut messages, role, block);
+                    }
+                }
+                ResponseItem::Reasoning {
+                    id,
+                    summary,
+                    encrypted_content,
+                    ..
+                } => {
+                    // Thinking blocks can only be replayed together with the
+                    // signature (or, for redacted thinking, the opaque data)
+                    // the API issued for them. Reasoning without either, or
+                    // without any text, did not come from this API.
+                    let Some(signature) = encrypted_content else {
+                        continue;
+                    };
+                    if id == REDACTED_THINKING_ID {
+                        push_block(
+                            &mut messages,
+                            "assistant",
+                            json!({"type": "redacted_thinking", "data": signature}),
+                        );
+                        continue;
+                    }
+                    let thinking: String = summary
+                        .iter()
+                        .map(|entry| match entry {
+                            ReasoningItemReasoningSummary::SummaryText { text } => text.as_str(),
+                        })
+                        .collect();
+                    if thinking.is_empty() {
+                        continue;
+                    }
+                    push_block(
+                        &mut messages,
+                        "assistant",
+                        json!({"type": "thinking", "thinking": thinking, "signature": signature}),
+                    );
+                }
+                ResponseItem::FunctionCall {
+                    name,
+                    arguments,
+                    call_id,
+                    ..
+                } => {
+                    let input = serde_json::from_str::<Value>(arguments)
+                        .ok()
+                        .filter(Value::is_object)
+                        .unwrap_or_else(|| json!({}));
+                    push_block(
+                        &mut messages,
+                        "assistant",
+                        json!({"type": "tool_use", "id": call_id, "name": name, "input": input}),
+                    );
+                }
+                ResponseItem::CustomToolCall {
+                    call_id,
+                    name,
+                    input,
+                    ..
+                } => {
+                    push_block(
+                        &mut messages,
+                        "assistant",
+                        json!({
+                            "type": "tool_use",
+                            "id": call_id,
+                            "name": name,
+                            "input": {"input": input},
+                        }),
+                    );
+                }
+                ResponseItem::LocalShellCall {
+                    id,
+                    call_id,
+                    action,
+                    ..
+                } => {
+                    let Some(call_id) = call_id.as_ref().or(id.as_ref()) else {
+                        continue;
+                    };
+                    push_block(
+                        &mut messages,
+                        "assistant",
+                        json!({
+                            "type": "tool_use",
+                            "id": call_id,
+                            "name": "local_shell",
+                            "input": action,
+                        }),
+                    );
+                }
+                ResponseItem::FunctionCallOutput { call_id, output } => {
+                    let content = match &output.content_items {
+                        Some(items) => json!(
+                            items
+                                .iter()
+                                .map(|item| match item {
+                                    FunctionCallOutputContentItem::InputText { text } => {
+                                        json!({"type": "text", "text": text})
+                                    }
+                                    FunctionCallOutputContentItem::InputImage { image_url } => {
+                                        image_block(image_url)
+                                    }
+                                })
+                                .collect::<Vec<_>>()
+                        ),
+                        None => json!(output.content),
+                    };
+                    let mut block = json!({
+                        "type": "tool_result",
+                        "tool_use_id": call_id,
+                        "content": content,
+                    });
+                    if output.success == Some(false)
+                        && let Some(obj) = block.as_object_mut()
+                    {
+                        obj.insert("is_error".to_string(), Value::Bool(true));
+                    }
+                    push_block(&mut messages, "user", block);
+                }
+                ResponseItem::CustomToolCallOutput { call_id, output } => {
+                    push_block(
+                        &mut messages,
+                        "user",
+                        json!({
+                            "type": "tool_result",
+                            "tool_use_id": call_id,
+                            "content": output,
+                        }),
+                    );
+                }
+                ResponseItem::WebSearchCall { .. }
+                | ResponseItem::GhostSnapshot { .. }
+                | ResponseItem::Compaction { .. }
+                | ResponseItem::Other => {}
+            }
+        }
+
+        let mut payload = json!({
+            "model": self.model,
+            "max_tokens": self.max_tokens,
+            "messages": messages,
+            "stream": true,
+        });
+        if let Some(obj) = payload.as_object_mut() {
+            // The API rejects an empty system prompt.
+            if !self.instructions.is_empty() {
+                obj.insert("system".to_string(), json!(self.instructions));
+            }
+            if !self.tools.is_empty() {
+                obj.insert("tools".to_string(), json!(self.tools));
+                obj.insert(
+                    "tool_choice".to_string(),
+                    json!({
+                        "type": "auto",
+                        "disable_parallel_tool_use": !self.parallel_tool_calls,
+                    }),
+                );
+            }
+            if let Some(budget) = self.thinking_budget {
+                obj.insert(
+                    "thinking".to_string(),
+                    json!({"type": "enabled", "budget_tokens": budget}),
+                );
+            } else if let Some(temperature) = self.temperature {
+                obj.insert("temperature".to_string(), json!(temperature));
+            }
+        }
+
+        let mut headers = build_conversation_headers(self.conversation_id);
+        insert_header(&mut headers, "anthropic-version", ANTHROPIC_VERSION);
+
+        Ok(MessagesRequest {
+            body: payload,
+            headers,
+        })
+    }
+}
+
+fn content_block(item: &ContentItem) -> Option<Value> {
+    match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            // The API rejects empty text blocks.
+            (!text.is_empty()).then(|| json!({"type": "text", "text": text}))
+        }
+        ContentItem::InputImage { image_url } => Some(image_block(image_url)),
+    }
+}
+
+fn image_block(image_url: &str) -> Value {
+    if let Some(rest) = image_url.strip_prefix("data:")
+        && let Some((media_type, data)) = rest.split_once(";base64,")
+    {
+        return json!({
+            "type": "image",
+            "source": {"type": "base64", "media_type": media_type, "data": data},
+        });
+    }
+    json!({"type": "image", "source": {"type": "url", "url": image_url}})
+}
+
+/// Appends `block` to the conversation, merging it into the previous message
+/// when the role matches since the API expects roles to alternate. Tool
+/// results are kept ahead of any other content in a user message, as the API
+/// requires them to directly answer the preceding `tool_use` blocks.
+fn push_block(messages: &mut Vec<Value>, role: &str, block: Value) {
+    if let Some(Value::Object(last)) = messages.last_mut()
+        && last.get("role").and_then(Value::as_str) == Some(role)
+        && let Some(content) = last.get_mut("content").and_then(Value::as_array_mut)
+    {
+        if is_tool_result(&block) {
+            let position = content
+                .iter()
+                .position(|existing| !is_tool_result(existing))
+                .unwrap_or(content.len());
+            content.insert(position, block);
+        } else {
+            content.push(block);
+        }
+        return;
+    }
+    messages.push(json!({"role": role, "content": [block]}));
+}
+
+fn is_tool_result(block: &Value) -> bool {
+    block.get("type").and_then(Value::as_str) == Some("tool_result")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::RetryConfig;
+    use crate::provider::WireApi;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use http::HeaderValue;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn provider() -> Provider {
+        Provider {
+            name: "anthropic".to_string(),
+            base_url: "https://api.anthropic.com/v1".to_string(),
+            query_params: None,
+            wire: WireApi::Messages,
+            headers: HeaderMap::new(),
+            retry: RetryConfig {
+                max_attempts: 1,
+                base_delay: Duration::from_millis(10),
+                retry_429: false,
+                retry_5xx: true,
+                retry_transport: true,
+            },
+            stream_idle_timeout: Duration::from_secs(5),
+        }
+    }
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn maps_tool_calls_and_results_to_content_blocks() {
+        let input = vec![
+            message("developer", "be careful"),
+            message("user", "list files"),
+            ResponseItem::Reasoning {
+                id: String::new(),
+                summary: vec![ReasoningItemReasoningSummary::SummaryText {
+                    text: "I should run ls".to_string(),
+                }],
+                content: None,
+                encrypted_content: Some("sig".to_string()),
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "Cargo.toml".to_string(),
+                    content_items: None,
+                    success: Some(false),
+                },
+            },
+            message("user", "thanks"),
+        ];
+        let tools = vec![json!({"name": "shell", "input_schema": {"type": "object"}})];
+
+        let request = MessagesRequestBuilder::new("claude", "system prompt", &input, &tools, 8192)
+            .parallel_tool_calls(false)
+            .thinking_budget(Some(4096))
+            .conversation_id(Some("conv-1".to_string()))
+            .build(&provider())
+            .unwrap();
+
+        assert_eq!(
+            request.body,
+            json!({
+                "model": "claude",
+                "max_tokens": 8192,
+                "system": "system prompt",
+                "stream": true,
+                "tools": tools,
+                "tool_choice": {"type": "auto", "disable_parallel_tool_use": true},
+                "thinking": {"type": "enabled", "budget_tokens": 4096},
+                "messages": [
+                    {"role": "user", "content": [
+                        {"type": "text", "text": "be careful"},
+                        {"type": "text", "text": "list files"},
+                    ]},
+                    {"role": "assistant", "content": [
+                        {"type": "thinking", "thinking": "I should run ls", "signature": "sig"},
+                        {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {"command": ["ls"]}},
+                    ]},
+                    {"role": "user", "content": [
+                        {"type": "tool_result", "tool_use_id": "toolu_1", "content": "Cargo.toml", "is_error": true},
+                        {"type": "text", "text": "thanks"},
+                    ]},
+                ],
+            })
+        );
+        assert_eq!(
+            request.headers.get("anthropic-version"),
+            Some(&HeaderValue::from_static(ANTHROPIC_VERSION))
+        );
+        assert_eq!(
+            request.headers.get("session_id"),
+            Some(&HeaderValue::from_static("conv-1"))
+        );
+    }
+
+    #[test]
+    fn tool_results_stay_ahead_of_user_text() {
+        let input = vec![
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "not json".to_string(),
+                call_id: "toolu_1".to_string(),
+            },
+            message("user", "while you're at it"),
+            ResponseItem::CustomToolCallOutput {
+                call_id: "toolu_1".to_string(),
+                output: "done".to_string(),
+            },
+        ];
+
+        let request = MessagesRequestBuilder::new("claude", "", &input, &[], 8192)
+            .build(&provider())
+            .unwrap();
+
+        assert_eq!(
+            request.body["messages"],
+            json!([
+                {"role": "assistant", "content": [
+                    {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {}},
+                ]},
+                {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1", "content": "done"},
+                    {"type": "text", "text": "while you're at it"},
+                ]},
+            ])
+        );
+        assert_eq!(request.body.get("tools"), None);
+        assert_eq!(request.body.get("system"), None);
+    }
+
+    #[test]
+    fn only_replays_reasoning_issued_by_the_messages_api() {
+        let reasoning =
+            |id: &str, text: &str, encrypted_content: Option<&str>| ResponseItem::Reasoning {
+                id: id.to_string(),
+                summary: vec![ReasoningItemReasoningSummary::SummaryText {
+                    text: text.to_string(),
+                }],
+                content: None,
+                encrypted_content: encrypted_content.map(str::to_string),
+            };
+        let input = vec![
+            reasoning("", "", Some("signature-without-text")),
+            reasoning("rs_1", "no signature", None),
+            reasoning(REDACTED_THINKING_ID, "", Some("opaque")),
+            reasoning("", "checked the tests", Some("sig")),
+        ];
+
+        let request = MessagesRequestBuilder::new("claude", "system", &input, &[], 8192)
+            .build(&provider())
+            .unwrap();
+
+        assert_eq!(
+            request.body["messages"],
+            json!([
+                {"role": "assistant", "content": [
+                    {"type": "redacted_thinking", "data": "opaque"},
+                    {"type": "thinking", "thinking": "checked the tests", "signature": "sig"},
+                ]},
+            ])
+        );
+    }
+
+    #[test]
+    fn data_url_images_are_sent_as_base64() {
+        assert_eq!(
+            image_block("data:image/png;base64,AAAA"),
+            json!({
+                "type": "image",
+                "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"},
+            })
+        );
+        assert_eq!(
+            image_block("https://example.com/a.png"),
+            json!({"type": "image", "source": {"type": "url", "url": "https://example.com/a.png"}})
+        );
+    }
+}
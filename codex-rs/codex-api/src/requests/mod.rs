@@ -1,8 +1,11 @@
 pub mod chat;
 pub(crate) mod headers;
+pub mod messages;
 pub mod responses;
 
 pub use chat::ChatRequest;
 pub use chat::ChatRequestBuilder;
+pub use messages::MessagesRequest;
+pub use messages::MessagesRequestBuilder;
 pub use responses::ResponsesRequest;
 pub use responses::ResponsesRequestBuilder;
@@ -36,6 +36,8 @@ pub struct ResponsesRequestBuilder<'a> {
     include: Vec<String>,
     prompt_cache_key: Option<String>,
     text: Option<TextControls>,
+    max_output_tokens: Option<u64>,
+    temperature: Option<f64>,
     conversation_id: Option<String>,
     session_source: Option<SessionSource>,
     store_override: Option<bool>,
@@ -83,6 +85,16 @@ impl<'a> ResponsesRequestBuilder<'a> {
         self
     }
 
+    pub fn max_output_tokens(mut self, max_output_tokens: Option<u64>) -> Self {
+        self.max_output_tokens = max_output_tokens;
+        self
+    }
+
+    pub fn temperature(mut self, temperature: Option<f64>) -> Self {
+        self.temperature = temperature;
+        self
+    }
+
     pub fn conversation(mut self, conversation_id: Option<String>) -> Self {
         self.conversation_id = conversation_id;
         self
@@ -137,6 +149,8 @@ impl<'a> ResponsesRequestBuilder<'a> {
             include: self.include,
             prompt_cache_key: self.prompt_cache_key,
             text: self.text,
+            max_output_tokens: self.max_output_tokens,
+            temperature: self.temperature,
         };
 
         let mut body = serde_json::to_value(&req)
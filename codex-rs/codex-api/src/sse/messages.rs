@@ -0,0 +1,518 @@
+use crate::common::ResponseEvent;
+use crate::common::ResponseStream;
+use crate::error::ApiError;
+use crate::telemetry::SseTelemetry;
+use codex_client::StreamResponse;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
+use eventsource_stream::Eventsource;
+use futures::Stream;
+use futures::StreamExt;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio::time::timeout;
+use tracing::debug;
+use tracing::trace;
+
+pub(crate) fn spawn_messages_stream(
+    stream_response: StreamResponse,
+    idle_timeout: Duration,
+    telemetry: Option<std::sync::Arc<dyn SseTelemetry>>,
+) -> ResponseStream {
+    let (tx_event, rx_event) = mpsc::channel::<Result<ResponseEvent, ApiError>>(1600);
+    tokio::spawn(async move {
+        process_messages_sse(stream_response.bytes, tx_event, idle_timeout, telemetry).await;
+    });
+    ResponseStream { rx_event }
+}
+
+/// A content block that has started streaming but not yet stopped.
+#[derive(Debug)]
+enum BlockState {
+    Text {
+        text: String,
+    },
+    ToolUse {
+        id: String,
+        name: String,
+        input: String,
+    },
+    Thinking {
+        thinking: String,
+        signature: String,
+    },
+    RedactedThinking {
+        data: String,
+    },
+}
+
+/// Token counters reported across `message_start` and `message_delta`.
+#[derive(Debug, Default)]
+struct MessagesUsage {
+    input_tokens: i64,
+    cache_creation_input_tokens: i64,
+    cache_read_input_tokens: i64,
+    output_tokens: i64,
+}
+
+impl MessagesUsage {
+    fn update(&mut self, usage: &Value) {
+        let read = |key: &str| usage.get(key).and_then(Value::as_i64);
+        if let Some(tokens) = read("input_tokens") {
+            self.input_tokens = tokens;
+        }
+        if let Some(tokens) = read("cache_creation_input_tokens") {
+            self.cache_creation_input_tokens = tokens;
+        }
+        if let Some(tokens) = read("cache_read_input_tokens") {
+            self.cache_read_input_tokens = tokens;
+        }
+        if let Some(tokens) = read("output_tokens") {
+            self.output_tokens = tokens;
+        }
+    }
+}
+
+impl From<MessagesUsage> for TokenUsage {
+    fn from(usage: MessagesUsage) -> Self {
+        // Anthropic reports cached and uncached input separately; Codex counts
+        // cached tokens as a subset of the input.
+        let input_tokens =
+            usage.input_tokens + usage.cache_creation_input_tokens + usage.cache_read_input_tokens;
+        TokenUsage {
+            input_tokens,
+            cached_input_tokens: usage.cache_read_input_tokens,
+            output_tokens: usage.output_tokens,
+            reasoning_output_tokens: 0,
+            total_tokens: input_tokens + usage.output_tokens,
+        }
+    }
+}
+
+/// Reasoning item id marking a `redacted_thinking` block, whose opaque data
+/// is kept in `encrypted_content` so it can be sent back verbatim.
+pub(crate) const REDACTED_THINKING_ID: &str = "redacted_thinking";
+
+/// Processes Server-Sent Events from the Anthropic Messages streaming API.
+///
+/// Text blocks become assistant messages, `tool_use` blocks become function
+/// calls and thinking blocks become reasoning items whose summary carries the
+/// thinking text and whose `encrypted_content` carries the signature needed
+/// to send the block back on the next request.
+pub async fn process_messages_sse<S>(
+    stream: S,
+    tx_event: mpsc::Sender<Result<ResponseEvent, ApiError>>,
+    idle_timeout: Duration,
+    telemetry: Option<std::sync::Arc<dyn SseTelemetry>>,
+) where
+    S: Stream<Item = Result<bytes::Bytes, codex_client::TransportError>> + Unpin,
+{
+    let mut stream = stream.eventsource();
+    let mut blocks: HashMap<u64, BlockState> = HashMap::new();
+    let mut response_id = String::new();
+    let mut usage = MessagesUsage::default();
+
+    loop {
+        let start = Instant::now();
+        let response = timeout(idle_timeout, stream.next()).await;
+        if let Some(t) = telemetry.as_ref() {
+            t.on_sse_poll(&response, start.elapsed());
+        }
+        let sse = match response {
+            Ok(Some(Ok(sse))) => sse,
+            Ok(Some(Err(e))) => {
+                let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
+                return;
+            }
+            Ok(None) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream(
+                        "stream closed before message_stop".into(),
+                    )))
+                    .await;
+                return;
+            }
+            Err(_) => {
+                let _ = tx_event
+                    .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
+                    .await;
+                return;
+            }
+        };
+
+        trace!("SSE event: {}", sse.data);
+
+        let data = sse.data.trim();
+        if data.is_empty() {
+            continue;
+        }
+
+        let event: Value = match serde_json::from_str(data) {
+            Ok(event) => event,
+            Err(err) => {
+                debug!("Failed to parse Messages SSE event: {err}, data: {data}");
+                continue;
+            }
+        };
+        let index = event.get("index").and_then(Value::as_u64).unwrap_or(0);
+
+        match event
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+        {
+            "message_start" => {
+                if let Some(message) = event.get("message") {
+                    if let Some(id) = message.get("id").and_then(Value::as_str) {
+                        response_id = id.to_string();
+                    }
+                    if let Some(message_usage) = message.get("usage") {
+                        usage.update(message_usage);
+                    }
+                }
+                let _ = tx_event.send(Ok(ResponseEvent::Created)).await;
+            }
+            "content_block_start" => {
+                let Some(block) = event.get("content_block") else {
+                    continue;
+                };
+                let text_field = |key: &str| {
+                    block
+                        .get(key)
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string()
+                };
+                let state = match block.get("type").and_then(Value::as_str) {
+                    Some("text") => {
+                        let item = ResponseItem::Message {
+                            id: None,
+                            role: "assistant".to_string(),
+                            content: vec![],
+                        };
+                        let _ = tx_event
+                            .send(Ok(ResponseEvent::OutputItemAdded(item)))
+                            .await;
+                        let text = text_field("text");
+                        if !text.is_empty() {
+                            let _ = tx_event
+                                .send(Ok(ResponseEvent::OutputTextDelta(text.clone())))
+                                .await;
+                        }
+                        BlockState::Text { text }
+                    }
+                    Some("tool_use") => BlockState::ToolUse {
+                        id: text_field("id"),
+                        name: text_field("name"),
+                        input: String::new(),
+                    },
+                    Some("thinking") => {
+                        let item = ResponseItem::Reasoning {
+                            id: String::new(),
+                            summary: Vec::new(),
+                            content: None,
+                            encrypted_content: None,
+                        };
+                        let _ = tx_event
+                            .send(Ok(ResponseEvent::OutputItemAdded(item)))
+                            .await;
+                        BlockState::Thinking {
+                            thinking: text_field("thinking"),
+                            signature: text_field("signature"),
+                        }
+                    }
+                    Some("redacted_thinking") => BlockState::RedactedThinking {
+                        data: text_field("data"),
+                    },
+                    other => {
+                        debug!("Ignoring unsupported Messages content block: {other:?}");
+                        continue;
+                    }
+                };
+                blocks.insert(index, state);
+            }
+            "content_block_delta" => {
+                let (Some(delta), Some(state)) = (event.get("delta"), blocks.get_mut(&index))
+                else {
+                    continue;
+                };
+                let field = |key: &str| delta.get(key).and_then(Value::as_str);
+                match (delta.get("type").and_then(Value::as_str), state) {
+                    (Some("text_delta"), BlockState::Text { text }) => {
+                        if let Some(delta) = field("text") {
+                            text.push_str(delta);
+                            let _ = tx_event
+                                .send(Ok(ResponseEvent::OutputTextDelta(delta.to_string())))
+                                .await;
+                        }
+                    }
+                    (Some("input_json_delta"), BlockState::ToolUse { input, .. }) => {
+                        if let Some(partial) = field("partial_json") {
+                            input.push_str(partial);
+                        }
+                    }
+                    (Some("thinking_delta"), BlockState::Thinking { thinking, .. }) => {
+                        if let Some(delta) = field("thinking") {
+                            thinking.push_str(delta);
+                            let _ = tx_event
+                                .send(Ok(ResponseEvent::ReasoningSummaryDelta {
+                                    delta: delta.to_string(),
+                                    summary_index: 0,
+                                }))
+                                .await;
+                        }
+                    }
+                    (Some("signature_delta"), BlockState::Thinking { signature, .. }) => {
+                        if let Some(delta) = field("signature") {
+                            signature.push_str(delta);
+                        }
+                    }
+                    (kind, _) => {
+                        debug!("Ignoring Messages delta {kind:?} for block {index}");
+                    }
+                }
+            }
+            "content_block_stop" => {
+                if let Some(state) = blocks.remove(&index) {
+                    let item = finish_block(state);
+                    let _ = tx_event.send(Ok(ResponseEvent::OutputItemDone(item))).await;
+                }
+            }
+            "message_delta" => {
+                if let Some(delta_usage) = event.get("usage") {
+                    usage.update(delta_usage);
+                }
+            }
+            "message_stop" => {
+                let _ = tx_event
+                    .send(Ok(ResponseEvent::Completed {
+                        response_id,
+                        token_usage: Some(usage.into()),
+                    }))
+                    .await;
+                return;
+            }
+            "error" => {
+                let error = event.get("error").cloned().unwrap_or_default();
+                let _ = tx_event.send(Err(map_stream_error(&error))).await;
+                return;
+            }
+            // `ping` and future event types.
+            _ => {}
+        }
+    }
+}
+
+fn finish_block(state: BlockState) -> ResponseItem {
+    match state {
+        BlockState::Text { text } => ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText { text }],
+        },
+        BlockState::ToolUse { id, name, input } => ResponseItem::FunctionCall {
+            id: None,
+            name,
+            // Tools without parameters stream no input at all.
+            arguments: if input.trim().is_empty() {
+                "{}".to_string()
+            } else {
+                input
+            },
+            call_id: id,
+        },
+        BlockState::Thinking {
+            thinking,
+            signature,
+        } => ResponseItem::Reasoning {
+            id: String::new(),
+            summary: vec![ReasoningItemReasoningSummary::SummaryText { text: thinking }],
+            content: None,
+            encrypted_content: (!signature.is_empty()).then_some(signature),
+        },
+        BlockState::RedactedThinking { data } => ResponseItem::Reasoning {
+            id: REDACTED_THINKING_ID.to_string(),
+            summary: Vec::new(),
+            content: None,
+            encrypted_content: Some(data),
+        },
+    }
+}
+
+fn map_stream_error(error: &Value) -> ApiError {
+    let kind = error
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let message = error
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or("Messages stream error")
+        .to_string();
+    match kind {
+        "invalid_request_error" if message.contains("prompt is too long") => {
+            ApiError::ContextWindowExceeded
+        }
+        "overloaded_error" | "rate_limit_error" | "api_error" => ApiError::Retryable {
+            message,
+            delay: None,
+        },
+        _ => ApiError::Stream(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tokio_util::io::ReaderStream;
+
+    fn build_body(events: &[Value]) -> String {
+        let mut body = String::new();
+        for e in events {
+            let kind = e["type"].as_str().unwrap_or_default();
+            body.push_str(&format!("event: {kind}\ndata: {e}\n\n"));
+        }
+        body
+    }
+
+    async fn collect_events(body: &str) -> Vec<Result<ResponseEvent, ApiError>> {
+        let reader = ReaderStream::new(std::io::Cursor::new(body.to_string()))
+            .map_err(|err| codex_client::TransportError::Network(err.to_string()));
+        let (tx, mut rx) = mpsc::channel::<Result<ResponseEvent, ApiError>>(64);
+        tokio::spawn(process_messages_sse(
+            reader,
+            tx,
+            Duration::from_millis(1000),
+            None,
+        ));
+
+        let mut out = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            out.push(ev);
+        }
+        out
+    }
+
+    fn message_start() -> Value {
+        json!({
+            "type": "message_start",
+            "message": {
+                "id": "msg_1",
+                "usage": {"input_tokens": 10, "cache_read_input_tokens": 5, "output_tokens": 1}
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn streams_thinking_text_and_tool_use() {
+        let body = build_body(&[
+            message_start(),
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "Let me look."}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "sig"}}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}),
+            json!({"type": "ping"}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "Listing"}}),
+            json!({"type": "content_block_stop", "index": 1}),
+            json!({"type": "content_block_start", "index": 2, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "shell", "input": {}}}),
+            json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "{\"command\":"}}),
+            json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "[\"ls\"]}"}}),
+            json!({"type": "content_block_stop", "index": 2}),
+            json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 42}}),
+            json!({"type": "message_stop"}),
+        ]);
+
+        let events: Vec<ResponseEvent> = collect_events(&body)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        assert_matches!(
+            &events[..],
+            [
+                ResponseEvent::Created,
+                ResponseEvent::OutputItemAdded(ResponseItem::Reasoning { .. }),
+                ResponseEvent::ReasoningSummaryDelta { delta: thinking_delta, summary_index: 0 },
+                ResponseEvent::OutputItemDone(ResponseItem::Reasoning { summary, encrypted_content: Some(signature), .. }),
+                ResponseEvent::OutputItemAdded(ResponseItem::Message { .. }),
+                ResponseEvent::OutputTextDelta(text_delta),
+                ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }),
+                ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { call_id, name, arguments, .. }),
+                ResponseEvent::Completed { response_id, token_usage: Some(usage) },
+            ] if thinking_delta == "Let me look."
+                && summary == &vec![ReasoningItemReasoningSummary::SummaryText { text: "Let me look.".to_string() }]
+                && signature == "sig"
+                && text_delta == "Listing"
+                && content == &vec![ContentItem::OutputText { text: "Listing".to_string() }]
+                && call_id == "toolu_1"
+                && name == "shell"
+                && arguments == "{\"command\":[\"ls\"]}"
+                && response_id == "msg_1"
+                && usage == &TokenUsage {
+                    input_tokens: 15,
+                    cached_input_tokens: 5,
+                    output_tokens: 42,
+                    reasoning_output_tokens: 0,
+                    total_tokens: 57,
+                }
+        );
+    }
+
+    #[tokio::test]
+    async fn tool_use_without_input_gets_empty_object() {
+        let body = build_body(&[
+            message_start(),
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "list_mcp_resources", "input": {}}}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "message_stop"}),
+        ]);
+
+        let events = collect_events(&body).await;
+        assert_matches!(
+            &events[1],
+            Ok(ResponseEvent::OutputItemDone(ResponseItem::FunctionCall { arguments, .. }))
+                if arguments == "{}"
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_error_events() {
+        let body = build_body(&[
+            message_start(),
+            json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+        ]);
+        let events = collect_events(&body).await;
+        assert_matches!(
+            &events[..],
+            [Ok(ResponseEvent::Created), Err(ApiError::Retryable { message, .. })]
+                if message == "Overloaded"
+        );
+
+        assert_matches!(
+            map_stream_error(&json!({
+                "type": "invalid_request_error",
+                "message": "prompt is too long: 210000 tokens > 200000 maximum"
+            })),
+            ApiError::ContextWindowExceeded
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_when_stream_ends_before_message_stop() {
+        let body = build_body(&[message_start()]);
+        let events = collect_events(&body).await;
+        assert_eq!(events.len(), 2);
+        assert_matches!(&events[1], Err(ApiError::Stream(message)) if message == "stream closed before message_stop");
+    }
+}
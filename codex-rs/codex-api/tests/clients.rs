@@ -7,6 +7,7 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use codex_api::AuthProvider;
 use codex_api::ChatClient;
+use codex_api::MessagesClient;
 use codex_api::Provider;
 use codex_api::ResponsesClient;
 use codex_api::ResponsesOptions;
@@ -291,6 +292,29 @@ async fn streaming_client_adds_auth_headers() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn messages_client_uses_messages_path_and_api_key_header() -> Result<()> {
+    let state = RecordingState::default();
+    let transport = RecordingTransport::new(state.clone());
+    let auth = StaticAuth::new("sk-ant-test", "acct-1");
+    let client = MessagesClient::new(transport, provider("anthropic", WireApi::Messages), auth);
+
+    let body = serde_json::json!({ "model": "claude-test" });
+    let _stream = client.stream(body, HeaderMap::new()).await?;
+
+    let requests = state.take_stream_requests();
+    assert_path_ends_with(&requests, "/messages");
+    let req = &requests[0];
+    assert_eq!(
+        req.headers
+            .get("x-api-key")
+            .and_then(|value| value.to_str().ok()),
+        Some("sk-ant-test")
+    );
+    assert_eq!(req.headers.get(http::header::AUTHORIZATION), None);
+    Ok(())
+}
+
 #[tokio::test]
 async fn streaming_client_retries_on_transport_error() -> Result<()> {
     let transport = FlakyTransport::new();
@@ -312,6 +336,8 @@ async fn streaming_client_retries_on_transport_error() -> Result<()> {
         tools: Vec::<Value>::new(),
         parallel_tool_calls: false,
         output_schema: None,
+        max_output_tokens: None,
+        temperature: None,
     };
 
     let options = ResponsesOptions::default();
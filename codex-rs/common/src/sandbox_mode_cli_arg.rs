@@ -3,8 +3,8 @@
 //! This mirrors the variants of [`codex_core::protocol::SandboxPolicy`], but
 //! without any of the associated data so it can be expressed as a simple flag
 //! on the command-line. Users that need to tweak the advanced options for
-//! `workspace-write` can continue to do so via `-c` overrides or their
-//! `config.toml`.
+//! `workspace-write` or `restricted-read` can continue to do so via `-c`
+//! overrides or their `config.toml`.
 
 use clap::ValueEnum;
 use codex_protocol::config_types::SandboxMode;
@@ -15,6 +15,7 @@ pub enum SandboxModeCliArg {
     ReadOnly,
     WorkspaceWrite,
     DangerFullAccess,
+    RestrictedRead,
 }
 
 impl From<SandboxModeCliArg> for SandboxMode {
@@ -23,6 +24,7 @@ impl From<SandboxModeCliArg> for SandboxMode {
             SandboxModeCliArg::ReadOnly => SandboxMode::ReadOnly,
             SandboxModeCliArg::WorkspaceWrite => SandboxMode::WorkspaceWrite,
             SandboxModeCliArg::DangerFullAccess => SandboxMode::DangerFullAccess,
+            SandboxModeCliArg::RestrictedRead => SandboxMode::RestrictedRead,
         }
     }
 }
@@ -43,5 +45,9 @@ mod tests {
             SandboxMode::DangerFullAccess,
             SandboxModeCliArg::DangerFullAccess.into()
         );
+        assert_eq!(
+            SandboxMode::RestrictedRead,
+            SandboxModeCliArg::RestrictedRead.into()
+        );
     }
 }
@@ -40,6 +40,41 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             }
             summary
         }
+        SandboxPolicy::RestrictedRead {
+            readable_roots,
+            writable_roots,
+            network_access,
+            exclude_tmpdir_env_var,
+            exclude_slash_tmp,
+        } => {
+            let mut summary = "restricted-read".to_string();
+
+            let mut readable_entries = vec!["workdir".to_string()];
+            readable_entries.extend(
+                readable_roots
+                    .iter()
+                    .map(|p| p.to_string_lossy().to_string()),
+            );
+            summary.push_str(&format!(" read [{}]", readable_entries.join(", ")));
+
+            let mut writable_entries = vec!["workdir".to_string()];
+            if !*exclude_slash_tmp {
+                writable_entries.push("/tmp".to_string());
+            }
+            if !*exclude_tmpdir_env_var {
+                writable_entries.push("$TMPDIR".to_string());
+            }
+            writable_entries.extend(
+                writable_roots
+                    .iter()
+                    .map(|p| p.to_string_lossy().to_string()),
+            );
+            summary.push_str(&format!(" write [{}]", writable_entries.join(", ")));
+            if *network_access {
+                summary.push_str(" (network access enabled)");
+            }
+            summary
+        }
     }
 }
 
@@ -83,4 +118,24 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn restricted_read_summary_lists_readable_and_writable_roots() {
+        let root = if cfg!(windows) { "C:\\docs" } else { "/docs" };
+        let readable_root = AbsolutePathBuf::try_from(root).unwrap();
+        let summary = summarize_sandbox_policy(&SandboxPolicy::RestrictedRead {
+            readable_roots: vec![readable_root.clone()],
+            writable_roots: Vec::new(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: false,
+        });
+        assert_eq!(
+            summary,
+            format!(
+                "restricted-read read [workdir, {}] write [workdir, /tmp]",
+                readable_root.to_string_lossy()
+            )
+        );
+    }
 }
@@ -1,12 +1,14 @@
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 use crate::protocol::FileChange;
+use crate::protocol::PatchHunk;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
 use crate::tools::sandboxing::ExecApprovalRequirement;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub const CODEX_APPLY_PATCH_ARG1: &str = "--codex-run-as-apply-patch";
@@ -100,6 +102,213 @@ pub(crate) fn convert_apply_patch_to_protocol(
     result
 }
 
+/// Builds `apply_patch` input for the part of a patch the user approved hunk
+/// by hunk. Update hunks are placed by the line numbers in their `@@` headers
+/// against the current file contents, and each emitted chunk carries just
+/// enough leading context for `apply_patch` to find it at that position.
+pub(crate) fn patch_for_approved_changes(
+    changes: &HashMap<PathBuf, FileChange>,
+    cwd: &Path,
+) -> Result<String, String> {
+    let mut paths: Vec<&PathBuf> = changes.keys().collect();
+    paths.sort();
+
+    let mut patch = String::from("*** Begin Patch\n");
+    for path in paths {
+        let display = patch_path(path, cwd);
+        match &changes[path] {
+            FileChange::Add { content } => {
+                patch.push_str(&format!("*** Add File: {display}\n"));
+                for line in content.lines() {
+                    patch.push_str(&format!("+{line}\n"));
+                }
+            }
+            FileChange::Delete { .. } => {
+                patch.push_str(&format!("*** Delete File: {display}\n"));
+            }
+            FileChange::Update {
+                unified_diff,
+                move_path,
+            } => {
+                let file = cwd.join(path);
+                let original = std::fs::read_to_string(&file)
+                    .map_err(|err| format!("failed to read {}: {err}", file.display()))?;
+                let chunks = update_chunks(&original, unified_diff)
+                    .map_err(|err| format!("{display}: {err}"))?;
+                patch.push_str(&format!("*** Update File: {display}\n"));
+                if let Some(dest) = move_path {
+                    patch.push_str(&format!("*** Move to: {}\n", patch_path(dest, cwd)));
+                }
+                patch.push_str(&chunks);
+            }
+        }
+    }
+    patch.push_str("*** End Patch");
+    Ok(patch)
+}
+
+fn patch_path(path: &Path, cwd: &Path) -> String {
+    path.strip_prefix(cwd).unwrap_or(path).display().to_string()
+}
+
+/// A hunk of a unified diff: the 1-based start line on the old side and the
+/// body lines, each prefixed with ` `, `-` or `+`.
+struct DiffHunk<'a> {
+    old_start: usize,
+    body: Vec<&'a str>,
+}
+
+impl DiffHunk<'_> {
+    fn old_lines(&self) -> Vec<&str> {
+        self.body
+            .iter()
+            .filter(|line| !line.starts_with('+'))
+            .map(|line| line.get(1..).unwrap_or_default())
+            .collect()
+    }
+}
+
+fn parse_unified_hunks(diff: &str) -> Result<Vec<DiffHunk<'_>>, String> {
+    let mut hunks: Vec<DiffHunk<'_>> = Vec::new();
+    for line in diff.lines() {
+        if let Some(header) = line.strip_prefix("@@") {
+            let old_start = header
+                .split_whitespace()
+                .find_map(|token| token.strip_prefix('-'))
+                .and_then(|range| range.split(',').next())
+                .and_then(|start| start.parse().ok())
+                .ok_or_else(|| format!("invalid hunk header `{line}`"))?;
+            hunks.push(DiffHunk {
+                old_start,
+                body: Vec::new(),
+            });
+        } else if line.starts_with('\\') {
+            // "\ No newline at end of file"
+        } else if let Some(hunk) = hunks.last_mut() {
+            hunk.body.push(line);
+        }
+    }
+    Ok(hunks)
+}
+
+/// Chunks (in `apply_patch` syntax) that apply `unified_diff` to `original`.
+fn update_chunks(original: &str, unified_diff: &str) -> Result<String, String> {
+    let mut lines: Vec<&str> = original.split('\n').collect();
+    if lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    let hunks = parse_unified_hunks(unified_diff)?;
+
+    let mut out = String::new();
+    if hunks.is_empty() {
+        // A pure move: anchor a no-op chunk on the first line.
+        let Some(first) = lines.first() else {
+            return Err("cannot move an empty file without changes".to_string());
+        };
+        out.push_str(&format!("@@\n {first}\n"));
+        return Ok(out);
+    }
+
+    // `apply_patch` searches for each chunk from the end of the previous one.
+    let mut prev_end = 0;
+    for hunk in &hunks {
+        let old = hunk.old_lines();
+        let start = if old.is_empty() {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        let end = start + old.len();
+        if start < prev_end || lines.get(start..end) != Some(&old[..]) {
+            return Err(format!(
+                "hunk at line {} does not match the file",
+                hunk.old_start
+            ));
+        }
+
+        let (mut ctx_start, mut ctx_end) = (start, end);
+        if old.is_empty() && start < lines.len() {
+            // Chunks without old lines are appended at the end of the file,
+            // so anchor insertions elsewhere on a neighbouring line.
+            if start > prev_end {
+                ctx_start -= 1;
+            } else {
+                ctx_end += 1;
+            }
+        }
+        while ctx_start > prev_end
+            && first_match(&lines, prev_end, &lines[ctx_start..ctx_end]) != Some(ctx_start)
+        {
+            ctx_start -= 1;
+        }
+
+        out.push_str("@@\n");
+        for line in &lines[ctx_start..start] {
+            out.push_str(&format!(" {line}\n"));
+        }
+        for line in &hunk.body {
+            if line.is_empty() {
+                out.push_str(" \n");
+            } else {
+                out.push_str(&format!("{line}\n"));
+            }
+        }
+        for line in &lines[end..ctx_end] {
+            out.push_str(&format!(" {line}\n"));
+        }
+        prev_end = ctx_end;
+    }
+    Ok(out)
+}
+
+fn first_match(lines: &[&str], from: usize, pattern: &[&str]) -> Option<usize> {
+    if pattern.is_empty() {
+        return Some(from);
+    }
+    (from..=lines.len().saturating_sub(pattern.len()))
+        .find(|&i| lines.get(i..i + pattern.len()) == Some(pattern))
+}
+
+/// Tool-output note telling the model which hunks of its patch the user
+/// rejected or edited. Returns `None` when the patch was applied as proposed.
+pub(crate) fn hunk_review_feedback(
+    rejected_hunks: &[PatchHunk],
+    edited_hunks: &[PatchHunk],
+    cwd: &Path,
+) -> Option<String> {
+    if rejected_hunks.is_empty() && edited_hunks.is_empty() {
+        return None;
+    }
+    let render = |hunks: &[PatchHunk]| {
+        hunks
+            .iter()
+            .map(|hunk| {
+                format!(
+                    "*** {}\n{}",
+                    patch_path(&hunk.path, cwd),
+                    hunk.diff.trim_end()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let mut sections =
+        vec!["The user reviewed this patch hunk by hunk and applied only part of it.".to_string()];
+    if !rejected_hunks.is_empty() {
+        sections.push(format!(
+            "Rejected hunks (NOT applied):\n{}",
+            render(rejected_hunks)
+        ));
+    }
+    if !edited_hunks.is_empty() {
+        sections.push(format!(
+            "Hunks edited by the user (applied as shown):\n{}",
+            render(edited_hunks)
+        ));
+    }
+    Some(sections.join("\n\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,4 +332,80 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn approved_changes_anchor_hunks_at_their_line_numbers() {
+        let tmp = tempdir().expect("tmp");
+        let path = tmp.path().join("lib.rs");
+        std::fs::write(&path, "}\na\n}\nb\n}\n").expect("write");
+        // Only the last of the proposed hunks was accepted. Its context line
+        // `}` also appears earlier in the file.
+        let changes = HashMap::from([(
+            path,
+            FileChange::Update {
+                unified_diff: "@@ -5,1 +5,2 @@\n+c\n }\n".to_string(),
+                move_path: None,
+            },
+        )]);
+
+        let patch = patch_for_approved_changes(&changes, tmp.path()).expect("patch");
+
+        assert_eq!(
+            patch,
+            "*** Begin Patch\n*** Update File: lib.rs\n@@\n b\n+c\n }\n*** End Patch"
+        );
+        let action = match codex_apply_patch::maybe_parse_apply_patch_verified(
+            &["apply_patch".to_string(), patch],
+            tmp.path(),
+        ) {
+            codex_apply_patch::MaybeApplyPatchVerified::Body(action) => action,
+            other => panic!("expected a valid patch, got {other:?}"),
+        };
+        let Some(ApplyPatchFileChange::Update { new_content, .. }) =
+            action.changes().values().next()
+        else {
+            panic!("expected an update");
+        };
+        assert_eq!(new_content, "}\na\n}\nb\nc\n}\n");
+    }
+
+    #[test]
+    fn approved_insertion_gets_unique_leading_context() {
+        let tmp = tempdir().expect("tmp");
+        let path = tmp.path().join("a.txt");
+        std::fs::write(&path, "x\ny\nx\ny\nw\n").expect("write");
+        let changes = HashMap::from([(
+            path,
+            FileChange::Update {
+                unified_diff: "@@ -4,0 +5,1 @@\n+z\n".to_string(),
+                move_path: None,
+            },
+        )]);
+
+        let patch = patch_for_approved_changes(&changes, tmp.path()).expect("patch");
+
+        assert_eq!(
+            patch,
+            "*** Begin Patch\n*** Update File: a.txt\n@@\n y\n x\n y\n+z\n*** End Patch"
+        );
+    }
+
+    #[test]
+    fn approved_changes_reject_stale_hunks() {
+        let tmp = tempdir().expect("tmp");
+        let path = tmp.path().join("a.txt");
+        std::fs::write(&path, "one\ntwo\n").expect("write");
+        let changes = HashMap::from([(
+            path,
+            FileChange::Update {
+                unified_diff: "@@ -1,1 +1,1 @@\n-uno\n+eins\n".to_string(),
+                move_path: None,
+            },
+        )]);
+
+        assert_eq!(
+            patch_for_approved_changes(&changes, tmp.path()),
+            Err("a.txt: hunk at line 1 does not match the file".to_string())
+        );
+    }
 }
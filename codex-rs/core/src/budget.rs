@@ -0,0 +1,242 @@
+//! Token and cost budgets configured with `max_session_tokens`,
+//! `max_turn_tokens`, `max_session_cost_usd` and `max_turn_cost_usd`.
+//!
+//! Usage is counted as non-cached input plus output tokens (the same
+//! "blended" total shown in the status line), starting from when the session
+//! was created or resumed. Costs use the `model_prices` entry for the model
+//! that served each response; responses from unpriced models cost nothing.
+//!
+//! The session checks the budget after every model response. Crossing
+//! [`BUDGET_WARNING_RATIO`] of a limit emits a single warning for that limit;
+//! exceeding it aborts the turn with [`CodexErr::BudgetExceeded`].
+//!
+//! [`CodexErr::BudgetExceeded`]: crate::error::CodexErr::BudgetExceeded
+
+use crate::config::types::BudgetConfig;
+use crate::config::types::ModelPrice;
+use crate::error::BudgetExceededError;
+use crate::error::BudgetLimit;
+use crate::protocol::TokenUsage;
+
+/// Fraction of a limit at which a warning is emitted.
+const BUDGET_WARNING_RATIO: f64 = 0.8;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct BudgetUsage {
+    pub(crate) tokens: i64,
+    pub(crate) cost_usd: f64,
+}
+
+impl BudgetUsage {
+    pub(crate) fn record(&mut self, usage: &TokenUsage, price: Option<&ModelPrice>) {
+        self.tokens += usage.blended_total();
+        if let Some(price) = price {
+            self.cost_usd += cost_usd(usage, price);
+        }
+    }
+
+    fn since(&self, start: &BudgetUsage) -> BudgetUsage {
+        BudgetUsage {
+            tokens: self.tokens - start.tokens,
+            cost_usd: self.cost_usd - start.cost_usd,
+        }
+    }
+}
+
+fn cost_usd(usage: &TokenUsage, price: &ModelPrice) -> f64 {
+    let non_cached_input = usage.non_cached_input() as f64;
+    let cached_input = usage.cached_input() as f64;
+    let output = usage.output_tokens.max(0) as f64;
+    let cached_price = price.cached_input.unwrap_or(price.input);
+    (non_cached_input * price.input + cached_input * cached_price + output * price.output)
+        / 1_000_000.0
+}
+
+/// Session-wide usage, stored on `SessionState`.
+#[derive(Debug, Default)]
+pub(crate) struct SessionBudget {
+    pub(crate) usage: BudgetUsage,
+    warned: Vec<BudgetLimit>,
+}
+
+/// Per-turn view of the budget: the session usage when the turn started and
+/// which turn limits have already been warned about.
+#[derive(Debug)]
+pub(crate) struct TurnBudget {
+    start: BudgetUsage,
+    warned: Vec<BudgetLimit>,
+}
+
+impl TurnBudget {
+    pub(crate) fn new(session: &SessionBudget) -> Self {
+        Self {
+            start: session.usage,
+            warned: Vec::new(),
+        }
+    }
+}
+
+/// Compares current usage against `config`.
+///
+/// Returns the warnings that have not been emitted yet, or the first limit
+/// that has been exceeded.
+pub(crate) fn check_budget(
+    config: &BudgetConfig,
+    session: &mut SessionBudget,
+    turn: &mut TurnBudget,
+) -> Result<Vec<String>, BudgetExceededError> {
+    let turn_usage = session.usage.since(&turn.start);
+    let checks = [
+        (
+            BudgetLimit::SessionTokens,
+            session.usage.tokens as f64,
+            config.max_session_tokens.map(|max| max as f64),
+        ),
+        (
+            BudgetLimit::SessionCostUsd,
+            session.usage.cost_usd,
+            config.max_session_cost_usd,
+        ),
+        (
+            BudgetLimit::TurnTokens,
+            turn_usage.tokens as f64,
+            config.max_turn_tokens.map(|max| max as f64),
+        ),
+        (
+            BudgetLimit::TurnCostUsd,
+            turn_usage.cost_usd,
+            config.max_turn_cost_usd,
+        ),
+    ];
+
+    let mut warnings = Vec::new();
+    for (limit, used, max) in checks {
+        let Some(max) = max else {
+            continue;
+        };
+        if used > max {
+            return Err(BudgetExceededError { limit, used, max });
+        }
+        if used < max * BUDGET_WARNING_RATIO {
+            continue;
+        }
+        let warned = match limit {
+            BudgetLimit::SessionTokens | BudgetLimit::SessionCostUsd => &mut session.warned,
+            BudgetLimit::TurnTokens | BudgetLimit::TurnCostUsd => &mut turn.warned,
+        };
+        if warned.contains(&limit) {
+            continue;
+        }
+        warned.push(limit);
+        warnings.push(format!(
+            "This {} has used {} of its `{}` budget of {}; it will be stopped once the limit is exceeded.",
+            limit.scope(),
+            limit.format_amount(used),
+            limit.config_key(),
+            limit.format_amount(max),
+        ));
+    }
+    Ok(warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn usage(input: i64, cached: i64, output: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    #[test]
+    fn records_blended_tokens_and_cost() {
+        let price = ModelPrice {
+            input: 2.0,
+            cached_input: Some(0.5),
+            output: 10.0,
+        };
+        let mut recorded = BudgetUsage::default();
+        recorded.record(&usage(1_000_000, 400_000, 100_000), Some(&price));
+        recorded.record(&usage(1_000, 0, 0), None);
+
+        assert_eq!(recorded.tokens, 701_000);
+        // 600k non-cached * $2 + 400k cached * $0.5 + 100k output * $10.
+        assert!((recorded.cost_usd - 2.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn warns_once_then_fails_when_exceeded() {
+        let config = BudgetConfig {
+            max_session_tokens: Some(1_000),
+            ..Default::default()
+        };
+        let mut session = SessionBudget::default();
+        let mut turn = TurnBudget::new(&session);
+
+        session.usage.record(&usage(500, 0, 0), None);
+        assert_eq!(
+            check_budget(&config, &mut session, &mut turn).ok(),
+            Some(Vec::new())
+        );
+
+        session.usage.record(&usage(300, 0, 0), None);
+        let warnings = check_budget(&config, &mut session, &mut turn).unwrap_or_default();
+        assert_eq!(
+            warnings,
+            vec![
+                "This session has used 800 tokens of its `max_session_tokens` budget of 1000 tokens; it will be stopped once the limit is exceeded."
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            check_budget(&config, &mut session, &mut turn).ok(),
+            Some(Vec::new())
+        );
+
+        session.usage.record(&usage(201, 0, 0), None);
+        let err = check_budget(&config, &mut session, &mut turn).expect_err("over budget");
+        assert_eq!(err.limit, BudgetLimit::SessionTokens);
+        assert_eq!(
+            err.to_string(),
+            "Token budget exceeded: this session used 1001 tokens, over the `max_session_tokens` limit of 1000 tokens."
+        );
+    }
+
+    #[test]
+    fn turn_limits_only_count_usage_since_turn_start() {
+        let config = BudgetConfig {
+            max_turn_cost_usd: Some(1.0),
+            model_prices: HashMap::new(),
+            ..Default::default()
+        };
+        let price = ModelPrice {
+            input: 1.0,
+            cached_input: None,
+            output: 1.0,
+        };
+        let mut session = SessionBudget::default();
+        session.usage.record(&usage(900_000, 0, 0), Some(&price));
+
+        let mut turn = TurnBudget::new(&session);
+        session.usage.record(&usage(500_000, 0, 0), Some(&price));
+        assert_eq!(
+            check_budget(&config, &mut session, &mut turn).ok(),
+            Some(Vec::new())
+        );
+
+        session.usage.record(&usage(600_000, 0, 0), Some(&price));
+        let err = check_budget(&config, &mut session, &mut turn).expect_err("over budget");
+        assert_eq!(err.limit, BudgetLimit::TurnCostUsd);
+        assert_eq!(
+            err.to_string(),
+            "Cost budget exceeded: this turn used $1.10, over the `max_turn_cost_usd` limit of $1.00."
+        );
+    }
+}
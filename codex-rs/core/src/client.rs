@@ -8,6 +8,8 @@ use codex_api::AggregateStreamExt;
 use codex_api::ChatClient as ApiChatClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
+use codex_api::MessagesClient as ApiMessagesClient;
+use codex_api::MessagesOptions as ApiMessagesOptions;
 use codex_api::Prompt as ApiPrompt;
 use codex_api::RequestTelemetry;
 use codex_api::ReqwestTransport;
@@ -62,6 +64,7 @@ use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
+use crate::tools::spec::create_tools_json_for_messages_api;
 use crate::tools::spec::create_tools_json_for_responses_api;
 
 #[derive(Debug)]
@@ -232,8 +235,8 @@ impl ModelClient {
 }
 
 impl ModelClientSession {
-    /// Streams a single model turn using either the Responses or Chat
-    /// Completions wire API, depending on the configured provider.
+    /// Streams a single model turn using the Responses, Chat Completions or
+    /// Anthropic Messages wire API, depending on the configured provider.
     ///
     /// For Chat providers, the underlying stream is optionally aggregated
     /// based on the `show_raw_agent_reasoning` flag in the config.
@@ -241,6 +244,13 @@ impl ModelClientSession {
         match self.state.provider.wire_api {
             WireApi::Responses => self.stream_responses_api(prompt).await,
             WireApi::ResponsesWebsocket => self.stream_responses_websocket(prompt).await,
+            WireApi::Messages => {
+                let api_stream = self.stream_messages_api(prompt).await?;
+                Ok(map_response_stream(
+                    api_stream,
+                    self.state.otel_manager.clone(),
+                ))
+            }
             WireApi::Chat => {
                 let api_stream = self.stream_chat_completions(prompt).await?;
 
@@ -263,7 +273,12 @@ impl ModelClientSession {
         let model_info = self.state.model_info.clone();
         let instructions = prompt.get_full_instructions(&model_info).into_owned();
         let tools_json: Vec<Value> = create_tools_json_for_responses_api(&prompt.tools)?;
-        Ok(build_api_prompt(prompt, instructions, tools_json))
+        let mut api_prompt = build_api_prompt(prompt, instructions, tools_json);
+        // Reasoning models reject any temperature other than the default.
+        if model_info.supports_reasoning_summaries {
+            api_prompt.temperature = None;
+        }
+        Ok(api_prompt)
     }
 
     fn build_responses_options(
@@ -373,6 +388,8 @@ impl ModelClientSession {
             include: include.clone(),
             prompt_cache_key: prompt_cache_key.clone(),
             text: text.clone(),
+            max_output_tokens: api_prompt.max_output_tokens,
+            temperature: api_prompt.temperature,
         };
 
         ResponsesWsRequest::ResponseCreate(payload)
@@ -478,6 +495,79 @@ impl ModelClientSession {
         }
     }
 
+    /// Streams a turn via the Anthropic Messages API.
+    ///
+    /// This path is only used when the provider is configured with
+    /// `WireApi::Messages`. Extended thinking is enabled when a reasoning
+    /// effort is explicitly selected; `output_schema` is not supported.
+    /// `model_max_output_tokens` must be configured since the API requires an
+    /// explicit output limit.
+    async fn stream_messages_api(&self, prompt: &Prompt) -> Result<ApiResponseStream> {
+        if prompt.output_schema.is_some() {
+            return Err(CodexErr::UnsupportedOperation(
+                "output_schema is not supported for Messages API".to_string(),
+            ));
+        }
+        let Some(configured_max_tokens) = self.state.config.model_max_output_tokens else {
+            return Err(CodexErr::UnsupportedOperation(
+                "set `model_max_output_tokens` to use a provider with wire_api = \"messages\""
+                    .to_string(),
+            ));
+        };
+        let max_tokens = prompt
+            .max_output_tokens
+            .map_or(configured_max_tokens, |limit| {
+                limit.min(configured_max_tokens)
+            });
+
+        let auth_manager = self.state.auth_manager.clone();
+        let model_info = self.state.model_info.clone();
+        let instructions = prompt.get_full_instructions(&model_info).into_owned();
+        let tools_json = create_tools_json_for_messages_api(&prompt.tools)?;
+        let api_prompt = build_api_prompt(prompt, instructions, tools_json);
+        let conversation_id = self.state.conversation_id.to_string();
+        let thinking_budget = messages_thinking_budget(self.state.effort, max_tokens);
+
+        let mut auth_recovery = auth_manager
+            .as_ref()
+            .map(super::auth::AuthManager::unauthorized_recovery);
+        loop {
+            let auth = match auth_manager.as_ref() {
+                Some(manager) => manager.auth().await,
+                None => None,
+            };
+            let api_provider = self
+                .state
+                .provider
+                .to_api_provider(auth.as_ref().map(|a| a.mode))?;
+            let api_auth = auth_provider_from_auth(auth.clone(), &self.state.provider)?;
+            let transport = ReqwestTransport::new(build_reqwest_client());
+            let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
+            let client = ApiMessagesClient::new(transport, api_provider, api_auth)
+                .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
+
+            let options = ApiMessagesOptions {
+                max_tokens,
+                thinking_budget,
+                conversation_id: Some(conversation_id.clone()),
+            };
+            let stream_result = client
+                .stream_prompt(&self.state.model_info.slug, &api_prompt, options)
+                .await;
+
+            match stream_result {
+                Ok(stream) => return Ok(stream),
+                Err(ApiError::Transport(TransportError::Http { status, .. }))
+                    if status == StatusCode::UNAUTHORIZED =>
+                {
+                    handle_unauthorized(status, &mut auth_recovery).await?;
+                    continue;
+                }
+                Err(err) => return Err(map_api_error(err)),
+            }
+        }
+    }
+
     /// Streams a turn via the OpenAI Responses API.
     ///
     /// Handles SSE fixtures, reasoning summaries, verbosity, and the
@@ -611,9 +701,31 @@ fn build_api_prompt(prompt: &Prompt, instructions: String, tools_json: Vec<Value
         tools: tools_json,
         parallel_tool_calls: prompt.parallel_tool_calls,
         output_schema: prompt.output_schema.clone(),
+        max_output_tokens: prompt.max_output_tokens,
+        temperature: prompt.temperature,
     }
 }
 
+/// Maps the configured reasoning effort onto an extended thinking budget for
+/// the Messages API. `None` leaves thinking disabled.
+/// Smallest `budget_tokens` the Messages API accepts for extended thinking.
+const MIN_THINKING_BUDGET_TOKENS: u64 = 1_024;
+
+fn messages_thinking_budget(effort: Option<ReasoningEffortConfig>, max_tokens: u64) -> Option<u64> {
+    let budget = match effort? {
+        ReasoningEffortConfig::None | ReasoningEffortConfig::Minimal => return None,
+        ReasoningEffortConfig::Low => 4_000,
+        ReasoningEffortConfig::Medium => 10_000,
+        ReasoningEffortConfig::High => 24_000,
+        ReasoningEffortConfig::XHigh => 30_000,
+    };
+    // Thinking counts towards `max_tokens`; leave at least half for the answer.
+    // The API rejects budgets below its minimum, so skip thinking entirely when
+    // the output limit is too small to fit one.
+    let budget = budget.min(max_tokens / 2);
+    (budget >= MIN_THINKING_BUDGET_TOKENS).then_some(budget)
+}
+
 fn beta_feature_headers(config: &Config) -> ApiHeaderMap {
     let enabled = FEATURES
         .iter()
@@ -765,3 +877,34 @@ impl SseTelemetry for ApiTelemetry {
         self.otel_manager.log_sse_event(result, duration);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn thinking_budget_is_clamped_to_half_of_max_tokens() {
+        assert_eq!(
+            messages_thinking_budget(Some(ReasoningEffortConfig::High), 8_000),
+            Some(4_000)
+        );
+        assert_eq!(
+            messages_thinking_budget(Some(ReasoningEffortConfig::Low), 64_000),
+            Some(4_000)
+        );
+    }
+
+    #[test]
+    fn thinking_is_disabled_when_max_tokens_cannot_fit_the_minimum_budget() {
+        assert_eq!(
+            messages_thinking_budget(Some(ReasoningEffortConfig::High), 2_046),
+            None
+        );
+        assert_eq!(
+            messages_thinking_budget(Some(ReasoningEffortConfig::High), 2_048),
+            Some(1_024)
+        );
+        assert_eq!(messages_thinking_budget(None, 64_000), None);
+    }
+}
@@ -39,6 +39,13 @@ pub struct Prompt {
 
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+
+    /// Upper bound on generated tokens; `None` leaves the provider default.
+    pub(crate) max_output_tokens: Option<u64>,
+
+    /// Sampling temperature; `None` leaves the provider default. Dropped for
+    /// reasoning models, which only support the default.
+    pub(crate) temperature: Option<f64>,
 }
 
 impl Prompt {
@@ -335,6 +342,8 @@ mod tests {
                 verbosity: Some(OpenAiVerbosity::Low),
                 format: None,
             }),
+            max_output_tokens: None,
+            temperature: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -373,6 +382,8 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: Some(text_controls),
+            max_output_tokens: None,
+            temperature: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
@@ -409,6 +420,8 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             text: None,
+            max_output_tokens: None,
+            temperature: None,
         };
 
         let v = serde_json::to_value(&req).expect("json");
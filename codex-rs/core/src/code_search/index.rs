@@ -0,0 +1,273 @@
+//! On-disk symbol index for a single workspace root.
+//!
+//! Each file records its size and modification time so a refresh only
+//! re-reads files that changed since the last query, and each directory
+//! records its modification time so the tree is only walked again when entries
+//! were added, removed or renamed. The index is stored as JSON under
+//! `$CODEX_HOME/code_search/`, keyed by a hash of the root path and readable
+//! only by its owner. Identifiers are stored as hashes, so the index holds no
+//! plaintext copy of the source beyond the names of definitions.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde::Serialize;
+use sha1::Digest;
+use sha1::Sha1;
+
+use super::symbols::Symbol;
+use super::symbols::extract_identifiers;
+use super::symbols::extract_symbols;
+
+/// Bumped whenever the on-disk layout or the extraction rules change so stale
+/// indexes are rebuilt instead of misread.
+const INDEX_VERSION: u32 = 2;
+/// Repository metadata is never indexed.
+const EXCLUDED_PATTERNS: &[&str] = &[".git"];
+/// A change to one of these can hide or reveal other files, so it forces a
+/// walk even when no directory changed.
+const IGNORE_FILE_NAMES: &[&str] = &[".gitignore", ".ignore"];
+/// Files larger than this are almost always generated or vendored.
+const MAX_INDEXED_FILE_BYTES: u64 = 1024 * 1024;
+/// Prefix inspected for NUL bytes to skip binary files.
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+const WALK_THREADS: NonZeroUsize = NonZeroUsize::new(4).unwrap();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct IndexedFile {
+    modified_ms: u64,
+    len: u64,
+    pub(crate) symbols: Vec<Symbol>,
+    /// Sorted, distinct hashes of the identifiers used in the file, used to
+    /// find reference candidates. Candidates are confirmed against the file's
+    /// contents, so a hash collision only costs an extra read.
+    identifier_hashes: Vec<u64>,
+}
+
+impl IndexedFile {
+    pub(crate) fn mentions(&self, identifier: &str) -> bool {
+        self.identifier_hashes
+            .binary_search(&identifier_hash(identifier))
+            .is_ok()
+    }
+
+    fn is_current(&self, metadata: &fs::Metadata) -> bool {
+        self.modified_ms == modified_ms(metadata) && self.len == metadata.len()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CodeIndex {
+    version: u32,
+    root: PathBuf,
+    /// Modification time of every directory seen by the last walk, keyed like
+    /// `files`; the root itself is `""`.
+    dirs: BTreeMap<String, u64>,
+    /// Keyed by path relative to `root`, using `/` separators.
+    pub(crate) files: BTreeMap<String, IndexedFile>,
+}
+
+/// Counts reported by [`CodeIndex::refresh`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RefreshStats {
+    /// Whether the tree was walked because a directory changed.
+    pub(crate) walked: bool,
+    pub(crate) indexed: usize,
+    pub(crate) removed: usize,
+}
+
+impl RefreshStats {
+    pub(crate) fn changed(self) -> bool {
+        self.walked || self.indexed > 0 || self.removed > 0
+    }
+}
+
+impl CodeIndex {
+    fn empty(root: &Path) -> Self {
+        Self {
+            version: INDEX_VERSION,
+            root: root.to_path_buf(),
+            dirs: BTreeMap::new(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Loads the stored index for `root`, or starts an empty one when nothing
+    /// usable is on disk.
+    pub(crate) fn load(index_dir: &Path, root: &Path) -> Self {
+        let path = index_path(index_dir, root);
+        let stored = fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CodeIndex>(&bytes).ok());
+        match stored {
+            Some(index) if index.version == INDEX_VERSION && index.root == root => index,
+            _ => Self::empty(root),
+        }
+    }
+
+    /// Writes the index through a uniquely named temporary file, so
+    /// concurrent sessions never interleave their writes, and with owner-only
+    /// permissions.
+    pub(crate) fn save(&self, index_dir: &Path) -> anyhow::Result<()> {
+        fs::create_dir_all(index_dir)
+            .with_context(|| format!("failed to create {}", index_dir.display()))?;
+        let path = index_path(index_dir, &self.root);
+        let mut tmp = tempfile::NamedTempFile::new_in(index_dir)
+            .with_context(|| format!("failed to create a file in {}", index_dir.display()))?;
+        serde_json::to_writer(&mut tmp, self)
+            .with_context(|| format!("failed to write {}", tmp.path().display()))?;
+        tmp.persist(&path)
+            .with_context(|| format!("failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Re-indexes every file whose size or modification time changed. The
+    /// root is walked again (respecting ignore files) only when a directory
+    /// or ignore file changed since the last walk; files that disappeared are
+    /// dropped.
+    pub(crate) fn refresh(&mut self, cancel_flag: Arc<AtomicBool>) -> anyhow::Result<RefreshStats> {
+        let mut stats = RefreshStats::default();
+        let paths = match self.unchanged_paths() {
+            Some(paths) => paths,
+            None => {
+                let list = codex_file_search::list_files(
+                    &self.root,
+                    EXCLUDED_PATTERNS.iter().map(ToString::to_string).collect(),
+                    WALK_THREADS,
+                    cancel_flag.clone(),
+                    true,
+                )?;
+                // An interrupted walk looks like an empty tree; keep the old index.
+                if cancel_flag.load(Ordering::Relaxed) {
+                    anyhow::bail!("code search was cancelled");
+                }
+                stats.walked = true;
+                self.dirs = list
+                    .dirs
+                    .into_iter()
+                    .filter_map(|dir| {
+                        let metadata = fs::metadata(self.root.join(&dir)).ok()?;
+                        Some((dir.replace('\\', "/"), modified_ms(&metadata)))
+                    })
+                    .collect();
+                let mut paths = list.files;
+                paths.sort_unstable();
+                paths
+            }
+        };
+
+        let mut files = BTreeMap::new();
+        for relative in paths {
+            let absolute = self.root.join(&relative);
+            let Ok(metadata) = fs::metadata(&absolute) else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let key = relative.replace('\\', "/");
+
+            let file = match self.files.remove(&key) {
+                Some(existing) if existing.is_current(&metadata) => existing,
+                _ => {
+                    stats.indexed += 1;
+                    // Large and binary files are kept with no symbols so they
+                    // are not re-read on every refresh.
+                    let text = if metadata.len() > MAX_INDEXED_FILE_BYTES {
+                        String::new()
+                    } else {
+                        read_text(&absolute).unwrap_or_default()
+                    };
+                    let mut identifier_hashes: Vec<u64> = extract_identifiers(&text)
+                        .into_iter()
+                        .map(|identifier| identifier_hash(&identifier))
+                        .collect();
+                    identifier_hashes.sort_unstable();
+                    identifier_hashes.dedup();
+                    IndexedFile {
+                        modified_ms: modified_ms(&metadata),
+                        len: metadata.len(),
+                        symbols: extract_symbols(&text),
+                        identifier_hashes,
+                    }
+                }
+            };
+            files.insert(key, file);
+        }
+
+        stats.removed = self.files.len();
+        self.files = files;
+        Ok(stats)
+    }
+
+    /// Returns the indexed paths when the tree has the same entries as at the
+    /// last walk: adding, removing or renaming an entry updates the
+    /// modification time of its directory.
+    fn unchanged_paths(&self) -> Option<Vec<String>> {
+        if self.dirs.is_empty() {
+            return None;
+        }
+        for (dir, modified) in &self.dirs {
+            let metadata = fs::metadata(self.root.join(dir)).ok()?;
+            if modified_ms(&metadata) != *modified {
+                return None;
+            }
+        }
+        for (path, file) in &self.files {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            if IGNORE_FILE_NAMES.contains(&name) {
+                let metadata = fs::metadata(self.root.join(path)).ok()?;
+                if !file.is_current(&metadata) {
+                    return None;
+                }
+            }
+        }
+        Some(self.files.keys().cloned().collect())
+    }
+}
+
+fn modified_ms(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+fn identifier_hash(identifier: &str) -> u64 {
+    let digest = Sha1::digest(identifier.as_bytes());
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(prefix)
+}
+
+/// Reads `path` as text, returning `None` for binary files.
+pub(crate) fn read_text(path: &Path) -> Option<String> {
+    let mut bytes = Vec::new();
+    fs::File::open(path).ok()?.read_to_end(&mut bytes).ok()?;
+    let sniff = &bytes[..bytes.len().min(BINARY_SNIFF_BYTES)];
+    if sniff.contains(&0) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn index_path(index_dir: &Path, root: &Path) -> PathBuf {
+    let digest = Sha1::digest(root.to_string_lossy().as_bytes());
+    let name = digest
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    index_dir.join(format!("{name}.json"))
+}
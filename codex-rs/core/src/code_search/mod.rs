@@ -0,0 +1,378 @@
+//! Backing store for the `code_search` tool.
+//!
+//! [`CodeSearchManager`] keeps one [`CodeIndex`] per workspace root in memory
+//! and mirrors it to disk so later sessions start warm. Every query first
+//! refreshes the index incrementally, so results reflect edits made earlier in
+//! the turn without re-reading unchanged files.
+
+mod index;
+mod symbols;
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+
+use anyhow::anyhow;
+use codex_file_search::FuzzyScorer;
+use tracing::warn;
+
+use index::CodeIndex;
+use index::read_text;
+use symbols::Symbol;
+use symbols::contains_word;
+
+const INDEX_DIR_NAME: &str = "code_search";
+/// Reference lines longer than this are cut so minified files stay readable.
+const MAX_LINE_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodeSearchKind {
+    /// Where a symbol with exactly this name is defined.
+    Definition,
+    /// Every line that mentions the identifier as a whole word.
+    References,
+    /// Definitions ranked by fuzzy similarity to the query.
+    Fuzzy,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct CodeSearchQuery {
+    pub(crate) kind: CodeSearchKind,
+    pub(crate) text: String,
+    /// Only report files under this root-relative path prefix.
+    pub(crate) path_prefix: Option<String>,
+    pub(crate) limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CodeSearchHit {
+    pub(crate) path: String,
+    pub(crate) line: u32,
+    pub(crate) detail: String,
+}
+
+pub(crate) struct CodeSearchManager {
+    index_dir: PathBuf,
+    indexes: Mutex<HashMap<PathBuf, Arc<Mutex<Option<CodeIndex>>>>>,
+}
+
+impl CodeSearchManager {
+    pub(crate) fn new(codex_home: &Path) -> Self {
+        Self {
+            index_dir: codex_home.join(INDEX_DIR_NAME),
+            indexes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Refreshes the index for `root` and runs `query` against it.
+    pub(crate) async fn search(
+        &self,
+        root: PathBuf,
+        query: CodeSearchQuery,
+        cancel_flag: Arc<AtomicBool>,
+    ) -> anyhow::Result<Vec<CodeSearchHit>> {
+        let slot = {
+            let mut indexes = self
+                .indexes
+                .lock()
+                .map_err(|_| anyhow!("code search index lock poisoned"))?;
+            Arc::clone(indexes.entry(root.clone()).or_default())
+        };
+        let index_dir = self.index_dir.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut slot = slot
+                .lock()
+                .map_err(|_| anyhow!("code search index lock poisoned"))?;
+            let index = slot.get_or_insert_with(|| CodeIndex::load(&index_dir, &root));
+            let stats = index.refresh(cancel_flag)?;
+            if stats.changed()
+                && let Err(err) = index.save(&index_dir)
+            {
+                warn!("failed to persist code search index: {err:#}");
+            }
+            Ok(run_query(index, &root, &query))
+        })
+        .await?
+    }
+}
+
+fn run_query(index: &CodeIndex, root: &Path, query: &CodeSearchQuery) -> Vec<CodeSearchHit> {
+    let prefix = query
+        .path_prefix
+        .as_deref()
+        .map(|prefix| prefix.trim_end_matches('/'))
+        .filter(|prefix| !prefix.is_empty());
+    let files = index.files.iter().filter(move |(path, _)| {
+        prefix.is_none_or(|prefix| {
+            path.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        })
+    });
+
+    match query.kind {
+        CodeSearchKind::Definition => {
+            let definitions = |exact: bool| {
+                files
+                    .clone()
+                    .flat_map(|(path, file)| {
+                        file.symbols
+                            .iter()
+                            .filter(move |symbol| {
+                                if exact {
+                                    symbol.name == query.text
+                                } else {
+                                    symbol.name.eq_ignore_ascii_case(&query.text)
+                                }
+                            })
+                            .map(move |symbol| definition_hit(path, symbol))
+                    })
+                    .take(query.limit)
+                    .collect::<Vec<_>>()
+            };
+            let exact = definitions(true);
+            if exact.is_empty() {
+                definitions(false)
+            } else {
+                exact
+            }
+        }
+        CodeSearchKind::References => {
+            let mut hits = Vec::new();
+            for (path, _) in files.filter(|(_, file)| file.mentions(&query.text)) {
+                let Some(text) = read_text(&root.join(path)) else {
+                    continue;
+                };
+                for (index, line) in text.lines().enumerate() {
+                    if !contains_word(line, &query.text) {
+                        continue;
+                    }
+                    hits.push(CodeSearchHit {
+                        path: path.clone(),
+                        line: u32::try_from(index + 1).unwrap_or(u32::MAX),
+                        detail: truncate_line(line.trim()),
+                    });
+                    if hits.len() >= query.limit {
+                        return hits;
+                    }
+                }
+            }
+            hits
+        }
+        CodeSearchKind::Fuzzy => {
+            let mut scorer = FuzzyScorer::new(&query.text);
+            let mut ranked = Vec::new();
+            for (path, file) in files {
+                for symbol in &file.symbols {
+                    if let Some(score) = scorer.score(&symbol.name) {
+                        ranked.push((score, path, symbol));
+                    }
+                }
+            }
+            ranked.sort_by(|(a_score, a_path, a_symbol), (b_score, b_path, b_symbol)| {
+                b_score
+                    .cmp(a_score)
+                    .then_with(|| a_symbol.name.len().cmp(&b_symbol.name.len()))
+                    .then_with(|| a_path.cmp(b_path))
+                    .then_with(|| a_symbol.line.cmp(&b_symbol.line))
+            });
+            ranked
+                .into_iter()
+                .take(query.limit)
+                .map(|(_, path, symbol)| definition_hit(path, symbol))
+                .collect()
+        }
+    }
+}
+
+fn definition_hit(path: &str, symbol: &Symbol) -> CodeSearchHit {
+    CodeSearchHit {
+        path: path.to_string(),
+        line: symbol.line,
+        detail: format!("{} {}", symbol.kind, symbol.name),
+    }
+}
+
+fn truncate_line(line: &str) -> String {
+    if line.chars().count() <= MAX_LINE_CHARS {
+        line.to_string()
+    } else {
+        let truncated: String = line.chars().take(MAX_LINE_CHARS).collect();
+        format!("{truncated}…")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::index::RefreshStats;
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn query(kind: CodeSearchKind, text: &str) -> CodeSearchQuery {
+        CodeSearchQuery {
+            kind,
+            text: text.to_string(),
+            path_prefix: None,
+            limit: 10,
+        }
+    }
+
+    fn hits(results: &[CodeSearchHit]) -> Vec<(&str, u32, &str)> {
+        results
+            .iter()
+            .map(|hit| (hit.path.as_str(), hit.line, hit.detail.as_str()))
+            .collect()
+    }
+
+    fn workspace() -> anyhow::Result<TempDir> {
+        let dir = TempDir::new()?;
+        fs::create_dir_all(dir.path().join("src"))?;
+        fs::write(
+            dir.path().join("src/walker.rs"),
+            "pub struct Walker;\n\nimpl Walker {\n    pub fn walk_tree(&self) {}\n}\n",
+        )?;
+        fs::write(
+            dir.path().join("src/main.rs"),
+            "fn main() {\n    Walker.walk_tree();\n}\n",
+        )?;
+        fs::write(dir.path().join(".gitignore"), "target/\n")?;
+        fs::create_dir_all(dir.path().join(".git"))?;
+        fs::write(dir.path().join(".git/hooks.rs"), "fn walk_tree() {}\n")?;
+        fs::create_dir_all(dir.path().join("target"))?;
+        fs::write(dir.path().join("target/gen.rs"), "fn walk_tree() {}\n")?;
+        Ok(dir)
+    }
+
+    #[tokio::test]
+    async fn finds_definitions_references_and_fuzzy_matches() -> anyhow::Result<()> {
+        let workspace = workspace()?;
+        let codex_home = TempDir::new()?;
+        let manager = CodeSearchManager::new(codex_home.path());
+        let root = workspace.path().to_path_buf();
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let definitions = manager
+            .search(
+                root.clone(),
+                query(CodeSearchKind::Definition, "walk_tree"),
+                cancel.clone(),
+            )
+            .await?;
+        assert_eq!(
+            hits(&definitions),
+            vec![("src/walker.rs", 4, "function walk_tree")]
+        );
+
+        let references = manager
+            .search(
+                root.clone(),
+                query(CodeSearchKind::References, "Walker"),
+                cancel.clone(),
+            )
+            .await?;
+        assert_eq!(
+            hits(&references),
+            vec![
+                ("src/main.rs", 2, "Walker.walk_tree();"),
+                ("src/walker.rs", 1, "pub struct Walker;"),
+                ("src/walker.rs", 3, "impl Walker {"),
+            ]
+        );
+
+        let fuzzy = manager
+            .search(root, query(CodeSearchKind::Fuzzy, "wtree"), cancel)
+            .await?;
+        assert_eq!(
+            hits(&fuzzy),
+            vec![("src/walker.rs", 4, "function walk_tree")]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn refresh_picks_up_edits_and_persists_the_index() -> anyhow::Result<()> {
+        let workspace = workspace()?;
+        let codex_home = TempDir::new()?;
+        let root = workspace.path().to_path_buf();
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let manager = CodeSearchManager::new(codex_home.path());
+        manager
+            .search(
+                root.clone(),
+                query(CodeSearchKind::Definition, "main"),
+                cancel.clone(),
+            )
+            .await?;
+
+        fs::write(
+            workspace.path().join("src/main.rs"),
+            "fn main() {}\n\nfn run_walker() {}\n",
+        )?;
+        fs::remove_file(workspace.path().join("src/walker.rs"))?;
+
+        // A fresh manager starts from the persisted index and refreshes it.
+        let manager = CodeSearchManager::new(codex_home.path());
+        let mut index = CodeIndex::load(&manager.index_dir, &root);
+        assert_eq!(
+            index.files.keys().collect::<Vec<_>>(),
+            vec![".gitignore", "src/main.rs", "src/walker.rs"]
+        );
+        let stats = index.refresh(cancel.clone())?;
+        assert_eq!((stats.indexed, stats.removed), (1, 1));
+        // Nothing changed since, so the tree is not walked again.
+        assert_eq!(index.refresh(cancel.clone())?, RefreshStats::default());
+
+        let definitions = manager
+            .search(
+                root,
+                query(CodeSearchKind::Definition, "run_walker"),
+                cancel,
+            )
+            .await?;
+        assert_eq!(
+            hits(&definitions),
+            vec![("src/main.rs", 3, "function run_walker")]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stored_index_does_not_contain_plaintext_identifiers() -> anyhow::Result<()> {
+        let workspace = workspace()?;
+        fs::write(
+            workspace.path().join("src/config.rs"),
+            "fn load() {\n    let key = internal_signing_secret;\n}\n",
+        )?;
+        let codex_home = TempDir::new()?;
+        let manager = CodeSearchManager::new(codex_home.path());
+
+        let references = manager
+            .search(
+                workspace.path().to_path_buf(),
+                query(CodeSearchKind::References, "internal_signing_secret"),
+                Arc::new(AtomicBool::new(false)),
+            )
+            .await?;
+        assert_eq!(
+            hits(&references),
+            vec![("src/config.rs", 2, "let key = internal_signing_secret;")]
+        );
+
+        let entry = fs::read_dir(&manager.index_dir)?
+            .next()
+            .expect("index file written")?;
+        let stored = fs::read_to_string(entry.path())?;
+        assert!(!stored.contains("internal_signing_secret"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(entry.metadata()?.permissions().mode() & 0o777, 0o600);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+//! Language-agnostic extraction of definitions and identifiers.
+//!
+//! Definitions are found with a keyword heuristic that covers the common
+//! shapes in Rust, Python, Go, JavaScript/TypeScript, Java, C# and friends. It
+//! is deliberately approximate: a missed definition still shows up as a
+//! reference, and a false positive only costs one extra result line.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use once_cell::sync::Lazy;
+use regex_lite::Regex;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Identifiers shorter than this are too common to be worth indexing.
+const MIN_IDENTIFIER_LEN: usize = 2;
+/// Longer "identifiers" are almost always generated data, not code.
+const MAX_IDENTIFIER_LEN: usize = 64;
+
+static DEFINITION_RE: Lazy<Option<Regex>> = Lazy::new(|| {
+    Regex::new(
+        r#"^\s*(?:(?:pub(?:\([^)]*\))?|export|default|declare|async|unsafe|extern(?:\s+"[^"]*")?|public|private|protected|internal|static|abstract|final|sealed|open|data|inline)\s+)*(fn|struct|enum|trait|union|type|mod|module|namespace|macro_rules!|class|interface|def|function|func(?:\s*\([^)]*\))?)(?:\s+|\s*\*\s*)([A-Za-z_$][A-Za-z0-9_$]*)"#,
+    )
+    .ok()
+});
+
+/// Top-level bindings only; indented `let`/`const` are usually locals.
+static BINDING_RE: Lazy<Option<Regex>> = Lazy::new(|| {
+    Regex::new(
+        r"^(?:(?:pub(?:\([^)]*\))?|export)\s+)?(const|static|let|var)\s+(?:mut\s+)?([A-Za-z_$][A-Za-z0-9_$]*)",
+    )
+    .ok()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum SymbolKind {
+    Function,
+    Type,
+    Module,
+    Macro,
+    Constant,
+}
+
+impl SymbolKind {
+    fn from_keyword(keyword: &str) -> Self {
+        match keyword {
+            "struct" | "enum" | "trait" | "union" | "type" | "class" | "interface" => {
+                SymbolKind::Type
+            }
+            "mod" | "module" | "namespace" => SymbolKind::Module,
+            "macro_rules!" => SymbolKind::Macro,
+            "const" | "static" | "let" | "var" => SymbolKind::Constant,
+            _ => SymbolKind::Function,
+        }
+    }
+}
+
+impl fmt::Display for SymbolKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SymbolKind::Function => "function",
+            SymbolKind::Type => "type",
+            SymbolKind::Module => "module",
+            SymbolKind::Macro => "macro",
+            SymbolKind::Constant => "constant",
+        };
+        f.write_str(label)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Symbol {
+    pub(crate) name: String,
+    pub(crate) kind: SymbolKind,
+    /// 1-based line number of the definition.
+    pub(crate) line: u32,
+}
+
+/// Returns every definition found in `text`, in file order.
+pub(crate) fn extract_symbols(text: &str) -> Vec<Symbol> {
+    let (Some(definition_re), Some(binding_re)) = (DEFINITION_RE.as_ref(), BINDING_RE.as_ref())
+    else {
+        return Vec::new();
+    };
+    let mut symbols = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let captures = definition_re
+            .captures(line)
+            .or_else(|| binding_re.captures(line));
+        let Some(captures) = captures else {
+            continue;
+        };
+        let (Some(keyword), Some(name)) = (captures.get(1), captures.get(2)) else {
+            continue;
+        };
+        let keyword = keyword.as_str();
+        // `func (r *Receiver) Name` keeps the receiver in the keyword group.
+        let keyword = if keyword.starts_with("func") {
+            "func"
+        } else {
+            keyword
+        };
+        symbols.push(Symbol {
+            name: name.as_str().to_string(),
+            kind: SymbolKind::from_keyword(keyword),
+            line: u32::try_from(index + 1).unwrap_or(u32::MAX),
+        });
+    }
+    symbols
+}
+
+/// Returns the distinct identifiers in `text`, sorted so callers can
+/// binary-search them.
+pub(crate) fn extract_identifiers(text: &str) -> Vec<String> {
+    text.split(|ch| !is_identifier_char(ch))
+        .filter(|token| {
+            (MIN_IDENTIFIER_LEN..=MAX_IDENTIFIER_LEN).contains(&token.len())
+                && !token.starts_with(|ch: char| ch.is_ascii_digit())
+        })
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+pub(crate) fn is_identifier_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || ch == '_' || ch == '$'
+}
+
+/// Whether `line` contains `word` delimited by non-identifier characters.
+pub(crate) fn contains_word(line: &str, word: &str) -> bool {
+    line.match_indices(word).any(|(start, _)| {
+        let before = line[..start].chars().next_back();
+        let after = line[start + word.len()..].chars().next();
+        !before.is_some_and(is_identifier_char) && !after.is_some_and(is_identifier_char)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn names(text: &str) -> Vec<(String, SymbolKind, u32)> {
+        extract_symbols(text)
+            .into_iter()
+            .map(|symbol| (symbol.name, symbol.kind, symbol.line))
+            .collect()
+    }
+
+    #[test]
+    fn extracts_rust_definitions() {
+        let text = "\
+pub(crate) struct Index {
+    files: Vec<String>,
+}
+
+impl Index {
+    pub async fn refresh(&mut self) {}
+}
+
+pub const MAX_FILES: usize = 10;
+macro_rules! index_log {}
+mod tests {}
+";
+        assert_eq!(
+            names(text),
+            vec![
+                ("Index".to_string(), SymbolKind::Type, 1),
+                ("refresh".to_string(), SymbolKind::Function, 6),
+                ("MAX_FILES".to_string(), SymbolKind::Constant, 9),
+                ("index_log".to_string(), SymbolKind::Macro, 10),
+                ("tests".to_string(), SymbolKind::Module, 11),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_definitions_in_other_languages() {
+        let text = "\
+class Walker:
+    def walk(self):
+        let_me_be = 1
+func (w *Walker) Walk() error {
+export default async function render() {}
+export interface Props {}
+const handler = () => {};
+    const local = 1;
+";
+        assert_eq!(
+            names(text),
+            vec![
+                ("Walker".to_string(), SymbolKind::Type, 1),
+                ("walk".to_string(), SymbolKind::Function, 2),
+                ("Walk".to_string(), SymbolKind::Function, 4),
+                ("render".to_string(), SymbolKind::Function, 5),
+                ("Props".to_string(), SymbolKind::Type, 6),
+                ("handler".to_string(), SymbolKind::Constant, 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn identifiers_are_sorted_and_deduplicated() {
+        assert_eq!(
+            extract_identifiers("let x = foo_bar(x2, foo_bar); 42abc $el"),
+            vec!["$el", "foo_bar", "let", "x2"]
+        );
+    }
+
+    #[test]
+    fn contains_word_respects_identifier_boundaries() {
+        assert!(contains_word("call(refresh);", "refresh"));
+        assert!(!contains_word("call(refresh_all);", "refresh"));
+        assert!(contains_word("refresh_all(); refresh()", "refresh"));
+    }
+}
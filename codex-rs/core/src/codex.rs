@@ -12,6 +12,8 @@ use crate::SandboxState;
 use crate::agent::AgentControl;
 use crate::agent::AgentStatus;
 use crate::agent::agent_status_from_event;
+use crate::budget::TurnBudget;
+use crate::budget::check_budget;
 use crate::client_common::REVIEW_PROMPT;
 use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
@@ -20,6 +22,8 @@ use crate::compact_remote::run_inline_remote_auto_compact_task;
 use crate::exec_policy::ExecPolicyManager;
 use crate::features::Feature;
 use crate::features::Features;
+use crate::hooks::HookEvent;
+use crate::hooks::Hooks;
 use crate::models_manager::manager::ModelsManager;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
@@ -49,6 +53,7 @@ use codex_protocol::protocol::TurnContextItem;
 use codex_protocol::protocol::TurnStartedEvent;
 use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_rmcp_client::ToolCallProgressSender;
 use futures::future::BoxFuture;
 use futures::prelude::*;
 use futures::stream::FuturesOrdered;
@@ -81,11 +86,14 @@ use crate::client::ModelClient;
 use crate::client::ModelClientSession;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
+use crate::code_search::CodeSearchManager;
 use crate::compact::collect_user_messages;
 use crate::config::Config;
 use crate::config::Constrained;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
+use crate::config::ensure_sandbox_policy_supported;
+use crate::config::types::CompactionStrategy;
 use crate::config::types::McpServerConfig;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
@@ -97,6 +105,9 @@ use crate::exec::StreamOutput;
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
 use crate::mcp::auth::compute_auth_statuses;
+use crate::mcp::prompts::McpPromptInjections;
+use crate::mcp::prompts::build_mcp_prompt_injections;
+use crate::mcp::sampling::session_sampler;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
 use crate::project_doc::get_user_instructions;
@@ -156,6 +167,8 @@ use crate::user_instructions::UserInstructions;
 use crate::user_notification::UserNotification;
 use crate::util::backoff;
 use codex_async_utils::OrCancelExt;
+use codex_network_proxy::DomainAllowlist;
+use codex_network_proxy::NetworkProxy;
 use codex_otel::OtelManager;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
 use codex_protocol::models::ContentItem;
@@ -218,6 +231,26 @@ fn maybe_push_chat_wire_api_deprecation(
     });
 }
 
+/// Starts the filtering proxy that lets sandboxed commands reach
+/// `network_allowlist`. Failing to start it only costs network access, so the
+/// error is logged rather than surfaced.
+async fn start_network_proxy(network_allowlist: &[String]) -> Option<NetworkProxy> {
+    let allowlist = DomainAllowlist::new(network_allowlist);
+    if allowlist.is_empty() {
+        return None;
+    }
+    match NetworkProxy::start(allowlist).await {
+        Ok(proxy) => {
+            info!("network proxy listening on {}", proxy.addr());
+            Some(proxy)
+        }
+        Err(err) => {
+            warn!("failed to start network proxy: {err}");
+            None
+        }
+    }
+}
+
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
     pub(crate) async fn spawn(
@@ -407,10 +440,14 @@ impl TurnContext {
             .map_or_else(|| self.cwd.clone(), |p| self.cwd.join(p))
     }
 
-    pub(crate) fn compact_prompt(&self) -> &str {
-        self.compact_prompt
-            .as_deref()
-            .unwrap_or(compact::SUMMARIZATION_PROMPT)
+    /// The configured compact prompt, or the built-in one for `strategy`.
+    pub(crate) fn compact_prompt(&self, strategy: CompactionStrategy) -> &str {
+        self.compact_prompt.as_deref().unwrap_or(match strategy {
+            CompactionStrategy::PerFileSummary => compact::PER_FILE_SUMMARIZATION_PROMPT,
+            CompactionStrategy::Summarize
+            | CompactionStrategy::DropToolOutputs
+            | CompactionStrategy::KeepRecentTurns => compact::SUMMARIZATION_PROMPT,
+        })
     }
 }
 
@@ -473,6 +510,7 @@ impl SessionConfiguration {
             next_configuration.approval_policy.set(approval_policy)?;
         }
         if let Some(sandbox_policy) = updates.sandbox_policy.clone() {
+            ensure_sandbox_policy_supported(&sandbox_policy)?;
             next_configuration.sandbox_policy.set(sandbox_policy)?;
         }
         if let Some(cwd) = updates.cwd.clone() {
@@ -692,12 +730,16 @@ impl Session {
                     .map(Arc::new);
         }
         let state = SessionState::new(session_configuration.clone());
+        let network_proxy = start_network_proxy(&config.network_allowlist).await;
 
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: Mutex::new(CancellationToken::new()),
             unified_exec_manager: UnifiedExecProcessManager::default(),
             notifier: UserNotifier::new(config.notify.clone()),
+            hooks: Hooks::new(config.hooks.clone()),
+            budget: config.budget.clone(),
+            compaction: config.compaction.clone(),
             rollout: Mutex::new(Some(rollout_recorder)),
             user_shell: Arc::new(default_shell),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -708,6 +750,9 @@ impl Session {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             agent_control,
+            network_proxy,
+            code_search: CodeSearchManager::new(&config.codex_home),
+            codex_home: config.codex_home.clone(),
         };
 
         let sess = Arc::new(Session {
@@ -755,23 +800,34 @@ impl Session {
         };
         let cancel_token = sess.mcp_startup_cancellation_token().await;
 
-        sess.services
-            .mcp_connection_manager
-            .write()
-            .await
-            .initialize(
-                &config.mcp_servers,
-                config.mcp_oauth_credentials_store_mode,
-                auth_statuses.clone(),
-                tx_event.clone(),
-                cancel_token,
-                sandbox_state,
-            )
-            .await;
+        {
+            let mut mcp_connection_manager = sess.services.mcp_connection_manager.write().await;
+            mcp_connection_manager.set_sampler(Some(session_sampler(Arc::downgrade(&sess))));
+            mcp_connection_manager
+                .initialize(
+                    &config.mcp_servers,
+                    config.mcp_oauth_credentials_store_mode,
+                    auth_statuses.clone(),
+                    tx_event.clone(),
+                    cancel_token,
+                    sandbox_state,
+                )
+                .await;
+        }
 
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
         sess.record_initial_history(initial_history).await;
 
+        sess.hooks()
+            .run(
+                HookEvent::SessionStart {
+                    thread_id: conversation_id.to_string(),
+                    cwd: session_configuration.cwd.clone(),
+                },
+                &session_configuration.cwd,
+            )
+            .await;
+
         Ok(sess)
     }
 
@@ -906,14 +962,15 @@ impl Session {
         sub_id: String,
         updates: SessionSettingsUpdate,
     ) -> ConstraintResult<Arc<TurnContext>> {
-        let (session_configuration, sandbox_policy_changed) = {
+        let (session_configuration, sandbox_state_changed) = {
             let mut state = self.state.lock().await;
             match state.session_configuration.clone().apply(&updates) {
                 Ok(next) => {
-                    let sandbox_policy_changed =
-                        state.session_configuration.sandbox_policy != next.sandbox_policy;
+                    let sandbox_state_changed = state.session_configuration.sandbox_policy
+                        != next.sandbox_policy
+                        || state.session_configuration.cwd != next.cwd;
                     state.session_configuration = next.clone();
-                    (next, sandbox_policy_changed)
+                    (next, sandbox_state_changed)
                 }
                 Err(err) => {
                     drop(state);
@@ -935,7 +992,7 @@ impl Session {
                 sub_id,
                 session_configuration,
                 updates.final_output_json_schema,
-                sandbox_policy_changed,
+                sandbox_state_changed,
             )
             .await)
     }
@@ -945,11 +1002,11 @@ impl Session {
         sub_id: String,
         session_configuration: SessionConfiguration,
         final_output_json_schema: Option<Option<Value>>,
-        sandbox_policy_changed: bool,
+        sandbox_state_changed: bool,
     ) -> Arc<TurnContext> {
         let per_turn_config = Self::build_per_turn_config(&session_configuration);
 
-        if sandbox_policy_changed {
+        if sandbox_state_changed {
             let sandbox_state = SandboxState {
                 sandbox_policy: per_turn_config.sandbox_policy.get().clone(),
                 codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
@@ -1439,11 +1496,45 @@ impl Session {
                     token_usage,
                     turn_context.client.get_model_context_window(),
                 );
+                let model = turn_context.client.get_model();
+                let price = self.services.budget.model_prices.get(&model);
+                state.budget.usage.record(token_usage, price);
             }
         }
         self.send_token_count_event(turn_context).await;
     }
 
+    pub(crate) async fn start_turn_budget(&self) -> TurnBudget {
+        let state = self.state.lock().await;
+        TurnBudget::new(&state.budget)
+    }
+
+    /// Emits warnings for budgets that are nearly used up and fails once any
+    /// configured budget has been exceeded.
+    pub(crate) async fn check_budget(
+        &self,
+        turn_context: &TurnContext,
+        turn_budget: &mut TurnBudget,
+    ) -> CodexResult<()> {
+        if self.services.budget.is_empty() {
+            return Ok(());
+        }
+        let result = {
+            let mut state = self.state.lock().await;
+            check_budget(&self.services.budget, &mut state.budget, turn_budget)
+        };
+        match result {
+            Ok(warnings) => {
+                for message in warnings {
+                    self.send_event(turn_context, EventMsg::Warning(WarningEvent { message }))
+                        .await;
+                }
+                Ok(())
+            }
+            Err(err) => Err(CodexErr::BudgetExceeded(err)),
+        }
+    }
+
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
         let Some(estimated_total_tokens) = self
             .clone_history()
@@ -1676,12 +1767,13 @@ impl Session {
         server: &str,
         tool: &str,
         arguments: Option<serde_json::Value>,
+        progress_tx: Option<ToolCallProgressSender>,
     ) -> anyhow::Result<CallToolResult> {
         self.services
             .mcp_connection_manager
             .read()
             .await
-            .call_tool(server, tool, arguments)
+            .call_tool(server, tool, arguments, progress_tx)
             .await
     }
 
@@ -1708,6 +1800,10 @@ impl Session {
         &self.services.notifier
     }
 
+    pub(crate) fn hooks(&self) -> &Hooks {
+        &self.services.hooks
+    }
+
     pub(crate) fn user_shell(&self) -> Arc<shell::Shell> {
         Arc::clone(&self.services.user_shell)
     }
@@ -1750,6 +1846,7 @@ impl Session {
         let cancel_token = self.reset_mcp_startup_cancellation_token().await;
 
         let mut refreshed_manager = McpConnectionManager::default();
+        refreshed_manager.set_sampler(self.services.mcp_connection_manager.read().await.sampler());
         refreshed_manager
             .initialize(
                 &mcp_servers,
@@ -1854,6 +1951,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ListCustomPrompts => {
                 handlers::list_custom_prompts(&sess, sub.id.clone()).await;
             }
+            Op::ListMcpPrompts => {
+                handlers::list_mcp_prompts(&sess, sub.id.clone()).await;
+            }
             Op::ListSkills { cwds, force_reload } => {
                 handlers::list_skills(&sess, sub.id.clone(), cwds, force_reload).await;
             }
@@ -1904,6 +2004,7 @@ mod handlers {
 
     use crate::codex::spawn_review_thread;
     use crate::config::Config;
+    use crate::hooks::HookEvent;
 
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
@@ -1918,6 +2019,7 @@ mod handlers {
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+    use codex_protocol::protocol::ListMcpPromptsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::McpServerRefreshConfig;
     use codex_protocol::protocol::Op;
@@ -2009,6 +2111,38 @@ mod handlers {
             .get_otel_manager()
             .user_prompt(&items);
 
+        let prompt = items
+            .iter()
+            .filter_map(|item| match item {
+                UserInput::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let hook_outcome = sess
+            .hooks()
+            .run(
+                HookEvent::UserPromptSubmit {
+                    thread_id: sess.conversation_id.to_string(),
+                    turn_id: current_context.sub_id.clone(),
+                    cwd: current_context.cwd.clone(),
+                    prompt,
+                },
+                &current_context.cwd,
+            )
+            .await;
+        if let Some(reason) = hook_outcome.block_reason {
+            sess.send_event_raw(Event {
+                id: current_context.sub_id.clone(),
+                msg: EventMsg::Error(ErrorEvent {
+                    message: format!("Prompt blocked by user_prompt_submit hook: {reason}"),
+                    codex_error_info: Some(CodexErrorInfo::Other),
+                }),
+            })
+            .await;
+            return;
+        }
+
         // Attempt to inject input into current task
         if let Err(items) = sess.inject_input(items).await {
             let mut update_items = Vec::new();
@@ -2200,6 +2334,22 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    pub async fn list_mcp_prompts(sess: &Session, sub_id: String) {
+        let prompts = sess
+            .services
+            .mcp_connection_manager
+            .read()
+            .await
+            .list_all_prompts()
+            .await;
+
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ListMcpPromptsResponse(ListMcpPromptsResponseEvent { prompts }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
     pub async fn list_skills(
         sess: &Session,
         sub_id: String,
@@ -2245,7 +2395,9 @@ mod handlers {
         sess.spawn_task(
             Arc::clone(&turn_context),
             vec![UserInput::Text {
-                text: turn_context.compact_prompt().to_string(),
+                text: turn_context
+                    .compact_prompt(sess.services.compaction.strategy)
+                    .to_string(),
             }],
             CompactTask,
         )
@@ -2302,6 +2454,15 @@ mod handlers {
             .terminate_all_processes()
             .await;
         info!("Shutting down Codex instance");
+        let cwd = sess.state.lock().await.session_configuration.cwd.clone();
+        sess.hooks()
+            .run(
+                HookEvent::SessionStop {
+                    thread_id: sess.conversation_id.to_string(),
+                },
+                &cwd,
+            )
+            .await;
         let history = sess.clone_history().await;
         let turn_count = history
             .raw_items()
@@ -2456,10 +2617,7 @@ async fn spawn_review_thread(
     sess.spawn_task(tc.clone(), input, ReviewTask::new()).await;
 
     // Announce entering review mode so UIs can switch modes.
-    let review_request = ReviewRequest {
-        target: resolved.target,
-        user_facing_hint: Some(resolved.user_facing_hint),
-    };
+    let review_request = ReviewRequest::from(resolved);
     sess.send_event(&tc, EventMsg::EnteredReviewMode(review_request))
         .await;
 }
@@ -2511,6 +2669,7 @@ pub(crate) async fn run_turn(
         return None;
     }
 
+    let mut turn_budget = sess.start_turn_budget().await;
     let model_info = turn_context.client.get_model_info();
     let auto_compact_limit = model_info.auto_compact_token_limit().unwrap_or(i64::MAX);
     let total_usage_tokens = sess.get_total_token_usage().await;
@@ -2522,6 +2681,15 @@ pub(crate) async fn run_turn(
     });
     sess.send_event(&turn_context, event).await;
 
+    // Refuse to start sampling when an earlier turn already used up the
+    // session budget.
+    if let Err(e) = sess.check_budget(&turn_context, &mut turn_budget).await {
+        info!("Turn error: {e:#}");
+        sess.send_event(&turn_context, EventMsg::Error(e.to_error_event(None)))
+            .await;
+        return None;
+    }
+
     let skills_outcome = Some(
         sess.services
             .skills_manager
@@ -2534,21 +2702,40 @@ pub(crate) async fn run_turn(
         warnings: skill_warnings,
     } = build_skill_injections(&input, skills_outcome.as_ref()).await;
 
-    for message in skill_warnings {
+    let McpPromptInjections {
+        items: mcp_prompt_items,
+        warnings: mcp_prompt_warnings,
+    } = build_mcp_prompt_injections(&input, &*sess.services.mcp_connection_manager.read().await)
+        .await;
+
+    for message in skill_warnings.into_iter().chain(mcp_prompt_warnings) {
         sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
             .await;
     }
 
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
-    let response_item: ResponseItem = initial_input_for_turn.clone().into();
-    sess.record_response_item_and_emit_turn_item(turn_context.as_ref(), response_item)
-        .await;
+    // Skill and MCP prompt selections convert to no content of their own; a
+    // turn made only of those should not record an empty user message.
+    let has_user_content = !matches!(
+        &initial_input_for_turn,
+        ResponseInputItem::Message { content, .. } if content.is_empty()
+    );
+    if has_user_content {
+        let response_item: ResponseItem = initial_input_for_turn.into();
+        sess.record_response_item_and_emit_turn_item(turn_context.as_ref(), response_item)
+            .await;
+    }
 
     if !skill_items.is_empty() {
         sess.record_conversation_items(&turn_context, &skill_items)
             .await;
     }
 
+    if !mcp_prompt_items.is_empty() {
+        sess.record_conversation_items(&turn_context, &mcp_prompt_items)
+            .await;
+    }
+
     sess.maybe_start_ghost_snapshot(Arc::clone(&turn_context), cancellation_token.child_token())
         .await;
     let mut last_agent_message: Option<String> = None;
@@ -2584,7 +2771,7 @@ pub(crate) async fn run_turn(
             })
             .map(|user_message| user_message.message())
             .collect::<Vec<String>>();
-        match run_model_turn(
+        let turn_result = match run_model_turn(
             Arc::clone(&sess),
             Arc::clone(&turn_context),
             Arc::clone(&turn_diff_tracker),
@@ -2594,6 +2781,13 @@ pub(crate) async fn run_turn(
         )
         .await
         {
+            Ok(turn_output) => sess
+                .check_budget(&turn_context, &mut turn_budget)
+                .await
+                .map(|()| turn_output),
+            Err(e) => Err(e),
+        };
+        match turn_result {
             Ok(turn_output) => {
                 let TurnRunResult {
                     needs_follow_up,
@@ -2610,6 +2804,17 @@ pub(crate) async fn run_turn(
 
                 if !needs_follow_up {
                     last_agent_message = turn_last_agent_message;
+                    sess.hooks()
+                        .run(
+                            HookEvent::TurnEnd {
+                                thread_id: sess.conversation_id.to_string(),
+                                turn_id: turn_context.sub_id.clone(),
+                                cwd: turn_context.cwd.clone(),
+                                last_assistant_message: last_agent_message.clone(),
+                            },
+                            &turn_context.cwd,
+                        )
+                        .await;
                     sess.notifier()
                         .notify(&UserNotification::AgentTurnComplete {
                             thread_id: sess.conversation_id.to_string(),
@@ -2647,17 +2852,28 @@ pub(crate) async fn run_turn(
 }
 
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
-    if should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider()) {
-        run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
+    let remote = should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider());
+    let span = turn_context.client.get_otel_manager().compaction_span(
+        &turn_context.sub_id,
+        if remote { "remote" } else { "local" },
+        sess.services.compaction.strategy.as_str(),
+    );
+    if remote {
+        run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context))
+            .instrument(span)
+            .await;
     } else {
-        run_inline_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
+        run_inline_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context))
+            .instrument(span)
+            .await;
     }
 }
 
 #[instrument(level = "trace",
     skip_all,
     fields(
-        turn_id = %turn_context.sub_id,
+        thread.id = %sess.conversation_id,
+        turn.id = %turn_context.sub_id,
         model = %turn_context.client.get_model(),
         cwd = %turn_context.cwd.display()
     )
@@ -2699,6 +2915,8 @@ async fn run_model_turn(
         parallel_tool_calls: model_supports_parallel,
         base_instructions_override: turn_context.base_instructions.clone(),
         output_schema: turn_context.final_output_json_schema.clone(),
+        max_output_tokens: None,
+        temperature: None,
     };
 
     let mut retries = 0;
@@ -2791,7 +3009,9 @@ async fn drain_in_flight(
 #[instrument(level = "trace",
     skip_all,
     fields(
-        turn_id = %turn_context.sub_id,
+        otel.name = "codex.sampling",
+        thread.id = %sess.conversation_id,
+        turn.id = %turn_context.sub_id,
         model = %turn_context.client.get_model()
     )
 )]
@@ -3054,6 +3274,8 @@ mod tests {
     use super::*;
     use crate::CodexAuth;
     use crate::config::ConfigBuilder;
+    use crate::config::types::BudgetConfig;
+    use crate::config::types::CompactionConfig;
     use crate::exec::ExecToolCallOutput;
     use crate::function_tool::FunctionCallError;
     use crate::shell::default_user_shell;
@@ -3698,6 +3920,9 @@ mod tests {
             mcp_startup_cancellation_token: Mutex::new(CancellationToken::new()),
             unified_exec_manager: UnifiedExecProcessManager::default(),
             notifier: UserNotifier::new(None),
+            hooks: Hooks::default(),
+            budget: BudgetConfig::default(),
+            compaction: CompactionConfig::default(),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -3708,6 +3933,9 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             agent_control,
+            network_proxy: None,
+            code_search: CodeSearchManager::new(&config.codex_home),
+            codex_home: config.codex_home.clone(),
         };
 
         let turn_context = Session::make_turn_context(
@@ -3793,6 +4021,9 @@ mod tests {
             mcp_startup_cancellation_token: Mutex::new(CancellationToken::new()),
             unified_exec_manager: UnifiedExecProcessManager::default(),
             notifier: UserNotifier::new(None),
+            hooks: Hooks::default(),
+            budget: BudgetConfig::default(),
+            compaction: CompactionConfig::default(),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             show_raw_agent_reasoning: config.show_raw_agent_reasoning,
@@ -3803,6 +4034,9 @@ mod tests {
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
             agent_control,
+            network_proxy: None,
+            code_search: CodeSearchManager::new(&config.codex_home),
+            codex_home: config.codex_home.clone(),
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -6,6 +6,8 @@ use crate::client_common::ResponseEvent;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::codex::get_last_assistant_message_from_turn;
+use crate::config::types::CompactionStrategy;
+use crate::context_manager::is_user_turn_boundary;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::features::Feature;
@@ -21,6 +23,7 @@ use crate::truncate::truncate_text;
 use crate::util::backoff;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::RolloutItem;
@@ -29,21 +32,31 @@ use futures::prelude::*;
 use tracing::error;
 
 pub const SUMMARIZATION_PROMPT: &str = include_str!("../templates/compact/prompt.md");
+pub const PER_FILE_SUMMARIZATION_PROMPT: &str =
+    include_str!("../templates/compact/per_file_prompt.md");
 pub const SUMMARY_PREFIX: &str = include_str!("../templates/compact/summary_prefix.md");
 const COMPACT_USER_MESSAGE_MAX_TOKENS: usize = 20_000;
+/// Replaces tool outputs dropped by the `drop_tool_outputs` strategy.
+const DROPPED_TOOL_OUTPUT_PLACEHOLDER: &str =
+    "[output omitted to save context; run the tool again if it is still needed]";
 
 pub(crate) fn should_use_remote_compact_task(
     session: &Session,
     provider: &ModelProviderInfo,
 ) -> bool {
-    provider.is_openai() && session.enabled(Feature::RemoteCompaction)
+    // Remote compaction always summarizes, so other strategies run locally.
+    provider.is_openai()
+        && session.enabled(Feature::RemoteCompaction)
+        && session.services.compaction.strategy == CompactionStrategy::Summarize
 }
 
 pub(crate) async fn run_inline_auto_compact_task(
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
 ) {
-    let prompt = turn_context.compact_prompt().to_string();
+    let prompt = turn_context
+        .compact_prompt(sess.services.compaction.strategy)
+        .to_string();
     let input = vec![UserInput::Text { text: prompt }];
 
     run_compact_task_inner(sess, turn_context, input).await;
@@ -66,9 +79,40 @@ async fn run_compact_task_inner(
     turn_context: Arc<TurnContext>,
     input: Vec<UserInput>,
 ) {
+    let compaction = sess.services.compaction.clone();
+    if compaction.strategy == CompactionStrategy::DropToolOutputs
+        && try_drop_tool_outputs(&sess, &turn_context, compaction.keep_recent_turns).await
+    {
+        return;
+    }
+
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
 
     let mut history = sess.clone_history().await;
+    // With `keep_recent_turns` only the older part of the thread is
+    // summarized; the recent turns are carried over verbatim.
+    let recent_turns = if compaction.strategy == CompactionStrategy::KeepRecentTurns {
+        let max_tokens = turn_context
+            .client
+            .get_model_info()
+            .auto_compact_token_limit()
+            .map_or(usize::MAX, |limit| usize::try_from(limit / 2).unwrap_or(0));
+        select_recent_turns(
+            history.raw_items(),
+            compaction.keep_recent_turns,
+            max_tokens,
+        )
+    } else {
+        None
+    };
+    let recent_items = match recent_turns {
+        Some(start) => {
+            let items = history.raw_items().to_vec();
+            history.replace(items[..start].to_vec());
+            items[start..].to_vec()
+        }
+        None => Vec::new(),
+    };
     history.record_items(
         &[initial_input_for_turn.into()],
         turn_context.truncation_policy,
@@ -161,22 +205,35 @@ async fn run_compact_task_inner(
     let history_items = history_snapshot.raw_items();
     let summary_suffix = get_last_assistant_message_from_turn(history_items).unwrap_or_default();
     let summary_text = format!("{SUMMARY_PREFIX}\n{summary_suffix}");
-    let user_messages = collect_user_messages(history_items);
+    let older_items = match recent_turns {
+        Some(start) => &history_items[..start.min(history_items.len())],
+        None => history_items,
+    };
+    let user_messages = collect_user_messages(older_items);
 
     let initial_context = sess.build_initial_context(turn_context.as_ref());
     let mut new_history = build_compacted_history(initial_context, &user_messages, &summary_text);
+    new_history.extend(
+        recent_items
+            .into_iter()
+            .filter(|item| !matches!(item, ResponseItem::GhostSnapshot { .. })),
+    );
     let ghost_snapshots: Vec<ResponseItem> = history_items
         .iter()
         .filter(|item| matches!(item, ResponseItem::GhostSnapshot { .. }))
         .cloned()
         .collect();
     new_history.extend(ghost_snapshots);
+
+    // Resume can rebuild the summarized history from the message alone, but
+    // not the turns kept verbatim.
+    let replacement_history = recent_turns.map(|_| new_history.clone());
     sess.replace_history(new_history).await;
     sess.recompute_token_usage(&turn_context).await;
 
     let rollout_item = RolloutItem::Compacted(CompactedItem {
         message: summary_text.clone(),
-        replacement_history: None,
+        replacement_history,
     });
     sess.persist_rollout_items(&[rollout_item]).await;
 
@@ -189,6 +246,139 @@ async fn run_compact_task_inner(
     sess.send_event(&turn_context, warning).await;
 }
 
+/// Applies the `drop_tool_outputs` strategy. Returns `false` without touching
+/// the history when dropping outputs would not bring the thread back under the
+/// auto-compaction limit, so the caller falls back to summarizing.
+async fn try_drop_tool_outputs(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    keep_recent_turns: usize,
+) -> bool {
+    let mut history = sess.clone_history().await;
+    let (items, dropped) = drop_old_tool_outputs(history.raw_items(), keep_recent_turns);
+    if dropped == 0 {
+        return false;
+    }
+    history.replace(items.clone());
+    let limit = turn_context
+        .client
+        .get_model_info()
+        .auto_compact_token_limit()
+        .unwrap_or(i64::MAX);
+    let estimate = history
+        .estimate_token_count(turn_context.as_ref())
+        .unwrap_or(i64::MAX);
+    if estimate >= limit {
+        return false;
+    }
+
+    sess.replace_history(items.clone()).await;
+    sess.recompute_token_usage(turn_context).await;
+
+    let rollout_item = RolloutItem::Compacted(CompactedItem {
+        message: String::new(),
+        replacement_history: Some(items),
+    });
+    sess.persist_rollout_items(&[rollout_item]).await;
+
+    sess.notify_background_event(
+        turn_context.as_ref(),
+        format!("Dropped the output of {dropped} older tool call(s) to free up context."),
+    )
+    .await;
+    let event = EventMsg::ContextCompacted(ContextCompactedEvent {});
+    sess.send_event(turn_context, event).await;
+    true
+}
+
+/// Replaces the outputs of tool calls made before the last
+/// `keep_recent_turns` user turns with a placeholder. Returns the new items
+/// and how many outputs were dropped.
+fn drop_old_tool_outputs(
+    items: &[ResponseItem],
+    keep_recent_turns: usize,
+) -> (Vec<ResponseItem>, usize) {
+    let Some(cutoff) = recent_turns_start(items, keep_recent_turns) else {
+        return (items.to_vec(), 0);
+    };
+    let mut dropped = 0usize;
+    let mut new_items = Vec::with_capacity(items.len());
+    for item in &items[..cutoff] {
+        let new_item = match item {
+            ResponseItem::FunctionCallOutput { call_id, output }
+                if output.content.len() > DROPPED_TOOL_OUTPUT_PLACEHOLDER.len()
+                    || output.content_items.is_some() =>
+            {
+                dropped += 1;
+                ResponseItem::FunctionCallOutput {
+                    call_id: call_id.clone(),
+                    output: FunctionCallOutputPayload {
+                        content: DROPPED_TOOL_OUTPUT_PLACEHOLDER.to_string(),
+                        content_items: None,
+                        success: output.success,
+                    },
+                }
+            }
+            ResponseItem::CustomToolCallOutput { call_id, output }
+                if output.len() > DROPPED_TOOL_OUTPUT_PLACEHOLDER.len() =>
+            {
+                dropped += 1;
+                ResponseItem::CustomToolCallOutput {
+                    call_id: call_id.clone(),
+                    output: DROPPED_TOOL_OUTPUT_PLACEHOLDER.to_string(),
+                }
+            }
+            item => item.clone(),
+        };
+        new_items.push(new_item);
+    }
+    new_items.extend_from_slice(&items[cutoff..]);
+    (new_items, dropped)
+}
+
+/// Picks where the verbatim tail starts for the `keep_recent_turns` strategy:
+/// up to `keep_recent_turns` turns, fewer if they would exceed `max_tokens`.
+/// Returns `None` when nothing older than the tail is left to summarize.
+fn select_recent_turns(
+    items: &[ResponseItem],
+    keep_recent_turns: usize,
+    max_tokens: usize,
+) -> Option<usize> {
+    (1..=keep_recent_turns).rev().find_map(|turns| {
+        let start = recent_turns_start(items, turns)?;
+        let tokens = items[start..]
+            .iter()
+            .map(|item| approx_token_count(&serde_json::to_string(item).unwrap_or_default()))
+            .fold(0usize, usize::saturating_add);
+        (tokens <= max_tokens).then_some(start)
+    })
+}
+
+/// Index of the first item of the last `turns` user turns. Returns `None`
+/// unless at least one older turn precedes them.
+fn recent_turns_start(items: &[ResponseItem], turns: usize) -> Option<usize> {
+    let starts: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| is_turn_start(item))
+        .map(|(index, _)| index)
+        .collect();
+    let older_turns = starts.len().checked_sub(turns).filter(|count| *count > 0)?;
+    Some(starts.get(older_turns).copied().unwrap_or(items.len()))
+}
+
+fn is_turn_start(item: &ResponseItem) -> bool {
+    if !is_user_turn_boundary(item) {
+        return false;
+    }
+    match item {
+        ResponseItem::Message { content, .. } => {
+            !content_items_to_text(content).is_some_and(|text| is_summary_message(&text))
+        }
+        _ => false,
+    }
+}
+
 pub fn content_items_to_text(content: &[ContentItem]) -> Option<String> {
     let mut pieces = Vec::new();
     for item in content {
@@ -482,4 +672,82 @@ mod tests {
         };
         assert_eq!(summary, summary_text);
     }
+
+    fn user_message(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn tool_call(call_id: &str, output: &str) -> [ResponseItem; 2] {
+        [
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{}".to_string(),
+                call_id: call_id.to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: call_id.to_string(),
+                output: FunctionCallOutputPayload {
+                    content: output.to_string(),
+                    content_items: None,
+                    success: Some(true),
+                },
+            },
+        ]
+    }
+
+    #[test]
+    fn drop_old_tool_outputs_keeps_recent_turns_intact() {
+        let long_output = "line of output\n".repeat(20);
+        let mut items = vec![user_message("first")];
+        items.extend(tool_call("call-1", &long_output));
+        items.extend(tool_call("call-2", "ok"));
+        items.push(user_message("second"));
+        items.extend(tool_call("call-3", &long_output));
+
+        let (dropped_items, dropped) = drop_old_tool_outputs(&items, 1);
+
+        assert_eq!(dropped, 1);
+        let mut expected = items.clone();
+        expected[2] = ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload {
+                content: DROPPED_TOOL_OUTPUT_PLACEHOLDER.to_string(),
+                content_items: None,
+                success: Some(true),
+            },
+        };
+        assert_eq!(dropped_items, expected);
+
+        // Nothing precedes the only turn, so nothing is dropped.
+        assert_eq!(
+            drop_old_tool_outputs(&items[5..], 1),
+            (items[5..].to_vec(), 0)
+        );
+    }
+
+    #[test]
+    fn select_recent_turns_splits_at_user_turns_within_budget() {
+        let mut items = vec![user_message("first")];
+        items.extend(tool_call("call-1", "ok"));
+        items.push(user_message(&format!("{SUMMARY_PREFIX}\nolder work")));
+        items.push(user_message("second"));
+        items.extend(tool_call("call-2", &"x".repeat(4_000)));
+        items.push(user_message("third"));
+
+        assert_eq!(select_recent_turns(&items, 1, usize::MAX), Some(7));
+        assert_eq!(select_recent_turns(&items, 2, usize::MAX), Some(4));
+        // The summary message does not start a turn, and at least one turn
+        // must remain to be summarized.
+        assert_eq!(select_recent_turns(&items, 5, usize::MAX), Some(4));
+        // Too large to keep two turns verbatim, so only the last one is kept.
+        assert_eq!(select_recent_turns(&items, 2, 100), Some(7));
+        assert_eq!(select_recent_turns(&items, 2, 0), None);
+    }
 }
@@ -18,6 +18,12 @@ pub enum ConstraintError {
 
     #[error("field `{field_name}` cannot be empty")]
     EmptyField { field_name: String },
+
+    #[error("`{candidate}` is not supported for `{field_name}` on this platform")]
+    UnsupportedOnPlatform {
+        field_name: &'static str,
+        candidate: String,
+    },
 }
 
 impl ConstraintError {
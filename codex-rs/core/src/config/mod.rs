@@ -1,17 +1,25 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::BudgetConfig;
+use crate::config::types::CompactionConfig;
+use crate::config::types::CompactionStrategy;
+use crate::config::types::DEFAULT_COMPACTION_KEEP_RECENT_TURNS;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::HooksConfig;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerTransportConfig;
+use crate::config::types::ModelPrice;
 use crate::config::types::Notice;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::SandboxRestrictedRead;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ScrollInputMode;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
+use crate::config::types::SyntaxTheme;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
 use crate::config_loader::ConfigLayerStack;
@@ -25,6 +33,7 @@ use crate::features::FeatureOverrides;
 use crate::features::Features;
 use crate::features::FeaturesToml;
 use crate::git_info::resolve_root_git_project_for_trust;
+use crate::memory::DEFAULT_MEMORY_MAX_TOKENS;
 use crate::model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::OLLAMA_CHAT_PROVIDER_ID;
@@ -54,6 +63,7 @@ use similar::DiffableStr;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::io::ErrorKind;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::path::PathBuf;
 #[cfg(test)]
@@ -117,6 +127,16 @@ pub struct Config {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Maximum number of tokens the model may generate per response. Required
+    /// by providers using the Messages wire API.
+    pub model_max_output_tokens: Option<u64>,
+
+    /// Token and cost limits enforced after each model response.
+    pub budget: BudgetConfig,
+
+    /// How and when conversation history is compacted.
+    pub compaction: CompactionConfig,
+
     /// Key into the model_providers map that specifies which provider to use.
     pub model_provider_id: String,
 
@@ -136,6 +156,10 @@ pub struct Config {
     /// was coerced to read-only because native auto mode is unsupported.
     pub forced_auto_mode_downgraded_on_windows: bool,
 
+    /// Domains reachable through the local filtering proxy when the sandbox
+    /// otherwise blocks network access. Empty disables the proxy.
+    pub network_allowlist: Vec<String>,
+
     pub shell_environment_policy: ShellEnvironmentPolicy,
 
     /// When `true`, `AgentReasoning` events emitted by the backend will be
@@ -181,6 +205,10 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
+    /// Commands run synchronously at lifecycle points (session start/stop,
+    /// prompt submit, before/after tool calls, turn end).
+    pub hooks: HooksConfig,
+
     /// TUI notifications preference. When set, the TUI will send OSC 9 notifications on approvals
     /// and turn completions when not focused.
     pub tui_notifications: Notifications,
@@ -251,6 +279,11 @@ pub struct Config {
     /// - `never`: Never use alternate screen (inline mode, preserves scrollback).
     pub tui_alternate_screen: AltScreenMode,
 
+    /// Syntax highlighting theme for code blocks and diffs in the TUI.
+    ///
+    /// This is the same `tui.syntax_theme` value from `config.toml` (see [`Tui`]).
+    pub tui_syntax_theme: SyntaxTheme,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -285,6 +318,10 @@ pub struct Config {
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: usize,
 
+    /// Approximate token budget for project memories included in the initial
+    /// context when the `memory` feature is enabled.
+    pub memory_max_tokens: usize,
+
     /// Additional filenames to try when looking for project-level docs.
     pub project_doc_fallback_filenames: Vec<String>,
 
@@ -642,6 +679,19 @@ fn ensure_no_inline_bearer_tokens(value: &TomlValue) -> std::io::Result<()> {
     Ok(())
 }
 
+/// Windows cannot enforce read restrictions, so `RestrictedRead` is refused
+/// there rather than silently granting full read access. Checked when the
+/// config is loaded and whenever a turn overrides the sandbox policy.
+pub(crate) fn ensure_sandbox_policy_supported(policy: &SandboxPolicy) -> ConstraintResult<()> {
+    if cfg!(target_os = "windows") && matches!(policy, SandboxPolicy::RestrictedRead { .. }) {
+        return Err(ConstraintError::UnsupportedOnPlatform {
+            field_name: "sandbox_policy",
+            candidate: "restricted-read".to_string(),
+        });
+    }
+    Ok(())
+}
+
 pub(crate) fn set_project_trust_level_inner(
     doc: &mut DocumentMut,
     project_path: &Path,
@@ -785,6 +835,44 @@ pub struct ConfigToml {
     /// Token usage threshold triggering auto-compaction of conversation history.
     pub model_auto_compact_token_limit: Option<i64>,
 
+    /// Maximum number of tokens the model may generate per response. Required
+    /// by providers using the Messages wire API.
+    pub model_max_output_tokens: Option<u64>,
+
+    /// Fraction of the context window (greater than 0, at most 1) at which
+    /// auto-compaction starts. Ignored when `model_auto_compact_token_limit`
+    /// is set.
+    pub model_auto_compact_context_fraction: Option<f64>,
+
+    /// Strategy used to compact conversation history. Defaults to
+    /// `summarize`.
+    pub compaction_strategy: Option<CompactionStrategy>,
+
+    /// Number of most recent user turns kept intact by the
+    /// `drop_tool_outputs` and `keep_recent_turns` strategies. Defaults to 3.
+    pub compaction_keep_recent_turns: Option<usize>,
+
+    /// Abort the turn once the session has used this many tokens (non-cached
+    /// input plus output) since it was started or resumed.
+    pub max_session_tokens: Option<i64>,
+
+    /// Abort the turn once it has used this many tokens (non-cached input plus
+    /// output).
+    pub max_turn_tokens: Option<i64>,
+
+    /// Abort the turn once the session has cost this many US dollars, as
+    /// computed from `model_prices`.
+    pub max_session_cost_usd: Option<f64>,
+
+    /// Abort the turn once it has cost this many US dollars, as computed from
+    /// `model_prices`.
+    pub max_turn_cost_usd: Option<f64>,
+
+    /// Per-model prices (USD per million tokens) used by the cost budgets.
+    /// Usage of models without a price does not count towards cost budgets.
+    #[serde(default)]
+    pub model_prices: HashMap<String, ModelPrice>,
+
     /// Default approval policy for executing commands.
     pub approval_policy: Option<AskForApproval>,
 
@@ -797,10 +885,17 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Sandbox configuration to apply if `sandbox` is `RestrictedRead`.
+    pub sandbox_restricted_read: Option<SandboxRestrictedRead>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// Commands run synchronously at lifecycle points such as before and
+    /// after tool calls.
+    pub hooks: Option<HooksConfig>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -851,6 +946,10 @@ pub struct ConfigToml {
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: Option<usize>,
 
+    /// Approximate token budget for project memories injected into the initial
+    /// context when the `memory` feature is enabled. Defaults to 1000.
+    pub memory_max_tokens: Option<usize>,
+
     /// Ordered list of fallback filenames to look for when AGENTS.md is missing.
     pub project_doc_fallback_filenames: Option<Vec<String>>,
 
@@ -1062,6 +1161,7 @@ impl ConfigToml {
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    network_allow: _,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
@@ -1070,6 +1170,27 @@ impl ConfigToml {
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
+            SandboxMode::RestrictedRead => {
+                let SandboxWorkspaceWrite {
+                    writable_roots,
+                    network_access,
+                    exclude_tmpdir_env_var,
+                    exclude_slash_tmp,
+                    network_allow: _,
+                } = self.sandbox_workspace_write.clone().unwrap_or_default();
+                let readable_roots = self
+                    .sandbox_restricted_read
+                    .as_ref()
+                    .map(|restricted_read| restricted_read.readable_roots.clone())
+                    .unwrap_or_default();
+                SandboxPolicy::RestrictedRead {
+                    readable_roots,
+                    writable_roots,
+                    network_access,
+                    exclude_tmpdir_env_var,
+                    exclude_slash_tmp,
+                }
+            }
             SandboxMode::DangerFullAccess => SandboxPolicy::DangerFullAccess,
         };
         let mut forced_auto_mode_downgraded_on_windows = false;
@@ -1284,7 +1405,10 @@ impl Config {
             policy: mut sandbox_policy,
             forced_auto_mode_downgraded_on_windows,
         } = cfg.derive_sandbox_policy(sandbox_mode, config_profile.sandbox_mode, &resolved_cwd);
-        if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy {
+        ensure_sandbox_policy_supported(&sandbox_policy)?;
+        if let SandboxPolicy::WorkspaceWrite { writable_roots, .. }
+        | SandboxPolicy::RestrictedRead { writable_roots, .. } = &mut sandbox_policy
+        {
             for path in additional_writable_roots {
                 if !writable_roots.iter().any(|existing| existing == &path) {
                     writable_roots.push(path);
@@ -1418,6 +1542,17 @@ impl Config {
             .or(cfg.review_model)
             .unwrap_or_else(default_review_model);
 
+        if let Some(fraction) = cfg.model_auto_compact_context_fraction
+            && (fraction.is_nan() || fraction <= 0.0 || fraction > 1.0)
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "model_auto_compact_context_fraction must be greater than 0 and at most 1, got {fraction}"
+                ),
+            ));
+        }
+
         let check_for_update_on_startup = cfg.check_for_update_on_startup.unwrap_or(true);
 
         // Ensure that every field of ConfigRequirements is applied to the final
@@ -1435,6 +1570,20 @@ impl Config {
             .set(sandbox_policy)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
 
+        let prometheus_listen_addr = cfg
+            .otel
+            .as_ref()
+            .and_then(|otel| otel.prometheus_listen_addr.as_deref())
+            .map(|addr| {
+                addr.parse::<SocketAddr>().map_err(|err| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("invalid otel.prometheus_listen_addr `{addr}`: {err}"),
+                    )
+                })
+            })
+            .transpose()?;
+
         let mcp_servers =
             constrain_mcp_servers(cfg.mcp_servers.clone(), mcp_server_requirements.as_ref())
                 .map_err(|e| {
@@ -1446,6 +1595,21 @@ impl Config {
             review_model,
             model_context_window: cfg.model_context_window,
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
+            model_max_output_tokens: cfg.model_max_output_tokens,
+            budget: BudgetConfig {
+                max_session_tokens: cfg.max_session_tokens,
+                max_turn_tokens: cfg.max_turn_tokens,
+                max_session_cost_usd: cfg.max_session_cost_usd,
+                max_turn_cost_usd: cfg.max_turn_cost_usd,
+                model_prices: cfg.model_prices,
+            },
+            compaction: CompactionConfig {
+                strategy: cfg.compaction_strategy.unwrap_or_default(),
+                keep_recent_turns: cfg
+                    .compaction_keep_recent_turns
+                    .unwrap_or(DEFAULT_COMPACTION_KEEP_RECENT_TURNS),
+                trigger_fraction: cfg.model_auto_compact_context_fraction,
+            },
             model_provider_id,
             model_provider,
             cwd: resolved_cwd,
@@ -1453,8 +1617,14 @@ impl Config {
             sandbox_policy: constrained_sandbox_policy,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             forced_auto_mode_downgraded_on_windows,
+            network_allowlist: cfg
+                .sandbox_workspace_write
+                .as_ref()
+                .map(|settings| settings.network_allow.clone())
+                .unwrap_or_default(),
             shell_environment_policy,
             notify: cfg.notify,
+            hooks: cfg.hooks.unwrap_or_default(),
             user_instructions,
             base_instructions,
             developer_instructions,
@@ -1469,6 +1639,7 @@ impl Config {
             mcp_oauth_callback_port: cfg.mcp_oauth_callback_port,
             model_providers,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
+            memory_max_tokens: cfg.memory_max_tokens.unwrap_or(DEFAULT_MEMORY_MAX_TOKENS),
             project_doc_fallback_filenames: cfg
                 .project_doc_fallback_filenames
                 .unwrap_or_default()
@@ -1563,6 +1734,7 @@ impl Config {
                 .as_ref()
                 .map(|t| t.alternate_screen)
                 .unwrap_or_default(),
+            tui_syntax_theme: cfg.tui.as_ref().map(|t| t.syntax_theme).unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1577,6 +1749,7 @@ impl Config {
                     exporter,
                     trace_exporter,
                     metrics_exporter: OtelExporterKind::Statsig,
+                    prometheus_listen_addr,
                 }
             },
         };
@@ -1787,6 +1960,7 @@ persistence = "none"
                 notifications: Notifications::Enabled(true),
                 animations: true,
                 show_tooltips: true,
+                syntax_theme: SyntaxTheme::Auto,
                 scroll_events_per_tick: None,
                 scroll_wheel_lines: None,
                 scroll_trackpad_lines: None,
@@ -1801,6 +1975,94 @@ persistence = "none"
         );
     }
 
+    #[test]
+    fn tui_syntax_theme_parses() {
+        let cfg = r#"
+[tui]
+syntax_theme = "none"
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("syntax_theme should parse");
+        let tui = parsed.tui.expect("config should include tui section");
+        assert_eq!(tui.syntax_theme, SyntaxTheme::None);
+    }
+
+    #[test]
+    fn compaction_config_parsing() -> anyhow::Result<()> {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+compaction_strategy = "keep_recent_turns"
+compaction_keep_recent_turns = 5
+model_auto_compact_context_fraction = 0.75
+"#,
+        )?;
+        let codex_home = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.compaction,
+            CompactionConfig {
+                strategy: CompactionStrategy::KeepRecentTurns,
+                keep_recent_turns: 5,
+                trigger_fraction: Some(0.75),
+            }
+        );
+
+        let cfg = toml::from_str::<ConfigToml>("model_auto_compact_context_fraction = 1.5")?;
+        let error = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )
+        .expect_err("fraction above 1 should be rejected");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        Ok(())
+    }
+
+    #[test]
+    fn budget_config_parsing() -> anyhow::Result<()> {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+max_session_tokens = 200000
+max_turn_cost_usd = 1.5
+
+[model_prices.gpt-5]
+input = 1.25
+cached_input = 0.125
+output = 10.0
+"#,
+        )?;
+        let codex_home = TempDir::new()?;
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.budget,
+            BudgetConfig {
+                max_session_tokens: Some(200_000),
+                max_turn_tokens: None,
+                max_session_cost_usd: None,
+                max_turn_cost_usd: Some(1.5),
+                model_prices: HashMap::from([(
+                    "gpt-5".to_string(),
+                    ModelPrice {
+                        input: 1.25,
+                        cached_input: Some(0.125),
+                        output: 10.0,
+                    },
+                )]),
+            }
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -1943,6 +2205,85 @@ trust_level = "trusted"
         }
     }
 
+    #[test]
+    fn restricted_read_sandbox_uses_readable_and_workspace_write_settings() {
+        let readable_root = test_absolute_path("/opt/reference");
+        let writable_root = test_absolute_path("/my/workspace");
+        let sandbox_restricted_read = format!(
+            r#"
+sandbox_mode = "restricted-read"
+
+[sandbox_workspace_write]
+writable_roots = [
+    {},
+]
+network_access = true
+
+[sandbox_restricted_read]
+readable_roots = [
+    {},
+]
+"#,
+            serde_json::json!(writable_root),
+            serde_json::json!(readable_root)
+        );
+
+        let cfg = toml::from_str::<ConfigToml>(&sandbox_restricted_read)
+            .expect("TOML deserialization should succeed");
+        let resolution = cfg.derive_sandbox_policy(None, None, &PathBuf::from("/tmp/test"));
+        assert_eq!(
+            resolution,
+            SandboxPolicyResolution {
+                policy: SandboxPolicy::RestrictedRead {
+                    readable_roots: vec![readable_root],
+                    writable_roots: vec![writable_root],
+                    network_access: true,
+                    exclude_tmpdir_env_var: false,
+                    exclude_slash_tmp: false,
+                },
+                forced_auto_mode_downgraded_on_windows: false,
+            }
+        );
+    }
+
+    #[test]
+    fn restricted_read_is_refused_on_windows() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(r#"sandbox_mode = "restricted-read""#)
+            .expect("TOML deserialization should succeed");
+
+        let result = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        );
+        let per_turn = ensure_sandbox_policy_supported(&SandboxPolicy::RestrictedRead {
+            readable_roots: Vec::new(),
+            writable_roots: Vec::new(),
+            network_access: false,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        });
+        if cfg!(target_os = "windows") {
+            let err = result.expect_err("restricted-read must be refused on Windows");
+            assert_eq!(err.kind(), ErrorKind::InvalidInput);
+            assert_eq!(
+                per_turn,
+                Err(ConstraintError::UnsupportedOnPlatform {
+                    field_name: "sandbox_policy",
+                    candidate: "restricted-read".to_string(),
+                })
+            );
+        } else {
+            assert!(matches!(
+                result?.sandbox_policy.get(),
+                SandboxPolicy::RestrictedRead { .. }
+            ));
+            assert_eq!(per_turn, Ok(()));
+        }
+        Ok(())
+    }
+
     #[test]
     fn filter_mcp_servers_by_allowlist_enforces_identity_rules() {
         const MISMATCHED_COMMAND_SERVER: &str = "mismatched-command-should-disable";
@@ -3489,15 +3830,20 @@ model_verbosity = "high"
                 review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
                 model_context_window: None,
                 model_auto_compact_token_limit: None,
+                model_max_output_tokens: None,
+                budget: BudgetConfig::default(),
+                compaction: CompactionConfig::default(),
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 forced_auto_mode_downgraded_on_windows: false,
+                network_allowlist: Vec::new(),
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 user_instructions: None,
                 notify: None,
+                hooks: HooksConfig::default(),
                 cwd: fixture.cwd(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -3505,6 +3851,7 @@ model_verbosity = "high"
                 mcp_oauth_callback_port: None,
                 model_providers: fixture.model_provider_map.clone(),
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+                memory_max_tokens: DEFAULT_MEMORY_MAX_TOKENS,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
                 codex_home: fixture.codex_home(),
@@ -3550,6 +3897,7 @@ model_verbosity = "high"
                 tui_scroll_wheel_like_max_duration_ms: None,
                 tui_scroll_invert: false,
                 tui_alternate_screen: AltScreenMode::Auto,
+                tui_syntax_theme: SyntaxTheme::Auto,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3576,15 +3924,20 @@ model_verbosity = "high"
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_max_output_tokens: None,
+            budget: BudgetConfig::default(),
+            compaction: CompactionConfig::default(),
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
+            network_allowlist: Vec::new(),
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            hooks: HooksConfig::default(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -3592,6 +3945,7 @@ model_verbosity = "high"
             mcp_oauth_callback_port: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            memory_max_tokens: DEFAULT_MEMORY_MAX_TOKENS,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             codex_home: fixture.codex_home(),
@@ -3637,6 +3991,7 @@ model_verbosity = "high"
             tui_scroll_wheel_like_max_duration_ms: None,
             tui_scroll_invert: false,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_syntax_theme: SyntaxTheme::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -3678,15 +4033,20 @@ model_verbosity = "high"
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_max_output_tokens: None,
+            budget: BudgetConfig::default(),
+            compaction: CompactionConfig::default(),
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
+            network_allowlist: Vec::new(),
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            hooks: HooksConfig::default(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -3694,6 +4054,7 @@ model_verbosity = "high"
             mcp_oauth_callback_port: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            memory_max_tokens: DEFAULT_MEMORY_MAX_TOKENS,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             codex_home: fixture.codex_home(),
@@ -3739,6 +4100,7 @@ model_verbosity = "high"
             tui_scroll_wheel_like_max_duration_ms: None,
             tui_scroll_invert: false,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_syntax_theme: SyntaxTheme::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -3766,15 +4128,20 @@ model_verbosity = "high"
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
             model_context_window: None,
             model_auto_compact_token_limit: None,
+            model_max_output_tokens: None,
+            budget: BudgetConfig::default(),
+            compaction: CompactionConfig::default(),
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: Constrained::allow_any(SandboxPolicy::new_read_only_policy()),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
+            network_allowlist: Vec::new(),
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            hooks: HooksConfig::default(),
             cwd: fixture.cwd(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: Constrained::allow_any(HashMap::new()),
@@ -3782,6 +4149,7 @@ model_verbosity = "high"
             mcp_oauth_callback_port: None,
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
+            memory_max_tokens: DEFAULT_MEMORY_MAX_TOKENS,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             codex_home: fixture.codex_home(),
@@ -3827,6 +4195,7 @@ model_verbosity = "high"
             tui_scroll_wheel_like_max_duration_ms: None,
             tui_scroll_invert: false,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_syntax_theme: SyntaxTheme::Auto,
             otel: OtelConfig::default(),
         };
 
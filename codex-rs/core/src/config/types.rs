@@ -7,6 +7,7 @@ pub use codex_protocol::config_types::AltScreenMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::time::Duration;
 use wildmatch::WildMatchPattern;
@@ -280,6 +281,170 @@ pub enum HistoryPersistence {
     None,
 }
 
+// ===== Budget configuration =====
+
+/// Price of a model in USD per million tokens, used to express budgets in
+/// dollars.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct ModelPrice {
+    /// USD per million non-cached input tokens.
+    pub input: f64,
+
+    /// USD per million cached input tokens. Defaults to `input`.
+    #[serde(default)]
+    pub cached_input: Option<f64>,
+
+    /// USD per million output tokens (including reasoning tokens).
+    pub output: f64,
+}
+
+/// Limits on how many tokens (or dollars) a session and a single turn may
+/// consume before Codex aborts the turn.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BudgetConfig {
+    pub max_session_tokens: Option<i64>,
+    pub max_turn_tokens: Option<i64>,
+    pub max_session_cost_usd: Option<f64>,
+    pub max_turn_cost_usd: Option<f64>,
+    /// Prices keyed by model slug.
+    pub model_prices: HashMap<String, ModelPrice>,
+}
+
+impl BudgetConfig {
+    pub fn is_empty(&self) -> bool {
+        self.max_session_tokens.is_none()
+            && self.max_turn_tokens.is_none()
+            && self.max_session_cost_usd.is_none()
+            && self.max_turn_cost_usd.is_none()
+    }
+}
+
+// ===== Compaction configuration =====
+
+/// How older conversation history is condensed once the context window fills
+/// up.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CompactionStrategy {
+    /// Ask the model for a summary and keep the most recent user messages.
+    #[default]
+    Summarize,
+    /// Replace the outputs of tool calls outside the recent turns with a short
+    /// placeholder, and only summarize if that does not free enough room.
+    DropToolOutputs,
+    /// Summarize everything except the most recent turns, which are kept
+    /// verbatim (including tool calls and their outputs).
+    KeepRecentTurns,
+    /// Summarize with a prompt that records the state of every file touched
+    /// so far, which keeps long refactors on track.
+    PerFileSummary,
+}
+
+impl CompactionStrategy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CompactionStrategy::Summarize => "summarize",
+            CompactionStrategy::DropToolOutputs => "drop_tool_outputs",
+            CompactionStrategy::KeepRecentTurns => "keep_recent_turns",
+            CompactionStrategy::PerFileSummary => "per_file_summary",
+        }
+    }
+}
+
+pub const DEFAULT_COMPACTION_KEEP_RECENT_TURNS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactionConfig {
+    pub strategy: CompactionStrategy,
+    /// Number of most recent user turns preserved by `drop_tool_outputs` and
+    /// `keep_recent_turns`.
+    pub keep_recent_turns: usize,
+    /// Fraction of the model context window at which auto-compaction starts.
+    /// Ignored when `model_auto_compact_token_limit` is set.
+    pub trigger_fraction: Option<f64>,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        Self {
+            strategy: CompactionStrategy::default(),
+            keep_recent_turns: DEFAULT_COMPACTION_KEEP_RECENT_TURNS,
+            trigger_fraction: None,
+        }
+    }
+}
+
+// ===== Hooks configuration =====
+
+/// Commands that run synchronously at points in the agent lifecycle.
+///
+/// Each hook receives a JSON payload describing the event on stdin. Hooks
+/// that run before an action (`pre_tool_use`, `user_prompt_submit`) can block
+/// it by exiting with status 2 or by printing `{"decision": "block"}` on
+/// stdout; `pre_tool_use` hooks can also rewrite the tool input. Hooks that
+/// run after a tool call can print `{"feedback": "..."}` to append text to the
+/// tool output the model sees.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Run once when a session starts.
+    #[serde(default)]
+    pub session_start: Vec<HookCommand>,
+
+    /// Run once when a session shuts down.
+    #[serde(default)]
+    pub session_stop: Vec<HookCommand>,
+
+    /// Run when the user submits a prompt, before the turn starts.
+    #[serde(default)]
+    pub user_prompt_submit: Vec<HookCommand>,
+
+    /// Run before a tool call is executed.
+    #[serde(default)]
+    pub pre_tool_use: Vec<HookCommand>,
+
+    /// Run after a tool call completes.
+    #[serde(default)]
+    pub post_tool_use: Vec<HookCommand>,
+
+    /// Run when the agent finishes a turn.
+    #[serde(default)]
+    pub turn_end: Vec<HookCommand>,
+}
+
+impl HooksConfig {
+    pub fn is_empty(&self) -> bool {
+        self.session_start.is_empty()
+            && self.session_stop.is_empty()
+            && self.user_prompt_submit.is_empty()
+            && self.pre_tool_use.is_empty()
+            && self.post_tool_use.is_empty()
+            && self.turn_end.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct HookCommand {
+    /// Program and arguments to run, e.g. `["cargo", "fmt"]`.
+    pub command: Vec<String>,
+
+    /// Tool names this hook applies to. Only used by `pre_tool_use` and
+    /// `post_tool_use`; an empty list matches every tool.
+    #[serde(default)]
+    pub tools: Vec<String>,
+
+    /// Kill the hook if it runs longer than this. Defaults to 60 seconds.
+    pub timeout_ms: Option<u64>,
+}
+
+impl HookCommand {
+    pub fn matches_tool(&self, tool_name: &str) -> bool {
+        self.tools.is_empty() || self.tools.iter().any(|tool| tool == tool_name)
+    }
+}
+
 // ===== Analytics configuration =====
 
 /// Analytics settings loaded from config.toml. Fields are optional so we can apply defaults.
@@ -356,6 +521,10 @@ pub struct OtelConfigToml {
 
     /// Optional trace exporter
     pub trace_exporter: Option<OtelExporterKind>,
+
+    /// Address (for example `127.0.0.1:9464`) on which `codex app-server`
+    /// serves Prometheus metrics at `/metrics`. Disabled when unset.
+    pub prometheus_listen_addr: Option<String>,
 }
 
 /// Effective OTEL settings after defaults are applied.
@@ -366,6 +535,7 @@ pub struct OtelConfig {
     pub exporter: OtelExporterKind,
     pub trace_exporter: OtelExporterKind,
     pub metrics_exporter: OtelExporterKind,
+    pub prometheus_listen_addr: Option<SocketAddr>,
 }
 
 impl Default for OtelConfig {
@@ -376,6 +546,7 @@ impl Default for OtelConfig {
             exporter: OtelExporterKind::None,
             trace_exporter: OtelExporterKind::None,
             metrics_exporter: OtelExporterKind::Statsig,
+            prometheus_listen_addr: None,
         }
     }
 }
@@ -415,6 +586,21 @@ impl Default for ScrollInputMode {
     }
 }
 
+/// Color theme used to syntax-highlight code blocks and diffs in the TUI.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyntaxTheme {
+    /// Pick `dark` or `light` from the terminal background color.
+    #[default]
+    Auto,
+    /// Colors tuned for dark terminal backgrounds.
+    Dark,
+    /// Colors tuned for light terminal backgrounds.
+    Light,
+    /// Disable syntax highlighting.
+    None,
+}
+
 /// Collection of settings that are specific to the TUI.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
 #[schemars(deny_unknown_fields)]
@@ -434,6 +620,14 @@ pub struct Tui {
     #[serde(default = "default_true")]
     pub show_tooltips: bool,
 
+    /// Syntax highlighting theme for fenced code blocks and diffs.
+    ///
+    /// - `auto` (default): pick `dark` or `light` from the terminal background.
+    /// - `dark` / `light`: force a palette.
+    /// - `none`: disable syntax highlighting.
+    #[serde(default)]
+    pub syntax_theme: SyntaxTheme,
+
     /// Override the *wheel* event density used to normalize TUI2 scrolling.
     ///
     /// Terminals generally deliver both mouse wheels and trackpads as discrete `scroll up/down`
@@ -591,6 +785,21 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// Domains that sandboxed commands may reach through a local filtering
+    /// proxy when `network_access` is false. Each entry also admits its
+    /// subdomains.
+    #[serde(default)]
+    pub network_allow: Vec<String>,
+}
+
+/// Additional settings for `sandbox_mode = "restricted-read"`. Write and
+/// network settings are taken from `[sandbox_workspace_write]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct SandboxRestrictedRead {
+    /// Directories (besides the writable roots) that commands may read.
+    #[serde(default)]
+    pub readable_roots: Vec<AbsolutePathBuf>,
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
@@ -168,6 +168,9 @@ pub enum SandboxModeRequirement {
 
     #[serde(rename = "external-sandbox")]
     ExternalSandbox,
+
+    #[serde(rename = "restricted-read")]
+    RestrictedRead,
 }
 
 impl From<SandboxMode> for SandboxModeRequirement {
@@ -176,6 +179,7 @@ impl From<SandboxMode> for SandboxModeRequirement {
             SandboxMode::ReadOnly => SandboxModeRequirement::ReadOnly,
             SandboxMode::WorkspaceWrite => SandboxModeRequirement::WorkspaceWrite,
             SandboxMode::DangerFullAccess => SandboxModeRequirement::DangerFullAccess,
+            SandboxMode::RestrictedRead => SandboxModeRequirement::RestrictedRead,
         }
     }
 }
@@ -255,6 +259,9 @@ impl TryFrom<ConfigRequirementsWithSources> for ConfigRequirements {
                         SandboxPolicy::ExternalSandbox { .. } => {
                             SandboxModeRequirement::ExternalSandbox
                         }
+                        SandboxPolicy::RestrictedRead { .. } => {
+                            SandboxModeRequirement::RestrictedRead
+                        }
                     };
                     if modes.contains(&mode) {
                         Ok(())
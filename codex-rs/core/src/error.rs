@@ -110,6 +110,11 @@ pub enum CodexErr {
     #[error("{0}")]
     UsageLimitReached(UsageLimitReachedError),
 
+    /// A configured session or turn token/cost budget was exceeded. Never
+    /// retried: the turn is aborted so runaway sessions stop spending.
+    #[error("{0}")]
+    BudgetExceeded(BudgetExceededError),
+
     #[error("{0}")]
     ResponseStreamFailed(ResponseStreamFailed),
 
@@ -201,7 +206,8 @@ impl CodexErr {
             | CodexErr::ThreadNotFound(_)
             | CodexErr::Spawn
             | CodexErr::SessionConfiguredNotFirstEvent
-            | CodexErr::UsageLimitReached(_) => false,
+            | CodexErr::UsageLimitReached(_)
+            | CodexErr::BudgetExceeded(_) => false,
             CodexErr::Stream(..)
             | CodexErr::Timeout
             | CodexErr::UnexpectedStatus(_)
@@ -347,6 +353,67 @@ impl std::fmt::Display for RetryLimitReachedError {
     }
 }
 
+/// A token or cost budget configured in `config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetLimit {
+    SessionTokens,
+    TurnTokens,
+    SessionCostUsd,
+    TurnCostUsd,
+}
+
+impl BudgetLimit {
+    /// The `config.toml` key that sets this limit.
+    pub fn config_key(self) -> &'static str {
+        match self {
+            BudgetLimit::SessionTokens => "max_session_tokens",
+            BudgetLimit::TurnTokens => "max_turn_tokens",
+            BudgetLimit::SessionCostUsd => "max_session_cost_usd",
+            BudgetLimit::TurnCostUsd => "max_turn_cost_usd",
+        }
+    }
+
+    pub(crate) fn scope(self) -> &'static str {
+        match self {
+            BudgetLimit::SessionTokens | BudgetLimit::SessionCostUsd => "session",
+            BudgetLimit::TurnTokens | BudgetLimit::TurnCostUsd => "turn",
+        }
+    }
+
+    pub(crate) fn format_amount(self, amount: f64) -> String {
+        match self {
+            BudgetLimit::SessionTokens | BudgetLimit::TurnTokens => {
+                format!("{amount:.0} tokens")
+            }
+            BudgetLimit::SessionCostUsd | BudgetLimit::TurnCostUsd => format!("${amount:.2}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BudgetExceededError {
+    pub limit: BudgetLimit,
+    pub used: f64,
+    pub max: f64,
+}
+
+impl std::fmt::Display for BudgetExceededError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.limit {
+            BudgetLimit::SessionTokens | BudgetLimit::TurnTokens => "Token",
+            BudgetLimit::SessionCostUsd | BudgetLimit::TurnCostUsd => "Cost",
+        };
+        write!(
+            f,
+            "{kind} budget exceeded: this {} used {}, over the `{}` limit of {}.",
+            self.limit.scope(),
+            self.limit.format_amount(self.used),
+            self.limit.config_key(),
+            self.limit.format_amount(self.max),
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct UsageLimitReachedError {
     pub(crate) plan_type: Option<PlanType>,
@@ -501,6 +568,7 @@ impl CodexErr {
                 CodexErrorInfo::BadRequest
             }
             CodexErr::Sandbox(_) => CodexErrorInfo::SandboxError,
+            CodexErr::BudgetExceeded(_) => CodexErrorInfo::BudgetExceeded,
             _ => CodexErrorInfo::Other,
         }
     }
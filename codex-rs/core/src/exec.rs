@@ -120,6 +120,17 @@ pub enum SandboxType {
     WindowsRestrictedToken,
 }
 
+impl SandboxType {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            SandboxType::None => "none",
+            SandboxType::MacosSeatbelt => "macos_seatbelt",
+            SandboxType::LinuxSeccomp => "linux_seccomp",
+            SandboxType::WindowsRestrictedToken => "windows_restricted_token",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct StdoutStream {
     pub sub_id: String,
@@ -177,6 +188,7 @@ pub async fn process_exec_tool_call(
             sandbox_type,
             sandbox_cwd,
             codex_linux_sandbox_exe.as_ref(),
+            None,
         )
         .map_err(CodexErr::from)?;
 
@@ -13,6 +13,7 @@ use codex_execpolicy::AmendError;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error as ExecPolicyRuleError;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::MatchContext;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
@@ -39,11 +40,51 @@ const DEFAULT_POLICY_FILE: &str = "default.rules";
 
 fn is_policy_match(rule_match: &RuleMatch) -> bool {
     match rule_match {
-        RuleMatch::PrefixRuleMatch { .. } => true,
+        RuleMatch::PrefixRuleMatch { .. }
+        | RuleMatch::ArgRuleMatch { .. }
+        | RuleMatch::PathRuleMatch { .. } => true,
         RuleMatch::HeuristicsRuleMatch { .. } => false,
     }
 }
 
+/// Describes what a policy rule matched, returning how specific the match is
+/// (used to pick the most relevant rule) and a fallback explanation used when
+/// the rule has no justification.
+fn describe_policy_match(rule_match: &RuleMatch) -> Option<(usize, String)> {
+    match rule_match {
+        RuleMatch::PrefixRuleMatch { matched_prefix, .. } => Some((
+            matched_prefix.len(),
+            format!(
+                "commands starting with `{}`",
+                render_shlex_command(matched_prefix)
+            ),
+        )),
+        RuleMatch::ArgRuleMatch {
+            program,
+            matched_args,
+            ..
+        } => Some((
+            matched_args.len() + 1,
+            format!(
+                "`{program}` with arguments `{}`",
+                render_shlex_command(matched_args)
+            ),
+        )),
+        RuleMatch::PathRuleMatch {
+            program,
+            outside_paths,
+            ..
+        } => Some((
+            1,
+            format!(
+                "`{program}` on paths outside the workspace (`{}`)",
+                render_shlex_command(outside_paths)
+            ),
+        )),
+        RuleMatch::HeuristicsRuleMatch { .. } => None,
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum ExecPolicyError {
     #[error("failed to read rules files from {dir}: {source}")]
@@ -114,6 +155,7 @@ impl ExecPolicyManager {
         &self,
         features: &Features,
         command: &[String],
+        cwd: &Path,
         approval_policy: AskForApproval,
         sandbox_policy: &SandboxPolicy,
         sandbox_permissions: SandboxPermissions,
@@ -121,6 +163,7 @@ impl ExecPolicyManager {
         let exec_policy = self.current();
         let commands =
             parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+        let match_context = match_context_for_sandbox(cwd, sandbox_policy);
         let exec_policy_fallback = |cmd: &[String]| {
             render_decision_for_unmatched_command(
                 approval_policy,
@@ -129,7 +172,11 @@ impl ExecPolicyManager {
                 sandbox_permissions,
             )
         };
-        let evaluation = exec_policy.check_multiple(commands.iter(), &exec_policy_fallback);
+        let evaluation = exec_policy.check_multiple_with_context(
+            commands.iter(),
+            &match_context,
+            &exec_policy_fallback,
+        );
 
         match evaluation.decision {
             Decision::Forbidden => ExecApprovalRequirement::Forbidden {
@@ -309,8 +356,10 @@ pub fn render_decision_for_unmatched_command(
                     // command has not been flagged as dangerous.
                     Decision::Allow
                 }
-                SandboxPolicy::ReadOnly | SandboxPolicy::WorkspaceWrite { .. } => {
-                    // In restricted sandboxes (ReadOnly/WorkspaceWrite), do not prompt for
+                SandboxPolicy::ReadOnly
+                | SandboxPolicy::WorkspaceWrite { .. }
+                | SandboxPolicy::RestrictedRead { .. } => {
+                    // In restricted sandboxes (ReadOnly/WorkspaceWrite/RestrictedRead), do not prompt for
                     // non‑escalated, non‑dangerous commands — let the sandbox enforce
                     // restrictions (e.g., block network/write) without a user prompt.
                     if sandbox_permissions.requires_escalated_permissions() {
@@ -324,6 +373,19 @@ pub fn render_decision_for_unmatched_command(
     }
 }
 
+/// The workspace for `path_rule`s is the cwd plus any additional writable roots
+/// granted by the sandbox policy.
+fn match_context_for_sandbox(cwd: &Path, sandbox_policy: &SandboxPolicy) -> MatchContext {
+    let mut workspace_roots = vec![cwd.to_path_buf()];
+    workspace_roots.extend(
+        sandbox_policy
+            .get_writable_roots_with_cwd(cwd)
+            .into_iter()
+            .map(|writable_root| writable_root.root.into_path_buf()),
+    );
+    MatchContext::new(cwd.to_path_buf(), workspace_roots)
+}
+
 fn default_policy_path(codex_home: &Path) -> PathBuf {
     codex_home.join(RULES_DIR_NAME).join(DEFAULT_POLICY_FILE)
 }
@@ -387,24 +449,18 @@ fn derive_prompt_reason(command_args: &[String], evaluation: &Evaluation) -> Opt
     let most_specific_prompt = evaluation
         .matched_rules
         .iter()
-        .filter_map(|rule_match| match rule_match {
-            RuleMatch::PrefixRuleMatch {
-                matched_prefix,
-                decision: Decision::Prompt,
-                justification,
-                ..
-            } => Some((matched_prefix.len(), justification.as_deref())),
-            _ => None,
+        .filter(|rule_match| rule_match.decision() == Decision::Prompt)
+        .filter_map(|rule_match| {
+            describe_policy_match(rule_match)
+                .map(|(specificity, _)| (specificity, rule_match.justification()))
         })
-        .max_by_key(|(matched_prefix_len, _)| *matched_prefix_len);
+        .max_by_key(|(specificity, _)| *specificity);
 
     match most_specific_prompt {
-        Some((_matched_prefix_len, Some(justification))) => {
+        Some((_specificity, Some(justification))) => {
             Some(format!("`{command}` requires approval: {justification}"))
         }
-        Some((_matched_prefix_len, None)) => {
-            Some(format!("`{command}` requires approval by policy"))
-        }
+        Some((_specificity, None)) => Some(format!("`{command}` requires approval by policy")),
         None => None,
     }
 }
@@ -422,25 +478,18 @@ fn derive_forbidden_reason(command_args: &[String], evaluation: &Evaluation) ->
     let most_specific_forbidden = evaluation
         .matched_rules
         .iter()
-        .filter_map(|rule_match| match rule_match {
-            RuleMatch::PrefixRuleMatch {
-                matched_prefix,
-                decision: Decision::Forbidden,
-                justification,
-                ..
-            } => Some((matched_prefix, justification.as_deref())),
-            _ => None,
+        .filter(|rule_match| rule_match.decision() == Decision::Forbidden)
+        .filter_map(|rule_match| {
+            describe_policy_match(rule_match)
+                .map(|(specificity, description)| (specificity, description, rule_match))
         })
-        .max_by_key(|(matched_prefix, _)| matched_prefix.len());
+        .max_by_key(|(specificity, _, _)| *specificity);
 
     match most_specific_forbidden {
-        Some((_matched_prefix, Some(justification))) => {
-            format!("`{command}` rejected: {justification}")
-        }
-        Some((matched_prefix, None)) => {
-            let prefix = render_shlex_command(matched_prefix);
-            format!("`{command}` rejected: policy forbids commands starting with `{prefix}`")
-        }
+        Some((_specificity, description, rule_match)) => match rule_match.justification() {
+            Some(justification) => format!("`{command}` rejected: {justification}"),
+            None => format!("`{command}` rejected: policy forbids {description}"),
+        },
         None => format!("`{command}` rejected: blocked by policy"),
     }
 }
@@ -716,6 +765,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             .create_exec_approval_requirement_for_command(
                 &Features::with_defaults(),
                 &forbidden_script,
+                Path::new("/workspace"),
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
@@ -730,6 +780,58 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         );
     }
 
+    #[tokio::test]
+    async fn path_rules_use_cwd_and_writable_roots_as_workspace() {
+        let policy_src = r#"
+path_rule(program="rm", decision="forbidden")
+"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.rules", policy_src)
+            .expect("parse policy");
+        let manager = ExecPolicyManager::new(Arc::new(parser.build()));
+        let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![
+                AbsolutePathBuf::from_absolute_path("/scratch").expect("absolute path"),
+            ],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        let inside = manager
+            .create_exec_approval_requirement_for_command(
+                &Features::with_defaults(),
+                &vec_str(&["rm", "-rf", "target", "/scratch/build"]),
+                Path::new("/workspace"),
+                AskForApproval::OnRequest,
+                &sandbox_policy,
+                SandboxPermissions::UseDefault,
+            )
+            .await;
+        assert!(
+            !matches!(inside, ExecApprovalRequirement::Forbidden { .. }),
+            "unexpected requirement: {inside:?}"
+        );
+
+        let outside = manager
+            .create_exec_approval_requirement_for_command(
+                &Features::with_defaults(),
+                &vec_str(&["rm", "-rf", "../other-team"]),
+                Path::new("/workspace"),
+                AskForApproval::OnRequest,
+                &sandbox_policy,
+                SandboxPermissions::UseDefault,
+            )
+            .await;
+        assert_eq!(
+            outside,
+            ExecApprovalRequirement::Forbidden {
+                reason: "`rm -rf ../other-team` rejected: policy forbids `rm` on paths outside the workspace (`../other-team`)".to_string()
+            }
+        );
+    }
+
     #[tokio::test]
     async fn justification_is_included_in_forbidden_exec_approval_requirement() {
         let policy_src = r#"
@@ -754,6 +856,7 @@ prefix_rule(
                     "-rf".to_string(),
                     "/some/important/folder".to_string(),
                 ],
+                Path::new("/workspace"),
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
@@ -783,6 +886,7 @@ prefix_rule(
             .create_exec_approval_requirement_for_command(
                 &Features::with_defaults(),
                 &command,
+                Path::new("/workspace"),
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
@@ -813,6 +917,7 @@ prefix_rule(
             .create_exec_approval_requirement_for_command(
                 &Features::with_defaults(),
                 &command,
+                Path::new("/workspace"),
                 AskForApproval::Never,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
@@ -836,6 +941,7 @@ prefix_rule(
             .create_exec_approval_requirement_for_command(
                 &Features::with_defaults(),
                 &command,
+                Path::new("/workspace"),
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
@@ -870,6 +976,7 @@ prefix_rule(
                 .create_exec_approval_requirement_for_command(
                     &Features::with_defaults(),
                     &command,
+                    Path::new("/workspace"),
                     AskForApproval::UnlessTrusted,
                     &SandboxPolicy::DangerFullAccess,
                     SandboxPermissions::UseDefault,
@@ -944,6 +1051,7 @@ prefix_rule(
             .create_exec_approval_requirement_for_command(
                 &Features::with_defaults(),
                 &command,
+                Path::new("/workspace"),
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
@@ -971,6 +1079,7 @@ prefix_rule(
             .create_exec_approval_requirement_for_command(
                 &features,
                 &command,
+                Path::new("/workspace"),
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
@@ -1001,6 +1110,7 @@ prefix_rule(
             .create_exec_approval_requirement_for_command(
                 &Features::with_defaults(),
                 &command,
+                Path::new("/workspace"),
                 AskForApproval::OnRequest,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
@@ -1028,6 +1138,7 @@ prefix_rule(
             .create_exec_approval_requirement_for_command(
                 &Features::with_defaults(),
                 &command,
+                Path::new("/workspace"),
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
@@ -1066,6 +1177,7 @@ prefix_rule(
                 .create_exec_approval_requirement_for_command(
                     &Features::with_defaults(),
                     &command,
+                    Path::new("/workspace"),
                     AskForApproval::UnlessTrusted,
                     &SandboxPolicy::ReadOnly,
                     SandboxPermissions::UseDefault,
@@ -1089,6 +1201,7 @@ prefix_rule(
             .create_exec_approval_requirement_for_command(
                 &Features::with_defaults(),
                 &command,
+                Path::new("/workspace"),
                 AskForApproval::OnRequest,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
@@ -1119,6 +1232,7 @@ prefix_rule(
             .create_exec_approval_requirement_for_command(
                 &Features::with_defaults(),
                 &command,
+                Path::new("/workspace"),
                 AskForApproval::OnRequest,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
@@ -1186,6 +1300,7 @@ prefix_rule(
                 .create_exec_approval_requirement_for_command(
                     &features,
                     &sneaky_command,
+                    Path::new("/workspace"),
                     AskForApproval::OnRequest,
                     &SandboxPolicy::ReadOnly,
                     permissions,
@@ -1209,6 +1324,7 @@ prefix_rule(
                 .create_exec_approval_requirement_for_command(
                     &features,
                     &dangerous_command,
+                    Path::new("/workspace"),
                     AskForApproval::OnRequest,
                     &SandboxPolicy::ReadOnly,
                     permissions,
@@ -1228,6 +1344,7 @@ prefix_rule(
                 .create_exec_approval_requirement_for_command(
                     &features,
                     &dangerous_command,
+                    Path::new("/workspace"),
                     AskForApproval::Never,
                     &SandboxPolicy::ReadOnly,
                     permissions,
@@ -98,6 +98,11 @@ pub enum Feature {
     EnableRequestCompression,
     /// Enable collab tools.
     Collab,
+    /// Expose the indexed `code_search` tool.
+    CodeSearch,
+    /// Per-project memories: `remember`/`recall` tools and memories in the
+    /// initial context.
+    Memory,
     /// Steer feature flag - when enabled, Enter submits immediately instead of queuing.
     Steer,
 }
@@ -418,6 +423,18 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Experimental,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::CodeSearch,
+        key: "code_search",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::Memory,
+        key: "memory",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::Tui2,
         key: "tui2",
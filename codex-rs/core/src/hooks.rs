@@ -0,0 +1,542 @@
+//! User-configured commands that run synchronously at lifecycle points.
+//!
+//! Unlike [`crate::user_notification::UserNotifier`], hooks are awaited and
+//! their output can influence the session: `pre_tool_use` and
+//! `user_prompt_submit` hooks can block the action, `pre_tool_use` hooks can
+//! rewrite the tool input, and `pre_tool_use` and `post_tool_use` hooks can
+//! append feedback to the tool output the model sees, even when the tool
+//! fails.
+//!
+//! Every hook receives the event as JSON on stdin. The exit status decides
+//! what happens next:
+//!
+//! - `0`: stdout is parsed as a [`HookResponse`]; plain text on stdout is
+//!   treated as feedback.
+//! - `2`: the action is blocked and stderr is used as the reason.
+//! - anything else (including timeouts): the failure is logged and ignored.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use codex_protocol::models::FunctionCallOutputContentItem;
+use mcp_types::ContentBlock;
+use mcp_types::TextContent;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use crate::config::types::HookCommand;
+use crate::config::types::HooksConfig;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+
+const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Exit status a hook uses to block the action it was run for.
+const BLOCK_EXIT_CODE: i32 = 2;
+
+/// Payload written to a hook's stdin.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(crate) enum HookEvent {
+    SessionStart {
+        thread_id: String,
+        cwd: PathBuf,
+    },
+    SessionStop {
+        thread_id: String,
+    },
+    UserPromptSubmit {
+        thread_id: String,
+        turn_id: String,
+        cwd: PathBuf,
+        prompt: String,
+    },
+    PreToolUse {
+        thread_id: String,
+        turn_id: String,
+        cwd: PathBuf,
+        tool_name: String,
+        call_id: String,
+        tool_input: Value,
+    },
+    PostToolUse {
+        thread_id: String,
+        turn_id: String,
+        cwd: PathBuf,
+        tool_name: String,
+        call_id: String,
+        tool_input: Value,
+        tool_output: String,
+        success: bool,
+    },
+    TurnEnd {
+        thread_id: String,
+        turn_id: String,
+        cwd: PathBuf,
+        last_assistant_message: Option<String>,
+    },
+}
+
+impl HookEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            HookEvent::SessionStart { .. } => "session_start",
+            HookEvent::SessionStop { .. } => "session_stop",
+            HookEvent::UserPromptSubmit { .. } => "user_prompt_submit",
+            HookEvent::PreToolUse { .. } => "pre_tool_use",
+            HookEvent::PostToolUse { .. } => "post_tool_use",
+            HookEvent::TurnEnd { .. } => "turn_end",
+        }
+    }
+
+    fn tool_name(&self) -> Option<&str> {
+        match self {
+            HookEvent::PreToolUse { tool_name, .. } | HookEvent::PostToolUse { tool_name, .. } => {
+                Some(tool_name)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// What a hook may print on stdout when it exits successfully.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub(crate) struct HookResponse {
+    pub(crate) decision: Option<HookDecision>,
+    pub(crate) reason: Option<String>,
+    /// Replacement tool input (only honored for `pre_tool_use`).
+    pub(crate) tool_input: Option<Value>,
+    /// Text appended to the tool output (only honored for `post_tool_use`).
+    pub(crate) feedback: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HookDecision {
+    Allow,
+    Block,
+}
+
+/// Combined result of every hook that ran for one event.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct HookOutcome {
+    /// Set when a hook blocked the action.
+    pub(crate) block_reason: Option<String>,
+    /// Latest rewritten tool input, if any hook provided one.
+    pub(crate) tool_input: Option<Value>,
+    pub(crate) feedback: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Hooks {
+    config: HooksConfig,
+}
+
+impl Hooks {
+    pub(crate) fn new(config: HooksConfig) -> Self {
+        Self { config }
+    }
+
+    pub(crate) fn has_pre_tool_use(&self, tool_name: &str) -> bool {
+        self.config
+            .pre_tool_use
+            .iter()
+            .any(|hook| hook.matches_tool(tool_name))
+    }
+
+    pub(crate) fn has_post_tool_use(&self, tool_name: &str) -> bool {
+        self.config
+            .post_tool_use
+            .iter()
+            .any(|hook| hook.matches_tool(tool_name))
+    }
+
+    /// Runs the hooks registered for `event` one after another. Stops at the
+    /// first hook that blocks. For `pre_tool_use`, each hook sees the input
+    /// as rewritten by the hooks before it.
+    pub(crate) async fn run(&self, mut event: HookEvent, cwd: &Path) -> HookOutcome {
+        let commands = match &event {
+            HookEvent::SessionStart { .. } => &self.config.session_start,
+            HookEvent::SessionStop { .. } => &self.config.session_stop,
+            HookEvent::UserPromptSubmit { .. } => &self.config.user_prompt_submit,
+            HookEvent::PreToolUse { .. } => &self.config.pre_tool_use,
+            HookEvent::PostToolUse { .. } => &self.config.post_tool_use,
+            HookEvent::TurnEnd { .. } => &self.config.turn_end,
+        };
+
+        let mut outcome = HookOutcome::default();
+        for hook in commands {
+            if let Some(tool_name) = event.tool_name()
+                && !hook.matches_tool(tool_name)
+            {
+                continue;
+            }
+            let Some(response) = run_hook(hook, &event, cwd).await else {
+                continue;
+            };
+            if response.decision == Some(HookDecision::Block) {
+                outcome.block_reason = Some(
+                    response
+                        .reason
+                        .unwrap_or_else(|| "no reason given".to_string()),
+                );
+                break;
+            }
+            if let Some(feedback) = response.feedback
+                && !feedback.trim().is_empty()
+            {
+                outcome.feedback.push(feedback.trim_end().to_string());
+            }
+            if let (HookEvent::PreToolUse { tool_input, .. }, Some(rewritten)) =
+                (&mut event, response.tool_input)
+            {
+                *tool_input = rewritten.clone();
+                outcome.tool_input = Some(rewritten);
+            }
+        }
+        outcome
+    }
+}
+
+/// Runs a single hook. Returns `None` when the hook could not be run or
+/// failed in a way that should not affect the session.
+async fn run_hook(hook: &HookCommand, event: &HookEvent, cwd: &Path) -> Option<HookResponse> {
+    let event_name = event.name();
+    let Some((program, args)) = hook.command.split_first() else {
+        warn!("ignoring {event_name} hook with an empty command");
+        return None;
+    };
+    let input = match serde_json::to_vec(event) {
+        Ok(input) => input,
+        Err(err) => {
+            warn!("failed to serialize {event_name} hook payload: {err}");
+            return None;
+        }
+    };
+
+    let mut child = match tokio::process::Command::new(program)
+        .args(args)
+        .current_dir(cwd)
+        .env("CODEX_HOOK_EVENT", event_name)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(err) => {
+            warn!("failed to spawn {event_name} hook '{program}': {err}");
+            return None;
+        }
+    };
+
+    let timeout = hook
+        .timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_HOOK_TIMEOUT);
+    let run = async move {
+        if let Some(mut stdin) = child.stdin.take() {
+            // A hook that ignores its input may exit before reading it; that
+            // is not an error.
+            let _ = stdin.write_all(&input).await;
+        }
+        child.wait_with_output().await
+    };
+    let output = match tokio::time::timeout(timeout, run).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => {
+            warn!("{event_name} hook '{program}' failed: {err}");
+            return None;
+        }
+        Err(_) => {
+            warn!(
+                "{event_name} hook '{program}' timed out after {}ms",
+                timeout.as_millis()
+            );
+            return None;
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    match output.status.code() {
+        Some(0) => Some(parse_hook_stdout(&stdout)),
+        Some(BLOCK_EXIT_CODE) => Some(HookResponse {
+            decision: Some(HookDecision::Block),
+            reason: Some(stderr.trim().to_string()).filter(|reason| !reason.is_empty()),
+            ..Default::default()
+        }),
+        _ => {
+            warn!(
+                "{event_name} hook '{program}' exited with {}: {}",
+                output.status,
+                stderr.trim()
+            );
+            None
+        }
+    }
+}
+
+fn parse_hook_stdout(stdout: &str) -> HookResponse {
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return HookResponse::default();
+    }
+    match serde_json::from_str::<HookResponse>(trimmed) {
+        Ok(response) => response,
+        Err(_) => HookResponse {
+            feedback: Some(trimmed.to_string()),
+            ..Default::default()
+        },
+    }
+}
+
+/// JSON view of a tool call's input handed to hooks.
+pub(crate) fn tool_input_for_hook(payload: &ToolPayload) -> Value {
+    match payload {
+        ToolPayload::Function { arguments } => {
+            serde_json::from_str(arguments).unwrap_or_else(|_| Value::String(arguments.clone()))
+        }
+        ToolPayload::Custom { input } => Value::String(input.clone()),
+        ToolPayload::LocalShell { params } => serde_json::to_value(params).unwrap_or(Value::Null),
+        ToolPayload::Mcp { raw_arguments, .. } => serde_json::from_str(raw_arguments)
+            .unwrap_or_else(|_| Value::String(raw_arguments.clone())),
+    }
+}
+
+/// Applies a hook-provided replacement input to `payload`.
+pub(crate) fn apply_hook_tool_input(
+    payload: &ToolPayload,
+    tool_input: Value,
+) -> Result<ToolPayload, String> {
+    let as_text = |value: Value| match value {
+        Value::String(text) => text,
+        other => other.to_string(),
+    };
+    Ok(match payload {
+        ToolPayload::Function { .. } => ToolPayload::Function {
+            arguments: as_text(tool_input),
+        },
+        ToolPayload::Custom { .. } => ToolPayload::Custom {
+            input: as_text(tool_input),
+        },
+        ToolPayload::LocalShell { .. } => ToolPayload::LocalShell {
+            params: serde_json::from_value(tool_input)
+                .map_err(|err| format!("invalid shell parameters from hook: {err}"))?,
+        },
+        ToolPayload::Mcp { server, tool, .. } => ToolPayload::Mcp {
+            server: server.clone(),
+            tool: tool.clone(),
+            raw_arguments: as_text(tool_input),
+        },
+    })
+}
+
+/// Text view of a tool's output handed to `post_tool_use` hooks.
+pub(crate) fn tool_output_for_hook(output: &ToolOutput) -> String {
+    match output {
+        ToolOutput::Function { content, .. } => content.clone(),
+        ToolOutput::Mcp { result: Ok(result) } => serde_json::to_string(result).unwrap_or_default(),
+        ToolOutput::Mcp { result: Err(err) } => err.clone(),
+    }
+}
+
+/// Appends hook feedback to the output returned to the model.
+pub(crate) fn append_hook_feedback(output: &mut ToolOutput, feedback: &[String]) {
+    if feedback.is_empty() {
+        return;
+    }
+    let text = feedback.join("\n");
+    match output {
+        ToolOutput::Function {
+            content,
+            content_items,
+            ..
+        } => {
+            if !content.is_empty() {
+                content.push_str("\n\n");
+            }
+            content.push_str(&text);
+            if let Some(items) = content_items {
+                items.push(FunctionCallOutputContentItem::InputText { text });
+            }
+        }
+        ToolOutput::Mcp { result: Ok(result) } => {
+            result.content.push(ContentBlock::TextContent(TextContent {
+                annotations: None,
+                text,
+                r#type: "text".to_string(),
+            }));
+        }
+        ToolOutput::Mcp { result: Err(err) } => {
+            err.push_str("\n\n");
+            err.push_str(&text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn hook(script: &str) -> HookCommand {
+        HookCommand {
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            tools: Vec::new(),
+            timeout_ms: None,
+        }
+    }
+
+    fn pre_tool_use(tool_name: &str, tool_input: Value) -> HookEvent {
+        HookEvent::PreToolUse {
+            thread_id: "thread".to_string(),
+            turn_id: "turn".to_string(),
+            cwd: PathBuf::from("/tmp"),
+            tool_name: tool_name.to_string(),
+            call_id: "call".to_string(),
+            tool_input,
+        }
+    }
+
+    #[test]
+    fn plain_stdout_becomes_feedback() {
+        assert_eq!(
+            parse_hook_stdout("formatted 3 files\n"),
+            HookResponse {
+                feedback: Some("formatted 3 files".to_string()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            parse_hook_stdout(r#"{"decision":"block","reason":"generated file"}"#),
+            HookResponse {
+                decision: Some(HookDecision::Block),
+                reason: Some("generated file".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn rewritten_input_maps_back_to_payload_kind() {
+        let payload = ToolPayload::Function {
+            arguments: r#"{"command":["ls"]}"#.to_string(),
+        };
+        assert_eq!(tool_input_for_hook(&payload), json!({"command": ["ls"]}));
+        let rewritten = apply_hook_tool_input(&payload, json!({"command": ["ls", "-a"]})).unwrap();
+        assert_eq!(rewritten.log_payload(), r#"{"command":["ls","-a"]}"#);
+
+        let custom = ToolPayload::Custom {
+            input: "*** Begin Patch".to_string(),
+        };
+        let rewritten = apply_hook_tool_input(&custom, json!("*** Begin Patch\n")).unwrap();
+        assert_eq!(rewritten.log_payload(), "*** Begin Patch\n");
+    }
+
+    #[test]
+    fn feedback_is_appended_to_function_output() {
+        let mut output = ToolOutput::Function {
+            content: "Success.".to_string(),
+            content_items: None,
+            success: Some(true),
+        };
+        append_hook_feedback(&mut output, &["cargo fmt: ok".to_string()]);
+        assert_eq!(tool_output_for_hook(&output), "Success.\n\ncargo fmt: ok");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn exit_code_two_blocks_with_stderr_reason() {
+        let hooks = Hooks::new(HooksConfig {
+            pre_tool_use: vec![
+                hook("echo 'edits to generated files are not allowed' >&2; exit 2"),
+                hook("echo never runs"),
+            ],
+            ..Default::default()
+        });
+        let outcome = hooks
+            .run(pre_tool_use("apply_patch", json!("patch")), Path::new("/"))
+            .await;
+        assert_eq!(
+            outcome,
+            HookOutcome {
+                block_reason: Some("edits to generated files are not allowed".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pre_tool_use_hooks_chain_rewrites_and_skip_other_tools() {
+        let mut only_shell = hook(r#"echo '{"tool_input":{"command":["false"]}}'"#);
+        only_shell.tools = vec!["shell".to_string()];
+        let hooks = Hooks::new(HooksConfig {
+            pre_tool_use: vec![
+                only_shell,
+                // Only succeeds if it sees the input rewritten by the first hook.
+                hook(r#"grep -q '\["false"\]' && echo saw-rewrite"#),
+                hook("exit 1"),
+            ],
+            ..Default::default()
+        });
+
+        let outcome = hooks
+            .run(
+                pre_tool_use("shell", json!({"command": ["true"]})),
+                Path::new("/"),
+            )
+            .await;
+        assert_eq!(
+            outcome,
+            HookOutcome {
+                block_reason: None,
+                tool_input: Some(json!({"command": ["false"]})),
+                feedback: vec!["saw-rewrite".to_string()],
+            }
+        );
+
+        let outcome = hooks
+            .run(
+                pre_tool_use("read_file", json!({"path": "a"})),
+                Path::new("/"),
+            )
+            .await;
+        assert_eq!(outcome, HookOutcome::default());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn hooks_receive_event_on_stdin_and_env() {
+        let hooks = Hooks::new(HooksConfig {
+            turn_end: vec![hook(r#"echo "$CODEX_HOOK_EVENT"; cat"#)],
+            ..Default::default()
+        });
+        let outcome = hooks
+            .run(
+                HookEvent::TurnEnd {
+                    thread_id: "thread".to_string(),
+                    turn_id: "turn".to_string(),
+                    cwd: PathBuf::from("/"),
+                    last_assistant_message: None,
+                },
+                Path::new("/"),
+            )
+            .await;
+        assert_eq!(
+            outcome.feedback,
+            vec![
+                "turn_end\n{\"event\":\"turn_end\",\"thread_id\":\"thread\",\"turn_id\":\"turn\",\"cwd\":\"/\",\"last_assistant_message\":null}".to_string()
+            ]
+        );
+    }
+}
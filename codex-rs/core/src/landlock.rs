@@ -25,7 +25,7 @@ pub async fn spawn_command_under_linux_sandbox<P>(
 where
     P: AsRef<Path>,
 {
-    let args = create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd);
+    let args = create_linux_sandbox_command_args(command, sandbox_policy, sandbox_policy_cwd, None);
     let arg0 = Some("codex-linux-sandbox");
     spawn_child_async(
         codex_linux_sandbox_exe.as_ref().to_path_buf(),
@@ -40,10 +40,15 @@ where
 }
 
 /// Converts the sandbox policy into the CLI invocation for `codex-linux-sandbox`.
+///
+/// When `network_proxy` is set to the proxy's loopback port and Unix socket,
+/// the helper runs the command in a private network namespace whose only
+/// reachable endpoint is that port, bridged to the proxy's socket.
 pub(crate) fn create_linux_sandbox_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+    network_proxy: Option<(u16, &Path)>,
 ) -> Vec<String> {
     #[expect(clippy::expect_used)]
     let sandbox_policy_cwd = sandbox_policy_cwd
@@ -60,10 +65,16 @@ pub(crate) fn create_linux_sandbox_command_args(
         sandbox_policy_cwd,
         "--sandbox-policy".to_string(),
         sandbox_policy_json,
-        // Separator so that command arguments starting with `-` are not parsed as
-        // options of the helper itself.
-        "--".to_string(),
     ];
+    if let Some((port, socket)) = network_proxy {
+        linux_cmd.push("--network-proxy-port".to_string());
+        linux_cmd.push(port.to_string());
+        linux_cmd.push("--network-proxy-socket".to_string());
+        linux_cmd.push(socket.to_string_lossy().to_string());
+    }
+    // Separator so that command arguments starting with `-` are not parsed as
+    // options of the helper itself.
+    linux_cmd.push("--".to_string());
 
     // Append the original tool command.
     linux_cmd.extend(command);
@@ -9,8 +9,10 @@ pub mod api_bridge;
 mod apply_patch;
 pub mod auth;
 pub mod bash;
+mod budget;
 mod client;
 mod client_common;
+mod code_search;
 pub mod codex;
 mod codex_thread;
 mod compact_remote;
@@ -31,6 +33,7 @@ mod exec_policy;
 pub mod features;
 mod flags;
 pub mod git_info;
+mod hooks;
 pub mod landlock;
 pub mod mcp;
 mod mcp_connection_manager;
@@ -39,6 +42,7 @@ pub use mcp_connection_manager::MCP_SANDBOX_STATE_CAPABILITY;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_METHOD;
 pub use mcp_connection_manager::SandboxState;
 mod mcp_tool_call;
+pub mod memory;
 mod message_history;
 mod model_provider_info;
 pub mod parse_command;
@@ -63,6 +67,7 @@ pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
 mod event_mapping;
+pub mod review_export;
 pub mod review_format;
 pub mod review_prompts;
 mod thread_manager;
@@ -95,6 +100,10 @@ pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
+pub use rollout::export::Transcript;
+pub use rollout::export::TranscriptEntry;
+pub use rollout::export::TranscriptFormat;
+pub use rollout::export::load_transcript;
 #[deprecated(note = "use find_thread_path_by_id_str")]
 pub use rollout::find_conversation_path_by_id_str;
 pub use rollout::find_thread_path_by_id_str;
@@ -103,6 +112,13 @@ pub use rollout::list::ThreadItem;
 pub use rollout::list::ThreadsPage;
 pub use rollout::list::parse_cursor;
 pub use rollout::list::read_head_for_summary;
+pub use rollout::search::SESSION_SEARCH_INDEX_FILE;
+pub use rollout::search::SearchMatch;
+pub use rollout::search::SearchMatchKind;
+pub use rollout::search::ThreadSearchHit;
+pub use rollout::search::ThreadSearchOptions;
+pub use rollout::search::ThreadSearchResults;
+pub use rollout::search::search_threads;
 mod function_tool;
 mod state;
 mod tasks;
@@ -1,4 +1,7 @@
 pub mod auth;
+pub(crate) mod prompts;
+pub(crate) mod sampling;
+
 use std::collections::HashMap;
 use std::env;
 use std::path::PathBuf;
@@ -6,10 +9,14 @@ use std::path::PathBuf;
 use async_channel::unbounded;
 use codex_protocol::protocol::McpListToolsResponseEvent;
 use codex_protocol::protocol::SandboxPolicy;
+use mcp_types::GetPromptResult;
+use mcp_types::Prompt as McpPrompt;
 use mcp_types::Tool as McpTool;
 use tokio_util::sync::CancellationToken;
 
 use crate::config::Config;
+use crate::config::types::McpServerConfig;
+use crate::mcp::auth::McpAuthStatusEntry;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::mcp_connection_manager::SandboxState;
@@ -33,12 +40,84 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
     )
     .await;
 
+    let (mcp_connection_manager, cancel_token) =
+        start_ephemeral_manager(config, &config.mcp_servers, auth_status_entries.clone()).await;
+
+    let snapshot =
+        collect_mcp_snapshot_from_manager(&mcp_connection_manager, auth_status_entries).await;
+
+    cancel_token.cancel();
+
+    snapshot
+}
+
+/// Lists the prompts published by the configured MCP servers, or only by
+/// `server` when given, grouped by server name.
+pub async fn collect_mcp_prompts(
+    config: &Config,
+    server: Option<&str>,
+) -> HashMap<String, Vec<McpPrompt>> {
+    let servers = select_servers(config, server);
+    if servers.is_empty() {
+        return HashMap::new();
+    }
+
+    let auth_status_entries =
+        compute_auth_statuses(servers.iter(), config.mcp_oauth_credentials_store_mode).await;
+    let (mcp_connection_manager, cancel_token) =
+        start_ephemeral_manager(config, &servers, auth_status_entries).await;
+    let prompts = mcp_connection_manager.list_all_prompts().await;
+    cancel_token.cancel();
+
+    prompts
+}
+
+/// Renders the prompt `name` published by `server` with `arguments`.
+pub async fn get_mcp_prompt(
+    config: &Config,
+    server: &str,
+    name: &str,
+    arguments: &HashMap<String, String>,
+) -> anyhow::Result<GetPromptResult> {
+    let servers = select_servers(config, Some(server));
+    if servers.is_empty() {
+        anyhow::bail!("unknown MCP server '{server}'");
+    }
+
+    let auth_status_entries =
+        compute_auth_statuses(servers.iter(), config.mcp_oauth_credentials_store_mode).await;
+    let (mcp_connection_manager, cancel_token) =
+        start_ephemeral_manager(config, &servers, auth_status_entries).await;
+    let result = mcp_connection_manager
+        .get_prompt(server, name, arguments)
+        .await;
+    cancel_token.cancel();
+
+    result
+}
+
+fn select_servers(config: &Config, server: Option<&str>) -> HashMap<String, McpServerConfig> {
+    config
+        .mcp_servers
+        .iter()
+        .filter(|(name, cfg)| cfg.enabled && server.is_none_or(|server| server == name.as_str()))
+        .map(|(name, cfg)| (name.clone(), cfg.clone()))
+        .collect()
+}
+
+/// Starts a short-lived connection manager for one-off queries made outside
+/// of a session. Cancel the returned token once done to shut the servers down.
+async fn start_ephemeral_manager(
+    config: &Config,
+    servers: &HashMap<String, McpServerConfig>,
+    auth_status_entries: HashMap<String, McpAuthStatusEntry>,
+) -> (McpConnectionManager, CancellationToken) {
     let mut mcp_connection_manager = McpConnectionManager::default();
     let (tx_event, rx_event) = unbounded();
     drop(rx_event);
     let cancel_token = CancellationToken::new();
 
-    // Use ReadOnly sandbox policy for MCP snapshot collection (safest default)
+    // Use ReadOnly sandbox policy for one-off MCP queries (safest default)
     let sandbox_state = SandboxState {
         sandbox_policy: SandboxPolicy::ReadOnly,
         codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
@@ -47,21 +126,16 @@ pub async fn collect_mcp_snapshot(config: &Config) -> McpListToolsResponseEvent
 
     mcp_connection_manager
         .initialize(
-            &config.mcp_servers,
+            servers,
             config.mcp_oauth_credentials_store_mode,
-            auth_status_entries.clone(),
+            auth_status_entries,
             tx_event,
             cancel_token.clone(),
             sandbox_state,
         )
         .await;
 
-    let snapshot =
-        collect_mcp_snapshot_from_manager(&mcp_connection_manager, auth_status_entries).await;
-
-    cancel_token.cancel();
-
-    snapshot
+    (mcp_connection_manager, cancel_token)
 }
 
 pub fn split_qualified_tool_name(qualified_name: &str) -> Option<(String, String)> {
@@ -95,7 +169,7 @@ pub fn group_tools_by_server(
 
 pub(crate) async fn collect_mcp_snapshot_from_manager(
     mcp_connection_manager: &McpConnectionManager,
-    auth_status_entries: HashMap<String, crate::mcp::auth::McpAuthStatusEntry>,
+    auth_status_entries: HashMap<String, McpAuthStatusEntry>,
 ) -> McpListToolsResponseEvent {
     let (tools, resources, resource_templates) = tokio::join!(
         mcp_connection_manager.list_all_tools(),
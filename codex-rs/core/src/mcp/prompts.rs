@@ -0,0 +1,187 @@
+//! Rendering of MCP prompts (`prompts/get`) into conversation items.
+//!
+//! A prompt selected by the user arrives as [`UserInput::McpPrompt`]. The
+//! session asks the owning server to render it and records the returned
+//! messages right after the user message, keeping their roles, so the model
+//! sees the conversation the server author intended.
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use mcp_types::ContentBlock;
+use mcp_types::EmbeddedResourceResource;
+use mcp_types::PromptMessage;
+use mcp_types::Role;
+
+use crate::mcp_connection_manager::McpConnectionManager;
+
+#[derive(Debug, Default)]
+pub(crate) struct McpPromptInjections {
+    pub(crate) items: Vec<ResponseItem>,
+    pub(crate) warnings: Vec<String>,
+}
+
+pub(crate) async fn build_mcp_prompt_injections(
+    inputs: &[UserInput],
+    mcp_connection_manager: &McpConnectionManager,
+) -> McpPromptInjections {
+    let mut result = McpPromptInjections::default();
+
+    for input in inputs {
+        let UserInput::McpPrompt {
+            server,
+            name,
+            arguments,
+        } = input
+        else {
+            continue;
+        };
+        match mcp_connection_manager
+            .get_prompt(server, name, arguments)
+            .await
+        {
+            Ok(rendered) => {
+                for message in rendered.messages {
+                    match prompt_message_to_response_item(message) {
+                        Ok(item) => result.items.push(item),
+                        Err(reason) => result.warnings.push(format!(
+                            "Skipped part of MCP prompt {server}:{name}: {reason}"
+                        )),
+                    }
+                }
+            }
+            Err(err) => {
+                result.warnings.push(format!(
+                    "Failed to load MCP prompt {server}:{name}: {err:#}"
+                ));
+            }
+        }
+    }
+
+    result
+}
+
+/// Converts one rendered prompt message into a conversation message with the
+/// same role. Returns a description of the problem when the content cannot be
+/// represented (for example, audio or images attributed to the assistant).
+fn prompt_message_to_response_item(message: PromptMessage) -> Result<ResponseItem, String> {
+    let (role, content) = match message.role {
+        Role::User => ("user", user_content(message.content)?),
+        Role::Assistant => ("assistant", assistant_content(message.content)?),
+    };
+    Ok(ResponseItem::Message {
+        id: None,
+        role: role.to_string(),
+        content: vec![content],
+    })
+}
+
+fn user_content(block: ContentBlock) -> Result<ContentItem, String> {
+    match block {
+        ContentBlock::ImageContent(image) => Ok(ContentItem::InputImage {
+            image_url: format!("data:{};base64,{}", image.mime_type, image.data),
+        }),
+        other => text_content(other).map(|text| ContentItem::InputText { text }),
+    }
+}
+
+fn assistant_content(block: ContentBlock) -> Result<ContentItem, String> {
+    text_content(block).map(|text| ContentItem::OutputText { text })
+}
+
+fn text_content(block: ContentBlock) -> Result<String, String> {
+    match block {
+        ContentBlock::TextContent(text) => Ok(text.text),
+        ContentBlock::ResourceLink(link) => Ok(link.uri),
+        ContentBlock::EmbeddedResource(embedded) => match embedded.resource {
+            EmbeddedResourceResource::TextResourceContents(contents) => Ok(contents.text),
+            EmbeddedResourceResource::BlobResourceContents(contents) => {
+                Err(format!("binary resource {} is not supported", contents.uri))
+            }
+        },
+        ContentBlock::ImageContent(_) => {
+            Err("images are only supported in user messages".to_string())
+        }
+        ContentBlock::AudioContent(_) => Err("audio content is not supported".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::AudioContent;
+    use mcp_types::ImageContent;
+    use mcp_types::TextContent;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: Role, content: ContentBlock) -> PromptMessage {
+        PromptMessage { content, role }
+    }
+
+    fn text(text: &str) -> ContentBlock {
+        ContentBlock::TextContent(TextContent {
+            annotations: None,
+            text: text.to_string(),
+            r#type: "text".to_string(),
+        })
+    }
+
+    #[test]
+    fn text_messages_keep_their_role() {
+        assert_eq!(
+            prompt_message_to_response_item(message(Role::User, text("review this"))),
+            Ok(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "review this".to_string(),
+                }],
+            })
+        );
+        assert_eq!(
+            prompt_message_to_response_item(message(Role::Assistant, text("on it"))),
+            Ok(ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "on it".to_string(),
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn user_images_become_data_urls() {
+        let image = ContentBlock::ImageContent(ImageContent {
+            annotations: None,
+            data: "AAAA".to_string(),
+            mime_type: "image/png".to_string(),
+            r#type: "image".to_string(),
+        });
+        assert_eq!(
+            prompt_message_to_response_item(message(Role::User, image.clone())),
+            Ok(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputImage {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                }],
+            })
+        );
+        assert!(prompt_message_to_response_item(message(Role::Assistant, image)).is_err());
+    }
+
+    #[test]
+    fn audio_is_rejected() {
+        let audio = ContentBlock::AudioContent(AudioContent {
+            annotations: None,
+            data: "AAAA".to_string(),
+            mime_type: "audio/wav".to_string(),
+            r#type: "audio".to_string(),
+        });
+        assert_eq!(
+            prompt_message_to_response_item(message(Role::User, audio)),
+            Err("audio content is not supported".to_string())
+        );
+    }
+}
@@ -0,0 +1,287 @@
+//! Answers MCP `sampling/createMessage` requests with the session's model.
+//!
+//! The connection manager asks the user to approve every request before
+//! calling the sampler built here. The server's messages are sent to the
+//! configured provider as a standalone prompt: no conversation history, no
+//! tools, no Codex instructions, and nothing is recorded into the thread.
+//! Token usage still counts towards the session.
+
+use std::sync::Arc;
+use std::sync::Weak;
+
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::prelude::*;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::CreateMessageResultContent;
+use mcp_types::Role;
+use mcp_types::SamplingMessage;
+use mcp_types::SamplingMessageContent;
+use mcp_types::TextContent;
+
+use crate::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::mcp_connection_manager::McpSampler;
+
+/// Stop reason reported when the model finished on its own.
+const END_TURN_STOP_REASON: &str = "endTurn";
+
+/// Stop reason reported when the output was cut at one of the request's
+/// stop sequences.
+const STOP_SEQUENCE_STOP_REASON: &str = "stopSequence";
+
+pub(crate) fn session_sampler(session: Weak<Session>) -> McpSampler {
+    Arc::new(move |_server_name, params| {
+        let session = session.clone();
+        async move {
+            let session = session
+                .upgrade()
+                .ok_or_else(|| anyhow!("session is no longer running"))?;
+            sample(&session, params).await
+        }
+        .boxed()
+    })
+}
+
+async fn sample(
+    session: &Session,
+    params: CreateMessageRequestParams,
+) -> Result<CreateMessageResult> {
+    let stop_sequences = params.stop_sequences.clone().unwrap_or_default();
+    let prompt = sampling_prompt(params)?;
+    let turn_context = session.new_default_turn().await;
+    let mut client_session = turn_context.client.new_session();
+    let mut stream = client_session.stream(&prompt).await?;
+
+    let mut text = String::new();
+    loop {
+        match stream.next().await {
+            Some(Ok(ResponseEvent::OutputItemDone(ResponseItem::Message {
+                role,
+                content,
+                ..
+            }))) if role == "assistant" => {
+                for item in content {
+                    if let ContentItem::OutputText { text: chunk } = item {
+                        text.push_str(&chunk);
+                    }
+                }
+            }
+            Some(Ok(ResponseEvent::RateLimits(snapshot))) => {
+                session
+                    .update_rate_limits(turn_context.as_ref(), snapshot)
+                    .await;
+            }
+            Some(Ok(ResponseEvent::Completed { token_usage, .. })) => {
+                session
+                    .update_token_usage_info(turn_context.as_ref(), token_usage.as_ref())
+                    .await;
+                break;
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(err)) => return Err(err.into()),
+            None => return Err(anyhow!("stream closed before response.completed")),
+        }
+    }
+
+    // Not every provider accepts stop sequences, so they are applied here.
+    let stop_reason = if truncate_at_stop_sequence(&mut text, &stop_sequences) {
+        STOP_SEQUENCE_STOP_REASON
+    } else {
+        END_TURN_STOP_REASON
+    };
+
+    Ok(CreateMessageResult {
+        content: CreateMessageResultContent::TextContent(TextContent {
+            annotations: None,
+            text,
+            r#type: "text".to_string(),
+        }),
+        model: turn_context.client.get_model(),
+        role: Role::Assistant,
+        stop_reason: Some(stop_reason.to_string()),
+    })
+}
+
+/// Builds the model prompt for a sampling request. The server's system prompt,
+/// if any, is the only instruction the model receives.
+fn sampling_prompt(params: CreateMessageRequestParams) -> Result<Prompt> {
+    let max_output_tokens = u64::try_from(params.max_tokens)
+        .ok()
+        .filter(|max_tokens| *max_tokens > 0)
+        .ok_or_else(|| anyhow!("maxTokens must be positive"))?;
+    let input = params
+        .messages
+        .into_iter()
+        .map(sampling_message_to_response_item)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Prompt {
+        input,
+        base_instructions_override: Some(params.system_prompt.unwrap_or_default()),
+        max_output_tokens: Some(max_output_tokens),
+        temperature: params.temperature,
+        ..Default::default()
+    })
+}
+
+/// Cuts `text` before the earliest occurrence of any stop sequence. Returns
+/// whether a stop sequence was found.
+fn truncate_at_stop_sequence(text: &mut String, stop_sequences: &[String]) -> bool {
+    let earliest = stop_sequences
+        .iter()
+        .filter(|sequence| !sequence.is_empty())
+        .filter_map(|sequence| text.find(sequence.as_str()))
+        .min();
+    match earliest {
+        Some(index) => {
+            text.truncate(index);
+            true
+        }
+        None => false,
+    }
+}
+
+fn sampling_message_to_response_item(message: SamplingMessage) -> Result<ResponseItem> {
+    let (role, content) = match (message.role, message.content) {
+        (Role::User, SamplingMessageContent::TextContent(text)) => {
+            ("user", ContentItem::InputText { text: text.text })
+        }
+        (Role::User, SamplingMessageContent::ImageContent(image)) => (
+            "user",
+            ContentItem::InputImage {
+                image_url: format!("data:{};base64,{}", image.mime_type, image.data),
+            },
+        ),
+        (Role::Assistant, SamplingMessageContent::TextContent(text)) => {
+            ("assistant", ContentItem::OutputText { text: text.text })
+        }
+        (Role::Assistant, SamplingMessageContent::ImageContent(_)) => {
+            return Err(anyhow!("images are only supported in user messages"));
+        }
+        (_, SamplingMessageContent::AudioContent(_)) => {
+            return Err(anyhow!("audio content is not supported"));
+        }
+    };
+    Ok(ResponseItem::Message {
+        id: None,
+        role: role.to_string(),
+        content: vec![content],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::AudioContent;
+    use pretty_assertions::assert_eq;
+
+    fn text_message(role: Role, text: &str) -> SamplingMessage {
+        SamplingMessage {
+            content: SamplingMessageContent::TextContent(TextContent {
+                annotations: None,
+                text: text.to_string(),
+                r#type: "text".to_string(),
+            }),
+            role,
+        }
+    }
+
+    fn params(messages: Vec<SamplingMessage>) -> CreateMessageRequestParams {
+        CreateMessageRequestParams {
+            include_context: None,
+            max_tokens: 128,
+            messages,
+            metadata: None,
+            model_preferences: None,
+            stop_sequences: None,
+            system_prompt: Some("Answer briefly.".to_string()),
+            temperature: None,
+        }
+    }
+
+    #[test]
+    fn sampling_prompt_keeps_roles_and_system_prompt() {
+        let prompt = sampling_prompt(params(vec![
+            text_message(Role::User, "What is 2 + 2?"),
+            text_message(Role::Assistant, "4"),
+            text_message(Role::User, "And times 3?"),
+        ]))
+        .expect("valid sampling request");
+
+        assert_eq!(
+            prompt.base_instructions_override.as_deref(),
+            Some("Answer briefly.")
+        );
+        assert_eq!(prompt.max_output_tokens, Some(128));
+        assert_eq!(
+            prompt.input,
+            vec![
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "What is 2 + 2?".to_string(),
+                    }],
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "4".to_string(),
+                    }],
+                },
+                ResponseItem::Message {
+                    id: None,
+                    role: "user".to_string(),
+                    content: vec![ContentItem::InputText {
+                        text: "And times 3?".to_string(),
+                    }],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn sampling_prompt_without_system_prompt_drops_codex_instructions() {
+        let mut request = params(vec![text_message(Role::User, "hi")]);
+        request.system_prompt = None;
+        request.temperature = Some(0.2);
+
+        let prompt = sampling_prompt(request).expect("valid sampling request");
+
+        assert_eq!(prompt.base_instructions_override.as_deref(), Some(""));
+        assert_eq!(prompt.temperature, Some(0.2));
+    }
+
+    #[test]
+    fn stop_sequences_truncate_at_the_earliest_match() {
+        let mut text = "one, two. three".to_string();
+
+        let stopped = truncate_at_stop_sequence(&mut text, &[".".to_string(), ",".to_string()]);
+
+        assert!(stopped);
+        assert_eq!(text, "one");
+        assert!(!truncate_at_stop_sequence(&mut text, &["!".to_string()]));
+        assert_eq!(text, "one");
+    }
+
+    #[test]
+    fn sampling_prompt_rejects_audio() {
+        let err = sampling_prompt(params(vec![SamplingMessage {
+            content: SamplingMessageContent::AudioContent(AudioContent {
+                annotations: None,
+                data: String::new(),
+                mime_type: "audio/wav".to_string(),
+                r#type: "audio".to_string(),
+            }),
+            role: Role::User,
+        }]))
+        .expect_err("audio is not supported");
+
+        assert_eq!(err.to_string(), "audio content is not supported");
+    }
+}
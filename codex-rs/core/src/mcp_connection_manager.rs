@@ -12,6 +12,7 @@ use std::env;
 use std::ffi::OsString;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Weak;
 use std::time::Duration;
 
 use crate::mcp::auth::McpAuthStatusEntry;
@@ -21,6 +22,7 @@ use anyhow::anyhow;
 use async_channel::Sender;
 use codex_async_utils::CancelErr;
 use codex_async_utils::OrCancelExt;
+use codex_otel::metrics::PrometheusRegistry;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
@@ -28,25 +30,40 @@ use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
 use codex_protocol::protocol::McpStartupUpdateEvent;
+use codex_protocol::protocol::McpToolsChangedEvent;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_rmcp_client::ElicitationAction;
 use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::ListChanged;
+use codex_rmcp_client::ListRoots;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_rmcp_client::RmcpClient;
 use codex_rmcp_client::SendElicitation;
+use codex_rmcp_client::SendSampling;
+use codex_rmcp_client::ToolCallProgressSender;
 use futures::future::BoxFuture;
 use futures::future::FutureExt;
 use futures::future::Shared;
 use mcp_types::ClientCapabilities;
+use mcp_types::ClientCapabilitiesRoots;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::Implementation;
+use mcp_types::ListPromptsRequestParams;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
 use mcp_types::ListResourcesResult;
+use mcp_types::Prompt;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
 use mcp_types::RequestId;
 use mcp_types::Resource;
 use mcp_types::ResourceTemplate;
+use mcp_types::Root;
+use mcp_types::SamplingMessageContent;
 use mcp_types::Tool;
 
 use serde::Deserialize;
@@ -55,11 +72,16 @@ use serde_json::json;
 use sha1::Digest;
 use sha1::Sha1;
 use tokio::sync::Mutex;
+use tokio::sync::RwLock;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::oneshot;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 use tracing::warn;
+use url::Url;
+use uuid::Uuid;
 
 use crate::codex::INITIAL_SUBMIT_ID;
 use crate::config::types::McpServerConfig;
@@ -206,15 +228,135 @@ impl ElicitationRequestManager {
             .boxed()
         })
     }
+
+    /// Asks the user to approve each `sampling/createMessage` request through
+    /// the elicitation prompt before handing it to `sampler`.
+    fn make_sampling_sender(
+        &self,
+        server_name: String,
+        tx_event: Sender<Event>,
+        sampler: McpSampler,
+    ) -> SendSampling {
+        let elicitation_requests = self.requests.clone();
+        Box::new(move |id, params| {
+            let elicitation_requests = elicitation_requests.clone();
+            let tx_event = tx_event.clone();
+            let server_name = server_name.clone();
+            let sampler = Arc::clone(&sampler);
+            async move {
+                let (tx, rx) = oneshot::channel();
+                {
+                    let mut lock = elicitation_requests.lock().await;
+                    lock.insert((server_name.clone(), id.clone()), tx);
+                }
+                // Each approval prompt gets its own event id so concurrent
+                // requests are not mistaken for one another.
+                let _ = tx_event
+                    .send(Event {
+                        id: format!("mcp_sampling_request_{}", Uuid::new_v4()),
+                        msg: EventMsg::ElicitationRequest(ElicitationRequestEvent {
+                            server_name: server_name.clone(),
+                            id,
+                            message: sampling_approval_message(&server_name, &params),
+                        }),
+                    })
+                    .await;
+                let response = rx
+                    .await
+                    .context("sampling approval channel closed unexpectedly")?;
+                if response.action != ElicitationAction::Accept {
+                    return Err(anyhow!("user declined the sampling request"));
+                }
+                sampler(server_name, params).await
+            }
+            .boxed()
+        })
+    }
+}
+
+/// Runs an approved `sampling/createMessage` request for the named server.
+pub(crate) type McpSampler = Arc<
+    dyn Fn(String, CreateMessageRequestParams) -> BoxFuture<'static, Result<CreateMessageResult>>
+        + Send
+        + Sync,
+>;
+
+/// Roots reported to servers via `roots/list`, derived from the sandbox state.
+type SharedRoots = Arc<std::sync::RwLock<Vec<Root>>>;
+
+/// Handlers for requests that servers send to Codex, shared by all clients of
+/// one [`McpConnectionManager`].
+#[derive(Clone, Default)]
+struct ServerRequestHandlers {
+    elicitation_requests: ElicitationRequestManager,
+    sampler: Option<McpSampler>,
+    roots: SharedRoots,
+}
+
+impl ServerRequestHandlers {
+    fn make_roots_lister(&self) -> ListRoots {
+        let roots = Arc::clone(&self.roots);
+        Box::new(move || {
+            let roots = roots
+                .read()
+                .map(|roots| roots.clone())
+                .map_err(|_| anyhow!("MCP roots lock poisoned"));
+            async move { roots }.boxed()
+        })
+    }
+}
+
+fn sampling_approval_message(server_name: &str, params: &CreateMessageRequestParams) -> String {
+    let last_text = params
+        .messages
+        .iter()
+        .rev()
+        .find_map(|message| match &message.content {
+            SamplingMessageContent::TextContent(text) => Some(text.text.as_str()),
+            _ => None,
+        });
+    match last_text {
+        Some(text) => {
+            format!("MCP server `{server_name}` wants to run a model request: {text}")
+        }
+        None => format!("MCP server `{server_name}` wants to run a model request."),
+    }
+}
+
+/// The session cwd followed by any additional writable roots, as `file://` roots.
+fn roots_from_sandbox_state(sandbox_state: &SandboxState) -> Vec<Root> {
+    let mut paths = vec![sandbox_state.sandbox_cwd.clone()];
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. }
+    | SandboxPolicy::RestrictedRead { writable_roots, .. } = &sandbox_state.sandbox_policy
+    {
+        paths.extend(writable_roots.iter().map(|root| root.to_path_buf()));
+    }
+
+    let mut seen = HashSet::new();
+    paths
+        .into_iter()
+        .filter(|path| seen.insert(path.clone()))
+        .filter_map(|path| {
+            let uri = Url::from_file_path(&path).ok()?;
+            Some(Root {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned()),
+                uri: uri.to_string(),
+            })
+        })
+        .collect()
 }
 
 #[derive(Clone)]
 struct ManagedClient {
     client: Arc<RmcpClient>,
-    tools: Vec<ToolInfo>,
+    /// Refreshed in the background when the server reports `tools/list_changed`.
+    tools: Arc<RwLock<Vec<ToolInfo>>>,
     tool_filter: ToolFilter,
     tool_timeout: Option<Duration>,
     server_supports_sandbox_state_capability: bool,
+    server_supports_prompts: bool,
 }
 
 impl ManagedClient {
@@ -247,7 +389,7 @@ impl AsyncManagedClient {
         store_mode: OAuthCredentialsStoreMode,
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
-        elicitation_requests: ElicitationRequestManager,
+        request_handlers: ServerRequestHandlers,
     ) -> Self {
         let tool_filter = ToolFilter::from_config(&config);
         let fut = async move {
@@ -264,7 +406,7 @@ impl AsyncManagedClient {
                 config.tool_timeout_sec.unwrap_or(DEFAULT_TOOL_TIMEOUT),
                 tool_filter,
                 tx_event,
-                elicitation_requests,
+                request_handlers,
             )
             .or_cancel(&cancel_token)
             .await
@@ -286,6 +428,11 @@ impl AsyncManagedClient {
         let managed = self.client().await?;
         managed.notify_sandbox_state_change(sandbox_state).await
     }
+
+    async fn notify_roots_list_changed(&self) -> Result<()> {
+        let managed = self.client().await?;
+        managed.client.notify_roots_list_changed().await
+    }
 }
 
 pub const MCP_SANDBOX_STATE_CAPABILITY: &str = "codex/sandbox-state";
@@ -306,10 +453,20 @@ pub struct SandboxState {
 #[derive(Default)]
 pub(crate) struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
-    elicitation_requests: ElicitationRequestManager,
+    request_handlers: ServerRequestHandlers,
 }
 
 impl McpConnectionManager {
+    /// Enables `sampling/createMessage` for servers started by the next
+    /// [`McpConnectionManager::initialize`].
+    pub(crate) fn set_sampler(&mut self, sampler: Option<McpSampler>) {
+        self.request_handlers.sampler = sampler;
+    }
+
+    pub(crate) fn sampler(&self) -> Option<McpSampler> {
+        self.request_handlers.sampler.clone()
+    }
+
     pub async fn initialize(
         &mut self,
         mcp_servers: &HashMap<String, McpServerConfig>,
@@ -324,7 +481,13 @@ impl McpConnectionManager {
         }
         let mut clients = HashMap::new();
         let mut join_set = JoinSet::new();
-        let elicitation_requests = ElicitationRequestManager::default();
+        let request_handlers = ServerRequestHandlers {
+            elicitation_requests: ElicitationRequestManager::default(),
+            sampler: self.request_handlers.sampler.clone(),
+            roots: Arc::new(std::sync::RwLock::new(roots_from_sandbox_state(
+                &initial_sandbox_state,
+            ))),
+        };
         let mcp_servers = mcp_servers.clone();
         for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
             let cancel_token = cancel_token.child_token();
@@ -342,7 +505,7 @@ impl McpConnectionManager {
                 store_mode,
                 cancel_token.clone(),
                 tx_event.clone(),
-                elicitation_requests.clone(),
+                request_handlers.clone(),
             );
             clients.insert(server_name.clone(), async_managed_client.clone());
             let tx_event = tx_event.clone();
@@ -389,7 +552,7 @@ impl McpConnectionManager {
             });
         }
         self.clients = clients;
-        self.elicitation_requests = elicitation_requests.clone();
+        self.request_handlers = request_handlers;
         tokio::spawn(async move {
             let outcomes = join_set.join_all().await;
             let mut summary = McpStartupCompleteEvent::default();
@@ -429,7 +592,8 @@ impl McpConnectionManager {
         id: RequestId,
         response: ElicitationResponse,
     ) -> Result<()> {
-        self.elicitation_requests
+        self.request_handlers
+            .elicitation_requests
             .resolve(server_name, id, response)
             .await
     }
@@ -441,8 +605,9 @@ impl McpConnectionManager {
         let mut tools = HashMap::new();
         for managed_client in self.clients.values() {
             if let Ok(client) = managed_client.client().await {
+                let client_tools = client.tools.read().await.clone();
                 tools.extend(qualify_tools(filter_tools(
-                    client.tools,
+                    client_tools,
                     client.tool_filter,
                 )));
             }
@@ -586,12 +751,102 @@ impl McpConnectionManager {
         aggregated
     }
 
-    /// Invoke the tool indicated by the (server, tool) pair.
+    /// Returns a single map that contains all prompts. Each key is the
+    /// server name and the value is a vector of prompts.
+    pub async fn list_all_prompts(&self) -> HashMap<String, Vec<Prompt>> {
+        let mut join_set = JoinSet::new();
+
+        for (server_name, async_managed_client) in &self.clients {
+            let server_name = server_name.clone();
+            let Ok(managed_client) = async_managed_client.client().await else {
+                continue;
+            };
+            if !managed_client.server_supports_prompts {
+                continue;
+            }
+            let timeout = managed_client.tool_timeout;
+            let client = managed_client.client.clone();
+
+            join_set.spawn(async move {
+                let mut collected: Vec<Prompt> = Vec::new();
+                let mut cursor: Option<String> = None;
+
+                loop {
+                    let params = cursor.as_ref().map(|next| ListPromptsRequestParams {
+                        cursor: Some(next.clone()),
+                    });
+                    let response = match client.list_prompts(params, timeout).await {
+                        Ok(result) => result,
+                        Err(err) => return (server_name, Err(err)),
+                    };
+
+                    collected.extend(response.prompts);
+
+                    match response.next_cursor {
+                        Some(next) => {
+                            if cursor.as_ref() == Some(&next) {
+                                return (
+                                    server_name,
+                                    Err(anyhow!("prompts/list returned duplicate cursor")),
+                                );
+                            }
+                            cursor = Some(next);
+                        }
+                        None => return (server_name, Ok(collected)),
+                    }
+                }
+            });
+        }
+
+        let mut aggregated: HashMap<String, Vec<Prompt>> = HashMap::new();
+
+        while let Some(join_res) = join_set.join_next().await {
+            match join_res {
+                Ok((server_name, Ok(prompts))) => {
+                    aggregated.insert(server_name, prompts);
+                }
+                Ok((server_name, Err(err))) => {
+                    warn!("Failed to list prompts for MCP server '{server_name}': {err:#}");
+                }
+                Err(err) => {
+                    warn!("Task panic when listing prompts for MCP server: {err:#}");
+                }
+            }
+        }
+
+        aggregated
+    }
+
+    /// Render the prompt `name` from the specified server with `arguments`.
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        name: &str,
+        arguments: &HashMap<String, String>,
+    ) -> Result<GetPromptResult> {
+        let managed = self.client_by_name(server).await?;
+        let client = managed.client.clone();
+        let timeout = managed.tool_timeout;
+        let params = GetPromptRequestParams {
+            arguments: (!arguments.is_empty()).then(|| json!(arguments)),
+            name: name.to_string(),
+        };
+
+        client
+            .get_prompt(params, timeout)
+            .await
+            .with_context(|| format!("prompts/get failed for `{server}/{name}`"))
+    }
+
+    /// Invoke the tool indicated by the (server, tool) pair. Progress and log
+    /// notifications received while the call runs are forwarded to
+    /// `progress_tx`.
     pub async fn call_tool(
         &self,
         server: &str,
         tool: &str,
         arguments: Option<serde_json::Value>,
+        progress_tx: Option<ToolCallProgressSender>,
     ) -> Result<mcp_types::CallToolResult> {
         let client = self.client_by_name(server).await?;
         if !client.tool_filter.allows(tool) {
@@ -602,7 +857,12 @@ impl McpConnectionManager {
 
         client
             .client
-            .call_tool(tool.to_string(), arguments, client.tool_timeout)
+            .call_tool_with_progress(
+                tool.to_string(),
+                arguments,
+                client.tool_timeout,
+                progress_tx,
+            )
             .await
             .with_context(|| format!("tool call failed for `{server}/{tool}`"))
     }
@@ -664,15 +924,31 @@ impl McpConnectionManager {
     }
 
     pub async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
+        let roots_changed = {
+            let next_roots = roots_from_sandbox_state(sandbox_state);
+            let mut roots = self
+                .request_handlers
+                .roots
+                .write()
+                .map_err(|_| anyhow!("MCP roots lock poisoned"))?;
+            let changed = *roots != next_roots;
+            *roots = next_roots;
+            changed
+        };
+
         let mut join_set = JoinSet::new();
 
         for async_managed_client in self.clients.values() {
             let sandbox_state = sandbox_state.clone();
             let async_managed_client = async_managed_client.clone();
             join_set.spawn(async move {
-                async_managed_client
+                let sandbox_result = async_managed_client
                     .notify_sandbox_state_change(&sandbox_state)
-                    .await
+                    .await;
+                if roots_changed {
+                    async_managed_client.notify_roots_list_changed().await?;
+                }
+                sandbox_result
             });
         }
 
@@ -696,6 +972,14 @@ async fn emit_update(
     tx_event: &Sender<Event>,
     update: McpStartupUpdateEvent,
 ) -> Result<(), async_channel::SendError<Event>> {
+    if let Some(registry) = PrometheusRegistry::global() {
+        let up = matches!(update.status, McpStartupStatus::Ready);
+        registry.gauge_set(
+            "codex.mcp.server.up",
+            if up { 1.0 } else { 0.0 },
+            &[("server", update.server.as_str())],
+        );
+    }
     tx_event
         .send(Event {
             id: INITIAL_SUBMIT_ID.to_owned(),
@@ -798,13 +1082,15 @@ async fn start_server_task(
     tool_timeout: Duration,
     tool_filter: ToolFilter,
     tx_event: Sender<Event>,
-    elicitation_requests: ElicitationRequestManager,
+    request_handlers: ServerRequestHandlers,
 ) -> Result<ManagedClient, StartupOutcomeError> {
     let params = mcp_types::InitializeRequestParams {
         capabilities: ClientCapabilities {
             experimental: None,
-            roots: None,
-            sampling: None,
+            roots: Some(ClientCapabilitiesRoots {
+                list_changed: Some(true),
+            }),
+            sampling: request_handlers.sampler.is_some().then(|| json!({})),
             // https://modelcontextprotocol.io/specification/2025-06-18/client/elicitation#capabilities
             // indicates this should be an empty object.
             elicitation: Some(json!({})),
@@ -821,10 +1107,28 @@ async fn start_server_task(
         protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_owned(),
     };
 
-    let send_elicitation = elicitation_requests.make_sender(server_name.clone(), tx_event);
+    // Subscribe before the handshake so notifications sent right after it are not lost.
+    let list_changed = client.subscribe_list_changed();
+    let send_elicitation = request_handlers
+        .elicitation_requests
+        .make_sender(server_name.clone(), tx_event.clone());
+    let send_sampling = request_handlers.sampler.clone().map(|sampler| {
+        request_handlers.elicitation_requests.make_sampling_sender(
+            server_name.clone(),
+            tx_event.clone(),
+            sampler,
+        )
+    });
+    let list_roots = request_handlers.make_roots_lister();
 
     let initialize_result = client
-        .initialize(params, startup_timeout, send_elicitation)
+        .initialize(
+            params,
+            startup_timeout,
+            send_elicitation,
+            send_sampling,
+            Some(list_roots),
+        )
         .await
         .map_err(StartupOutcomeError::from)?;
 
@@ -838,18 +1142,79 @@ async fn start_server_task(
         .as_ref()
         .and_then(|exp| exp.get(MCP_SANDBOX_STATE_CAPABILITY))
         .is_some();
+    let server_supports_prompts = initialize_result.capabilities.prompts.is_some();
 
     let managed = ManagedClient {
         client: Arc::clone(&client),
-        tools,
+        tools: Arc::new(RwLock::new(tools)),
         tool_timeout: Some(tool_timeout),
         tool_filter,
         server_supports_sandbox_state_capability,
+        server_supports_prompts,
     };
 
+    tokio::spawn(refresh_tools_on_list_changed(
+        server_name,
+        Arc::downgrade(&client),
+        Arc::clone(&managed.tools),
+        managed.tool_filter.clone(),
+        startup_timeout,
+        tx_event,
+        list_changed,
+    ));
+
     Ok(managed)
 }
 
+/// Re-fetches a server's tools whenever it sends `notifications/tools/list_changed`.
+/// The tool router is rebuilt from [`McpConnectionManager::list_all_tools`] for
+/// every model request, so the new list takes effect on the next turn.
+async fn refresh_tools_on_list_changed(
+    server_name: String,
+    client: Weak<RmcpClient>,
+    tools: Arc<RwLock<Vec<ToolInfo>>>,
+    tool_filter: ToolFilter,
+    timeout: Option<Duration>,
+    tx_event: Sender<Event>,
+    mut list_changed: broadcast::Receiver<ListChanged>,
+) {
+    loop {
+        match list_changed.recv().await {
+            // A lagged receiver may have missed a tools notification, so refresh anyway.
+            Ok(ListChanged::Tools) | Err(RecvError::Lagged(_)) => {}
+            Ok(ListChanged::Resources | ListChanged::Prompts) => continue,
+            Err(RecvError::Closed) => return,
+        }
+        // Only hold a strong reference while refreshing so the client can shut down.
+        let Some(client) = client.upgrade() else {
+            return;
+        };
+        let refreshed = match list_tools_for_client(&server_name, &client, timeout).await {
+            Ok(refreshed) => refreshed,
+            Err(error) => {
+                warn!("Failed to refresh tools for MCP server {server_name}: {error:#}");
+                continue;
+            }
+        };
+        drop(client);
+
+        let tool_names = filter_tools(refreshed.clone(), tool_filter.clone())
+            .into_iter()
+            .map(|tool| tool.tool_name)
+            .collect();
+        *tools.write().await = refreshed;
+        let _ = tx_event
+            .send(Event {
+                id: "mcp_tools_changed".to_string(),
+                msg: EventMsg::McpToolsChanged(McpToolsChangedEvent {
+                    server: server_name.clone(),
+                    tools: tool_names,
+                }),
+            })
+            .await;
+    }
+}
+
 async fn make_rmcp_client(
     server_name: &str,
     transport: McpServerTransportConfig,
@@ -985,7 +1350,9 @@ mod mcp_init_error_display_tests {}
 mod tests {
     use super::*;
     use codex_protocol::protocol::McpAuthStatus;
+    use codex_utils_absolute_path::AbsolutePathBuf;
     use mcp_types::ToolInputSchema;
+    use pretty_assertions::assert_eq;
     use std::collections::HashSet;
 
     fn create_test_tool(server_name: &str, tool_name: &str) -> ToolInfo {
@@ -1159,6 +1526,37 @@ mod tests {
         assert_eq!(filtered[0].tool_name, "tool_a");
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn roots_cover_cwd_and_writable_roots_once() {
+        let writable_root =
+            |path: &str| AbsolutePathBuf::from_absolute_path(path).expect("absolute path");
+        let sandbox_state = SandboxState {
+            sandbox_policy: SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![writable_root("/work/shared"), writable_root("/work/repo")],
+                network_access: false,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+            },
+            codex_linux_sandbox_exe: None,
+            sandbox_cwd: PathBuf::from("/work/repo"),
+        };
+
+        assert_eq!(
+            roots_from_sandbox_state(&sandbox_state),
+            vec![
+                Root {
+                    name: Some("repo".to_string()),
+                    uri: "file:///work/repo".to_string(),
+                },
+                Root {
+                    name: Some("shared".to_string()),
+                    uri: "file:///work/shared".to_string(),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn mcp_init_error_display_prompts_for_github_pat() {
         let server_name = "github";
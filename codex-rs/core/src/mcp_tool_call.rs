@@ -1,5 +1,8 @@
 use std::time::Instant;
 
+use codex_rmcp_client::ToolCallProgress;
+use tokio::sync::mpsc;
+use tracing::Instrument;
 use tracing::error;
 
 use crate::codex::Session;
@@ -8,11 +11,16 @@ use crate::protocol::EventMsg;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
 use crate::protocol::McpToolCallEndEvent;
+use crate::protocol::McpToolCallProgressEvent;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
 
 /// Handles the specified tool call dispatches the appropriate
-/// `McpToolCallBegin` and `McpToolCallEnd` events to the `Session`.
+/// `McpToolCallBegin`, `McpToolCallProgress` and `McpToolCallEnd` events to
+/// the `Session`.
+///
+/// Interrupting the turn drops the in-flight call, which cancels it on the
+/// server with `notifications/cancelled`.
 pub(crate) async fn handle_mcp_tool_call(
     sess: &Session,
     turn_context: &TurnContext,
@@ -55,11 +63,42 @@ pub(crate) async fn handle_mcp_tool_call(
     notify_mcp_tool_call_event(sess, turn_context, tool_call_begin_event).await;
 
     let start = Instant::now();
-    // Perform the tool call.
-    let result = sess
-        .call_tool(&server, &tool_name, arguments_value.clone())
-        .await
-        .map_err(|e| format!("tool call error: {e:?}"));
+    let mcp_span = turn_context.client.get_otel_manager().mcp_call_span(
+        &turn_context.sub_id,
+        &server,
+        &tool_name,
+        &call_id,
+    );
+    // Perform the tool call, streaming progress while it runs.
+    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+    let call = sess
+        .call_tool(
+            &server,
+            &tool_name,
+            arguments_value.clone(),
+            Some(progress_tx),
+        )
+        .instrument(mcp_span.clone());
+    tokio::pin!(call);
+    let result = loop {
+        tokio::select! {
+            result = &mut call => break result,
+            Some(progress) = progress_rx.recv() => {
+                let event = progress_event(&call_id, progress);
+                notify_mcp_tool_call_event(sess, turn_context, event).await;
+            }
+        }
+    };
+    while let Ok(progress) = progress_rx.try_recv() {
+        notify_mcp_tool_call_event(sess, turn_context, progress_event(&call_id, progress)).await;
+    }
+    mcp_span.record(
+        "success",
+        result
+            .as_ref()
+            .is_ok_and(|result| result.is_error != Some(true)),
+    );
+    let result = result.map_err(|e| format!("tool call error: {e:?}"));
     if let Err(e) = &result {
         tracing::warn!("MCP tool call error: {e:?}");
     }
@@ -81,6 +120,15 @@ pub(crate) async fn handle_mcp_tool_call(
     ResponseInputItem::McpToolCallOutput { call_id, result }
 }
 
+fn progress_event(call_id: &str, progress: ToolCallProgress) -> EventMsg {
+    EventMsg::McpToolCallProgress(McpToolCallProgressEvent {
+        call_id: call_id.to_string(),
+        progress: progress.progress,
+        total: progress.total,
+        message: progress.message,
+    })
+}
+
 async fn notify_mcp_tool_call_event(sess: &Session, turn_context: &TurnContext, event: EventMsg) {
     sess.send_event(turn_context, event).await;
 }
@@ -0,0 +1,353 @@
+//! Persistent, per-project memories shared across sessions.
+//!
+//! Memories are short notes about a project (build quirks, conventions,
+//! decisions) saved by the agent through the `remember` tool or by the user
+//! through `codex memory`. They are stored as JSON under
+//! `$CODEX_HOME/memories/`, keyed by a hash of the project root: the Git root
+//! of the working directory, or the directory itself outside a repository.
+
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use sha1::Digest;
+use sha1::Sha1;
+use tempfile::NamedTempFile;
+
+use crate::git_info::get_git_repo_root;
+use crate::truncate::approx_token_count;
+
+/// Directory under `$CODEX_HOME` that holds one memory file per project.
+pub const MEMORIES_SUBDIR: &str = "memories";
+/// Default token budget for memories injected into the initial context.
+pub const DEFAULT_MEMORY_MAX_TOKENS: usize = 1_000;
+
+const STORE_VERSION: u32 = 1;
+const MEMORY_ID_LEN: usize = 8;
+const MAX_LOCK_RETRIES: usize = 10;
+const LOCK_RETRY_SLEEP: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Memory {
+    /// Short identifier used to edit or delete the memory.
+    pub id: String,
+    pub text: String,
+    /// RFC 3339 timestamp of when the memory was saved or last edited.
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MemoryFile {
+    version: u32,
+    project: PathBuf,
+    memories: Vec<Memory>,
+}
+
+/// Memories of a single project, oldest first.
+#[derive(Debug, Clone)]
+pub struct MemoryStore {
+    project: PathBuf,
+    path: PathBuf,
+}
+
+impl MemoryStore {
+    /// Store for the project containing `cwd`.
+    pub fn for_project(codex_home: &Path, cwd: &Path) -> Self {
+        let project = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+        let digest = Sha1::digest(project.to_string_lossy().as_bytes());
+        let name = digest
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        let path = codex_home
+            .join(MEMORIES_SUBDIR)
+            .join(format!("{name}.json"));
+        Self { project, path }
+    }
+
+    /// Root directory the memories belong to.
+    pub fn project(&self) -> &Path {
+        &self.project
+    }
+
+    pub fn list(&self) -> anyhow::Result<Vec<Memory>> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read {}", self.path.display()));
+            }
+        };
+        let file: MemoryFile = serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse {}", self.path.display()))?;
+        Ok(file.memories)
+    }
+
+    /// Saves a new memory and returns it.
+    pub fn add(&self, text: &str) -> anyhow::Result<Memory> {
+        let text = normalize_text(text)?;
+        self.with_lock(|| {
+            let mut memories = self.list()?;
+            let memory = Memory {
+                id: new_memory_id(&memories),
+                text,
+                updated_at: now(),
+            };
+            memories.push(memory.clone());
+            self.save(memories)?;
+            Ok(memory)
+        })
+    }
+
+    /// Replaces the text of memory `id`. Returns `None` when it does not exist.
+    pub fn update(&self, id: &str, text: &str) -> anyhow::Result<Option<Memory>> {
+        let text = normalize_text(text)?;
+        self.with_lock(|| {
+            let mut memories = self.list()?;
+            let Some(memory) = memories.iter_mut().find(|memory| memory.id == id) else {
+                return Ok(None);
+            };
+            memory.text = text;
+            memory.updated_at = now();
+            let updated = memory.clone();
+            self.save(memories)?;
+            Ok(Some(updated))
+        })
+    }
+
+    /// Deletes memory `id`. Returns `false` when it does not exist.
+    pub fn delete(&self, id: &str) -> anyhow::Result<bool> {
+        self.with_lock(|| {
+            let mut memories = self.list()?;
+            let before = memories.len();
+            memories.retain(|memory| memory.id != id);
+            if memories.len() == before {
+                return Ok(false);
+            }
+            self.save(memories)?;
+            Ok(true)
+        })
+    }
+
+    /// Memories that mention any term of `query` (case-insensitive), best
+    /// matches first and newest first among equal matches.
+    pub fn search(&self, query: &str, limit: usize) -> anyhow::Result<Vec<Memory>> {
+        let terms: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|term| !term.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        let mut scored: Vec<(usize, usize, Memory)> = self
+            .list()?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(position, memory)| {
+                let haystack = memory.text.to_lowercase();
+                let score = terms
+                    .iter()
+                    .filter(|term| haystack.contains(term.as_str()))
+                    .count();
+                (score > 0).then_some((score, position, memory))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)));
+        Ok(scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, memory)| memory)
+            .collect())
+    }
+
+    /// Runs a read-modify-write of the store under an advisory lock on a
+    /// sibling `.lock` file, so concurrent sessions do not drop each other's
+    /// changes. Readers need no lock because `save` replaces the file
+    /// atomically.
+    fn with_lock<T>(&self, f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+        let dir = self.dir()?;
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        let lock_path = self.path.with_extension("json.lock");
+        let mut options = OpenOptions::new();
+        options.read(true).write(true).create(true).truncate(false);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let lock_file = options
+            .open(&lock_path)
+            .with_context(|| format!("failed to open {}", lock_path.display()))?;
+        // Retry a few times to avoid indefinite blocking when contended.
+        for _ in 0..MAX_LOCK_RETRIES {
+            match lock_file.try_lock() {
+                Ok(()) => return f(),
+                Err(std::fs::TryLockError::WouldBlock) => std::thread::sleep(LOCK_RETRY_SLEEP),
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("failed to lock {}", lock_path.display()));
+                }
+            }
+        }
+        anyhow::bail!(
+            "could not acquire exclusive lock on {} after multiple attempts",
+            lock_path.display()
+        )
+    }
+
+    /// Writes `memories` to a uniquely named temporary file and moves it over
+    /// the store. Callers must hold the lock taken by `with_lock`.
+    fn save(&self, memories: Vec<Memory>) -> anyhow::Result<()> {
+        let dir = self.dir()?;
+        let file = MemoryFile {
+            version: STORE_VERSION,
+            project: self.project.clone(),
+            memories,
+        };
+        let mut tmp = NamedTempFile::new_in(dir)
+            .with_context(|| format!("failed to create a temporary file in {}", dir.display()))?;
+        tmp.write_all(&serde_json::to_vec_pretty(&file)?)
+            .with_context(|| format!("failed to write {}", tmp.path().display()))?;
+        tmp.persist(&self.path)
+            .with_context(|| format!("failed to replace {}", self.path.display()))?;
+        Ok(())
+    }
+
+    fn dir(&self) -> anyhow::Result<&Path> {
+        self.path
+            .parent()
+            .with_context(|| format!("invalid memory path {}", self.path.display()))
+    }
+}
+
+/// Renders the newest memories that fit in `max_tokens` as a section of the
+/// user instructions. Returns `None` when there is nothing to include.
+pub(crate) fn render_memories_section(memories: &[Memory], max_tokens: usize) -> Option<String> {
+    let header = "## Memories\nNotes saved in earlier sessions for this project. Use `recall` to search them and `remember` to save new ones.";
+    let mut remaining = max_tokens.saturating_sub(approx_token_count(header));
+    let mut lines = Vec::new();
+    for memory in memories.iter().rev() {
+        let line = format!("- [{}] {}", memory.id, memory.text);
+        let cost = approx_token_count(&line);
+        if cost > remaining {
+            break;
+        }
+        remaining -= cost;
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    let omitted = memories.len() - lines.len();
+    let mut section = format!("{header}\n{}", lines.join("\n"));
+    if omitted > 0 {
+        section.push_str(&format!(
+            "\n({omitted} older memories omitted; use `recall` to find them.)"
+        ));
+    }
+    Some(section)
+}
+
+fn normalize_text(text: &str) -> anyhow::Result<String> {
+    let text = text.trim();
+    if text.is_empty() {
+        anyhow::bail!("memory text must not be empty");
+    }
+    Ok(text.to_string())
+}
+
+fn new_memory_id(existing: &[Memory]) -> String {
+    loop {
+        let id = uuid::Uuid::new_v4().simple().to_string()[..MEMORY_ID_LEN].to_string();
+        if existing.iter().all(|memory| memory.id != id) {
+            return id;
+        }
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn store_round_trips_and_searches() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let project = TempDir::new()?;
+        let store = MemoryStore::for_project(codex_home.path(), project.path());
+
+        let build = store.add("Run `just fmt` before committing Rust changes")?;
+        let tests = store.add("  Integration tests need `--features test-support`  ")?;
+        assert_eq!(
+            tests.text,
+            "Integration tests need `--features test-support`"
+        );
+
+        let found = store.search("rust fmt", 5)?;
+        assert_eq!(found, vec![build.clone()]);
+        let found = store.search("tests features", 5)?;
+        assert_eq!(found, vec![tests.clone()]);
+
+        let edited = store
+            .update(&build.id, "Run `just fmt` in codex-rs")?
+            .expect("memory exists");
+        assert_eq!(edited.text, "Run `just fmt` in codex-rs");
+        assert!(store.delete(&tests.id)?);
+        assert!(!store.delete(&tests.id)?);
+
+        let reopened = MemoryStore::for_project(codex_home.path(), project.path());
+        assert_eq!(reopened.list()?, vec![edited]);
+        Ok(())
+    }
+
+    #[test]
+    fn concurrent_adds_keep_every_memory() -> anyhow::Result<()> {
+        let codex_home = TempDir::new()?;
+        let project = TempDir::new()?;
+        let store = MemoryStore::for_project(codex_home.path(), project.path());
+
+        let writers: Vec<_> = (0..8)
+            .map(|i| {
+                let store = store.clone();
+                std::thread::spawn(move || store.add(&format!("memory {i}")))
+            })
+            .collect();
+        for writer in writers {
+            writer.join().expect("writer thread panicked")?;
+        }
+
+        assert_eq!(store.list()?.len(), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn render_keeps_newest_memories_within_budget() {
+        let memories: Vec<Memory> = (0..3)
+            .map(|i| Memory {
+                id: format!("id{i}"),
+                text: format!("memory number {i} {}", "x".repeat(40)),
+                updated_at: "2025-01-01T00:00:00Z".to_string(),
+            })
+            .collect();
+
+        let section = render_memories_section(&memories, 70).expect("section");
+        assert!(section.contains("[id2]"));
+        assert!(section.contains("[id1]"));
+        assert!(!section.contains("[id0]"));
+        assert!(section.ends_with("(1 older memories omitted; use `recall` to find them.)"));
+
+        assert_eq!(render_memories_section(&memories, 10), None);
+        assert_eq!(render_memories_section(&[], 1_000), None);
+    }
+}
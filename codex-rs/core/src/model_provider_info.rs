@@ -50,6 +50,10 @@ pub enum WireApi {
     /// Regular Chat Completions compatible with `/v1/chat/completions`.
     #[default]
     Chat,
+
+    /// Anthropic Messages API compatible with `/v1/messages`. The provider's
+    /// API key is sent in the `x-api-key` header.
+    Messages,
 }
 
 /// Serializable representation of a provider definition.
@@ -164,6 +168,7 @@ impl ModelProviderInfo {
                 WireApi::Responses => ApiWireApi::Responses,
                 WireApi::ResponsesWebsocket => ApiWireApi::Responses,
                 WireApi::Chat => ApiWireApi::Chat,
+                WireApi::Messages => ApiWireApi::Messages,
             },
             headers,
             retry,
@@ -396,6 +401,34 @@ query_params = { api-version = "2025-04-01-preview" }
         assert_eq!(expected_provider, provider);
     }
 
+    #[test]
+    fn test_deserialize_anthropic_model_provider_toml() {
+        let anthropic_provider_toml = r#"
+name = "Anthropic"
+base_url = "https://api.anthropic.com/v1"
+env_key = "ANTHROPIC_API_KEY"
+wire_api = "messages"
+        "#;
+        let expected_provider = ModelProviderInfo {
+            name: "Anthropic".into(),
+            base_url: Some("https://api.anthropic.com/v1".into()),
+            env_key: Some("ANTHROPIC_API_KEY".into()),
+            env_key_instructions: None,
+            experimental_bearer_token: None,
+            wire_api: WireApi::Messages,
+            query_params: None,
+            http_headers: None,
+            env_http_headers: None,
+            request_max_retries: None,
+            stream_max_retries: None,
+            stream_idle_timeout_ms: None,
+            requires_openai_auth: false,
+        };
+
+        let provider: ModelProviderInfo = toml::from_str(anthropic_provider_toml).unwrap();
+        assert_eq!(expected_provider, provider);
+    }
+
     #[test]
     fn test_deserialize_example_model_provider_toml() {
         let azure_provider_toml = r#"
@@ -72,6 +72,10 @@ pub(crate) fn with_config_overrides(mut model: ModelInfo, config: &Config) -> Mo
     }
     if let Some(auto_compact_token_limit) = config.model_auto_compact_token_limit {
         model.auto_compact_token_limit = Some(auto_compact_token_limit);
+    } else if let (Some(fraction), Some(context_window)) =
+        (config.compaction.trigger_fraction, model.context_window)
+    {
+        model.auto_compact_token_limit = Some((context_window as f64 * fraction) as i64);
     }
     if let Some(token_limit) = config.tool_output_token_limit {
         model.truncation_policy = match model.truncation_policy.mode {
@@ -15,6 +15,8 @@
 
 use crate::config::Config;
 use crate::features::Feature;
+use crate::memory::MemoryStore;
+use crate::memory::render_memories_section;
 use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
 use dunce::canonicalize as normalize_path;
@@ -61,6 +63,22 @@ pub(crate) async fn get_user_instructions(
         }
     };
 
+    if config.features.enabled(Feature::Memory) {
+        let store = MemoryStore::for_project(&config.codex_home, &config.cwd);
+        match store.list() {
+            Ok(memories) => {
+                if let Some(section) = render_memories_section(&memories, config.memory_max_tokens)
+                {
+                    if !output.is_empty() {
+                        output.push_str("\n\n");
+                    }
+                    output.push_str(&section);
+                }
+            }
+            Err(e) => error!("error reading project memories: {e:#}"),
+        }
+    }
+
     let skills_section = skills.and_then(render_skills_section);
     if let Some(skills_section) = skills_section {
         if !output.is_empty() {
@@ -547,6 +565,30 @@ mod tests {
         assert_eq!(res, expected);
     }
 
+    #[tokio::test]
+    async fn memories_are_appended_when_feature_enabled() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        fs::write(tmp.path().join("AGENTS.md"), "base doc").unwrap();
+        let mut cfg = make_config(&tmp, 4096, None).await;
+        let store = MemoryStore::for_project(&cfg.codex_home, &cfg.cwd);
+        let memory = store.add("Run `just fmt` before committing").unwrap();
+
+        let res = get_user_instructions(&cfg, None).await;
+        assert_eq!(res, Some("base doc".to_string()));
+
+        cfg.features.enable(Feature::Memory);
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("instructions expected");
+        assert_eq!(
+            res,
+            format!(
+                "base doc\n\n## Memories\nNotes saved in earlier sessions for this project. Use `recall` to search them and `remember` to save new ones.\n- [{}] Run `just fmt` before committing",
+                memory.id
+            )
+        );
+    }
+
     fn create_skill(codex_home: PathBuf, name: &str, description: &str) {
         let skill_dir = codex_home.join(format!("skills/{name}"));
         fs::create_dir_all(&skill_dir).unwrap();
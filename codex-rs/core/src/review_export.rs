@@ -0,0 +1,610 @@
+//! Machine-readable exports of review findings for CI: SARIF 2.1.0 for
+//! code-scanning uploads and the payload of GitHub's "create a review for a
+//! pull request" endpoint.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use codex_git::merge_base_with_head;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::git_info::run_git_command_with_timeout;
+use crate::protocol::ReviewFinding;
+use crate::protocol::ReviewOutputEvent;
+use crate::protocol::ReviewTarget;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const TOOL_NAME: &str = "codex-review";
+const TOOL_INFORMATION_URI: &str = "https://github.com/openai/codex";
+
+/// Priority levels defined by the review prompt, most urgent first.
+const PRIORITIES: [(&str, &str); 4] = [
+    (
+        "P0",
+        "Drop everything to fix. Blocking release, operations, or major usage.",
+    ),
+    ("P1", "Urgent. Should be addressed in the next cycle."),
+    ("P2", "Normal. To be fixed eventually."),
+    ("P3", "Low. Nice to have."),
+];
+
+/// Index into [`PRIORITIES`], clamping out-of-range priorities.
+fn priority_index(priority: i32) -> usize {
+    usize::try_from(priority).map_or(0, |priority| priority.min(PRIORITIES.len() - 1))
+}
+
+fn rule_id(priority: i32) -> String {
+    format!("review/{}", PRIORITIES[priority_index(priority)].0)
+}
+
+/// SARIF `level` for a review priority: P0/P1 fail a code-scanning gate,
+/// P2 warns and P3 is informational.
+fn sarif_level(priority: i32) -> &'static str {
+    match priority_index(priority) {
+        0 | 1 => "error",
+        2 => "warning",
+        _ => "note",
+    }
+}
+
+/// `path` relative to `root` with `/` separators, or `None` when the file
+/// lies outside `root`.
+fn repo_relative_path(path: &Path, root: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    let parts: Vec<String> = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+/// Inclusive, 1-based line range of a finding.
+fn line_range(finding: &ReviewFinding) -> (u32, u32) {
+    let range = &finding.code_location.line_range;
+    let start = range.start.max(1);
+    (start, range.end.max(start))
+}
+
+fn finding_message(finding: &ReviewFinding) -> String {
+    let body = finding.body.trim();
+    if body.is_empty() {
+        finding.title.clone()
+    } else {
+        format!("{}\n\n{body}", finding.title)
+    }
+}
+
+/// Render review findings as a SARIF 2.1.0 log. Paths inside `root` are
+/// emitted relative to the `SRCROOT` base so code scanning can anchor them.
+pub fn review_output_to_sarif(output: &ReviewOutputEvent, root: &Path) -> Value {
+    let rules: Vec<Value> = (0..)
+        .zip(PRIORITIES)
+        .map(|(priority, (name, description))| {
+            json!({
+                "id": rule_id(priority),
+                "name": name,
+                "shortDescription": { "text": format!("{name} review finding") },
+                "fullDescription": { "text": description },
+                "defaultConfiguration": { "level": sarif_level(priority) },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = output
+        .findings
+        .iter()
+        .map(|finding| {
+            let path = &finding.code_location.absolute_file_path;
+            let artifact_location = match repo_relative_path(path, root) {
+                Some(uri) => json!({ "uri": uri, "uriBaseId": "SRCROOT" }),
+                None => json!({ "uri": file_uri(path) }),
+            };
+            let (start, end) = line_range(finding);
+            json!({
+                "ruleId": rule_id(finding.priority),
+                "ruleIndex": priority_index(finding.priority),
+                "level": sarif_level(finding.priority),
+                "message": { "text": finding_message(finding) },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": artifact_location,
+                        "region": { "startLine": start, "endLine": end },
+                    },
+                }],
+                "properties": {
+                    "title": finding.title,
+                    "priority": finding.priority,
+                    "confidence": finding.confidence_score,
+                },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "informationUri": TOOL_INFORMATION_URI,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                },
+            },
+            "originalUriBaseIds": {
+                "SRCROOT": { "uri": format!("{}/", file_uri(root).trim_end_matches('/')) },
+            },
+            "results": results,
+            "properties": {
+                "overallCorrectness": output.overall_correctness,
+                "overallExplanation": output.overall_explanation,
+                "overallConfidenceScore": output.overall_confidence_score,
+            },
+        }],
+    })
+}
+
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.starts_with('/') {
+        format!("file://{path}")
+    } else {
+        format!("file:///{path}")
+    }
+}
+
+/// Lines on the new (RIGHT) side of a unified diff, per repo-relative path.
+/// GitHub only accepts review comments on lines that appear in a hunk.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffLines {
+    hunks: HashMap<String, Vec<(u32, u32)>>,
+}
+
+impl DiffLines {
+    /// Collects the inclusive new-side line range of every hunk in `diff`.
+    pub fn parse(diff: &str) -> Self {
+        let mut lines = Self::default();
+        let mut current: Option<String> = None;
+        for line in diff.lines() {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                current = parse_new_path(path);
+            } else if line.starts_with("diff --git ") {
+                current = None;
+            } else if let Some(header) = line.strip_prefix("@@ ")
+                && let Some(path) = &current
+                && let Some(range) = parse_new_range(header)
+            {
+                lines.insert(path.clone(), range);
+            }
+        }
+        lines
+    }
+
+    fn insert(&mut self, path: String, range: (u32, u32)) {
+        self.hunks.entry(path).or_default().push(range);
+    }
+
+    /// Part of `start..=end` in `path` that falls in a single hunk, or `None`
+    /// when the range does not touch the diff.
+    fn clamp(&self, path: &str, start: u32, end: u32) -> Option<(u32, u32)> {
+        self.hunks.get(path)?.iter().find_map(|&(first, last)| {
+            let start = start.max(first);
+            let end = end.min(last);
+            (start <= end).then_some((start, end))
+        })
+    }
+}
+
+/// Path from a `+++ ` header line, without its `b/` prefix. Deleted files
+/// (`/dev/null`) have no new side.
+fn parse_new_path(path: &str) -> Option<String> {
+    let path = path.trim_end();
+    let path = path
+        .strip_prefix('"')
+        .and_then(|path| path.strip_suffix('"'))
+        .unwrap_or(path);
+    path.strip_prefix("b/").map(str::to_string)
+}
+
+/// New-side range of a hunk header (`-a,b +c,d @@`); `None` when the hunk
+/// only removes lines.
+fn parse_new_range(header: &str) -> Option<(u32, u32)> {
+    let new = header
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix('+'))?;
+    let (start, count) = match new.split_once(',') {
+        Some((start, count)) => (start.parse::<u32>().ok()?, count.parse::<u32>().ok()?),
+        None => (new.parse::<u32>().ok()?, 1),
+    };
+    (count > 0).then(|| (start, start + count - 1))
+}
+
+/// What a review covered, for deciding which findings GitHub can anchor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewedDiff {
+    /// The target is not a diff (custom instructions), so any line in the
+    /// repository may be commented on.
+    NotADiff,
+    /// Lines changed by the reviewed diff.
+    Lines(DiffLines),
+    /// git could not produce the diff, so no line is known to be on it.
+    Unavailable,
+}
+
+/// Lines changed by `target` in the repository at `root`.
+pub async fn reviewed_diff(target: &ReviewTarget, root: &Path) -> ReviewedDiff {
+    if matches!(target, ReviewTarget::Custom { .. }) {
+        return ReviewedDiff::NotADiff;
+    }
+    match diff_lines(target, root).await {
+        Some(lines) => ReviewedDiff::Lines(lines),
+        None => ReviewedDiff::Unavailable,
+    }
+}
+
+/// Full SHA of the commit a review of `target` looked at: the commit itself,
+/// the end of a range, or `HEAD` for working-tree targets.
+pub async fn reviewed_commit(target: &ReviewTarget, root: &Path) -> Option<String> {
+    let revision = match target {
+        ReviewTarget::Commit { sha, .. } => sha.as_str(),
+        ReviewTarget::CommitRange { to, .. } => to.as_str(),
+        ReviewTarget::UncommittedChanges
+        | ReviewTarget::BaseBranch { .. }
+        | ReviewTarget::Custom { .. } => "HEAD",
+    };
+    let revision = format!("{revision}^{{commit}}");
+    let output =
+        run_git_command_with_timeout(&["rev-parse", "--verify", "--quiet", &revision], root)
+            .await?;
+    if !output.status.success() {
+        return None;
+    }
+    let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!sha.is_empty()).then_some(sha)
+}
+
+/// Parsed diff of a diff-based `target`, or `None` when git fails.
+async fn diff_lines(target: &ReviewTarget, root: &Path) -> Option<DiffLines> {
+    const DIFF_ARGS: [&str; 4] = [
+        "--no-textconv",
+        "--no-ext-diff",
+        "--src-prefix=a/",
+        "--dst-prefix=b/",
+    ];
+    let (command, revisions, include_untracked) = match target {
+        ReviewTarget::UncommittedChanges => (vec!["diff"], vec!["HEAD".to_string()], true),
+        ReviewTarget::BaseBranch { branch } => {
+            let base = merge_base_with_head(root, branch).ok().flatten()?;
+            (vec!["diff"], vec![base], true)
+        }
+        ReviewTarget::Commit { sha, .. } => {
+            let parent = format!("{sha}^");
+            let has_parent =
+                run_git_command_with_timeout(&["rev-parse", "--verify", "--quiet", &parent], root)
+                    .await?
+                    .status
+                    .success();
+            if has_parent {
+                (vec!["diff"], vec![parent, sha.clone()], false)
+            } else {
+                // A root commit has no parent to diff against; `--root` shows
+                // it as adding every file.
+                (
+                    vec!["diff-tree", "--root", "-r", "-p", "--no-commit-id"],
+                    vec![sha.clone()],
+                    false,
+                )
+            }
+        }
+        ReviewTarget::CommitRange { from, to } => {
+            (vec!["diff"], vec![from.clone(), to.clone()], false)
+        }
+        ReviewTarget::Custom { .. } => return None,
+    };
+    let mut args = command;
+    args.extend(DIFF_ARGS);
+    args.extend(revisions.iter().map(String::as_str));
+    let output = run_git_command_with_timeout(&args, root).await?;
+    if !output.status.success() {
+        return None;
+    }
+    let mut lines = DiffLines::parse(&String::from_utf8_lossy(&output.stdout));
+
+    // Untracked files are entirely new, so every line is on the diff.
+    if include_untracked
+        && let Some(output) = run_git_command_with_timeout(
+            &["ls-files", "--others", "--exclude-standard", "--full-name"],
+            root,
+        )
+        .await
+        && output.status.success()
+    {
+        for path in String::from_utf8_lossy(&output.stdout).lines() {
+            let Ok(contents) = std::fs::read(root.join(path)) else {
+                continue;
+            };
+            let count = contents.split(|byte| *byte == b'\n').count();
+            let count = if contents.ends_with(b"\n") {
+                count - 1
+            } else {
+                count
+            };
+            if let Ok(count) = u32::try_from(count)
+                && count > 0
+            {
+                lines.insert(path.to_string(), (1, count));
+            }
+        }
+    }
+    Some(lines)
+}
+
+/// Render review findings as the body of GitHub's `POST
+/// /repos/{owner}/{repo}/pulls/{pull_number}/reviews` request. Findings on
+/// lines of `diff` (or, when the target is not a diff, anywhere under
+/// `root`) become line-anchored comments on the new side; the rest are listed
+/// in the review body, since GitHub rejects comments outside the pull request
+/// diff. When the diff is unavailable every finding goes in the body.
+pub fn review_output_to_github_review(
+    output: &ReviewOutputEvent,
+    root: &Path,
+    commit_id: Option<&str>,
+    diff: &ReviewedDiff,
+) -> Value {
+    let mut comments = Vec::new();
+    let mut off_diff = Vec::new();
+    for finding in &output.findings {
+        let path = &finding.code_location.absolute_file_path;
+        let (start, end) = line_range(finding);
+        let Some(relative) = repo_relative_path(path, root) else {
+            off_diff.push(off_diff_entry(finding, &path.display().to_string(), start));
+            continue;
+        };
+        let anchor = match diff {
+            ReviewedDiff::NotADiff => Some((start, end)),
+            ReviewedDiff::Lines(lines) => lines.clamp(&relative, start, end),
+            ReviewedDiff::Unavailable => None,
+        };
+        let Some((start, end)) = anchor else {
+            off_diff.push(off_diff_entry(finding, &relative, start));
+            continue;
+        };
+        let mut comment = json!({
+            "path": relative,
+            "line": end,
+            "side": "RIGHT",
+            "body": finding_message(finding),
+        });
+        if start < end {
+            comment["start_line"] = json!(start);
+            comment["start_side"] = json!("RIGHT");
+        }
+        comments.push(comment);
+    }
+
+    let mut sections = Vec::new();
+    let explanation = output.overall_explanation.trim();
+    if !explanation.is_empty() {
+        sections.push(explanation.to_string());
+    }
+    let correctness = output.overall_correctness.trim();
+    if !correctness.is_empty() {
+        sections.push(format!("Overall correctness: {correctness}"));
+    }
+    if !off_diff.is_empty() {
+        sections.push(format!(
+            "Findings outside the reviewed diff:\n{}",
+            off_diff.join("\n")
+        ));
+    }
+
+    let mut review = json!({
+        "body": sections.join("\n\n"),
+        "event": "COMMENT",
+        "comments": comments,
+    });
+    if let Some(commit_id) = commit_id {
+        review["commit_id"] = json!(commit_id);
+    }
+    review
+}
+
+/// Review-body bullet for a finding that cannot be anchored to the diff.
+fn off_diff_entry(finding: &ReviewFinding, location: &str, start: u32) -> String {
+    let mut entry = format!("- {} — {location}:{start}", finding.title);
+    for line in finding.body.trim().lines() {
+        entry.push('\n');
+        if !line.is_empty() {
+            entry.push_str("  ");
+            entry.push_str(line);
+        }
+    }
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ReviewCodeLocation;
+    use crate::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn finding(title: &str, path: &str, start: u32, end: u32, priority: i32) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: "Details.".to_string(),
+            confidence_score: 0.75,
+            priority,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: PathBuf::from(path),
+                line_range: ReviewLineRange { start, end },
+            },
+        }
+    }
+
+    fn review_output() -> ReviewOutputEvent {
+        ReviewOutputEvent {
+            findings: vec![
+                finding("[P1] Off by one", "/repo/src/lib.rs", 10, 12, 1),
+                finding("[P3] Typo", "/repo/README.md", 4, 4, 3),
+                finding("[P2] Shared config", "/etc/app.toml", 0, 0, 2),
+            ],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "One real bug.".to_string(),
+            overall_confidence_score: 0.8,
+        }
+    }
+
+    #[test]
+    fn sarif_maps_priorities_and_locations() {
+        let sarif = review_output_to_sarif(&review_output(), Path::new("/repo"));
+        let run = &sarif["runs"][0];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(run["originalUriBaseIds"]["SRCROOT"]["uri"], "file:///repo/");
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "review/P1");
+
+        let results = run["results"].as_array().expect("results");
+        assert_eq!(
+            results
+                .iter()
+                .map(|r| (r["ruleId"].clone(), r["level"].clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (json!("review/P1"), json!("error")),
+                (json!("review/P3"), json!("note")),
+                (json!("review/P2"), json!("warning")),
+            ]
+        );
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "src/lib.rs", "uriBaseId": "SRCROOT" },
+                "region": { "startLine": 10, "endLine": 12 },
+            })
+        );
+        assert_eq!(results[0]["message"]["text"], "[P1] Off by one\n\nDetails.");
+        assert_eq!(
+            results[2]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "file:///etc/app.toml" },
+                "region": { "startLine": 1, "endLine": 1 },
+            })
+        );
+    }
+
+    #[test]
+    fn github_review_anchors_comments_in_repo() {
+        let review = review_output_to_github_review(
+            &review_output(),
+            Path::new("/repo"),
+            Some("abc123"),
+            &ReviewedDiff::NotADiff,
+        );
+
+        assert_eq!(
+            review,
+            json!({
+                "commit_id": "abc123",
+                "event": "COMMENT",
+                "body": "One real bug.\n\nOverall correctness: patch is incorrect\n\nFindings outside the reviewed diff:\n- [P2] Shared config — /etc/app.toml:1\n  Details.",
+                "comments": [
+                    {
+                        "path": "src/lib.rs",
+                        "start_line": 10,
+                        "start_side": "RIGHT",
+                        "line": 12,
+                        "side": "RIGHT",
+                        "body": "[P1] Off by one\n\nDetails.",
+                    },
+                    {
+                        "path": "README.md",
+                        "line": 4,
+                        "side": "RIGHT",
+                        "body": "[P3] Typo\n\nDetails.",
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn github_review_moves_findings_off_the_diff_into_the_body() {
+        let diff = DiffLines::parse(
+            "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -9,3 +9,4 @@ fn main() {
+ context
+-old
++new
++added
+ context
+@@ -30,2 +31,0 @@
+-gone
+-gone
+diff --git a/README.md b/README.md
+deleted file mode 100644
+--- a/README.md
++++ /dev/null
+@@ -1,4 +0,0 @@
+-a
+-b
+-c
+-d
+",
+        );
+        let review = review_output_to_github_review(
+            &review_output(),
+            Path::new("/repo"),
+            None,
+            &ReviewedDiff::Lines(diff),
+        );
+
+        assert_eq!(
+            review["comments"],
+            json!([{
+                "path": "src/lib.rs",
+                "start_line": 10,
+                "start_side": "RIGHT",
+                "line": 12,
+                "side": "RIGHT",
+                "body": "[P1] Off by one\n\nDetails.",
+            }])
+        );
+        assert_eq!(
+            review["body"],
+            "One real bug.\n\nOverall correctness: patch is incorrect\n\nFindings outside the reviewed diff:\n- [P3] Typo — README.md:4\n  Details.\n- [P2] Shared config — /etc/app.toml:1\n  Details."
+        );
+    }
+
+    #[test]
+    fn github_review_without_a_diff_keeps_every_finding_in_the_body() {
+        let review = review_output_to_github_review(
+            &review_output(),
+            Path::new("/repo"),
+            None,
+            &ReviewedDiff::Unavailable,
+        );
+
+        assert_eq!(review["comments"], json!([]));
+        assert_eq!(
+            review["body"],
+            "One real bug.\n\nOverall correctness: patch is incorrect\n\nFindings outside the reviewed diff:\n- [P1] Off by one — src/lib.rs:10\n  Details.\n- [P3] Typo — README.md:4\n  Details.\n- [P2] Shared config — /etc/app.toml:1\n  Details."
+        );
+    }
+
+    #[test]
+    fn diff_lines_clamp_findings_to_a_hunk() {
+        let diff = DiffLines::parse("+++ b/a.rs\n@@ -1 +1 @@\n@@ -8,2 +8,3 @@\n");
+
+        assert_eq!(diff.clamp("a.rs", 1, 1), Some((1, 1)));
+        assert_eq!(diff.clamp("a.rs", 5, 9), Some((8, 9)));
+        assert_eq!(diff.clamp("a.rs", 3, 6), None);
+        assert_eq!(diff.clamp("b.rs", 1, 1), None);
+    }
+}
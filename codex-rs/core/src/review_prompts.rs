@@ -1,4 +1,5 @@
 use codex_git::merge_base_with_head;
+use codex_git::upstream_base_branch;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
 use std::path::Path;
@@ -8,6 +9,7 @@ pub struct ResolvedReviewRequest {
     pub target: ReviewTarget,
     pub prompt: String,
     pub user_facing_hint: String,
+    pub paths: Vec<String>,
 }
 
 const UNCOMMITTED_PROMPT: &str = "Review the current code changes (staged, unstaged, and untracked files) and provide prioritized findings.";
@@ -19,23 +21,57 @@ const COMMIT_PROMPT_WITH_TITLE: &str = "Review the code changes introduced by co
 const COMMIT_PROMPT: &str =
     "Review the code changes introduced by commit {sha}. Provide prioritized, actionable findings.";
 
+const COMMIT_RANGE_PROMPT: &str = "Review the code changes introduced by the commits in the range {from}..{to}. Run `git log {from}..{to}` to list the commits and `git diff {from} {to}` to inspect their combined changes. Only review changes made by these commits. Provide prioritized, actionable findings.";
+
+const PATHS_PROMPT: &str = "Only review changes to the following paths and ignore changes elsewhere: {paths}. Pass them as pathspecs when running git (e.g. `git diff ... -- <paths>`).";
+
 pub fn resolve_review_request(
     request: ReviewRequest,
     cwd: &Path,
 ) -> anyhow::Result<ResolvedReviewRequest> {
     let target = request.target;
-    let prompt = review_prompt(&target, cwd)?;
+    let paths: Vec<String> = request
+        .paths
+        .iter()
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+        .collect();
+    let mut prompt = review_prompt(&target, cwd)?;
+    if !paths.is_empty() {
+        prompt.push_str("\n\n");
+        prompt.push_str(&PATHS_PROMPT.replace("{paths}", &format_paths(&paths)));
+    }
     let user_facing_hint = request
         .user_facing_hint
-        .unwrap_or_else(|| user_facing_hint(&target));
+        .unwrap_or_else(|| user_facing_hint_with_paths(&target, &paths));
 
     Ok(ResolvedReviewRequest {
         target,
         prompt,
         user_facing_hint,
+        paths,
     })
 }
 
+fn format_paths(paths: &[String]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("`{path}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Base-branch target for the branch a pull request from `cwd` would target:
+/// the upstream tracking branch, or the remote's default branch.
+pub fn upstream_review_target(cwd: &Path) -> anyhow::Result<ReviewTarget> {
+    match upstream_base_branch(cwd)? {
+        Some(branch) => Ok(ReviewTarget::BaseBranch { branch }),
+        None => anyhow::bail!(
+            "Could not detect a base branch: the current branch has no upstream and the remote has no default branch"
+        ),
+    }
+}
+
 pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String> {
     match target {
         ReviewTarget::UncommittedChanges => Ok(UNCOMMITTED_PROMPT.to_string()),
@@ -57,6 +93,14 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
                 Ok(COMMIT_PROMPT.replace("{sha}", sha))
             }
         }
+        ReviewTarget::CommitRange { from, to } => {
+            if from.trim().is_empty() || to.trim().is_empty() {
+                anyhow::bail!("Commit range must name both its start and end");
+            }
+            Ok(COMMIT_RANGE_PROMPT
+                .replace("{from}", from)
+                .replace("{to}", to))
+        }
         ReviewTarget::Custom { instructions } => {
             let prompt = instructions.trim();
             if prompt.is_empty() {
@@ -67,6 +111,22 @@ pub fn review_prompt(target: &ReviewTarget, cwd: &Path) -> anyhow::Result<String
     }
 }
 
+/// [`user_facing_hint`] followed by the paths the review is restricted to,
+/// ignoring blank entries.
+pub fn user_facing_hint_with_paths(target: &ReviewTarget, paths: &[String]) -> String {
+    let hint = user_facing_hint(target);
+    let paths: Vec<&str> = paths
+        .iter()
+        .map(|path| path.trim())
+        .filter(|path| !path.is_empty())
+        .collect();
+    if paths.is_empty() {
+        hint
+    } else {
+        format!("{hint} in {}", paths.join(", "))
+    }
+}
+
 pub fn user_facing_hint(target: &ReviewTarget) -> String {
     match target {
         ReviewTarget::UncommittedChanges => "current changes".to_string(),
@@ -79,15 +139,65 @@ pub fn user_facing_hint(target: &ReviewTarget) -> String {
                 format!("commit {short_sha}")
             }
         }
+        ReviewTarget::CommitRange { from, to } => {
+            format!("commits {}..{}", short_rev(from), short_rev(to))
+        }
         ReviewTarget::Custom { instructions } => instructions.trim().to_string(),
     }
 }
 
+/// Abbreviates full commit hashes; symbolic revisions such as `HEAD~3` are
+/// kept as-is.
+fn short_rev(rev: &str) -> String {
+    if rev.len() > 7 && rev.chars().all(|c| c.is_ascii_hexdigit()) {
+        rev.chars().take(7).collect()
+    } else {
+        rev.to_string()
+    }
+}
+
 impl From<ResolvedReviewRequest> for ReviewRequest {
     fn from(resolved: ResolvedReviewRequest) -> Self {
         ReviewRequest {
             target: resolved.target,
             user_facing_hint: Some(resolved.user_facing_hint),
+            paths: resolved.paths,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn commit_range_with_paths_scopes_prompt_and_hint() {
+        let resolved = resolve_review_request(
+            ReviewRequest {
+                target: ReviewTarget::CommitRange {
+                    from: "HEAD~3".to_string(),
+                    to: "0123456789abcdef".to_string(),
+                },
+                user_facing_hint: None,
+                paths: vec!["core/src".to_string(), "  ".to_string()],
+            },
+            Path::new("."),
+        )
+        .expect("resolves commit range review");
+
+        assert_eq!(
+            resolved.user_facing_hint,
+            "commits HEAD~3..0123456 in core/src"
+        );
+        assert_eq!(resolved.paths, vec!["core/src".to_string()]);
+        assert!(
+            resolved
+                .prompt
+                .starts_with("Review the code changes introduced by the commits in the range HEAD~3..0123456789abcdef.")
+        );
+        assert!(resolved.prompt.ends_with(
+            "Only review changes to the following paths and ignore changes elsewhere: `core/src`. Pass them as pathspecs when running git (e.g. `git diff ... -- <paths>`)."
+        ));
+    }
+}
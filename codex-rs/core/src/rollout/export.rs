@@ -0,0 +1,772 @@
+//! Render a recorded rollout as a human-readable transcript.
+//!
+//! Rollout JSONL files are the only durable record of a session. This module
+//! replays their [`RolloutItem`]s into a [`Transcript`] (messages, reasoning
+//! summaries, commands with output, patches as unified diffs and token usage)
+//! that can be rendered as Markdown, a self-contained HTML page or JSON.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_apply_patch::Hunk;
+use codex_apply_patch::parse_patch;
+use codex_protocol::ThreadId;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::WebSearchAction;
+use codex_protocol::protocol::FinalOutput;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::TokenUsage;
+use serde::Serialize;
+use similar::ChangeTag;
+use similar::TextDiff;
+
+use crate::parse_command::extract_shell_command;
+use crate::protocol::EventMsg;
+
+/// Tool output longer than this is cut in Markdown and HTML transcripts.
+const MAX_OUTPUT_LINES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    Markdown,
+    Html,
+    Json,
+}
+
+/// A session transcript rebuilt from rollout items.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct Transcript {
+    pub thread_id: Option<ThreadId>,
+    pub created_at: Option<String>,
+    pub cwd: Option<PathBuf>,
+    pub cli_version: Option<String>,
+    pub model_provider: Option<String>,
+    /// Model used for the most recent turn.
+    pub model: Option<String>,
+    pub entries: Vec<TranscriptEntry>,
+    /// Cumulative token usage from the last token count recorded.
+    pub token_usage: Option<TokenUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TranscriptEntry {
+    UserMessage {
+        message: String,
+    },
+    AgentMessage {
+        message: String,
+    },
+    Reasoning {
+        text: String,
+    },
+    Command {
+        call_id: Option<String>,
+        command: String,
+        output: Option<String>,
+    },
+    Patch {
+        call_id: String,
+        /// Unified diff of the patch, or the raw patch if it could not be parsed.
+        diff: String,
+        output: Option<String>,
+    },
+    ToolCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+        output: Option<String>,
+    },
+    WebSearch {
+        query: String,
+    },
+    Compacted {
+        message: String,
+    },
+}
+
+impl TranscriptEntry {
+    fn set_output(&mut self, value: String) {
+        match self {
+            TranscriptEntry::Command { output, .. }
+            | TranscriptEntry::Patch { output, .. }
+            | TranscriptEntry::ToolCall { output, .. } => *output = Some(value),
+            TranscriptEntry::UserMessage { .. }
+            | TranscriptEntry::AgentMessage { .. }
+            | TranscriptEntry::Reasoning { .. }
+            | TranscriptEntry::WebSearch { .. }
+            | TranscriptEntry::Compacted { .. } => {}
+        }
+    }
+}
+
+/// Read the rollout at `path` and rebuild its transcript.
+pub async fn load_transcript(path: &Path) -> io::Result<Transcript> {
+    let contents = tokio::fs::read_to_string(path).await?;
+    let items: Vec<RolloutItem> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<RolloutLine>(line).ok())
+        .map(|line| line.item)
+        .collect();
+    Ok(Transcript::from_rollout_items(&items))
+}
+
+impl Transcript {
+    /// Replay `items` in order. User, agent and reasoning text come from the
+    /// persisted events; tool calls come from the response items, which are
+    /// the only place their arguments and outputs are recorded.
+    pub fn from_rollout_items(items: &[RolloutItem]) -> Self {
+        let mut transcript = Transcript::default();
+        let mut calls: HashMap<String, usize> = HashMap::new();
+
+        for item in items {
+            match item {
+                RolloutItem::SessionMeta(meta_line) => {
+                    let meta = &meta_line.meta;
+                    transcript.thread_id.get_or_insert(meta.id);
+                    if transcript.created_at.is_none() && !meta.timestamp.is_empty() {
+                        transcript.created_at = Some(meta.timestamp.clone());
+                    }
+                    transcript.cwd.get_or_insert_with(|| meta.cwd.clone());
+                    transcript
+                        .cli_version
+                        .get_or_insert_with(|| meta.cli_version.clone());
+                    if transcript.model_provider.is_none() {
+                        transcript.model_provider = meta.model_provider.clone();
+                    }
+                }
+                RolloutItem::TurnContext(context) => {
+                    transcript.model = Some(context.model.clone());
+                }
+                RolloutItem::Compacted(compacted) => {
+                    transcript.entries.push(TranscriptEntry::Compacted {
+                        message: compacted.message.clone(),
+                    });
+                }
+                RolloutItem::EventMsg(event) => match event {
+                    EventMsg::UserMessage(event) => {
+                        transcript.entries.push(TranscriptEntry::UserMessage {
+                            message: event.message.clone(),
+                        });
+                    }
+                    EventMsg::AgentMessage(event) => {
+                        transcript.entries.push(TranscriptEntry::AgentMessage {
+                            message: event.message.clone(),
+                        });
+                    }
+                    EventMsg::AgentReasoning(event) => {
+                        transcript.entries.push(TranscriptEntry::Reasoning {
+                            text: event.text.clone(),
+                        });
+                    }
+                    EventMsg::TokenCount(event) => {
+                        if let Some(info) = &event.info {
+                            transcript.token_usage = Some(info.total_token_usage.clone());
+                        }
+                    }
+                    _ => {}
+                },
+                RolloutItem::ResponseItem(item) => {
+                    let entry = match item {
+                        ResponseItem::FunctionCall {
+                            name,
+                            arguments,
+                            call_id,
+                            ..
+                        } => Some(function_call_entry(name, arguments, call_id)),
+                        ResponseItem::CustomToolCall {
+                            name,
+                            input,
+                            call_id,
+                            ..
+                        } => Some(if name == "apply_patch" {
+                            TranscriptEntry::Patch {
+                                call_id: call_id.clone(),
+                                diff: patch_to_unified_diff(input),
+                                output: None,
+                            }
+                        } else {
+                            TranscriptEntry::ToolCall {
+                                call_id: call_id.clone(),
+                                name: name.clone(),
+                                arguments: input.clone(),
+                                output: None,
+                            }
+                        }),
+                        ResponseItem::LocalShellCall {
+                            call_id,
+                            id,
+                            action: LocalShellAction::Exec(exec),
+                            ..
+                        } => Some(TranscriptEntry::Command {
+                            call_id: call_id.clone().or_else(|| id.clone()),
+                            command: display_command(&exec.command),
+                            output: None,
+                        }),
+                        ResponseItem::WebSearchCall {
+                            action: WebSearchAction::Search { query: Some(query) },
+                            ..
+                        } => Some(TranscriptEntry::WebSearch {
+                            query: query.clone(),
+                        }),
+                        ResponseItem::FunctionCallOutput { call_id, output } => {
+                            if let Some(index) = calls.get(call_id) {
+                                transcript.entries[*index].set_output(output.content.clone());
+                            }
+                            None
+                        }
+                        ResponseItem::CustomToolCallOutput { call_id, output } => {
+                            if let Some(index) = calls.get(call_id) {
+                                transcript.entries[*index].set_output(output.clone());
+                            }
+                            None
+                        }
+                        _ => None,
+                    };
+                    if let Some(entry) = entry {
+                        let call_id = match &entry {
+                            TranscriptEntry::Command { call_id, .. } => call_id.clone(),
+                            TranscriptEntry::Patch { call_id, .. }
+                            | TranscriptEntry::ToolCall { call_id, .. } => Some(call_id.clone()),
+                            _ => None,
+                        };
+                        if let Some(call_id) = call_id {
+                            calls.insert(call_id, transcript.entries.len());
+                        }
+                        transcript.entries.push(entry);
+                    }
+                }
+            }
+        }
+
+        transcript
+    }
+
+    pub fn render(&self, format: TranscriptFormat) -> String {
+        match format {
+            TranscriptFormat::Markdown => self.to_markdown(),
+            TranscriptFormat::Html => self.to_html(),
+            TranscriptFormat::Json => {
+                serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+            }
+        }
+    }
+
+    fn title(&self) -> String {
+        match self.thread_id {
+            Some(id) => format!("Codex session {id}"),
+            None => "Codex session".to_string(),
+        }
+    }
+
+    fn metadata(&self) -> Vec<(&'static str, String)> {
+        let mut fields = Vec::new();
+        if let Some(created_at) = &self.created_at {
+            fields.push(("Started", created_at.clone()));
+        }
+        if let Some(model) = &self.model {
+            fields.push(("Model", model.clone()));
+        }
+        if let Some(provider) = &self.model_provider {
+            fields.push(("Provider", provider.clone()));
+        }
+        if let Some(cwd) = &self.cwd {
+            fields.push(("Working directory", cwd.display().to_string()));
+        }
+        if let Some(version) = &self.cli_version {
+            fields.push(("Codex version", version.clone()));
+        }
+        fields
+    }
+
+    fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", self.title());
+        for (label, value) in self.metadata() {
+            let _ = writeln!(out, "- **{label}:** {value}");
+        }
+
+        for entry in &self.entries {
+            out.push('\n');
+            match entry {
+                TranscriptEntry::UserMessage { message } => {
+                    let _ = write!(out, "## User\n\n{}\n", message.trim_end());
+                }
+                TranscriptEntry::AgentMessage { message } => {
+                    let _ = write!(out, "## Codex\n\n{}\n", message.trim_end());
+                }
+                TranscriptEntry::Reasoning { text } => {
+                    out.push_str("> **Reasoning**\n>\n");
+                    for line in text.trim_end().lines() {
+                        if line.is_empty() {
+                            out.push_str(">\n");
+                        } else {
+                            let _ = writeln!(out, "> {line}");
+                        }
+                    }
+                }
+                TranscriptEntry::Command {
+                    command, output, ..
+                } => {
+                    out.push_str("### Command\n\n");
+                    out.push_str(&fenced("sh", command));
+                    if let Some(output) = output {
+                        out.push_str("\nOutput:\n\n");
+                        out.push_str(&fenced("text", &truncate_output(output)));
+                    }
+                }
+                TranscriptEntry::Patch { diff, output, .. } => {
+                    out.push_str("### Patch\n\n");
+                    out.push_str(&fenced("diff", diff));
+                    if let Some(output) = output {
+                        out.push('\n');
+                        out.push_str(&fenced("text", &truncate_output(output)));
+                    }
+                }
+                TranscriptEntry::ToolCall {
+                    name,
+                    arguments,
+                    output,
+                    ..
+                } => {
+                    let _ = write!(out, "### Tool call `{name}`\n\n");
+                    out.push_str(&fenced("json", arguments));
+                    if let Some(output) = output {
+                        out.push_str("\nOutput:\n\n");
+                        out.push_str(&fenced("text", &truncate_output(output)));
+                    }
+                }
+                TranscriptEntry::WebSearch { query } => {
+                    let _ = writeln!(out, "_Searched the web for:_ {query}");
+                }
+                TranscriptEntry::Compacted { message } => {
+                    out.push_str("_Context compacted._\n");
+                    if !message.trim().is_empty() {
+                        let _ = write!(out, "\n{}\n", message.trim_end());
+                    }
+                }
+            }
+        }
+
+        if let Some(usage) = &self.token_usage {
+            let _ = write!(out, "\n---\n\n{}\n", FinalOutput::from(usage.clone()));
+        }
+        out
+    }
+
+    fn to_html(&self) -> String {
+        let title = escape_html(&self.title());
+        let mut out = format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n<style>{HTML_STYLE}</style>\n</head>\n<body>\n\
+             <h1>{title}</h1>\n"
+        );
+
+        let metadata = self.metadata();
+        if !metadata.is_empty() {
+            out.push_str("<dl class=\"meta\">\n");
+            for (label, value) in metadata {
+                let _ = writeln!(out, "<dt>{label}</dt><dd>{}</dd>", escape_html(&value));
+            }
+            out.push_str("</dl>\n");
+        }
+
+        for entry in &self.entries {
+            match entry {
+                TranscriptEntry::UserMessage { message } => {
+                    let _ = writeln!(
+                        out,
+                        "<section class=\"user\"><h2>User</h2><div class=\"text\">{}</div></section>",
+                        escape_html(message.trim_end())
+                    );
+                }
+                TranscriptEntry::AgentMessage { message } => {
+                    let _ = writeln!(
+                        out,
+                        "<section class=\"agent\"><h2>Codex</h2><div class=\"text\">{}</div></section>",
+                        escape_html(message.trim_end())
+                    );
+                }
+                TranscriptEntry::Reasoning { text } => {
+                    let _ = writeln!(
+                        out,
+                        "<details class=\"reasoning\"><summary>Reasoning</summary><div class=\"text\">{}</div></details>",
+                        escape_html(text.trim_end())
+                    );
+                }
+                TranscriptEntry::Command {
+                    command, output, ..
+                } => {
+                    let _ = write!(
+                        out,
+                        "<section class=\"tool\"><h3>Command</h3><pre class=\"command\">$ {}</pre>",
+                        escape_html(command)
+                    );
+                    push_html_output(&mut out, output.as_deref());
+                    out.push_str("</section>\n");
+                }
+                TranscriptEntry::Patch { diff, output, .. } => {
+                    out.push_str("<section class=\"tool\"><h3>Patch</h3><pre class=\"diff\">");
+                    for line in diff.lines() {
+                        let class = diff_line_class(line);
+                        let _ =
+                            writeln!(out, "<span class=\"{class}\">{}</span>", escape_html(line));
+                    }
+                    out.push_str("</pre>");
+                    push_html_output(&mut out, output.as_deref());
+                    out.push_str("</section>\n");
+                }
+                TranscriptEntry::ToolCall {
+                    name,
+                    arguments,
+                    output,
+                    ..
+                } => {
+                    let _ = write!(
+                        out,
+                        "<section class=\"tool\"><h3>Tool call <code>{}</code></h3><pre>{}</pre>",
+                        escape_html(name),
+                        escape_html(arguments)
+                    );
+                    push_html_output(&mut out, output.as_deref());
+                    out.push_str("</section>\n");
+                }
+                TranscriptEntry::WebSearch { query } => {
+                    let _ = writeln!(
+                        out,
+                        "<p class=\"note\">Searched the web for: {}</p>",
+                        escape_html(query)
+                    );
+                }
+                TranscriptEntry::Compacted { message } => {
+                    let _ = writeln!(
+                        out,
+                        "<p class=\"note\">Context compacted.</p><div class=\"text\">{}</div>",
+                        escape_html(message.trim_end())
+                    );
+                }
+            }
+        }
+
+        if let Some(usage) = &self.token_usage {
+            let _ = writeln!(
+                out,
+                "<footer>{}</footer>",
+                escape_html(&FinalOutput::from(usage.clone()).to_string())
+            );
+        }
+        out.push_str("</body>\n</html>\n");
+        out
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:60rem;margin:2rem auto;padding:0 1rem;line-height:1.5}\
+pre,code{font-family:ui-monospace,monospace;font-size:0.9em}\
+pre{background:#f6f8fa;padding:0.75rem;overflow-x:auto;white-space:pre-wrap}\
+.text{white-space:pre-wrap}\
+.user{border-left:4px solid #0969da;padding-left:1rem}\
+.agent{border-left:4px solid #8250df;padding-left:1rem}\
+.reasoning{color:#57606a;font-style:italic}\
+.meta dt{font-weight:bold;float:left;clear:left;margin-right:0.5rem}\
+.note{color:#57606a;font-style:italic}\
+.add{color:#1a7f37}.del{color:#cf222e}.hunk{color:#0969da}\
+footer{border-top:1px solid #d0d7de;margin-top:2rem;padding-top:1rem;color:#57606a}";
+
+fn push_html_output(out: &mut String, output: Option<&str>) {
+    if let Some(output) = output {
+        let _ = write!(
+            out,
+            "<pre class=\"output\">{}</pre>",
+            escape_html(&truncate_output(output))
+        );
+    }
+}
+
+fn diff_line_class(line: &str) -> &'static str {
+    if line.starts_with("+++") || line.starts_with("---") {
+        "file"
+    } else if line.starts_with('+') {
+        "add"
+    } else if line.starts_with('-') {
+        "del"
+    } else if line.starts_with("@@") {
+        "hunk"
+    } else {
+        "ctx"
+    }
+}
+
+fn function_call_entry(name: &str, arguments: &str, call_id: &str) -> TranscriptEntry {
+    let args = serde_json::from_str::<serde_json::Value>(arguments).ok();
+    let command = args.as_ref().and_then(|args| match name {
+        "shell" | "container.exec" | "local_shell" => {
+            let command: Vec<String> = args
+                .get("command")?
+                .as_array()?
+                .iter()
+                .filter_map(|part| part.as_str().map(str::to_string))
+                .collect();
+            Some(display_command(&command))
+        }
+        "shell_command" => args.get("command")?.as_str().map(str::to_string),
+        "exec_command" => args.get("cmd")?.as_str().map(str::to_string),
+        _ => None,
+    });
+    if let Some(command) = command {
+        return TranscriptEntry::Command {
+            call_id: Some(call_id.to_string()),
+            command,
+            output: None,
+        };
+    }
+
+    if name == "apply_patch"
+        && let Some(input) = args
+            .as_ref()
+            .and_then(|args| args.get("input"))
+            .and_then(|input| input.as_str())
+    {
+        return TranscriptEntry::Patch {
+            call_id: call_id.to_string(),
+            diff: patch_to_unified_diff(input),
+            output: None,
+        };
+    }
+
+    TranscriptEntry::ToolCall {
+        call_id: call_id.to_string(),
+        name: name.to_string(),
+        arguments: arguments.to_string(),
+        output: None,
+    }
+}
+
+/// Same presentation as the TUI: show the script of `bash -lc` invocations,
+/// otherwise shell-escape the argv.
+fn display_command(command: &[String]) -> String {
+    if let Some((_, script)) = extract_shell_command(command) {
+        return script.to_string();
+    }
+    shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
+/// Convert an `apply_patch` body into a unified diff. Falls back to the raw
+/// patch when it does not parse.
+fn patch_to_unified_diff(patch: &str) -> String {
+    let Ok(args) = parse_patch(patch) else {
+        return patch.to_string();
+    };
+
+    let mut out = String::new();
+    for hunk in args.hunks {
+        match hunk {
+            Hunk::AddFile { path, contents } => {
+                let _ = writeln!(out, "--- /dev/null\n+++ b/{}", path.display());
+                let _ = writeln!(out, "@@ -0,0 +1,{} @@", contents.lines().count());
+                for line in contents.lines() {
+                    let _ = writeln!(out, "+{line}");
+                }
+            }
+            Hunk::DeleteFile { path } => {
+                let _ = writeln!(out, "--- a/{}\n+++ /dev/null", path.display());
+            }
+            Hunk::UpdateFile {
+                path,
+                move_path,
+                chunks,
+            } => {
+                let new_path = move_path.as_ref().unwrap_or(&path);
+                let _ = writeln!(
+                    out,
+                    "--- a/{}\n+++ b/{}",
+                    path.display(),
+                    new_path.display()
+                );
+                for chunk in chunks {
+                    match &chunk.change_context {
+                        Some(context) => {
+                            let _ = writeln!(out, "@@ {context} @@");
+                        }
+                        None => out.push_str("@@\n"),
+                    }
+                    let old_lines: Vec<&str> = chunk.old_lines.iter().map(String::as_str).collect();
+                    let new_lines: Vec<&str> = chunk.new_lines.iter().map(String::as_str).collect();
+                    let diff = TextDiff::from_slices(&old_lines, &new_lines);
+                    for change in diff.iter_all_changes() {
+                        let sign = match change.tag() {
+                            ChangeTag::Equal => ' ',
+                            ChangeTag::Delete => '-',
+                            ChangeTag::Insert => '+',
+                        };
+                        let _ = writeln!(out, "{sign}{}", change.value());
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+fn truncate_output(output: &str) -> String {
+    let total = output.lines().count();
+    if total <= MAX_OUTPUT_LINES {
+        return output.trim_end().to_string();
+    }
+    let mut kept: Vec<&str> = output.lines().take(MAX_OUTPUT_LINES).collect();
+    let omitted = format!("… ({} more lines)", total - MAX_OUTPUT_LINES);
+    kept.push(&omitted);
+    kept.join("\n")
+}
+
+/// Wrap `content` in a code fence longer than any backtick run it contains.
+fn fenced(lang: &str, content: &str) -> String {
+    let mut longest = 0;
+    let mut run = 0;
+    for c in content.chars() {
+        if c == '`' {
+            run += 1;
+            longest = longest.max(run);
+        } else {
+            run = 0;
+        }
+    }
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{fence}{lang}\n{}\n{fence}\n", content.trim_end())
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn items() -> Vec<RolloutItem> {
+        let lines = [
+            json!({"type": "session_meta", "payload": {
+                "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "timestamp": "2025-01-02T12:00:00Z",
+                "cwd": "/repo",
+                "originator": "codex",
+                "cli_version": "1.2.3",
+                "instructions": null,
+                "model_provider": "openai",
+            }}),
+            json!({"type": "event_msg", "payload": {"type": "user_message", "message": "Fix <the> test"}}),
+            json!({"type": "event_msg", "payload": {"type": "agent_reasoning", "text": "Look at the test first."}}),
+            json!({"type": "response_item", "payload": {
+                "type": "function_call",
+                "name": "shell",
+                "arguments": "{\"command\":[\"bash\",\"-lc\",\"cargo test\"]}",
+                "call_id": "call-1",
+            }}),
+            json!({"type": "response_item", "payload": {
+                "type": "function_call_output",
+                "call_id": "call-1",
+                "output": "test result: ok",
+            }}),
+            json!({"type": "response_item", "payload": {
+                "type": "custom_tool_call",
+                "name": "apply_patch",
+                "input": "*** Begin Patch\n*** Update File: src/lib.rs\n@@ fn main\n-let a = 1;\n+let a = 2;\n*** End Patch",
+                "call_id": "call-2",
+            }}),
+            json!({"type": "event_msg", "payload": {"type": "agent_message", "message": "Done."}}),
+            json!({"type": "event_msg", "payload": {"type": "token_count", "info": {
+                "total_token_usage": {"input_tokens": 10, "cached_input_tokens": 0, "output_tokens": 5, "reasoning_output_tokens": 0, "total_tokens": 15},
+                "last_token_usage": {"input_tokens": 10, "cached_input_tokens": 0, "output_tokens": 5, "reasoning_output_tokens": 0, "total_tokens": 15},
+                "model_context_window": null,
+            }, "rate_limits": null}}),
+        ];
+        lines
+            .into_iter()
+            .map(|mut value| {
+                value["timestamp"] = json!("2025-01-02T12:00:00Z");
+                serde_json::from_value::<RolloutLine>(value).unwrap().item
+            })
+            .collect()
+    }
+
+    #[test]
+    fn replays_rollout_items_into_entries() {
+        let transcript = Transcript::from_rollout_items(&items());
+
+        assert_eq!(transcript.cli_version.as_deref(), Some("1.2.3"));
+        assert_eq!(
+            transcript.entries,
+            vec![
+                TranscriptEntry::UserMessage {
+                    message: "Fix <the> test".to_string(),
+                },
+                TranscriptEntry::Reasoning {
+                    text: "Look at the test first.".to_string(),
+                },
+                TranscriptEntry::Command {
+                    call_id: Some("call-1".to_string()),
+                    command: "cargo test".to_string(),
+                    output: Some("test result: ok".to_string()),
+                },
+                TranscriptEntry::Patch {
+                    call_id: "call-2".to_string(),
+                    diff: "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ fn main @@\n-let a = 1;\n+let a = 2;\n"
+                        .to_string(),
+                    output: None,
+                },
+                TranscriptEntry::AgentMessage {
+                    message: "Done.".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            transcript.token_usage.map(|usage| usage.total_tokens),
+            Some(15)
+        );
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = Transcript::from_rollout_items(&items()).render(TranscriptFormat::Markdown);
+
+        assert!(markdown.starts_with("# Codex session 67e55044-10b1-426f-9247-bb680e5fe0c8\n"));
+        assert!(markdown.contains("- **Working directory:** /repo\n"));
+        assert!(markdown.contains("## User\n\nFix <the> test\n"));
+        assert!(markdown.contains("> **Reasoning**\n>\n> Look at the test first.\n"));
+        assert!(markdown.contains("### Command\n\n```sh\ncargo test\n```\n"));
+        assert!(markdown.contains("```diff\n--- a/src/lib.rs\n"));
+        assert!(markdown.contains("Token usage: total=15"));
+    }
+
+    #[test]
+    fn renders_escaped_html() {
+        let html = Transcript::from_rollout_items(&items()).render(TranscriptFormat::Html);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("Fix &lt;the&gt; test"));
+        assert!(html.contains("<span class=\"add\">+let a = 2;</span>"));
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn fence_outgrows_backticks_in_content() {
+        assert_eq!(fenced("text", "a ``` b"), "````text\na ``` b\n````\n");
+    }
+}
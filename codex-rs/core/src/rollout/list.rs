@@ -65,7 +65,7 @@ struct HeadTailSummary {
 }
 
 /// Hard cap to bound worst‑case work per request.
-const MAX_SCAN_FILES: usize = 10000;
+pub(super) const MAX_SCAN_FILES: usize = 10000;
 const HEAD_RECORD_LIMIT: usize = 10;
 
 /// Pagination cursor identifying a file by timestamp and UUID.
@@ -295,7 +295,10 @@ fn build_next_cursor(items: &[ThreadItem]) -> Option<Cursor> {
 
 /// Collects immediate subdirectories of `parent`, parses their (string) names with `parse`,
 /// and returns them sorted descending by the parsed key.
-async fn collect_dirs_desc<T, F>(parent: &Path, parse: F) -> io::Result<Vec<(T, PathBuf)>>
+pub(super) async fn collect_dirs_desc<T, F>(
+    parent: &Path,
+    parse: F,
+) -> io::Result<Vec<(T, PathBuf)>>
 where
     T: Ord + Copy,
     F: Fn(&str) -> Option<T>,
@@ -319,7 +322,7 @@ where
 }
 
 /// Collects files in a directory and parses them with `parse`.
-async fn collect_files<T, F>(parent: &Path, parse: F) -> io::Result<Vec<T>>
+pub(super) async fn collect_files<T, F>(parent: &Path, parse: F) -> io::Result<Vec<T>>
 where
     F: Fn(&str, &Path) -> Option<T>,
 {
@@ -340,7 +343,7 @@ where
     Ok(collected)
 }
 
-fn parse_timestamp_uuid_from_filename(name: &str) -> Option<(OffsetDateTime, Uuid)> {
+pub(super) fn parse_timestamp_uuid_from_filename(name: &str) -> Option<(OffsetDateTime, Uuid)> {
     // Expected: rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl
     let core = name.strip_prefix("rollout-")?.strip_suffix(".jsonl")?;
 
@@ -452,7 +455,7 @@ pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Va
     Ok(summary.head)
 }
 
-async fn file_modified_rfc3339(path: &Path) -> io::Result<Option<String>> {
+pub(super) async fn file_modified_rfc3339(path: &Path) -> io::Result<Option<String>> {
     let meta = tokio::fs::metadata(path).await?;
     let modified = meta.modified().ok();
     let Some(modified) = modified else {
@@ -8,9 +8,11 @@ pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
     &[SessionSource::Cli, SessionSource::VSCode];
 
 pub(crate) mod error;
+pub mod export;
 pub mod list;
 pub(crate) mod policy;
 pub mod recorder;
+pub mod search;
 pub(crate) mod truncation;
 
 pub use codex_protocol::protocol::SessionMeta;
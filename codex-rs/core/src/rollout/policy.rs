@@ -65,6 +65,9 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::ExecCommandBegin(_)
         | EventMsg::TerminalInteraction(_)
         | EventMsg::ExecCommandOutputDelta(_)
+        // Servers may report progress many times per call; like output
+        // deltas, these updates are only useful while the call is running.
+        | EventMsg::McpToolCallProgress(_)
         | EventMsg::ExecCommandEnd(_)
         | EventMsg::ExecApprovalRequest(_)
         | EventMsg::ElicitationRequest(_)
@@ -79,7 +82,9 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::McpListToolsResponse(_)
         | EventMsg::McpStartupUpdate(_)
         | EventMsg::McpStartupComplete(_)
+        | EventMsg::McpToolsChanged(_)
         | EventMsg::ListCustomPromptsResponse(_)
+        | EventMsg::ListMcpPromptsResponse(_)
         | EventMsg::ListSkillsResponse(_)
         | EventMsg::PlanUpdate(_)
         | EventMsg::ShutdownComplete
@@ -93,3 +98,21 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::SkillsUpdateAvailable => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::McpToolCallProgressEvent;
+
+    #[test]
+    fn mcp_tool_call_progress_is_not_persisted() {
+        let item = RolloutItem::EventMsg(EventMsg::McpToolCallProgress(McpToolCallProgressEvent {
+            call_id: "call-1".to_string(),
+            progress: Some(1.0),
+            total: Some(2.0),
+            message: Some("halfway".to_string()),
+        }));
+
+        assert!(!is_persisted_response_item(&item));
+    }
+}
@@ -0,0 +1,661 @@
+//! Full-text search across recorded session rollouts.
+//!
+//! Unlike [`super::list`], which only reads the head of each rollout, search
+//! scans whole files and extracts the parts a user is likely to remember: what
+//! they asked, what the agent answered, commands that ran and files that were
+//! patched. Extraction results can optionally be cached in an index file under
+//! `CODEX_HOME` so repeated searches only re-read rollouts that changed.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use codex_protocol::ThreadId;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionSource;
+use serde::Deserialize;
+use serde::Serialize;
+use tempfile::NamedTempFile;
+use tokio::io::AsyncBufReadExt;
+
+use super::SESSIONS_SUBDIR;
+use super::list::MAX_SCAN_FILES;
+use super::list::collect_dirs_desc;
+use super::list::collect_files;
+use super::list::file_modified_rfc3339;
+use super::list::parse_timestamp_uuid_from_filename;
+use crate::protocol::EventMsg;
+
+/// File name (relative to `CODEX_HOME`) of the optional search index.
+pub const SESSION_SEARCH_INDEX_FILE: &str = "session_search_index.jsonl";
+
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_MATCHES_PER_THREAD: usize = 3;
+/// Characters of context kept on each side of the first hit in a snippet.
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+const APPLY_PATCH_FILE_MARKERS: &[&str] = &[
+    "*** Add File: ",
+    "*** Update File: ",
+    "*** Delete File: ",
+    "*** Move to: ",
+];
+
+/// Which part of a rollout a search match came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchMatchKind {
+    UserMessage,
+    AgentMessage,
+    Command,
+    File,
+}
+
+/// A single match within a thread, with surrounding context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub kind: SearchMatchKind,
+    /// Single-line excerpt around the first matched term.
+    pub snippet: String,
+}
+
+/// A thread whose rollout matched every term of the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadSearchHit {
+    /// Absolute path to the rollout file.
+    pub path: PathBuf,
+    pub thread_id: Option<ThreadId>,
+    /// RFC3339 timestamp string for when the session was created, if available.
+    pub created_at: Option<String>,
+    /// RFC3339 timestamp string for the most recent update (from file mtime).
+    pub updated_at: Option<String>,
+    /// First user message of the thread, if any.
+    pub preview: Option<String>,
+    /// Up to `MAX_MATCHES_PER_THREAD` matches, in rollout order.
+    pub matches: Vec<SearchMatch>,
+}
+
+/// Result of [`search_threads`].
+#[derive(Debug, Default, PartialEq)]
+pub struct ThreadSearchResults {
+    /// Matching threads ordered newest first.
+    pub hits: Vec<ThreadSearchHit>,
+    /// Total number of rollout files considered.
+    pub num_scanned_files: usize,
+    /// True if the scan stopped early because the hard scan cap was hit.
+    pub reached_scan_cap: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ThreadSearchOptions<'a> {
+    /// Maximum number of threads to return.
+    pub limit: usize,
+    /// When non-empty, only threads recorded from these sources are returned.
+    pub allowed_sources: &'a [SessionSource],
+    /// Read and update the on-disk index at [`SESSION_SEARCH_INDEX_FILE`].
+    pub use_index: bool,
+}
+
+impl Default for ThreadSearchOptions<'_> {
+    fn default() -> Self {
+        Self {
+            limit: DEFAULT_SEARCH_LIMIT,
+            allowed_sources: &[],
+            use_index: false,
+        }
+    }
+}
+
+/// Searchable text extracted from one rollout file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct RolloutDocument {
+    thread_id: Option<ThreadId>,
+    created_at: Option<String>,
+    source: Option<SessionSource>,
+    preview: Option<String>,
+    entries: Vec<DocumentEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct DocumentEntry {
+    kind: SearchMatchKind,
+    text: String,
+}
+
+/// One line of the index file. `len` and `modified_ms` detect stale entries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedRollout {
+    path: PathBuf,
+    len: u64,
+    modified_ms: u64,
+    document: RolloutDocument,
+}
+
+/// Search all rollouts under `codex_home` for threads containing every
+/// whitespace-separated term of `query` (case-insensitive). Terms may match
+/// different messages, commands or files of the same thread.
+pub async fn search_threads(
+    codex_home: &Path,
+    query: &str,
+    options: ThreadSearchOptions<'_>,
+) -> io::Result<ThreadSearchResults> {
+    let terms: Vec<Vec<char>> = query
+        .split_whitespace()
+        .map(fold_case)
+        .filter(|term| !term.is_empty())
+        .collect();
+    let root = codex_home.join(SESSIONS_SUBDIR);
+    if terms.is_empty() || options.limit == 0 || !root.exists() {
+        return Ok(ThreadSearchResults::default());
+    }
+
+    let index_path = codex_home.join(SESSION_SEARCH_INDEX_FILE);
+    let mut index = if options.use_index {
+        load_index(&index_path).await
+    } else {
+        HashMap::new()
+    };
+    let mut index_dirty = false;
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+
+    let mut results = ThreadSearchResults::default();
+    let mut scan_complete = true;
+
+    let year_dirs = collect_dirs_desc(&root, |s| s.parse::<u16>().ok()).await?;
+    'outer: for (_year, year_path) in year_dirs.iter() {
+        let month_dirs = collect_dirs_desc(year_path, |s| s.parse::<u8>().ok()).await?;
+        for (_month, month_path) in month_dirs.iter() {
+            let day_dirs = collect_dirs_desc(month_path, |s| s.parse::<u8>().ok()).await?;
+            for (_day, day_path) in day_dirs.iter() {
+                let mut day_files = collect_files(day_path, |name_str, path| {
+                    parse_timestamp_uuid_from_filename(name_str)
+                        .map(|(ts, id)| (ts, id, path.to_path_buf()))
+                })
+                .await?;
+                day_files.sort_by_key(|(ts, id, _path)| (Reverse(*ts), Reverse(*id)));
+
+                for (_ts, _id, path) in day_files {
+                    if results.hits.len() >= options.limit {
+                        scan_complete = false;
+                        break 'outer;
+                    }
+                    if results.num_scanned_files >= MAX_SCAN_FILES {
+                        results.reached_scan_cap = true;
+                        scan_complete = false;
+                        break 'outer;
+                    }
+                    results.num_scanned_files += 1;
+
+                    let Ok(metadata) = tokio::fs::metadata(&path).await else {
+                        continue;
+                    };
+                    let len = metadata.len();
+                    let modified_ms = metadata
+                        .modified()
+                        .ok()
+                        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                        .map(|duration| duration.as_millis() as u64)
+                        .unwrap_or_default();
+
+                    let document = match index.get(&path) {
+                        Some(cached) if cached.len == len && cached.modified_ms == modified_ms => {
+                            cached.document.clone()
+                        }
+                        _ => {
+                            let Ok(document) = read_rollout_document(&path).await else {
+                                continue;
+                            };
+                            if options.use_index {
+                                index.insert(
+                                    path.clone(),
+                                    IndexedRollout {
+                                        path: path.clone(),
+                                        len,
+                                        modified_ms,
+                                        document: document.clone(),
+                                    },
+                                );
+                                index_dirty = true;
+                            }
+                            document
+                        }
+                    };
+                    if options.use_index {
+                        visited.insert(path.clone());
+                    }
+
+                    if !options.allowed_sources.is_empty()
+                        && !document
+                            .source
+                            .as_ref()
+                            .is_some_and(|source| options.allowed_sources.contains(source))
+                    {
+                        continue;
+                    }
+
+                    let Some(matches) = match_document(&document, &terms) else {
+                        continue;
+                    };
+                    let updated_at = file_modified_rfc3339(&path)
+                        .await
+                        .unwrap_or(None)
+                        .or_else(|| document.created_at.clone());
+                    results.hits.push(ThreadSearchHit {
+                        path,
+                        thread_id: document.thread_id,
+                        created_at: document.created_at,
+                        updated_at,
+                        preview: document.preview,
+                        matches,
+                    });
+                }
+            }
+        }
+    }
+
+    if options.use_index {
+        // Only prune entries for deleted rollouts when every file was visited;
+        // an early stop says nothing about the files we did not reach.
+        if scan_complete {
+            let before = index.len();
+            index.retain(|path, _| visited.contains(path));
+            index_dirty |= index.len() != before;
+        }
+        if index_dirty && let Err(err) = write_index(&index_path, &index).await {
+            tracing::warn!("failed to write session search index: {err}");
+        }
+    }
+
+    Ok(results)
+}
+
+/// Parse a rollout file into the searchable parts of the conversation.
+async fn read_rollout_document(path: &Path) -> io::Result<RolloutDocument> {
+    let file = tokio::fs::File::open(path).await?;
+    let mut lines = tokio::io::BufReader::new(file).lines();
+    let mut document = RolloutDocument::default();
+    let mut seen: HashSet<DocumentEntry> = HashSet::new();
+
+    while let Some(line) = lines.next_line().await? {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let Ok(rollout_line) = serde_json::from_str::<RolloutLine>(trimmed) else {
+            continue;
+        };
+
+        let mut push = |kind: SearchMatchKind, text: String| {
+            let text = text.trim().to_string();
+            if text.is_empty() {
+                return;
+            }
+            let entry = DocumentEntry { kind, text };
+            if seen.insert(entry.clone()) {
+                document.entries.push(entry);
+            }
+        };
+
+        match rollout_line.item {
+            RolloutItem::SessionMeta(session_meta_line) => {
+                let meta = session_meta_line.meta;
+                document.thread_id.get_or_insert(meta.id);
+                document.source.get_or_insert(meta.source);
+                if document.created_at.is_none() && !meta.timestamp.is_empty() {
+                    document.created_at = Some(meta.timestamp);
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                if document.preview.is_none() && !event.message.trim().is_empty() {
+                    document.preview = Some(event.message.trim().to_string());
+                }
+                push(SearchMatchKind::UserMessage, event.message);
+            }
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                push(SearchMatchKind::AgentMessage, event.message);
+            }
+            RolloutItem::ResponseItem(ResponseItem::LocalShellCall {
+                action: LocalShellAction::Exec(exec),
+                ..
+            }) => {
+                push(SearchMatchKind::Command, exec.command.join(" "));
+            }
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                name, arguments, ..
+            }) => {
+                let Ok(args) = serde_json::from_str::<serde_json::Value>(&arguments) else {
+                    continue;
+                };
+                if name == "apply_patch" {
+                    if let Some(input) = args.get("input").and_then(|v| v.as_str()) {
+                        for file in patch_file_paths(input) {
+                            push(SearchMatchKind::File, file);
+                        }
+                    }
+                } else if let Some(command) = command_from_arguments(&args) {
+                    push(SearchMatchKind::Command, command);
+                }
+            }
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall { name, input, .. })
+                if name == "apply_patch" =>
+            {
+                for file in patch_file_paths(&input) {
+                    push(SearchMatchKind::File, file);
+                }
+            }
+            RolloutItem::ResponseItem(_)
+            | RolloutItem::EventMsg(_)
+            | RolloutItem::TurnContext(_)
+            | RolloutItem::Compacted(_) => {}
+        }
+    }
+
+    Ok(document)
+}
+
+/// Extract a shell command from tool call arguments (`shell`, `shell_command`
+/// and `exec_command` all use slightly different shapes).
+fn command_from_arguments(args: &serde_json::Value) -> Option<String> {
+    let command = args.get("command").or_else(|| args.get("cmd"))?;
+    match command {
+        serde_json::Value::String(command) => Some(command.clone()),
+        serde_json::Value::Array(parts) => {
+            let parts: Vec<&str> = parts.iter().filter_map(|part| part.as_str()).collect();
+            (!parts.is_empty()).then(|| parts.join(" "))
+        }
+        _ => None,
+    }
+}
+
+fn patch_file_paths(patch: &str) -> Vec<String> {
+    patch
+        .lines()
+        .filter_map(|line| {
+            APPLY_PATCH_FILE_MARKERS
+                .iter()
+                .find_map(|marker| line.strip_prefix(marker))
+        })
+        .map(|path| path.trim().to_string())
+        .collect()
+}
+
+/// Returns snippets for `document` if every term occurs somewhere in it.
+fn match_document(document: &RolloutDocument, terms: &[Vec<char>]) -> Option<Vec<SearchMatch>> {
+    let mut found = vec![false; terms.len()];
+    let mut matches = Vec::new();
+
+    for entry in &document.entries {
+        let folded = fold_case(&entry.text);
+        let mut first_hit: Option<(usize, usize)> = None;
+        for (term, found) in terms.iter().zip(found.iter_mut()) {
+            if let Some(position) = find_chars(&folded, term) {
+                *found = true;
+                if first_hit.is_none_or(|(start, _)| position < start) {
+                    first_hit = Some((position, term.len()));
+                }
+            }
+        }
+        if let Some((start, len)) = first_hit
+            && matches.len() < MAX_MATCHES_PER_THREAD
+        {
+            matches.push(SearchMatch {
+                kind: entry.kind,
+                snippet: snippet(&entry.text, start, len),
+            });
+        }
+    }
+
+    found.iter().all(|found| *found).then_some(matches)
+}
+
+/// Lowercase `text` one char at a time so char offsets line up with the input.
+fn fold_case(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+fn find_chars(haystack: &[char], needle: &[char]) -> Option<usize> {
+    if needle.is_empty() || needle.len() > haystack.len() {
+        return None;
+    }
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Build a single-line excerpt of `text` around the char range
+/// `start..start + len`, marking truncation with ellipses.
+fn snippet(text: &str, start: usize, len: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let from = start.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let to = (start + len + SNIPPET_CONTEXT_CHARS).min(chars.len());
+    let excerpt: String = chars[from..to].iter().collect();
+    let excerpt = excerpt.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut out = String::new();
+    if from > 0 {
+        out.push('…');
+    }
+    out.push_str(&excerpt);
+    if to < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+async fn load_index(path: &Path) -> HashMap<PathBuf, IndexedRollout> {
+    let Ok(contents) = tokio::fs::read_to_string(path).await else {
+        return HashMap::new();
+    };
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<IndexedRollout>(line).ok())
+        .map(|entry| (entry.path.clone(), entry))
+        .collect()
+}
+
+async fn write_index(path: &Path, index: &HashMap<PathBuf, IndexedRollout>) -> io::Result<()> {
+    let mut entries: Vec<&IndexedRollout> = index.values().collect();
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let mut contents = String::new();
+    for entry in entries {
+        contents.push_str(&serde_json::to_string(entry).map_err(io::Error::other)?);
+        contents.push('\n');
+    }
+
+    // Write to a uniquely named sibling file first so concurrent readers never
+    // see a partially written index and concurrent writers never share a
+    // temporary file.
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut tmp = NamedTempFile::new_in(dir)?;
+        tmp.write_all(contents.as_bytes())?;
+        tmp.persist(&path).map_err(|err| err.error)?;
+        Ok(())
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    const THREAD_A: &str = "00000000-0000-0000-0000-00000000000a";
+    const THREAD_B: &str = "00000000-0000-0000-0000-00000000000b";
+
+    fn write_rollout(codex_home: &Path, ts: &str, id: &str, items: &[serde_json::Value]) {
+        let day_dir = codex_home.join(SESSIONS_SUBDIR).join("2025/01/02");
+        std::fs::create_dir_all(&day_dir).unwrap();
+        let mut lines = vec![
+            json!({
+                "timestamp": "2025-01-02T12:00:00Z",
+                "type": "session_meta",
+                "payload": {
+                    "id": id,
+                    "timestamp": "2025-01-02T12:00:00Z",
+                    "cwd": "/repo",
+                    "originator": "codex",
+                    "cli_version": "0.0.0",
+                    "instructions": null,
+                    "source": "cli",
+                    "model_provider": "openai",
+                },
+            })
+            .to_string(),
+        ];
+        for item in items {
+            lines.push(item.to_string());
+        }
+        std::fs::write(
+            day_dir.join(format!("rollout-{ts}-{id}.jsonl")),
+            lines.join("\n") + "\n",
+        )
+        .unwrap();
+    }
+
+    fn event(payload: serde_json::Value) -> serde_json::Value {
+        json!({"timestamp": "2025-01-02T12:00:01Z", "type": "event_msg", "payload": payload})
+    }
+
+    fn response_item(payload: serde_json::Value) -> serde_json::Value {
+        json!({"timestamp": "2025-01-02T12:00:01Z", "type": "response_item", "payload": payload})
+    }
+
+    fn seed(codex_home: &Path) {
+        write_rollout(
+            codex_home,
+            "2025-01-02T12-00-00",
+            THREAD_A,
+            &[
+                event(json!({"type": "user_message", "message": "Fix the flaky migration test"})),
+                response_item(json!({
+                    "type": "function_call",
+                    "name": "shell",
+                    "arguments": "{\"command\":[\"cargo\",\"test\",\"-p\",\"migrations\"]}",
+                    "call_id": "call-1",
+                })),
+                response_item(json!({
+                    "type": "custom_tool_call",
+                    "name": "apply_patch",
+                    "input": "*** Begin Patch\n*** Update File: db/migrate.rs\n@@\n-a\n+b\n*** End Patch",
+                    "call_id": "call-2",
+                })),
+                event(
+                    json!({"type": "agent_message", "message": "The migration test now waits for the lock."}),
+                ),
+            ],
+        );
+        write_rollout(
+            codex_home,
+            "2025-01-02T13-00-00",
+            THREAD_B,
+            &[event(
+                json!({"type": "user_message", "message": "Write a README for the CLI"}),
+            )],
+        );
+    }
+
+    #[tokio::test]
+    async fn finds_threads_matching_all_terms_with_snippets() {
+        let home = TempDir::new().unwrap();
+        seed(home.path());
+
+        let results = search_threads(home.path(), "FLAKY migrate.rs", Default::default())
+            .await
+            .unwrap();
+
+        assert_eq!(results.num_scanned_files, 2);
+        assert_eq!(results.hits.len(), 1);
+        let hit = &results.hits[0];
+        assert_eq!(
+            hit.thread_id,
+            Some(ThreadId::from_string(THREAD_A).unwrap())
+        );
+        assert_eq!(hit.preview.as_deref(), Some("Fix the flaky migration test"));
+        assert_eq!(
+            hit.matches,
+            vec![
+                SearchMatch {
+                    kind: SearchMatchKind::UserMessage,
+                    snippet: "Fix the flaky migration test".to_string(),
+                },
+                SearchMatch {
+                    kind: SearchMatchKind::File,
+                    snippet: "db/migrate.rs".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn matches_commands_and_orders_newest_first() {
+        let home = TempDir::new().unwrap();
+        seed(home.path());
+
+        let results = search_threads(home.path(), "cargo migrations", Default::default())
+            .await
+            .unwrap();
+        assert_eq!(results.hits.len(), 1);
+        assert_eq!(results.hits[0].matches[0].kind, SearchMatchKind::Command);
+        assert_eq!(
+            results.hits[0].matches[0].snippet,
+            "cargo test -p migrations"
+        );
+
+        let results = search_threads(home.path(), "the", Default::default())
+            .await
+            .unwrap();
+        let ids: Vec<String> = results
+            .hits
+            .iter()
+            .filter_map(|hit| hit.thread_id.map(|id| id.to_string()))
+            .collect();
+        assert_eq!(ids, vec![THREAD_B.to_string(), THREAD_A.to_string()]);
+    }
+
+    #[tokio::test]
+    async fn index_is_written_and_reused() {
+        let home = TempDir::new().unwrap();
+        seed(home.path());
+        let options = ThreadSearchOptions {
+            use_index: true,
+            ..Default::default()
+        };
+
+        let first = search_threads(home.path(), "readme", options.clone())
+            .await
+            .unwrap();
+        let index = std::fs::read_to_string(home.path().join(SESSION_SEARCH_INDEX_FILE)).unwrap();
+        assert_eq!(index.lines().count(), 2);
+
+        let second = search_threads(home.path(), "readme", options)
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(second.hits.len(), 1);
+    }
+
+    #[test]
+    fn snippet_truncates_long_text_around_hit() {
+        let text = format!("{}needle{}", "a ".repeat(100), " b".repeat(100));
+        let start = text.find("needle").unwrap();
+        let out = snippet(&text, start, "needle".len());
+        assert!(out.starts_with('…'));
+        assert!(out.ends_with('…'));
+        assert!(out.contains("needle"));
+    }
+}
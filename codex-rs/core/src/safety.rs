@@ -153,7 +153,9 @@ fn is_write_patch_constrained_to_writable_paths(
         SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
             return true;
         }
-        SandboxPolicy::WorkspaceWrite { .. } => sandbox_policy.get_writable_roots_with_cwd(cwd),
+        SandboxPolicy::WorkspaceWrite { .. } | SandboxPolicy::RestrictedRead { .. } => {
+            sandbox_policy.get_writable_roots_with_cwd(cwd)
+        }
     };
 
     // Normalize a path by removing `.` and resolving `..` without touching the
@@ -16,11 +16,12 @@ use crate::protocol::SandboxPolicy;
 #[cfg(target_os = "macos")]
 use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
 #[cfg(target_os = "macos")]
-use crate::seatbelt::create_seatbelt_command_args;
+use crate::seatbelt::create_seatbelt_command_args_with_network_proxy;
 #[cfg(target_os = "macos")]
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
 use crate::tools::sandboxing::SandboxablePreference;
+use codex_network_proxy::NetworkProxy;
 pub use codex_protocol::models::SandboxPermissions;
 use std::collections::HashMap;
 use std::path::Path;
@@ -100,6 +101,7 @@ impl SandboxManager {
         sandbox: SandboxType,
         sandbox_policy_cwd: &Path,
         codex_linux_sandbox_exe: Option<&PathBuf>,
+        network_proxy: Option<&NetworkProxy>,
     ) -> Result<ExecEnv, SandboxTransformError> {
         let mut env = spec.env;
         if !policy.has_full_network_access() {
@@ -109,6 +111,14 @@ impl SandboxManager {
             );
         }
 
+        // The filtering proxy only matters when a sandbox blocks direct
+        // network access; unsandboxed commands already reach the network.
+        let network_proxy = network_proxy
+            .filter(|_| sandbox != SandboxType::None && !policy.has_full_network_access());
+        if let Some(proxy) = network_proxy {
+            env.extend(codex_network_proxy::proxy_env_vars(proxy.addr()));
+        }
+
         let mut command = Vec::with_capacity(1 + spec.args.len());
         command.push(spec.program);
         command.append(&mut spec.args);
@@ -119,8 +129,12 @@ impl SandboxManager {
             SandboxType::MacosSeatbelt => {
                 let mut seatbelt_env = HashMap::new();
                 seatbelt_env.insert(CODEX_SANDBOX_ENV_VAR.to_string(), "seatbelt".to_string());
-                let mut args =
-                    create_seatbelt_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_seatbelt_command_args_with_network_proxy(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    network_proxy.map(|proxy| proxy.addr().port()),
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(MACOS_PATH_TO_SEATBELT_EXECUTABLE.to_string());
                 full_command.append(&mut args);
@@ -131,8 +145,13 @@ impl SandboxManager {
             SandboxType::LinuxSeccomp => {
                 let exe = codex_linux_sandbox_exe
                     .ok_or(SandboxTransformError::MissingLinuxSandboxExecutable)?;
-                let mut args =
-                    create_linux_sandbox_command_args(command.clone(), policy, sandbox_policy_cwd);
+                let mut args = create_linux_sandbox_command_args(
+                    command.clone(),
+                    policy,
+                    sandbox_policy_cwd,
+                    network_proxy
+                        .and_then(|proxy| Some((proxy.addr().port(), proxy.unix_socket_path()?))),
+                );
                 let mut full_command = Vec::with_capacity(1 + args.len());
                 full_command.push(exe.to_string_lossy().to_string());
                 full_command.append(&mut args);
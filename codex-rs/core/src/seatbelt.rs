@@ -20,6 +20,33 @@ const MACOS_SEATBELT_NETWORK_POLICY: &str = include_str!("seatbelt_network_polic
 /// already has root access.
 pub(crate) const MACOS_PATH_TO_SEATBELT_EXECUTABLE: &str = "/usr/bin/sandbox-exec";
 
+/// System paths that stay readable under a restricted-read policy so that
+/// dyld, shells, and common tools keep working. Mirrors the Linux sandbox's
+/// list: user data and credential stores are deliberately left out.
+const MACOS_SYSTEM_READABLE_ROOTS: &[&str] = &[
+    "/bin",
+    "/sbin",
+    "/usr",
+    "/System",
+    "/Library/Apple",
+    "/private/var/db/dyld",
+    "/private/var/db/timezone",
+    "/dev/null",
+    "/dev/zero",
+    "/dev/random",
+    "/dev/urandom",
+    "/dev/tty",
+    "/private/etc/bashrc",
+    "/private/etc/group",
+    "/private/etc/hosts",
+    "/private/etc/localtime",
+    "/private/etc/passwd",
+    "/private/etc/profile",
+    "/private/var/run/resolv.conf",
+    "/private/etc/ssl",
+    "/private/etc/zshrc",
+];
+
 pub async fn spawn_command_under_seatbelt(
     command: Vec<String>,
     command_cwd: PathBuf,
@@ -47,6 +74,24 @@ pub(crate) fn create_seatbelt_command_args(
     command: Vec<String>,
     sandbox_policy: &SandboxPolicy,
     sandbox_policy_cwd: &Path,
+) -> Vec<String> {
+    create_seatbelt_command_args_with_network_proxy(
+        command,
+        sandbox_policy,
+        sandbox_policy_cwd,
+        None,
+    )
+}
+
+/// Like [`create_seatbelt_command_args`], but when the policy blocks network
+/// access and `network_proxy_port` is set, outbound connections to that
+/// loopback port are still allowed so the command can use the filtering
+/// proxy.
+pub(crate) fn create_seatbelt_command_args_with_network_proxy(
+    command: Vec<String>,
+    sandbox_policy: &SandboxPolicy,
+    sandbox_policy_cwd: &Path,
+    network_proxy_port: Option<u16>,
 ) -> Vec<String> {
     let (file_write_policy, file_write_dir_params) = {
         if sandbox_policy.has_full_disk_write_access() {
@@ -105,24 +150,61 @@ pub(crate) fn create_seatbelt_command_args(
         }
     };
 
-    let file_read_policy = if sandbox_policy.has_full_disk_read_access() {
-        "; allow read-only file operations\n(allow file-read*)"
+    let (file_read_policy, file_read_dir_params) = if sandbox_policy.has_full_disk_read_access() {
+        (
+            "; allow read-only file operations\n(allow file-read*)".to_string(),
+            Vec::new(),
+        )
+    } else if let Some(readable_roots) =
+        sandbox_policy.get_readable_roots_with_cwd(sandbox_policy_cwd)
+    {
+        let mut read_policies: Vec<String> = MACOS_SYSTEM_READABLE_ROOTS
+            .iter()
+            .map(|path| format!("(subpath \"{path}\")"))
+            .collect();
+        let mut file_read_params = Vec::new();
+        for (index, root) in readable_roots.iter().enumerate() {
+            // Canonicalize to avoid mismatches like /var vs /private/var on macOS.
+            let canonical_root = root
+                .as_path()
+                .canonicalize()
+                .unwrap_or_else(|_| root.to_path_buf());
+            let root_param = format!("READABLE_ROOT_{index}");
+            read_policies.push(format!("(subpath (param \"{root_param}\"))"));
+            file_read_params.push((root_param, canonical_root));
+        }
+        (
+            format!(
+                "; allow metadata lookups for path traversal, and reads of the readable roots\n(allow file-read-metadata)\n(allow file-read*\n{}\n)",
+                read_policies.join(" ")
+            ),
+            file_read_params,
+        )
     } else {
-        ""
+        (String::new(), Vec::new())
     };
 
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
-        MACOS_SEATBELT_NETWORK_POLICY
+        MACOS_SEATBELT_NETWORK_POLICY.to_string()
+    } else if let Some(port) = network_proxy_port {
+        format!(
+            "; allow connecting to the local network proxy only\n(allow system-socket)\n(allow network-outbound (remote ip \"localhost:{port}\"))"
+        )
     } else {
-        ""
+        String::new()
     };
 
     let full_policy = format!(
         "{MACOS_SEATBELT_BASE_POLICY}\n{file_read_policy}\n{file_write_policy}\n{network_policy}"
     );
 
-    let dir_params = [file_write_dir_params, macos_dir_params()].concat();
+    let dir_params = [
+        file_read_dir_params,
+        file_write_dir_params,
+        macos_dir_params(),
+    ]
+    .concat();
 
     let mut seatbelt_args: Vec<String> = vec!["-p".to_string(), full_policy];
     let definition_args = dir_params
@@ -164,6 +246,7 @@ fn macos_dir_params() -> Vec<(String, PathBuf)> {
 mod tests {
     use super::MACOS_SEATBELT_BASE_POLICY;
     use super::create_seatbelt_command_args;
+    use super::create_seatbelt_command_args_with_network_proxy;
     use super::macos_dir_params;
     use crate::protocol::SandboxPolicy;
     use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
@@ -174,6 +257,47 @@ mod tests {
     use std::process::Command;
     use tempfile::TempDir;
 
+    #[test]
+    fn network_proxy_port_only_allows_loopback_connections_to_proxy() {
+        let cwd = std::env::temp_dir();
+        let policy = SandboxPolicy::new_workspace_write_policy();
+        let command = vec!["true".to_string()];
+
+        let without_proxy = create_seatbelt_command_args(command.clone(), &policy, &cwd);
+        assert!(!without_proxy[1].contains("network-outbound"));
+
+        let with_proxy =
+            create_seatbelt_command_args_with_network_proxy(command, &policy, &cwd, Some(3128));
+        assert!(with_proxy[1].contains(r#"(allow network-outbound (remote ip "localhost:3128"))"#));
+    }
+
+    #[test]
+    fn restricted_read_policy_limits_reads_to_readable_roots() {
+        let tmp = TempDir::new().expect("tempdir");
+        let docs = tmp.path().join("docs");
+        fs::create_dir_all(&docs).expect("create docs");
+        let cwd = tmp.path().join("cwd");
+        fs::create_dir_all(&cwd).expect("create cwd");
+        let policy = SandboxPolicy::RestrictedRead {
+            readable_roots: vec![docs.as_path().try_into().expect("absolute docs")],
+            writable_roots: Vec::new(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        let args = create_seatbelt_command_args(vec!["true".to_string()], &policy, &cwd);
+        let full_policy = &args[1];
+        assert!(!full_policy.contains("(allow file-read*)"));
+        assert!(full_policy.contains(r#"(subpath (param "READABLE_ROOT_0"))"#));
+        assert!(full_policy.contains(r#"(subpath "/usr")"#));
+        let docs_param = format!(
+            "-DREADABLE_ROOT_0={}",
+            docs.canonicalize().expect("canonical docs").display()
+        );
+        assert!(args.contains(&docs_param), "{args:?}");
+    }
+
     #[test]
     fn create_seatbelt_args_with_read_only_git_and_codex_subpaths() {
         // Create a temporary workspace with two writable roots: one containing
@@ -1,15 +1,21 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::AuthManager;
 use crate::RolloutRecorder;
 use crate::agent::AgentControl;
+use crate::code_search::CodeSearchManager;
+use crate::config::types::BudgetConfig;
+use crate::config::types::CompactionConfig;
 use crate::exec_policy::ExecPolicyManager;
+use crate::hooks::Hooks;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::models_manager::manager::ModelsManager;
 use crate::skills::SkillsManager;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::user_notification::UserNotifier;
+use codex_network_proxy::NetworkProxy;
 use codex_otel::OtelManager;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
@@ -20,6 +26,9 @@ pub(crate) struct SessionServices {
     pub(crate) mcp_startup_cancellation_token: Mutex<CancellationToken>,
     pub(crate) unified_exec_manager: UnifiedExecProcessManager,
     pub(crate) notifier: UserNotifier,
+    pub(crate) hooks: Hooks,
+    pub(crate) budget: BudgetConfig,
+    pub(crate) compaction: CompactionConfig,
     pub(crate) rollout: Mutex<Option<RolloutRecorder>>,
     pub(crate) user_shell: Arc<crate::shell::Shell>,
     pub(crate) show_raw_agent_reasoning: bool,
@@ -30,4 +39,10 @@ pub(crate) struct SessionServices {
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
     pub(crate) skills_manager: Arc<SkillsManager>,
     pub(crate) agent_control: AgentControl,
+    /// Filtering proxy for `network_allow` domains; `None` when no allowlist
+    /// is configured.
+    pub(crate) network_proxy: Option<NetworkProxy>,
+    pub(crate) code_search: CodeSearchManager,
+    /// `$CODEX_HOME`, used to locate the project memory store.
+    pub(crate) codex_home: PathBuf,
 }
@@ -2,6 +2,7 @@
 
 use codex_protocol::models::ResponseItem;
 
+use crate::budget::SessionBudget;
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
 use crate::protocol::RateLimitSnapshot;
@@ -14,6 +15,7 @@ pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    pub(crate) budget: SessionBudget,
 }
 
 impl SessionState {
@@ -24,6 +26,7 @@ impl SessionState {
             session_configuration,
             history,
             latest_rate_limits: None,
+            budget: SessionBudget::default(),
         }
     }
 
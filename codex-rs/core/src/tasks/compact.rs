@@ -7,6 +7,7 @@ use crate::state::TaskKind;
 use async_trait::async_trait;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 #[derive(Clone, Copy, Default)]
 pub(crate) struct CompactTask;
@@ -25,23 +26,28 @@ impl SessionTask for CompactTask {
         _cancellation_token: CancellationToken,
     ) -> Option<String> {
         let session = session.clone_session();
-        if crate::compact::should_use_remote_compact_task(
+        let remote = crate::compact::should_use_remote_compact_task(
             session.as_ref(),
             &ctx.client.get_provider(),
-        ) {
-            let _ = session.services.otel_manager.counter(
-                "codex.task.compact",
-                1,
-                &[("type", "remote")],
-            );
-            crate::compact_remote::run_remote_compact_task(session, ctx).await
+        );
+        let mode = if remote { "remote" } else { "local" };
+        let _ = session
+            .services
+            .otel_manager
+            .counter("codex.task.compact", 1, &[("type", mode)]);
+        let span = ctx.client.get_otel_manager().compaction_span(
+            &ctx.sub_id,
+            mode,
+            session.services.compaction.strategy.as_str(),
+        );
+        if remote {
+            crate::compact_remote::run_remote_compact_task(session, ctx)
+                .instrument(span)
+                .await
         } else {
-            let _ = session.services.otel_manager.counter(
-                "codex.task.compact",
-                1,
-                &[("type", "local")],
-            );
-            crate::compact::run_compact_task(session, ctx, input).await
+            crate::compact::run_compact_task(session, ctx, input)
+                .instrument(span)
+                .await
         }
 
         None
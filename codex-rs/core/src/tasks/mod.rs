@@ -13,6 +13,7 @@ use tokio::select;
 use tokio::sync::Notify;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
+use tracing::Instrument;
 use tracing::trace;
 use tracing::warn;
 
@@ -27,6 +28,7 @@ use crate::protocol::TurnCompleteEvent;
 use crate::state::ActiveTurn;
 use crate::state::RunningTask;
 use crate::state::TaskKind;
+use codex_otel::OtelManager;
 use codex_protocol::user_input::UserInput;
 
 pub(crate) use compact::CompactTask;
@@ -37,6 +39,7 @@ pub(crate) use undo::UndoTask;
 pub(crate) use user_shell::UserShellCommandTask;
 
 const GRACEFULL_INTERRUPTION_TIMEOUT_MS: u64 = 100;
+const TURNS_IN_FLIGHT_METRIC: &str = "codex.turns.in_flight";
 
 /// Thin wrapper that exposes the parts of [`Session`] task runners need.
 #[derive(Clone)]
@@ -102,6 +105,27 @@ pub(crate) trait SessionTask: Send + Sync + 'static {
     }
 }
 
+/// Counts a turn in the `codex.turns.in_flight` gauge until dropped, which
+/// also covers tasks that are aborted before they finish.
+struct InFlightTurn {
+    otel_manager: OtelManager,
+    kind: &'static str,
+}
+
+impl InFlightTurn {
+    fn new(otel_manager: OtelManager, kind: &'static str) -> Self {
+        otel_manager.gauge_add(TURNS_IN_FLIGHT_METRIC, 1, &[("kind", kind)]);
+        Self { otel_manager, kind }
+    }
+}
+
+impl Drop for InFlightTurn {
+    fn drop(&mut self) {
+        self.otel_manager
+            .gauge_add(TURNS_IN_FLIGHT_METRIC, -1, &[("kind", self.kind)]);
+    }
+}
+
 impl Session {
     pub async fn spawn_task<T: SessionTask>(
         self: &Arc<Self>,
@@ -118,30 +142,37 @@ impl Session {
         let done = Arc::new(Notify::new());
 
         let done_clone = Arc::clone(&done);
+        let otel_manager = turn_context.client.get_otel_manager();
+        let turn_span = otel_manager.turn_span(&turn_context.sub_id, task_kind.as_str());
+        let in_flight = InFlightTurn::new(otel_manager, task_kind.as_str());
         let handle = {
             let session_ctx = Arc::new(SessionTaskContext::new(Arc::clone(self)));
             let ctx = Arc::clone(&turn_context);
             let task_for_run = Arc::clone(&task);
             let task_cancellation_token = cancellation_token.child_token();
-            tokio::spawn(async move {
-                let ctx_for_finish = Arc::clone(&ctx);
-                let last_agent_message = task_for_run
-                    .run(
-                        Arc::clone(&session_ctx),
-                        ctx,
-                        input,
-                        task_cancellation_token.child_token(),
-                    )
-                    .await;
-                session_ctx.clone_session().flush_rollout().await;
-                if !task_cancellation_token.is_cancelled() {
-                    // Emit completion uniformly from spawn site so all tasks share the same lifecycle.
-                    let sess = session_ctx.clone_session();
-                    sess.on_task_finished(ctx_for_finish, last_agent_message)
+            tokio::spawn(
+                async move {
+                    let _in_flight = in_flight;
+                    let ctx_for_finish = Arc::clone(&ctx);
+                    let last_agent_message = task_for_run
+                        .run(
+                            Arc::clone(&session_ctx),
+                            ctx,
+                            input,
+                            task_cancellation_token.child_token(),
+                        )
                         .await;
+                    session_ctx.clone_session().flush_rollout().await;
+                    if !task_cancellation_token.is_cancelled() {
+                        // Emit completion uniformly from spawn site so all tasks share the same lifecycle.
+                        let sess = session_ctx.clone_session();
+                        sess.on_task_finished(ctx_for_finish, last_agent_message)
+                            .await;
+                    }
+                    done_clone.notify_waiters();
                 }
-                done_clone.notify_waiters();
-            })
+                .instrument(turn_span),
+            )
         };
 
         let running_task = RunningTask {
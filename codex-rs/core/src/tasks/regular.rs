@@ -6,8 +6,6 @@ use crate::state::TaskKind;
 use async_trait::async_trait;
 use codex_protocol::user_input::UserInput;
 use tokio_util::sync::CancellationToken;
-use tracing::Instrument;
-use tracing::trace_span;
 
 use super::SessionTask;
 use super::SessionTaskContext;
@@ -29,12 +27,6 @@ impl SessionTask for RegularTask {
         cancellation_token: CancellationToken,
     ) -> Option<String> {
         let sess = session.clone_session();
-        let run_turn_span = trace_span!("run_turn");
-        sess.services
-            .otel_manager
-            .apply_traceparent_parent(&run_turn_span);
-        run_turn(sess, ctx, input, cancellation_token)
-            .instrument(run_turn_span)
-            .await
+        run_turn(sess, ctx, input, cancellation_token).await
     }
 }
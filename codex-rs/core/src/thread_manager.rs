@@ -18,6 +18,7 @@ use crate::protocol::SessionConfiguredEvent;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::truncation;
 use crate::skills::SkillsManager;
+use codex_otel::metrics::PrometheusRegistry;
 use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::protocol::InitialHistory;
@@ -215,7 +216,7 @@ impl ThreadManager {
     /// as `Arc<CodexThread>`, it is possible that other references to it exist elsewhere.
     /// Returns the thread if the thread was found and removed.
     pub async fn remove_thread(&self, thread_id: &ThreadId) -> Option<Arc<CodexThread>> {
-        self.state.threads.write().await.remove(thread_id)
+        self.state.remove_thread(thread_id).await
     }
 
     /// Fork an existing thread by taking messages up to the given position (not including
@@ -279,7 +280,10 @@ impl ThreadManagerState {
 
     /// Remove a thread from the manager by ID, returning it when present.
     pub(crate) async fn remove_thread(&self, thread_id: &ThreadId) -> Option<Arc<CodexThread>> {
-        self.threads.write().await.remove(thread_id)
+        let mut threads = self.threads.write().await;
+        let removed = threads.remove(thread_id);
+        record_active_threads(threads.len());
+        removed
     }
 
     /// Spawn a new thread with no history using a provided config.
@@ -340,7 +344,10 @@ impl ThreadManagerState {
             codex,
             session_configured.rollout_path.clone(),
         ));
-        self.threads.write().await.insert(thread_id, thread.clone());
+        let mut threads = self.threads.write().await;
+        threads.insert(thread_id, thread.clone());
+        record_active_threads(threads.len());
+        drop(threads);
 
         Ok(NewThread {
             thread_id,
@@ -350,6 +357,13 @@ impl ThreadManagerState {
     }
 }
 
+/// Publish the number of threads held in memory on the Prometheus endpoint.
+fn record_active_threads(count: usize) {
+    if let Some(registry) = PrometheusRegistry::global() {
+        registry.gauge_set("codex.threads.active", count as f64, &[]);
+    }
+}
+
 /// Return a prefix of `items` obtained by cutting strictly before the nth user message
 /// (0-based) and all items that follow it.
 fn truncate_before_nth_user_message(history: InitialHistory, n: usize) -> InitialHistory {
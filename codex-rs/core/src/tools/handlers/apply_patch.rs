@@ -59,6 +59,24 @@ fn to_abs_path(cwd: &Path, path: &Path) -> Option<AbsolutePathBuf> {
     AbsolutePathBuf::resolve_path_against_base(path, cwd).ok()
 }
 
+/// Appends the note about rejected or edited hunks when the user approved
+/// only part of the patch, whether or not the approved hunks applied.
+fn with_hunk_review_feedback(
+    result: Result<String, FunctionCallError>,
+    runtime: &ApplyPatchRuntime,
+) -> Result<String, FunctionCallError> {
+    let Some(feedback) = runtime.hunk_review_feedback() else {
+        return result;
+    };
+    match result {
+        Ok(content) => Ok(format!("{content}\n\n{feedback}")),
+        Err(FunctionCallError::RespondToModel(message)) => Err(FunctionCallError::RespondToModel(
+            format!("{message}\n\n{feedback}"),
+        )),
+        Err(err) => Err(err),
+    }
+}
+
 #[async_trait]
 impl ToolHandler for ApplyPatchHandler {
     fn kind(&self) -> ToolKind {
@@ -117,15 +135,6 @@ impl ToolHandler for ApplyPatchHandler {
                     InternalApplyPatchInvocation::DelegateToExec(apply) => {
                         let changes = convert_apply_patch_to_protocol(&apply.action);
                         let file_paths = file_paths_for_action(&apply.action);
-                        let emitter =
-                            ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
-                        let event_ctx = ToolEventCtx::new(
-                            session.as_ref(),
-                            turn.as_ref(),
-                            &call_id,
-                            Some(&tracker),
-                        );
-                        emitter.begin(event_ctx).await;
 
                         let req = ApplyPatchRequest {
                             action: apply.action,
@@ -137,7 +146,8 @@ impl ToolHandler for ApplyPatchHandler {
                         };
 
                         let mut orchestrator = ToolOrchestrator::new();
-                        let mut runtime = ApplyPatchRuntime::new();
+                        let mut runtime =
+                            ApplyPatchRuntime::new(apply.auto_approved, Some(tracker.clone()));
                         let tool_ctx = ToolCtx {
                             session: session.as_ref(),
                             turn: turn.as_ref(),
@@ -147,13 +157,21 @@ impl ToolHandler for ApplyPatchHandler {
                         let out = orchestrator
                             .run(&mut runtime, &req, &tool_ctx, &turn, turn.approval_policy)
                             .await;
+                        // Report only the hunks the user approved.
+                        let emitter = ToolEmitter::apply_patch(
+                            runtime.approved_changes(&req).clone(),
+                            apply.auto_approved,
+                        );
                         let event_ctx = ToolEventCtx::new(
                             session.as_ref(),
                             turn.as_ref(),
                             &call_id,
                             Some(&tracker),
                         );
-                        let content = emitter.finish(event_ctx, out).await?;
+                        let content = with_hunk_review_feedback(
+                            emitter.finish(event_ctx, out).await,
+                            &runtime,
+                        )?;
                         Ok(ToolOutput::Function {
                             content,
                             content_items: None,
@@ -213,10 +231,6 @@ pub(crate) async fn intercept_apply_patch(
                 InternalApplyPatchInvocation::DelegateToExec(apply) => {
                     let changes = convert_apply_patch_to_protocol(&apply.action);
                     let approval_keys = file_paths_for_action(&apply.action);
-                    let emitter = ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
-                    let event_ctx =
-                        ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
-                    emitter.begin(event_ctx).await;
 
                     let req = ApplyPatchRequest {
                         action: apply.action,
@@ -228,7 +242,7 @@ pub(crate) async fn intercept_apply_patch(
                     };
 
                     let mut orchestrator = ToolOrchestrator::new();
-                    let mut runtime = ApplyPatchRuntime::new();
+                    let mut runtime = ApplyPatchRuntime::new(apply.auto_approved, tracker.cloned());
                     let tool_ctx = ToolCtx {
                         session,
                         turn,
@@ -238,9 +252,15 @@ pub(crate) async fn intercept_apply_patch(
                     let out = orchestrator
                         .run(&mut runtime, &req, &tool_ctx, turn, turn.approval_policy)
                         .await;
+                    // Report only the hunks the user approved.
+                    let emitter = ToolEmitter::apply_patch(
+                        runtime.approved_changes(&req).clone(),
+                        apply.auto_approved,
+                    );
                     let event_ctx =
                         ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
-                    let content = emitter.finish(event_ctx, out).await?;
+                    let content =
+                        with_hunk_review_feedback(emitter.finish(event_ctx, out).await, &runtime)?;
                     Ok(Some(ToolOutput::Function {
                         content,
                         content_items: None,
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::code_search::CodeSearchHit;
+use crate::code_search::CodeSearchKind;
+use crate::code_search::CodeSearchQuery;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_path_readable;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct CodeSearchHandler;
+
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 500;
+
+fn default_limit() -> usize {
+    DEFAULT_LIMIT
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum QueryKind {
+    Definition,
+    References,
+    #[default]
+    Fuzzy,
+}
+
+impl From<QueryKind> for CodeSearchKind {
+    fn from(kind: QueryKind) -> Self {
+        match kind {
+            QueryKind::Definition => CodeSearchKind::Definition,
+            QueryKind::References => CodeSearchKind::References,
+            QueryKind::Fuzzy => CodeSearchKind::Fuzzy,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CodeSearchArgs {
+    query: String,
+    #[serde(default)]
+    kind: QueryKind,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+/// Stops the index walk when the tool call is dropped (e.g. on interrupt).
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+#[async_trait]
+impl ToolHandler for CodeSearchHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "code_search handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: CodeSearchArgs = parse_arguments(&arguments)?;
+
+        let text = args.query.trim().to_string();
+        if text.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+
+        let path_prefix = match args.path.as_deref().map(str::trim) {
+            None | Some("") => {
+                ensure_path_readable(&turn, &turn.cwd)?;
+                None
+            }
+            Some(path) => {
+                let resolved = turn.resolve_path(Some(path.to_string()));
+                ensure_path_readable(&turn, &resolved)?;
+                let relative = resolved.strip_prefix(&turn.cwd).map_err(|_| {
+                    FunctionCallError::RespondToModel(format!(
+                        "path `{}` is outside the workspace `{}`",
+                        resolved.display(),
+                        turn.cwd.display()
+                    ))
+                })?;
+                Some(relative.to_string_lossy().replace('\\', "/"))
+            }
+        };
+
+        let query = CodeSearchQuery {
+            kind: args.kind.into(),
+            text,
+            path_prefix,
+            limit: args.limit.min(MAX_LIMIT),
+        };
+        let cancel = CancelOnDrop(Arc::new(AtomicBool::new(false)));
+        let mut hits = session
+            .services
+            .code_search
+            .search(turn.cwd.clone(), query.clone(), Arc::clone(&cancel.0))
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("code search failed: {err:#}"))
+            })?;
+        // The walk follows symlinks, which may lead outside the readable roots.
+        hits.retain(|hit| ensure_path_readable(&turn, &turn.cwd.join(&hit.path)).is_ok());
+
+        if hits.is_empty() {
+            return Ok(ToolOutput::Function {
+                content: "No matches found.".to_string(),
+                content_items: None,
+                success: Some(false),
+            });
+        }
+
+        Ok(ToolOutput::Function {
+            content: format_hits(&query, &hits),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+fn format_hits(query: &CodeSearchQuery, hits: &[CodeSearchHit]) -> String {
+    let heading = match query.kind {
+        CodeSearchKind::Definition => "Definitions of",
+        CodeSearchKind::References => "References to",
+        CodeSearchKind::Fuzzy => "Symbols matching",
+    };
+    let mut lines = vec![format!("{heading} `{}`:", query.text)];
+    lines.extend(
+        hits.iter()
+            .map(|hit| format!("{}:{}: {}", hit.path, hit.line, hit.detail)),
+    );
+    if hits.len() >= query.limit {
+        lines.push(format!(
+            "(showing the first {} results; narrow with `path` or raise `limit`)",
+            query.limit
+        ));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn format_hits_lists_locations_and_notes_truncation() {
+        let query = CodeSearchQuery {
+            kind: CodeSearchKind::References,
+            text: "Walker".to_string(),
+            path_prefix: None,
+            limit: 2,
+        };
+        let hits = vec![
+            CodeSearchHit {
+                path: "src/main.rs".to_string(),
+                line: 2,
+                detail: "Walker.walk_tree();".to_string(),
+            },
+            CodeSearchHit {
+                path: "src/walker.rs".to_string(),
+                line: 1,
+                detail: "pub struct Walker;".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            format_hits(&query, &hits),
+            "References to `Walker`:\n\
+             src/main.rs:2: Walker.walk_tree();\n\
+             src/walker.rs:1: pub struct Walker;\n\
+             (showing the first 2 results; narrow with `path` or raise `limit`)"
+        );
+    }
+}
@@ -10,6 +10,7 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_path_readable;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -72,6 +73,7 @@ impl ToolHandler for GrepFilesHandler {
         let search_path = turn.resolve_path(args.path.clone());
 
         verify_path_exists(&search_path).await?;
+        ensure_path_readable(&turn, &search_path)?;
 
         let include = args.include.as_deref().map(str::trim).and_then(|val| {
             if val.is_empty() {
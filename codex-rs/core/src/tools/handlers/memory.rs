@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::memory::Memory;
+use crate::memory::MemoryStore;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+/// Handles the `remember` and `recall` tools backed by the project memory
+/// store.
+pub struct MemoryHandler;
+
+const DEFAULT_RECALL_LIMIT: usize = 10;
+const MAX_RECALL_LIMIT: usize = 50;
+/// Memories are meant to be short notes, not documents.
+const MAX_MEMORY_CHARS: usize = 2_000;
+
+fn default_recall_limit() -> usize {
+    DEFAULT_RECALL_LIMIT
+}
+
+#[derive(Deserialize)]
+struct RememberArgs {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct RecallArgs {
+    query: String,
+    #[serde(default = "default_recall_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for MemoryHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{tool_name} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let codex_home = session.services.codex_home.clone();
+        let cwd = turn.cwd.clone();
+        let content = match tool_name.as_str() {
+            "remember" => {
+                let args: RememberArgs = parse_arguments(&arguments)?;
+                if args.text.chars().count() > MAX_MEMORY_CHARS {
+                    return Err(FunctionCallError::RespondToModel(format!(
+                        "memory text must be at most {MAX_MEMORY_CHARS} characters; save a shorter note"
+                    )));
+                }
+                let memory = run_blocking(move || {
+                    MemoryStore::for_project(&codex_home, &cwd).add(&args.text)
+                })
+                .await?
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("failed to save memory: {err:#}"))
+                })?;
+                format!("Saved memory [{}].", memory.id)
+            }
+            "recall" => {
+                let args: RecallArgs = parse_arguments(&arguments)?;
+                if args.limit == 0 {
+                    return Err(FunctionCallError::RespondToModel(
+                        "limit must be greater than zero".to_string(),
+                    ));
+                }
+                let limit = args.limit.min(MAX_RECALL_LIMIT);
+                let memories = run_blocking(move || {
+                    MemoryStore::for_project(&codex_home, &cwd).search(&args.query, limit)
+                })
+                .await?
+                .map_err(|err| {
+                    FunctionCallError::RespondToModel(format!("failed to read memories: {err:#}"))
+                })?;
+                if memories.is_empty() {
+                    return Ok(ToolOutput::Function {
+                        content: "No memories match.".to_string(),
+                        content_items: None,
+                        success: Some(false),
+                    });
+                }
+                format_memories(&memories)
+            }
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported memory tool: {other}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// Runs a store operation on the blocking pool: the store reads and writes
+/// files and may sleep while waiting for its lock.
+async fn run_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, FunctionCallError> {
+    tokio::task::spawn_blocking(f).await.map_err(|err| {
+        FunctionCallError::RespondToModel(format!("memory store task failed: {err}"))
+    })
+}
+
+fn format_memories(memories: &[Memory]) -> String {
+    memories
+        .iter()
+        .map(|memory| format!("[{}] ({}) {}", memory.id, memory.updated_at, memory.text))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1,9 +1,11 @@
 pub mod apply_patch;
+mod code_search;
 pub(crate) mod collab;
 mod grep_files;
 mod list_dir;
 mod mcp;
 mod mcp_resource;
+mod memory;
 mod plan;
 mod read_file;
 mod shell;
@@ -11,16 +13,21 @@ mod test_sync;
 mod unified_exec;
 mod view_image;
 
+use std::path::Path;
+
 pub use plan::PLAN_TOOL;
 use serde::Deserialize;
 
+use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 pub use apply_patch::ApplyPatchHandler;
+pub use code_search::CodeSearchHandler;
 pub use collab::CollabHandler;
 pub use grep_files::GrepFilesHandler;
 pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
+pub use memory::MemoryHandler;
 pub use plan::PlanHandler;
 pub use read_file::ReadFileHandler;
 pub use shell::ShellCommandHandler;
@@ -37,3 +44,65 @@ where
         FunctionCallError::RespondToModel(format!("failed to parse function arguments: {err}"))
     })
 }
+
+/// Handlers that read the file-system in-process bypass the sandbox, so they
+/// must check the policy's readable roots themselves before touching `path`.
+pub(crate) fn ensure_path_readable(
+    turn: &TurnContext,
+    path: &Path,
+) -> Result<(), FunctionCallError> {
+    let Some(readable_roots) = turn.sandbox_policy.get_readable_roots_with_cwd(&turn.cwd) else {
+        return Ok(());
+    };
+    // Resolve symlinks and `..` so a link inside a readable root cannot point
+    // outside of it.
+    let resolved = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let readable = readable_roots.iter().any(|root| {
+        let root = std::fs::canonicalize(root.as_path()).unwrap_or_else(|_| root.to_path_buf());
+        resolved.starts_with(root)
+    });
+    if readable {
+        Ok(())
+    } else {
+        Err(FunctionCallError::RespondToModel(format!(
+            "access to `{}` is denied: it is outside the readable roots of the sandbox policy",
+            path.display()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::protocol::SandboxPolicy;
+
+    #[tokio::test]
+    async fn restricted_read_rejects_paths_outside_readable_roots() {
+        let (_session, mut turn) = make_session_and_context().await;
+        let workspace = tempfile::tempdir().expect("workspace");
+        let outside = tempfile::tempdir().expect("outside");
+        std::fs::write(workspace.path().join("notes.txt"), "ok").expect("write notes");
+        std::fs::write(outside.path().join("secret"), "no").expect("write secret");
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(outside.path().join("secret"), workspace.path().join("link"))
+            .expect("symlink");
+
+        turn.cwd = workspace.path().to_path_buf();
+        turn.sandbox_policy = SandboxPolicy::RestrictedRead {
+            readable_roots: Vec::new(),
+            writable_roots: Vec::new(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        assert!(ensure_path_readable(&turn, &workspace.path().join("notes.txt")).is_ok());
+        assert!(ensure_path_readable(&turn, &outside.path().join("secret")).is_err());
+        #[cfg(unix)]
+        assert!(ensure_path_readable(&turn, &workspace.path().join("link")).is_err());
+
+        turn.sandbox_policy = SandboxPolicy::new_workspace_write_policy();
+        assert!(ensure_path_readable(&turn, &outside.path().join("secret")).is_ok());
+    }
+}
@@ -9,10 +9,13 @@ use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_path_readable;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 
+mod outline;
+
 pub struct ReadFileHandler;
 
 const MAX_LINE_LENGTH: usize = 500;
@@ -32,12 +35,16 @@ struct ReadFileArgs {
     /// Maximum number of lines to return; defaults to 2000.
     #[serde(default = "defaults::limit")]
     limit: usize,
-    /// Determines whether the handler reads a simple slice or indentation-aware block.
+    /// Determines whether the handler reads a simple slice, an indentation-aware block, or
+    /// uses the syntax tree to outline the file or read a single symbol.
     #[serde(default)]
     mode: ReadMode,
     /// Optional indentation configuration used when `mode` is `Indentation`.
     #[serde(default)]
     indentation: Option<IndentationArgs>,
+    /// Name of the item to read when `mode` is `Symbol`, e.g. `Walker.walk`.
+    #[serde(default)]
+    symbol: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -45,6 +52,8 @@ struct ReadFileArgs {
 enum ReadMode {
     Slice,
     Indentation,
+    Outline,
+    Symbol,
 }
 /// Additional configuration for indentation-aware reads.
 #[derive(Deserialize, Clone)]
@@ -97,7 +106,7 @@ impl ToolHandler for ReadFileHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation { payload, turn, .. } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -116,6 +125,7 @@ impl ToolHandler for ReadFileHandler {
             limit,
             mode,
             indentation,
+            symbol,
         } = args;
 
         if offset == 0 {
@@ -136,6 +146,7 @@ impl ToolHandler for ReadFileHandler {
                 "file_path must be an absolute path".to_string(),
             ));
         }
+        ensure_path_readable(&turn, &path)?;
 
         let collected = match mode {
             ReadMode::Slice => slice::read(&path, offset, limit).await?,
@@ -143,6 +154,19 @@ impl ToolHandler for ReadFileHandler {
                 let indentation = indentation.unwrap_or_default();
                 indentation::read_block(&path, offset, limit, indentation).await?
             }
+            ReadMode::Outline => structure::read_outline(&path, limit).await?,
+            ReadMode::Symbol => {
+                let symbol = symbol
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|symbol| !symbol.is_empty())
+                    .ok_or_else(|| {
+                        FunctionCallError::RespondToModel(
+                            "symbol is required when mode is \"symbol\"".to_string(),
+                        )
+                    })?;
+                structure::read_symbol(&path, symbol, limit).await?
+            }
         };
         Ok(ToolOutput::Function {
             content: collected.join("\n"),
@@ -428,6 +452,51 @@ mod indentation {
     }
 }
 
+mod structure {
+    use crate::function_tool::FunctionCallError;
+    use crate::tools::handlers::read_file::format_line;
+    use crate::tools::handlers::read_file::outline;
+    use std::path::Path;
+
+    pub async fn read_outline(path: &Path, limit: usize) -> Result<Vec<String>, FunctionCallError> {
+        let source = read_source(path).await?;
+        let items = outline::outline(path, &source).map_err(FunctionCallError::RespondToModel)?;
+        if items.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "no outline items found; use mode \"slice\" to read the file".to_string(),
+            ));
+        }
+        let mut lines = outline::format_outline(&items);
+        lines.truncate(limit);
+        Ok(lines)
+    }
+
+    pub async fn read_symbol(
+        path: &Path,
+        symbol: &str,
+        limit: usize,
+    ) -> Result<Vec<String>, FunctionCallError> {
+        let source = read_source(path).await?;
+        let items = outline::outline(path, &source).map_err(FunctionCallError::RespondToModel)?;
+        let item =
+            outline::find_symbol(&items, symbol).map_err(FunctionCallError::RespondToModel)?;
+        Ok(source
+            .lines()
+            .enumerate()
+            .skip(item.doc_start_line - 1)
+            .take((item.end_line + 1 - item.doc_start_line).min(limit))
+            .map(|(index, line)| format!("L{}: {}", index + 1, format_line(line.as_bytes())))
+            .collect())
+    }
+
+    async fn read_source(path: &Path) -> Result<String, FunctionCallError> {
+        let bytes = tokio::fs::read(path).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to read file: {err}"))
+        })?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
 fn format_line(bytes: &[u8]) -> String {
     let decoded = String::from_utf8_lossy(bytes);
     if decoded.len() > MAX_LINE_LENGTH {
@@ -585,6 +654,46 @@ third
         Ok(())
     }
 
+    #[tokio::test]
+    async fn outline_and_symbol_modes_use_the_syntax_tree() -> anyhow::Result<()> {
+        let mut temp = tempfile::Builder::new().suffix(".rs").tempfile()?;
+        use std::io::Write as _;
+        write!(
+            temp,
+            "pub struct Walker;
+
+impl Walker {{
+    /// Walks the tree.
+    pub fn walk(&self) {{
+        todo!()
+    }}
+}}
+"
+        )?;
+
+        let outline = super::structure::read_outline(temp.path(), 10).await?;
+        assert_eq!(
+            outline,
+            vec![
+                "L1-L1: pub struct Walker".to_string(),
+                "L3-L8: impl Walker".to_string(),
+                "L5-L7:   pub fn walk(&self)".to_string(),
+            ]
+        );
+
+        let symbol = super::structure::read_symbol(temp.path(), "walk", 10).await?;
+        assert_eq!(
+            symbol,
+            vec![
+                "L4:     /// Walks the tree.".to_string(),
+                "L5:     pub fn walk(&self) {".to_string(),
+                "L6:         todo!()".to_string(),
+                "L7:     }".to_string(),
+            ]
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn indentation_mode_captures_block() -> anyhow::Result<()> {
         let mut temp = NamedTempFile::new()?;
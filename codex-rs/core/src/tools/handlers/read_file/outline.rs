@@ -0,0 +1,559 @@
+//! Structural `read_file` modes backed by tree-sitter grammars.
+//!
+//! `outline` lists the items of a file (functions, types, impls, classes, ...)
+//! with their signatures and line ranges; `symbol` returns the full source of
+//! one item, including the doc comments and attributes directly above it.
+
+use std::path::Path;
+
+use tree_sitter::Language;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+
+/// Signatures longer than this are cut to keep outlines compact.
+const MAX_SIGNATURE_CHARS: usize = 160;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SourceLanguage {
+    Rust,
+    TypeScript,
+    Tsx,
+    Python,
+    Go,
+    Java,
+}
+
+impl SourceLanguage {
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "rs" => Some(Self::Rust),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::Tsx),
+            "py" | "pyi" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            "java" => Some(Self::Java),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+            Self::Java => tree_sitter_java::LANGUAGE.into(),
+        }
+    }
+
+    /// How a node of `kind` takes part in the outline.
+    fn role(self, kind: &str) -> Role {
+        match (self, kind) {
+            (
+                Self::Rust,
+                "function_item"
+                | "function_signature_item"
+                | "struct_item"
+                | "enum_item"
+                | "union_item"
+                | "type_item"
+                | "const_item"
+                | "static_item"
+                | "macro_definition",
+            ) => Role::Item,
+            (Self::Rust, "impl_item" | "trait_item" | "mod_item") => Role::Container,
+
+            (Self::TypeScript | Self::Tsx, "export_statement" | "lexical_declaration") => {
+                Role::Wrapper
+            }
+            (
+                Self::TypeScript | Self::Tsx,
+                "function_declaration"
+                | "generator_function_declaration"
+                | "function_signature"
+                | "type_alias_declaration"
+                | "enum_declaration"
+                | "method_definition"
+                | "method_signature"
+                | "abstract_method_signature",
+            ) => Role::Item,
+            (Self::TypeScript | Self::Tsx, "variable_declarator") => Role::FunctionBinding,
+            (
+                Self::TypeScript | Self::Tsx,
+                "class_declaration"
+                | "abstract_class_declaration"
+                | "interface_declaration"
+                | "internal_module"
+                | "module",
+            ) => Role::Container,
+
+            (Self::Python, "decorated_definition") => Role::Wrapper,
+            (Self::Python, "function_definition") => Role::Item,
+            (Self::Python, "class_definition") => Role::Container,
+
+            (Self::Go, "type_declaration") => Role::Wrapper,
+            (Self::Go, "function_declaration" | "method_declaration" | "type_spec") => Role::Item,
+
+            (Self::Java, "enum_body_declarations") => Role::Wrapper,
+            (
+                Self::Java,
+                "method_declaration" | "constructor_declaration" | "annotation_type_declaration",
+            ) => Role::Item,
+            (
+                Self::Java,
+                "class_declaration"
+                | "interface_declaration"
+                | "enum_declaration"
+                | "record_declaration",
+            ) => Role::Container,
+
+            _ => Role::Skip,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    /// Not part of the outline.
+    Skip,
+    /// Transparent node (e.g. `export`, decorators) whose children are items.
+    Wrapper,
+    /// A leaf item such as a function.
+    Item,
+    /// An item whose body holds further items (classes, impls, modules).
+    Container,
+    /// `const name = () => ...`; only an item when bound to a function.
+    FunctionBinding,
+}
+
+/// One entry of a file outline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct OutlineItem {
+    /// Dotted path through enclosing containers, e.g. `Walker.walk`.
+    pub(crate) qualified_name: String,
+    pub(crate) name: String,
+    pub(crate) signature: String,
+    pub(crate) depth: usize,
+    /// 1-indexed, inclusive; includes `export`/decorators around the item.
+    pub(crate) start_line: usize,
+    pub(crate) end_line: usize,
+    /// First line of the doc comments and attributes attached to the item.
+    pub(crate) doc_start_line: usize,
+}
+
+/// Parses `source` and returns its items in document order, or an error
+/// message suitable for the model when the language is not supported.
+pub(crate) fn outline(path: &Path, source: &str) -> Result<Vec<OutlineItem>, String> {
+    let language = SourceLanguage::from_path(path).ok_or_else(|| {
+        format!(
+            "outline and symbol modes support Rust, TypeScript/JavaScript, Python, Go and Java \
+             files; `{}` is not one of them",
+            path.display()
+        )
+    })?;
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language.grammar())
+        .map_err(|err| format!("failed to load {language:?} grammar: {err}"))?;
+    let tree = parser
+        .parse(source, None)
+        .ok_or_else(|| "failed to parse file".to_string())?;
+
+    let mut items = Vec::new();
+    collect_items(
+        language,
+        tree.root_node(),
+        source,
+        &Scope {
+            prefix: None,
+            depth: 0,
+        },
+        &mut items,
+    );
+    Ok(items)
+}
+
+/// Renders the outline as one line per item, indented by nesting depth.
+pub(crate) fn format_outline(items: &[OutlineItem]) -> Vec<String> {
+    items
+        .iter()
+        .map(|item| {
+            format!(
+                "L{}-L{}: {}{}",
+                item.start_line,
+                item.end_line,
+                "  ".repeat(item.depth),
+                item.signature
+            )
+        })
+        .collect()
+}
+
+/// Finds the single item named `symbol`, matching either its qualified name
+/// or its bare name.
+pub(crate) fn find_symbol<'a>(
+    items: &'a [OutlineItem],
+    symbol: &str,
+) -> Result<&'a OutlineItem, String> {
+    if let Some(item) = items.iter().find(|item| item.qualified_name == symbol) {
+        return Ok(item);
+    }
+    let matches: Vec<&OutlineItem> = items.iter().filter(|item| item.name == symbol).collect();
+    match matches.as_slice() {
+        [item] => Ok(item),
+        [] => Err(format!(
+            "no symbol named `{symbol}`; use mode \"outline\" to list the symbols in this file"
+        )),
+        candidates => Err(format!(
+            "symbol `{symbol}` is ambiguous; use one of: {}",
+            candidates
+                .iter()
+                .map(|item| format!("{} (L{})", item.qualified_name, item.start_line))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+struct Scope<'a> {
+    prefix: Option<&'a str>,
+    depth: usize,
+}
+
+fn collect_items(
+    language: SourceLanguage,
+    parent: Node<'_>,
+    source: &str,
+    scope: &Scope<'_>,
+    items: &mut Vec<OutlineItem>,
+) {
+    let mut cursor = parent.walk();
+    for child in parent.named_children(&mut cursor) {
+        collect_node(language, child, child, source, scope, items);
+    }
+}
+
+/// `outer` is the outermost wrapper around `node` (e.g. `export` or a
+/// decorator list); it determines where the item starts.
+fn collect_node(
+    language: SourceLanguage,
+    node: Node<'_>,
+    outer: Node<'_>,
+    source: &str,
+    scope: &Scope<'_>,
+    items: &mut Vec<OutlineItem>,
+) {
+    let role = language.role(node.kind());
+    match role {
+        Role::Skip => {}
+        Role::Wrapper => {
+            let mut cursor = node.walk();
+            for child in node.named_children(&mut cursor) {
+                collect_node(language, child, outer, source, scope, items);
+            }
+        }
+        Role::FunctionBinding => {
+            let is_function = node.child_by_field_name("value").is_some_and(|value| {
+                matches!(
+                    value.kind(),
+                    "arrow_function" | "function_expression" | "generator_function"
+                )
+            });
+            if is_function {
+                push_item(language, node, outer, source, scope, items);
+            }
+        }
+        Role::Item => {
+            push_item(language, node, outer, source, scope, items);
+        }
+        Role::Container => {
+            let name = push_item(language, node, outer, source, scope, items);
+            if let Some(body) = node.child_by_field_name("body") {
+                let prefix = match scope.prefix {
+                    Some(prefix) => format!("{prefix}.{name}"),
+                    None => name,
+                };
+                let inner = Scope {
+                    prefix: Some(&prefix),
+                    depth: scope.depth + 1,
+                };
+                collect_items(language, body, source, &inner, items);
+            }
+        }
+    }
+}
+
+/// Records `node` and returns its name.
+fn push_item(
+    language: SourceLanguage,
+    node: Node<'_>,
+    outer: Node<'_>,
+    source: &str,
+    scope: &Scope<'_>,
+    items: &mut Vec<OutlineItem>,
+) -> String {
+    let name = item_name(language, node, source);
+    let qualified_name = match scope.prefix {
+        Some(prefix) => format!("{prefix}.{name}"),
+        None => name.clone(),
+    };
+    let start_line = outer.start_position().row + 1;
+    items.push(OutlineItem {
+        qualified_name,
+        signature: signature(node, outer, source),
+        depth: scope.depth,
+        start_line,
+        end_line: node.end_position().row + 1,
+        doc_start_line: doc_start_row(outer).map_or(start_line, |row| row + 1),
+        name: name.clone(),
+    });
+    name
+}
+
+fn item_name(language: SourceLanguage, node: Node<'_>, source: &str) -> String {
+    let text = |node: Node<'_>| node.utf8_text(source.as_bytes()).unwrap_or_default();
+    if language == SourceLanguage::Rust && node.kind() == "impl_item" {
+        // `impl Trait for Type` is addressed by the implementing type.
+        if let Some(ty) = node.child_by_field_name("type") {
+            let ty = text(ty);
+            return ty.split('<').next().unwrap_or(ty).trim().to_string();
+        }
+    }
+    node.child_by_field_name("name")
+        .map(text)
+        .unwrap_or_else(|| node.kind())
+        .to_string()
+}
+
+/// The item's source up to (not including) its body, collapsed to one line.
+fn signature(node: Node<'_>, outer: Node<'_>, source: &str) -> String {
+    let body = node.child_by_field_name("body").or_else(|| {
+        // `const f = () => { ... }` keeps its body on the bound function.
+        node.child_by_field_name("value")
+            .and_then(|value| value.child_by_field_name("body"))
+    });
+    let end = body.map_or_else(|| node.end_byte(), |body| body.start_byte());
+    let text = source.get(outer.start_byte()..end).unwrap_or_default();
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let collapsed = collapsed.trim_end_matches(['{', ';']).trim_end();
+    if collapsed.chars().count() > MAX_SIGNATURE_CHARS {
+        let truncated: String = collapsed.chars().take(MAX_SIGNATURE_CHARS).collect();
+        format!("{truncated}…")
+    } else {
+        collapsed.to_string()
+    }
+}
+
+/// Walks back over comments and attributes that directly precede `node`.
+fn doc_start_row(node: Node<'_>) -> Option<usize> {
+    let mut start_row = None;
+    let mut next_row = node.start_position().row;
+    let mut sibling = node.prev_sibling();
+    while let Some(previous) = sibling {
+        let kind = previous.kind();
+        let attached = kind.contains("comment") || kind.contains("attribute");
+        if !attached || previous.end_position().row + 1 < next_row {
+            break;
+        }
+        next_row = previous.start_position().row;
+        start_row = Some(next_row);
+        sibling = previous.prev_sibling();
+    }
+    start_row
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn outline_of(file_name: &str, source: &str) -> Vec<String> {
+        match outline(Path::new(file_name), source) {
+            Ok(items) => format_outline(&items),
+            Err(err) => vec![err],
+        }
+    }
+
+    #[test]
+    fn outlines_rust_items_and_impls() {
+        let source = "\
+use std::fmt;
+
+/// A directory walker.
+#[derive(Debug)]
+pub struct Walker {
+    root: String,
+}
+
+impl fmt::Display for Walker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, \"{}\", self.root)
+    }
+}
+
+pub trait Visit {
+    fn visit(&mut self, path: &str);
+}
+
+pub fn walk(root: &str) -> Walker {
+    Walker { root: root.to_string() }
+}
+";
+        assert_eq!(
+            outline_of("walker.rs", source),
+            vec![
+                "L5-L7: pub struct Walker",
+                "L9-L13: impl fmt::Display for Walker",
+                "L10-L12:   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result",
+                "L15-L17: pub trait Visit",
+                "L16-L16:   fn visit(&mut self, path: &str)",
+                "L19-L21: pub fn walk(root: &str) -> Walker",
+            ]
+        );
+    }
+
+    #[test]
+    fn outlines_python_classes_with_decorators() {
+        let source = "\
+import os
+
+class Walker:
+    def __init__(self, root):
+        self.root = root
+
+    @property
+    def name(self):
+        return os.path.basename(self.root)
+
+def walk(root):
+    return Walker(root)
+";
+        assert_eq!(
+            outline_of("walker.py", source),
+            vec![
+                "L3-L9: class Walker:",
+                "L4-L5:   def __init__(self, root):",
+                "L7-L9:   @property def name(self):",
+                "L11-L12: def walk(root):",
+            ]
+        );
+    }
+
+    #[test]
+    fn outlines_typescript_exports_and_arrow_functions() {
+        let source = "\
+export interface Options {
+  depth: number;
+}
+
+export class Walker {
+  constructor(private root: string) {}
+
+  walk(options: Options): string[] {
+    return [];
+  }
+}
+
+export const walk = (root: string) => new Walker(root);
+const internal = 42;
+";
+        assert_eq!(
+            outline_of("walker.ts", source),
+            vec![
+                "L1-L3: export interface Options",
+                "L5-L11: export class Walker",
+                "L6-L6:   constructor(private root: string)",
+                "L8-L10:   walk(options: Options): string[]",
+                "L13-L13: export const walk = (root: string) =>",
+            ]
+        );
+    }
+
+    #[test]
+    fn outlines_go_and_java() {
+        let go = "\
+package walk
+
+type Walker struct {
+\troot string
+}
+
+func (w *Walker) Walk() []string {
+\treturn nil
+}
+";
+        assert_eq!(
+            outline_of("walk.go", go),
+            vec![
+                "L3-L5: type Walker struct { root string }",
+                "L7-L9: func (w *Walker) Walk() []string",
+            ]
+        );
+
+        let java = "\
+public class Walker {
+    private final String root;
+
+    public Walker(String root) {
+        this.root = root;
+    }
+
+    public List<String> walk() {
+        return List.of();
+    }
+}
+";
+        assert_eq!(
+            outline_of("Walker.java", java),
+            vec![
+                "L1-L11: public class Walker",
+                "L4-L6:   public Walker(String root)",
+                "L8-L10:   public List<String> walk()",
+            ]
+        );
+    }
+
+    #[test]
+    fn find_symbol_prefers_qualified_names_and_reports_ambiguity() {
+        let source = "\
+struct A;
+struct B;
+
+impl A {
+    /// Creates an A.
+    fn new() -> Self { A }
+}
+
+impl B {
+    fn new() -> Self { B }
+}
+";
+        let items = outline(Path::new("lib.rs"), source).expect("outline");
+
+        let item = find_symbol(&items, "A.new").expect("qualified lookup");
+        assert_eq!(
+            (item.doc_start_line, item.start_line, item.end_line),
+            (5, 6, 6)
+        );
+
+        assert_eq!(
+            find_symbol(&items, "new").map(|item| item.start_line),
+            Err("symbol `new` is ambiguous; use one of: A.new (L6), B.new (L10)".to_string())
+        );
+        assert_eq!(find_symbol(&items, "B").map(|item| item.start_line), Ok(2));
+    }
+
+    #[test]
+    fn rejects_unsupported_languages() {
+        assert_eq!(
+            outline_of("notes.txt", "hello"),
+            vec![
+                "outline and symbol modes support Rust, TypeScript/JavaScript, Python, Go and \
+                 Java files; `notes.txt` is not one of them"
+                    .to_string()
+            ]
+        );
+    }
+}
@@ -251,6 +251,7 @@ impl ShellHandler {
             .create_exec_approval_requirement_for_command(
                 &features,
                 &exec_params.command,
+                &exec_params.cwd,
                 turn.approval_policy,
                 &turn.sandbox_policy,
                 exec_params.sandbox_permissions,
@@ -8,6 +8,7 @@ use crate::protocol::ViewImageToolCallEvent;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::ensure_path_readable;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
@@ -49,6 +50,7 @@ impl ToolHandler for ViewImageHandler {
         let args: ViewImageArgs = parse_arguments(&arguments)?;
 
         let abs_path = turn.resolve_path(Some(args.path));
+        ensure_path_readable(&turn, &abs_path)?;
 
         let metadata = fs::metadata(&abs_path).await.map_err(|error| {
             FunctionCallError::RespondToModel(format!(
@@ -17,9 +17,12 @@ use crate::tools::sandboxing::ToolCtx;
 use crate::tools::sandboxing::ToolError;
 use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::default_exec_approval_requirement;
+use codex_otel::OtelManager;
 use codex_otel::ToolDecisionSource;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::ReviewDecision;
+use std::time::Instant;
+use tracing::Instrument;
 
 pub(crate) struct ToolOrchestrator {
     sandbox: SandboxManager,
@@ -69,7 +72,14 @@ impl ToolOrchestrator {
                     call_id: &tool_ctx.call_id,
                     retry_reason: reason,
                 };
-                let decision = tool.start_approval_async(req, approval_ctx).await;
+                let approval_span = otel.approval_span(&turn_ctx.sub_id, otel_tn, otel_ci);
+                let approval_started = Instant::now();
+                let decision = tool
+                    .start_approval_async(req, approval_ctx)
+                    .instrument(approval_span.clone())
+                    .await;
+                OtelManager::record_approval_decision(&approval_span, &decision);
+                otel.approval_wait(otel_tn, &decision, approval_started.elapsed());
 
                 otel.tool_decision(otel_tn, otel_ci, &decision, otel_user.clone());
 
@@ -79,7 +89,8 @@ impl ToolOrchestrator {
                     }
                     ReviewDecision::Approved
                     | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                    | ReviewDecision::ApprovedForSession => {}
+                    | ReviewDecision::ApprovedForSession
+                    | ReviewDecision::ApprovedPartially { .. } => {}
                 }
                 already_approved = true;
             }
@@ -101,14 +112,27 @@ impl ToolOrchestrator {
             manager: &self.sandbox,
             sandbox_cwd: &turn_ctx.cwd,
             codex_linux_sandbox_exe: turn_ctx.codex_linux_sandbox_exe.as_ref(),
+            network_proxy: tool_ctx.session.services.network_proxy.as_ref(),
         };
 
-        match tool.run(req, &initial_attempt, tool_ctx).await {
+        let attempt_span = otel.exec_span(
+            &turn_ctx.sub_id,
+            otel_tn,
+            otel_ci,
+            initial_sandbox.as_str(),
+            false,
+        );
+        let initial_result = tool
+            .run(req, &initial_attempt, tool_ctx)
+            .instrument(attempt_span.clone())
+            .await;
+        match initial_result {
             Ok(out) => {
                 // We have a successful initial result
                 Ok(out)
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
+                attempt_span.record("exit_code", output.exit_code);
                 if !tool.escalate_on_failure() {
                     return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                         output,
@@ -132,7 +156,14 @@ impl ToolOrchestrator {
                         retry_reason: Some(reason_msg),
                     };
 
-                    let decision = tool.start_approval_async(req, approval_ctx).await;
+                    let approval_span = otel.approval_span(&turn_ctx.sub_id, otel_tn, otel_ci);
+                    let approval_started = Instant::now();
+                    let decision = tool
+                        .start_approval_async(req, approval_ctx)
+                        .instrument(approval_span.clone())
+                        .await;
+                    OtelManager::record_approval_decision(&approval_span, &decision);
+                    otel.approval_wait(otel_tn, &decision, approval_started.elapsed());
                     otel.tool_decision(otel_tn, otel_ci, &decision, otel_user);
 
                     match decision {
@@ -141,7 +172,8 @@ impl ToolOrchestrator {
                         }
                         ReviewDecision::Approved
                         | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                        | ReviewDecision::ApprovedForSession => {}
+                        | ReviewDecision::ApprovedForSession
+                        | ReviewDecision::ApprovedPartially { .. } => {}
                     }
                 }
 
@@ -151,10 +183,21 @@ impl ToolOrchestrator {
                     manager: &self.sandbox,
                     sandbox_cwd: &turn_ctx.cwd,
                     codex_linux_sandbox_exe: None,
+                    network_proxy: None,
                 };
 
                 // Second attempt.
-                (*tool).run(req, &escalated_attempt, tool_ctx).await
+                let escalated_span = otel.exec_span(
+                    &turn_ctx.sub_id,
+                    otel_tn,
+                    otel_ci,
+                    escalated_attempt.sandbox.as_str(),
+                    true,
+                );
+                (*tool)
+                    .run(req, &escalated_attempt, tool_ctx)
+                    .instrument(escalated_span)
+                    .await
             }
             other => other,
         }
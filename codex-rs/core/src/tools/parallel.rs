@@ -7,7 +7,6 @@ use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
 use tracing::Instrument;
 use tracing::instrument;
-use tracing::trace_span;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
@@ -60,12 +59,10 @@ impl ToolCallRuntime {
         let lock = Arc::clone(&self.parallel_execution);
         let started = Instant::now();
 
-        let dispatch_span = trace_span!(
-            "dispatch_tool_call",
-            otel.name = call.tool_name.as_str(),
-            tool_name = call.tool_name.as_str(),
-            call_id = call.call_id.as_str(),
-            aborted = false,
+        let dispatch_span = turn.client.get_otel_manager().tool_call_span(
+            &turn.sub_id,
+            &call.tool_name,
+            &call.call_id,
         );
 
         let handle: AbortOnDropHandle<Result<ResponseInputItem, FunctionCallError>> =
@@ -83,10 +80,12 @@ impl ToolCallRuntime {
                             Either::Right(lock.write().await)
                         };
 
-                        router
+                        let res = router
                             .dispatch_tool_call(session, turn, tracker, call.clone())
                             .instrument(dispatch_span.clone())
-                            .await
+                            .await;
+                        dispatch_span.record("success", res.as_ref().is_ok_and(output_success));
+                        res
                     } => res,
                 }
             }));
@@ -105,6 +104,18 @@ impl ToolCallRuntime {
     }
 }
 
+/// Whether a tool output reports success; outputs without a status (custom
+/// tools) count as successful.
+fn output_success(response: &ResponseInputItem) -> bool {
+    match response {
+        ResponseInputItem::FunctionCallOutput { output, .. } => output.success != Some(false),
+        ResponseInputItem::McpToolCallOutput { result, .. } => result
+            .as_ref()
+            .is_ok_and(|result| result.is_error != Some(true)),
+        ResponseInputItem::CustomToolCallOutput { .. } | ResponseInputItem::Message { .. } => true,
+    }
+}
+
 impl ToolCallRuntime {
     fn aborted_response(call: &ToolCall, secs: f32) -> ResponseInputItem {
         match &call.payload {
@@ -4,6 +4,11 @@ use std::time::Duration;
 
 use crate::client_common::tools::ToolSpec;
 use crate::function_tool::FunctionCallError;
+use crate::hooks::HookEvent;
+use crate::hooks::append_hook_feedback;
+use crate::hooks::apply_hook_tool_input;
+use crate::hooks::tool_input_for_hook;
+use crate::hooks::tool_output_for_hook;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -104,6 +109,59 @@ impl ToolRegistry {
             return Err(FunctionCallError::Fatal(message));
         }
 
+        let mut invocation = invocation;
+        let session = Arc::clone(&invocation.session);
+        let turn = Arc::clone(&invocation.turn);
+        let hooks = session.hooks();
+        // Feedback from pre and post hooks is appended to whatever the model
+        // sees for this call, including errors.
+        let mut hook_feedback = Vec::new();
+        if hooks.has_pre_tool_use(&tool_name) {
+            let outcome = hooks
+                .run(
+                    HookEvent::PreToolUse {
+                        thread_id: session.conversation_id.to_string(),
+                        turn_id: turn.sub_id.clone(),
+                        cwd: turn.cwd.clone(),
+                        tool_name: tool_name.clone(),
+                        call_id: call_id_owned.clone(),
+                        tool_input: tool_input_for_hook(&invocation.payload),
+                    },
+                    &turn.cwd,
+                )
+                .await;
+            if let Some(reason) = outcome.block_reason {
+                let message = format!("tool call blocked by pre_tool_use hook: {reason}");
+                otel.tool_result(
+                    tool_name.as_ref(),
+                    &call_id_owned,
+                    log_payload.as_ref(),
+                    Duration::ZERO,
+                    false,
+                    &message,
+                );
+                return Err(FunctionCallError::RespondToModel(message));
+            }
+            hook_feedback = outcome.feedback;
+            if let Some(tool_input) = outcome.tool_input {
+                invocation.payload = apply_hook_tool_input(&invocation.payload, tool_input)
+                    .map_err(FunctionCallError::RespondToModel)?;
+            }
+        }
+        let post_hook_input = hooks
+            .has_post_tool_use(&tool_name)
+            .then(|| tool_input_for_hook(&invocation.payload));
+        let post_tool_use = |tool_input, tool_output, success| HookEvent::PostToolUse {
+            thread_id: session.conversation_id.to_string(),
+            turn_id: turn.sub_id.clone(),
+            cwd: turn.cwd.clone(),
+            tool_name: tool_name.clone(),
+            call_id: call_id_owned.clone(),
+            tool_input,
+            tool_output,
+            success,
+        };
+
         let output_cell = tokio::sync::Mutex::new(None);
 
         let result = otel
@@ -139,11 +197,31 @@ impl ToolRegistry {
         match result {
             Ok(_) => {
                 let mut guard = output_cell.lock().await;
-                let output = guard.take().ok_or_else(|| {
+                let mut output = guard.take().ok_or_else(|| {
                     FunctionCallError::Fatal("tool produced no output".to_string())
                 })?;
+                if let Some(tool_input) = post_hook_input {
+                    let event = post_tool_use(
+                        tool_input,
+                        tool_output_for_hook(&output),
+                        output.success_for_logging(),
+                    );
+                    hook_feedback.extend(hooks.run(event, &turn.cwd).await.feedback);
+                }
+                append_hook_feedback(&mut output, &hook_feedback);
                 Ok(output.into_response(&call_id_owned, &payload_for_response))
             }
+            Err(FunctionCallError::RespondToModel(mut message)) => {
+                if let Some(tool_input) = post_hook_input {
+                    let event = post_tool_use(tool_input, message.clone(), false);
+                    hook_feedback.extend(hooks.run(event, &turn.cwd).await.feedback);
+                }
+                if !hook_feedback.is_empty() {
+                    message.push_str("\n\n");
+                    message.push_str(&hook_feedback.join("\n"));
+                }
+                Err(FunctionCallError::RespondToModel(message))
+            }
             Err(err) => Err(err),
         }
     }
@@ -3,12 +3,19 @@
 //! Assumes `apply_patch` verification/approval happened upstream. Reuses that
 //! decision to avoid re-prompting, builds the self-invocation command for
 //! `codex --codex-run-as-apply-patch`, and runs under the current
-//! `SandboxAttempt` with a minimal environment.
+//! `SandboxAttempt` with a minimal environment. `PatchApplyBegin` is sent from
+//! the attempt itself so it lists only the changes the user approved, and is
+//! sent again when a retry is approved with fewer hunks.
 use crate::CODEX_APPLY_PATCH_ARG1;
+use crate::apply_patch::hunk_review_feedback;
+use crate::apply_patch::patch_for_approved_changes;
 use crate::exec::ExecToolCallOutput;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::execute_env;
+use crate::tools::context::SharedTurnDiffTracker;
+use crate::tools::events::ToolEmitter;
+use crate::tools::events::ToolEventCtx;
 use crate::tools::sandboxing::Approvable;
 use crate::tools::sandboxing::ApprovalCtx;
 use crate::tools::sandboxing::ExecApprovalRequirement;
@@ -38,15 +45,61 @@ pub struct ApplyPatchRequest {
     pub codex_exe: Option<PathBuf>,
 }
 
-#[derive(Default)]
-pub struct ApplyPatchRuntime;
+/// Outcome of a hunk-by-hunk review: the patch to run in place of the
+/// proposed one, and the note for the model about what was left out.
+#[derive(Debug)]
+struct PartialApproval {
+    /// The approved subset of the proposed changes.
+    changes: HashMap<PathBuf, FileChange>,
+    /// `None` when every hunk was rejected.
+    patch: Option<Result<String, String>>,
+    feedback: Option<String>,
+}
+
+pub struct ApplyPatchRuntime {
+    partial_approval: Option<PartialApproval>,
+    auto_approved: bool,
+    tracker: Option<SharedTurnDiffTracker>,
+    /// Changes listed by the last `PatchApplyBegin`. A sandbox-denied first
+    /// attempt announces every change; if the retry prompt is then approved
+    /// hunk by hunk, a corrected `PatchApplyBegin` is sent so it matches
+    /// `PatchApplyEnd`.
+    begin_changes: Option<HashMap<PathBuf, FileChange>>,
+}
 
 impl ApplyPatchRuntime {
-    pub fn new() -> Self {
-        Self
+    pub fn new(auto_approved: bool, tracker: Option<SharedTurnDiffTracker>) -> Self {
+        Self {
+            partial_approval: None,
+            auto_approved,
+            tracker,
+            begin_changes: None,
+        }
     }
 
-    fn build_command_spec(req: &ApplyPatchRequest) -> Result<CommandSpec, ToolError> {
+    /// Changes the patch applies: the approved subset after a partial
+    /// approval, otherwise everything in `req`.
+    pub(crate) fn approved_changes<'a>(
+        &'a self,
+        req: &'a ApplyPatchRequest,
+    ) -> &'a HashMap<PathBuf, FileChange> {
+        self.partial_approval
+            .as_ref()
+            .map_or(&req.changes, |partial| &partial.changes)
+    }
+
+    /// Note describing the hunks the user rejected or edited, when the patch
+    /// was only partially approved.
+    pub(crate) fn hunk_review_feedback(&self) -> Option<&str> {
+        self.partial_approval
+            .as_ref()
+            .and_then(|partial| partial.feedback.as_deref())
+    }
+
+    fn build_command_spec(
+        req: &ApplyPatchRequest,
+        patch: Option<&str>,
+    ) -> Result<CommandSpec, ToolError> {
         use std::env;
         let exe = if let Some(path) = &req.codex_exe {
             path.clone()
@@ -57,7 +110,10 @@ impl ApplyPatchRuntime {
         let program = exe.to_string_lossy().to_string();
         Ok(CommandSpec {
             program,
-            args: vec![CODEX_APPLY_PATCH_ARG1.to_string(), req.action.patch.clone()],
+            args: vec![
+                CODEX_APPLY_PATCH_ARG1.to_string(),
+                patch.unwrap_or(&req.action.patch).to_string(),
+            ],
             cwd: req.action.cwd.clone(),
             expiration: req.timeout_ms.into(),
             // Run apply_patch with a minimal environment for determinism and to avoid leaks.
@@ -103,26 +159,42 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
         let retry_reason = ctx.retry_reason.clone();
         let approval_keys = self.approval_keys(req);
         let changes = req.changes.clone();
+        let cwd = req.action.cwd.clone();
+        let partial_approval = &mut self.partial_approval;
         Box::pin(async move {
-            if let Some(reason) = retry_reason {
+            let decision = if let Some(reason) = retry_reason {
                 let rx_approve = session
                     .request_patch_approval(turn, call_id, changes.clone(), Some(reason), None)
                     .await;
-                return rx_approve.await.unwrap_or_default();
-            }
+                rx_approve.await.unwrap_or_default()
+            } else {
+                with_cached_approval(
+                    &session.services,
+                    "apply_patch",
+                    approval_keys,
+                    || async move {
+                        let rx_approve = session
+                            .request_patch_approval(turn, call_id, changes, None, None)
+                            .await;
+                        rx_approve.await.unwrap_or_default()
+                    },
+                )
+                .await
+            };
 
-            with_cached_approval(
-                &session.services,
-                "apply_patch",
-                approval_keys,
-                || async move {
-                    let rx_approve = session
-                        .request_patch_approval(turn, call_id, changes, None, None)
-                        .await;
-                    rx_approve.await.unwrap_or_default()
-                },
-            )
-            .await
+            *partial_approval = match &decision {
+                ReviewDecision::ApprovedPartially {
+                    changes,
+                    rejected_hunks,
+                    edited_hunks,
+                } => Some(PartialApproval {
+                    changes: changes.clone(),
+                    patch: (!changes.is_empty()).then(|| patch_for_approved_changes(changes, &cwd)),
+                    feedback: hunk_review_feedback(rejected_hunks, edited_hunks, &cwd),
+                }),
+                _ => None,
+            };
+            decision
         })
     }
 
@@ -149,13 +221,47 @@ impl ToolRuntime<ApplyPatchRequest, ExecToolCallOutput> for ApplyPatchRuntime {
         attempt: &SandboxAttempt<'_>,
         ctx: &ToolCtx<'_>,
     ) -> Result<ExecToolCallOutput, ToolError> {
-        let spec = Self::build_command_spec(req)?;
+        let patch = match &self.partial_approval {
+            None => None,
+            Some(PartialApproval {
+                patch: Some(Ok(patch)),
+                ..
+            }) => Some(patch.as_str()),
+            Some(PartialApproval {
+                patch: Some(Err(err)),
+                ..
+            }) => {
+                return Err(ToolError::Rejected(format!(
+                    "failed to apply the approved hunks: {err}"
+                )));
+            }
+            // The handler appends the hunk review feedback.
+            Some(PartialApproval { patch: None, .. }) => {
+                return Err(ToolError::Rejected("patch rejected by user".to_string()));
+            }
+        };
+        let spec = Self::build_command_spec(req, patch)?;
+        let changes = self.approved_changes(req);
+        if self.begin_changes.as_ref() != Some(changes) {
+            let changes = changes.clone();
+            ToolEmitter::apply_patch(changes.clone(), self.auto_approved)
+                .begin(ToolEventCtx::new(
+                    ctx.session,
+                    ctx.turn,
+                    &ctx.call_id,
+                    self.tracker.as_ref(),
+                ))
+                .await;
+            self.begin_changes = Some(changes);
+        }
         let env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
         let out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
             .await
             .map_err(ToolError::Codex)?;
+        // Recorded on the `codex.exec` span the orchestrator runs attempts in.
+        tracing::Span::current().record("exit_code", out.exit_code);
         Ok(out)
     }
 }
@@ -169,6 +169,8 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
         let out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
             .await
             .map_err(ToolError::Codex)?;
+        // Recorded on the `codex.exec` span the orchestrator runs attempts in.
+        tracing::Span::current().record("exit_code", out.exit_code);
         Ok(out)
     }
 }
@@ -274,6 +274,8 @@ pub(crate) struct SandboxAttempt<'a> {
     pub(crate) manager: &'a SandboxManager,
     pub(crate) sandbox_cwd: &'a Path,
     pub codex_linux_sandbox_exe: Option<&'a std::path::PathBuf>,
+    /// The session's filtering network proxy, if one is running.
+    pub network_proxy: Option<&'a codex_network_proxy::NetworkProxy>,
 }
 
 impl<'a> SandboxAttempt<'a> {
@@ -287,6 +289,7 @@ impl<'a> SandboxAttempt<'a> {
             self.sandbox,
             self.sandbox_cwd,
             self.codex_linux_sandbox_exe,
+            self.network_proxy,
         )
     }
 }
@@ -26,6 +26,8 @@ pub(crate) struct ToolsConfig {
     pub web_search_request: bool,
     pub web_search_cached: bool,
     pub collab_tools: bool,
+    pub code_search: bool,
+    pub memory_tools: bool,
     pub experimental_supported_tools: Vec<String>,
 }
 
@@ -44,6 +46,8 @@ impl ToolsConfig {
         let include_web_search_request = features.enabled(Feature::WebSearchRequest);
         let include_web_search_cached = features.enabled(Feature::WebSearchCached);
         let include_collab_tools = features.enabled(Feature::Collab);
+        let include_code_search = features.enabled(Feature::CodeSearch);
+        let include_memory_tools = features.enabled(Feature::Memory);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
@@ -76,6 +80,8 @@ impl ToolsConfig {
             web_search_request: include_web_search_request,
             web_search_cached: include_web_search_cached,
             collab_tools: include_collab_tools,
+            code_search: include_code_search,
+            memory_tools: include_memory_tools,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
         }
     }
@@ -714,8 +720,20 @@ fn create_read_file_tool() -> ToolSpec {
             "mode".to_string(),
             JsonSchema::String {
                 description: Some(
-                    "Optional mode selector: \"slice\" for simple ranges (default) or \"indentation\" \
-                     to expand around an anchor line."
+                    "Optional mode selector: \"slice\" for simple ranges (default), \"indentation\" \
+                     to expand around an anchor line, \"outline\" to list the file's items with \
+                     signatures and line ranges, or \"symbol\" to read one item by name. Outline \
+                     and symbol modes support Rust, TypeScript/JavaScript, Python, Go and Java."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "symbol".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Item to read when mode is \"symbol\", either a bare name or a dotted path \
+                     such as \"Walker.walk\"."
                         .to_string(),
                 ),
             },
@@ -733,7 +751,7 @@ fn create_read_file_tool() -> ToolSpec {
     ToolSpec::Function(ResponsesApiTool {
         name: "read_file".to_string(),
         description:
-            "Reads a local file with 1-indexed line numbers, supporting slice and indentation-aware block modes."
+            "Reads a local file with 1-indexed line numbers, supporting slice, indentation-aware block, outline, and symbol modes."
                 .to_string(),
         strict: false,
         parameters: JsonSchema::Object {
@@ -744,6 +762,120 @@ fn create_read_file_tool() -> ToolSpec {
     })
 }
 
+fn create_code_search_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Symbol name to look up, or a fuzzy pattern when kind is \"fuzzy\"."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "kind".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "One of \"definition\" (where the symbol is defined), \"references\" \
+                     (every line that uses the identifier) or \"fuzzy\" (ranked symbol names \
+                     similar to the query). Defaults to \"fuzzy\"."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "path".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Directory or file inside the workspace to restrict results to.".to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of results to return (defaults to 50).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "code_search".to_string(),
+        description: "Searches a persistent symbol index of the workspace (respecting .gitignore) \
+                      for definitions, references, or fuzzy symbol matches. Prefer this over \
+                      repeated text searches when looking for where code is defined or used."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_remember_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "text".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "A short, self-contained note to keep for future sessions in this project."
+                    .to_string(),
+            ),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "remember".to_string(),
+        description: "Saves a note to this project's persistent memory so later sessions start \
+             with it. Use it for durable facts worth not re-discovering (build or test \
+             quirks, conventions, decisions), not for transient task state."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["text".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_recall_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Words to look for; memories mentioning more of them rank first.".to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Maximum number of memories to return (defaults to 10).".to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "recall".to_string(),
+        description: "Searches notes saved with `remember` in earlier sessions of this project."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_list_dir_tool() -> ToolSpec {
     let properties = BTreeMap::from([
         (
@@ -951,6 +1083,44 @@ pub(crate) fn create_tools_json_for_chat_completions_api(
     Ok(tools_json)
 }
 
+/// Returns JSON values that are compatible with tool use in the Anthropic
+/// Messages API: https://docs.anthropic.com/en/docs/build-with-claude/tool-use
+pub(crate) fn create_tools_json_for_messages_api(
+    tools: &[ToolSpec],
+) -> crate::error::Result<Vec<serde_json::Value>> {
+    let mut tools_json = Vec::with_capacity(tools.len());
+    for tool in tools {
+        match tool {
+            ToolSpec::Function(function) => tools_json.push(json!({
+                "name": function.name,
+                "description": function.description,
+                "input_schema": serde_json::to_value(&function.parameters)?,
+            })),
+            // Freeform tools become a function with a single string `input`
+            // argument, the same shape the JSON variant of apply_patch uses.
+            ToolSpec::Freeform(freeform) => tools_json.push(json!({
+                "name": freeform.name,
+                "description": format!(
+                    "{}\n\nThe `input` argument must follow this {} grammar:\n{}",
+                    freeform.description, freeform.format.syntax, freeform.format.definition
+                ),
+                "input_schema": {
+                    "type": "object",
+                    "properties": { "input": { "type": "string" } },
+                    "required": ["input"],
+                },
+            })),
+            ToolSpec::LocalShell {} | ToolSpec::WebSearch { .. } => {
+                tracing::warn!(
+                    "tool `{}` has no Messages API equivalent and is not offered to the model",
+                    tool.name()
+                );
+            }
+        }
+    }
+    Ok(tools_json)
+}
+
 pub(crate) fn mcp_tool_to_openai_tool(
     fully_qualified_name: String,
     tool: mcp_types::Tool,
@@ -1103,11 +1273,13 @@ pub(crate) fn build_specs(
     mcp_tools: Option<HashMap<String, mcp_types::Tool>>,
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::CodeSearchHandler;
     use crate::tools::handlers::CollabHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::ListDirHandler;
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
+    use crate::tools::handlers::MemoryHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::ShellCommandHandler;
@@ -1207,6 +1379,20 @@ pub(crate) fn build_specs(
         builder.register_handler("list_dir", list_dir_handler);
     }
 
+    if config.code_search {
+        let code_search_handler = Arc::new(CodeSearchHandler);
+        builder.push_spec_with_parallel_support(create_code_search_tool(), true);
+        builder.register_handler("code_search", code_search_handler);
+    }
+
+    if config.memory_tools {
+        let memory_handler = Arc::new(MemoryHandler);
+        builder.push_spec(create_remember_tool());
+        builder.push_spec_with_parallel_support(create_recall_tool(), true);
+        builder.register_handler("remember", memory_handler.clone());
+        builder.register_handler("recall", memory_handler);
+    }
+
     if config
         .experimental_supported_tools
         .contains(&"test_sync_tool".to_string())
@@ -1265,6 +1451,7 @@ pub(crate) fn build_specs(
 #[cfg(test)]
 mod tests {
     use crate::client_common::tools::FreeformTool;
+    use crate::client_common::tools::FreeformToolFormat;
     use crate::config::test_config;
     use crate::models_manager::manager::ModelsManager;
     use crate::tools::registry::ConfiguredToolSpec;
@@ -1438,6 +1625,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_specs_code_search_enabled() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::CodeSearch);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(find_tool(&tools, "code_search").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn test_build_specs_memory_enabled() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::Memory);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(!find_tool(&tools, "remember").supports_parallel_tool_calls);
+        assert!(find_tool(&tools, "recall").supports_parallel_tool_calls);
+    }
+
     fn assert_model_tools(model_slug: &str, features: &Features, expected_tools: &[&str]) {
         let config = test_config();
         let model_info = ModelsManager::construct_model_info_offline(model_slug, &config);
@@ -2362,4 +2578,59 @@ Examples of valid command strings:
             })]
         );
     }
+
+    #[test]
+    fn messages_tools_use_input_schema() {
+        let properties =
+            BTreeMap::from([("foo".to_string(), JsonSchema::String { description: None })]);
+        let tools = vec![
+            ToolSpec::Function(ResponsesApiTool {
+                name: "demo".to_string(),
+                description: "A demo tool".to_string(),
+                strict: false,
+                parameters: JsonSchema::Object {
+                    properties,
+                    required: None,
+                    additional_properties: None,
+                },
+            }),
+            ToolSpec::Freeform(FreeformTool {
+                name: "patch".to_string(),
+                description: "Edit files".to_string(),
+                format: FreeformToolFormat {
+                    r#type: "grammar".to_string(),
+                    syntax: "lark".to_string(),
+                    definition: "start: \"x\"".to_string(),
+                },
+            }),
+            ToolSpec::LocalShell {},
+        ];
+
+        let tools_json = create_tools_json_for_messages_api(&tools).unwrap();
+
+        assert_eq!(
+            tools_json,
+            vec![
+                json!({
+                    "name": "demo",
+                    "description": "A demo tool",
+                    "input_schema": {
+                        "type": "object",
+                        "properties": {
+                            "foo": { "type": "string" }
+                        },
+                    },
+                }),
+                json!({
+                    "name": "patch",
+                    "description": "Edit files\n\nThe `input` argument must follow this lark grammar:\nstart: \"x\"",
+                    "input_schema": {
+                        "type": "object",
+                        "properties": { "input": { "type": "string" } },
+                        "required": ["input"],
+                    },
+                }),
+            ]
+        );
+    }
 }
@@ -508,6 +508,7 @@ impl UnifiedExecProcessManager {
             .create_exec_approval_requirement_for_command(
                 &features,
                 command,
+                &cwd,
                 context.turn.approval_policy,
                 &context.turn.sandbox_policy,
                 sandbox_permissions,
@@ -1678,6 +1678,223 @@ async fn approving_apply_patch_for_session_skips_future_prompts_for_same_file()
     Ok(())
 }
 
+#[tokio::test(flavor = "current_thread")]
+#[cfg(unix)]
+async fn partially_approved_patch_reports_only_approved_changes() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let approval_policy = AskForApproval::OnRequest;
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        exclude_tmpdir_env_var: false,
+        exclude_slash_tmp: false,
+    };
+    let sandbox_policy_for_config = sandbox_policy.clone();
+
+    let mut builder = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(move |config| {
+            config.approval_policy = Constrained::allow_any(approval_policy);
+            config.sandbox_policy = Constrained::allow_any(sandbox_policy_for_config);
+        });
+    let test = builder.build(&server).await?;
+
+    let (kept_path, kept_patch_path) =
+        TargetPath::OutsideWorkspace("apply_patch_partial_kept.txt").resolve_for_patch(&test);
+    let (skipped_path, skipped_patch_path) =
+        TargetPath::OutsideWorkspace("apply_patch_partial_skipped.txt").resolve_for_patch(&test);
+    let _ = fs::remove_file(&kept_path);
+    let _ = fs::remove_file(&skipped_path);
+    let patch = format!(
+        "*** Begin Patch\n*** Add File: {kept_patch_path}\n+kept\n*** Add File: {skipped_patch_path}\n+skipped\n*** End Patch\n"
+    );
+
+    let call_id = "apply_patch_partial";
+    let _ = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_apply_patch_function_call(call_id, &patch),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let _ = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    submit_turn(
+        &test,
+        "apply_patch partially",
+        approval_policy,
+        sandbox_policy.clone(),
+    )
+    .await?;
+    let approval = expect_patch_approval(&test, call_id).await;
+    let mut changes = approval.changes;
+    changes.retain(|path, _| path == &kept_path);
+    assert_eq!(changes.len(), 1, "approval should list the kept file");
+    test.codex
+        .submit(Op::PatchApproval {
+            id: "0".into(),
+            decision: ReviewDecision::ApprovedPartially {
+                changes: changes.clone(),
+                rejected_hunks: Vec::new(),
+                edited_hunks: Vec::new(),
+            },
+        })
+        .await?;
+
+    let EventMsg::PatchApplyBegin(begin) = wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::PatchApplyBegin(_))
+    })
+    .await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert_eq!(begin.changes, changes);
+    let EventMsg::PatchApplyEnd(end) = wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::PatchApplyEnd(_))
+    })
+    .await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert!(end.success, "patch should apply: {}", end.stderr);
+    assert_eq!(end.changes, changes);
+    wait_for_completion(&test).await;
+
+    assert_eq!(fs::read_to_string(&kept_path)?, "kept\n");
+    assert!(!skipped_path.exists());
+    let _ = fs::remove_file(kept_path);
+
+    Ok(())
+}
+
+// Linux reports the sandboxed write differently; see
+// `workspace_write_on_failure_escalates_outside_workspace`.
+#[tokio::test(flavor = "current_thread")]
+#[cfg(all(unix, not(target_os = "linux")))]
+async fn partially_approved_retry_sends_corrected_patch_begin() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let approval_policy = AskForApproval::OnFailure;
+    let sandbox_policy = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![],
+        network_access: false,
+        exclude_tmpdir_env_var: false,
+        exclude_slash_tmp: false,
+    };
+    let sandbox_policy_for_config = sandbox_policy.clone();
+
+    let mut builder = test_codex()
+        .with_model("gpt-5.1-codex")
+        .with_config(move |config| {
+            config.approval_policy = Constrained::allow_any(approval_policy);
+            config.sandbox_policy = Constrained::allow_any(sandbox_policy_for_config);
+        });
+    let test = builder.build(&server).await?;
+
+    let (kept_path, kept_patch_path) =
+        TargetPath::OutsideWorkspace("apply_patch_retry_kept.txt").resolve_for_patch(&test);
+    let (skipped_path, skipped_patch_path) =
+        TargetPath::OutsideWorkspace("apply_patch_retry_skipped.txt").resolve_for_patch(&test);
+    let _ = fs::remove_file(&kept_path);
+    let _ = fs::remove_file(&skipped_path);
+    let patch = format!(
+        "*** Begin Patch\n*** Add File: {kept_patch_path}\n+kept\n*** Add File: {skipped_patch_path}\n+skipped\n*** End Patch\n"
+    );
+
+    let call_id = "apply_patch_partial_retry";
+    let _ = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_apply_patch_function_call(call_id, &patch),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let _ = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_assistant_message("msg-1", "done"),
+            ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    submit_turn(
+        &test,
+        "apply_patch partially after a sandbox denial",
+        approval_policy,
+        sandbox_policy.clone(),
+    )
+    .await?;
+
+    // The auto-approved sandboxed attempt announces every change.
+    let EventMsg::PatchApplyBegin(first_begin) = wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::PatchApplyBegin(_))
+    })
+    .await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert_eq!(first_begin.changes.len(), 2);
+
+    let approval = expect_patch_approval(&test, call_id).await;
+    assert_eq!(
+        approval.reason.as_deref(),
+        Some("command failed; retry without sandbox?")
+    );
+    let mut changes = approval.changes;
+    changes.retain(|path, _| path == &kept_path);
+    assert_eq!(changes.len(), 1, "approval should list the kept file");
+    test.codex
+        .submit(Op::PatchApproval {
+            id: "0".into(),
+            decision: ReviewDecision::ApprovedPartially {
+                changes: changes.clone(),
+                rejected_hunks: Vec::new(),
+                edited_hunks: Vec::new(),
+            },
+        })
+        .await?;
+
+    let EventMsg::PatchApplyBegin(corrected_begin) = wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::PatchApplyBegin(_))
+    })
+    .await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert_eq!(corrected_begin.changes, changes);
+    let EventMsg::PatchApplyEnd(end) = wait_for_event(&test.codex, |event| {
+        matches!(event, EventMsg::PatchApplyEnd(_))
+    })
+    .await
+    else {
+        unreachable!("wait_for_event returned an unexpected event");
+    };
+    assert!(end.success, "patch should apply: {}", end.stderr);
+    assert_eq!(end.changes, changes);
+    wait_for_completion(&test).await;
+
+    assert_eq!(fs::read_to_string(&kept_path)?, "kept\n");
+    assert!(!skipped_path.exists());
+    let _ = fs::remove_file(kept_path);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "current_thread")]
 #[cfg(unix)]
 async fn approving_execpolicy_amendment_persists_policy_and_skips_future_prompts() -> Result<()> {
@@ -0,0 +1,113 @@
+use anyhow::Result;
+use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed_with_tokens;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+
+async fn submit_and_collect(test: &TestCodex, text: &str) -> Result<Vec<EventMsg>> {
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text { text: text.into() }],
+            final_output_json_schema: None,
+        })
+        .await?;
+
+    let mut events = Vec::new();
+    loop {
+        let event = wait_for_event(&test.codex, |_| true).await;
+        if matches!(event, EventMsg::TurnComplete(_)) {
+            return Ok(events);
+        }
+        events.push(event);
+    }
+}
+
+fn warnings(events: &[EventMsg]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            EventMsg::Warning(warning) => Some(warning.message.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn budget_errors(events: &[EventMsg]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            EventMsg::Error(err)
+                if err.codex_error_info == Some(CodexErrorInfo::BudgetExceeded) =>
+            {
+                Some(err.message.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn session_token_budget_warns_then_aborts() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+    let response_mock = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_assistant_message("msg-1", "first"),
+                ev_completed_with_tokens("resp-1", 900),
+            ]),
+            sse(vec![
+                ev_response_created("resp-2"),
+                ev_assistant_message("msg-2", "second"),
+                ev_completed_with_tokens("resp-2", 200),
+            ]),
+        ],
+    )
+    .await;
+
+    let mut builder = test_codex().with_config(|config| {
+        config.budget.max_session_tokens = Some(1_000);
+    });
+    let test = builder.build(&server).await?;
+
+    let events = submit_and_collect(&test, "one").await?;
+    assert_eq!(
+        warnings(&events),
+        vec![
+            "This session has used 900 tokens of its `max_session_tokens` budget of 1000 tokens; it will be stopped once the limit is exceeded."
+                .to_string()
+        ]
+    );
+    assert!(budget_errors(&events).is_empty());
+
+    let events = submit_and_collect(&test, "two").await?;
+    assert!(warnings(&events).is_empty());
+    assert_eq!(
+        budget_errors(&events),
+        vec![
+            "Token budget exceeded: this session used 1100 tokens, over the `max_session_tokens` limit of 1000 tokens."
+                .to_string()
+        ]
+    );
+
+    // Later turns fail before sending another request.
+    let events = submit_and_collect(&test, "three").await?;
+    assert_eq!(budget_errors(&events).len(), 1);
+    assert_eq!(response_mock.requests().len(), 2);
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::expect_used)]
 use codex_core::CodexAuth;
+use codex_core::CodexThread;
 use codex_core::ModelProviderInfo;
 use codex_core::NewThread;
 use codex_core::ThreadManager;
@@ -7,6 +8,7 @@ use codex_core::built_in_model_providers;
 use codex_core::compact::SUMMARIZATION_PROMPT;
 use codex_core::compact::SUMMARY_PREFIX;
 use codex_core::config::Config;
+use codex_core::config::types::CompactionStrategy;
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
@@ -60,6 +62,10 @@ const DUMMY_FUNCTION_NAME: &str = "unsupported_tool";
 const DUMMY_CALL_ID: &str = "call-multi-auto";
 const FUNCTION_CALL_LIMIT_MSG: &str = "function call limit push";
 const POST_AUTO_USER_MSG: &str = "post auto follow-up";
+/// Long enough that the tool's "unsupported call" output is larger than the
+/// placeholder the `drop_tool_outputs` strategy substitutes for it.
+const VERBOSE_TOOL_NAME: &str =
+    "unsupported_tool_whose_name_makes_its_error_output_longer_than_the_placeholder";
 
 pub(super) const COMPACT_WARNING_MESSAGE: &str = "Heads up: Long threads and multiple compactions can cause the model to be less accurate. Start a new thread when possible to keep threads small and targeted.";
 
@@ -103,6 +109,17 @@ fn json_fragment(text: &str) -> String {
         .to_string()
 }
 
+async fn submit_user_turn(codex: &CodexThread, text: &str) {
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text { text: text.into() }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+    wait_for_event(codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+}
+
 fn non_openai_model_provider(server: &MockServer) -> ModelProviderInfo {
     let mut provider = built_in_model_providers()["openai"].clone();
     provider.name = "OpenAI (test)".into();
@@ -2124,3 +2141,228 @@ async fn auto_compact_counts_encrypted_reasoning_before_last_user() {
         "third turn should include compaction summary item"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn drop_tool_outputs_strategy_replaces_old_outputs_without_summarizing() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+    let request_log = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_function_call("call-old", VERBOSE_TOOL_NAME, "{}"),
+                ev_completed("r1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("m1", FIRST_REPLY),
+                ev_completed("r2"),
+            ]),
+            sse(vec![
+                ev_function_call("call-recent", VERBOSE_TOOL_NAME, "{}"),
+                ev_completed("r3"),
+            ]),
+            sse(vec![
+                ev_assistant_message("m2", "SECOND_REPLY"),
+                ev_completed("r4"),
+            ]),
+            sse(vec![ev_completed("r5")]),
+        ],
+    )
+    .await;
+
+    let codex = test_codex()
+        .with_config(|config| {
+            config.compaction.strategy = CompactionStrategy::DropToolOutputs;
+            config.compaction.keep_recent_turns = 1;
+        })
+        .build(&server)
+        .await
+        .expect("build codex")
+        .codex;
+
+    submit_user_turn(&codex, "first turn").await;
+    submit_user_turn(&codex, "second turn").await;
+    codex.submit(Op::Compact).await.unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    submit_user_turn(&codex, "third turn").await;
+
+    let requests = request_log.requests();
+    assert_eq!(
+        requests.len(),
+        5,
+        "dropping tool outputs should not need a summarization request"
+    );
+    let after_compact = &requests[4];
+    let old_output = after_compact
+        .function_call_output_text("call-old")
+        .expect("old tool call is kept");
+    assert!(
+        !old_output.contains(VERBOSE_TOOL_NAME),
+        "output of the older turn should be replaced, got {old_output:?}"
+    );
+    let recent_output = after_compact
+        .function_call_output_text("call-recent")
+        .expect("recent tool call is kept");
+    assert!(
+        recent_output.contains(VERBOSE_TOOL_NAME),
+        "output of the most recent turn should be kept, got {recent_output:?}"
+    );
+    let user_texts = after_compact.message_input_texts("user");
+    for text in ["first turn", "second turn", "third turn"] {
+        assert!(
+            user_texts.iter().any(|user| user == text),
+            "expected {text:?} in {user_texts:?}"
+        );
+    }
+    let body = after_compact.body_json().to_string();
+    assert!(body_contains_text(&body, FIRST_REPLY));
+    assert!(!body_contains_text(&body, SUMMARY_PREFIX.trim()));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn keep_recent_turns_strategy_summarizes_only_older_turns() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+    let request_log = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_assistant_message("m1", FIRST_REPLY),
+                ev_completed("r1"),
+            ]),
+            sse(vec![
+                ev_function_call("call-recent", DUMMY_FUNCTION_NAME, "{}"),
+                ev_completed("r2"),
+            ]),
+            sse(vec![
+                ev_assistant_message("m2", "SECOND_REPLY"),
+                ev_completed("r3"),
+            ]),
+            sse(vec![
+                ev_assistant_message("m3", SUMMARY_TEXT),
+                ev_completed("r4"),
+            ]),
+            sse(vec![ev_completed("r5")]),
+        ],
+    )
+    .await;
+
+    let codex = test_codex()
+        .with_config(|config| {
+            set_test_compact_prompt(config);
+            config.compaction.strategy = CompactionStrategy::KeepRecentTurns;
+            config.compaction.keep_recent_turns = 1;
+        })
+        .build(&server)
+        .await
+        .expect("build codex")
+        .codex;
+
+    submit_user_turn(&codex, "first turn").await;
+    submit_user_turn(&codex, "second turn").await;
+    codex.submit(Op::Compact).await.unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+    submit_user_turn(&codex, "third turn").await;
+
+    let requests = request_log.requests();
+    assert_eq!(requests.len(), 5);
+
+    // Only the older turn is sent to be summarized.
+    let compact_body = requests[3].body_json().to_string();
+    assert!(body_contains_text(&compact_body, SUMMARIZATION_PROMPT));
+    assert!(body_contains_text(&compact_body, FIRST_REPLY));
+    assert!(!compact_body.contains("second turn"));
+    assert!(!requests[3].has_function_call("call-recent"));
+
+    // Afterwards the summary replaces the older turn and the recent turn is
+    // carried over verbatim, including its tool call.
+    let after_compact = &requests[4];
+    let body = after_compact.body_json().to_string();
+    assert!(body_contains_text(
+        &body,
+        &summary_with_prefix(SUMMARY_TEXT)
+    ));
+    assert!(!body_contains_text(&body, FIRST_REPLY));
+    assert!(body_contains_text(&body, "SECOND_REPLY"));
+    assert!(after_compact.has_function_call("call-recent"));
+    assert!(
+        after_compact
+            .function_call_output_text("call-recent")
+            .is_some_and(|output| output.contains(DUMMY_FUNCTION_NAME))
+    );
+    let user_texts = after_compact.message_input_texts("user");
+    for text in ["first turn", "second turn", "third turn"] {
+        assert!(
+            user_texts.iter().any(|user| user == text),
+            "expected {text:?} in {user_texts:?}"
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn auto_compact_triggers_at_configured_context_fraction() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+    // 6,000 tokens is past half of the 10,000 token window but below the
+    // default limit of 90%.
+    let request_log = mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_assistant_message("m1", FIRST_REPLY),
+                ev_completed_with_tokens("r1", 6_000),
+            ]),
+            sse(vec![
+                ev_assistant_message("m2", AUTO_SUMMARY_TEXT),
+                ev_completed_with_tokens("r2", 100),
+            ]),
+            sse(vec![
+                ev_assistant_message("m3", FINAL_REPLY),
+                ev_completed_with_tokens("r3", 200),
+            ]),
+        ],
+    )
+    .await;
+
+    let codex = test_codex()
+        .with_config(|config| {
+            set_test_compact_prompt(config);
+            config.model_context_window = Some(10_000);
+            config.model_auto_compact_token_limit = None;
+            config.compaction.trigger_fraction = Some(0.5);
+        })
+        .build(&server)
+        .await
+        .expect("build codex")
+        .codex;
+
+    submit_user_turn(&codex, FIRST_AUTO_MSG).await;
+    submit_user_turn(&codex, POST_AUTO_USER_MSG).await;
+
+    let requests = request_log.requests();
+    assert_eq!(
+        requests.len(),
+        3,
+        "expected the first turn, an auto compaction, and the follow-up turn"
+    );
+    let compact_body = requests[1].body_json().to_string();
+    assert!(body_contains_text(&compact_body, SUMMARIZATION_PROMPT));
+
+    let after_compact = &requests[2];
+    let body = after_compact.body_json().to_string();
+    assert!(body_contains_text(
+        &body,
+        &summary_with_prefix(AUTO_SUMMARY_TEXT)
+    ));
+    assert!(!body_contains_text(&body, FIRST_REPLY));
+    let user_texts = after_compact.message_input_texts("user");
+    for text in [FIRST_AUTO_MSG, POST_AUTO_USER_MSG] {
+        assert!(
+            user_texts.iter().any(|user| user == text),
+            "expected {text:?} in {user_texts:?}"
+        );
+    }
+}
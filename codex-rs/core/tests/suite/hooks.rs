@@ -0,0 +1,74 @@
+#![cfg(not(target_os = "windows"))]
+
+use anyhow::Result;
+use codex_core::config::types::HookCommand;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodexHarness;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn hook(script: String) -> HookCommand {
+    HookCommand {
+        command: vec!["sh".to_string(), "-c".to_string(), script],
+        tools: vec!["shell_command".to_string()],
+        timeout_ms: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn hooks_run_and_report_feedback_when_the_tool_fails() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let hook_dir = TempDir::new()?;
+    let post_event_path = hook_dir.path().join("post_tool_use.json");
+    let post_script = format!("cat > '{}'; echo post-feedback", post_event_path.display());
+    let harness = TestCodexHarness::with_config(move |config| {
+        config.hooks.pre_tool_use = vec![hook("cat > /dev/null; echo pre-feedback".to_string())];
+        config.hooks.post_tool_use = vec![hook(post_script)];
+    })
+    .await?;
+
+    // Arguments that fail to parse make the handler return an error.
+    let call_id = "hook-call";
+    mount_sse_sequence(
+        harness.server(),
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_function_call(call_id, "shell_command", "not json"),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "done"),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+    harness.submit("run a command").await?;
+
+    let output = harness.function_call_stdout(call_id).await;
+    assert!(
+        output.starts_with("failed to parse function arguments"),
+        "unexpected output: {output}"
+    );
+    assert!(
+        output.ends_with("\n\npre-feedback\npost-feedback"),
+        "unexpected output: {output}"
+    );
+
+    let post_event: Value = serde_json::from_str(&std::fs::read_to_string(&post_event_path)?)?;
+    assert_eq!(post_event["event"], json!("post_tool_use"));
+    assert_eq!(post_event["call_id"], json!(call_id));
+    assert_eq!(post_event["success"], json!(false));
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+//! Drives a full turn against a provider that speaks the Anthropic Messages
+//! wire API.
+
+use codex_core::ModelProviderInfo;
+use codex_core::WireApi;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::sse_response;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::TestCodex;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+fn messages_sse(events: &[Value]) -> String {
+    events
+        .iter()
+        .map(|event| {
+            format!(
+                "event: {}\ndata: {event}\n\n",
+                event["type"].as_str().unwrap_or("")
+            )
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn messages_provider_streams_a_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = MockServer::start().await;
+    let body = messages_sse(&[
+        json!({"type": "message_start", "message": {"id": "msg_1", "usage": {"input_tokens": 12, "output_tokens": 1}}}),
+        json!({"type": "content_block_start", "index": 0, "content_block": {"type": "text", "text": ""}}),
+        json!({"type": "content_block_delta", "index": 0, "delta": {"type": "text_delta", "text": "Hello from Claude"}}),
+        json!({"type": "content_block_stop", "index": 0}),
+        json!({"type": "message_delta", "delta": {"stop_reason": "end_turn"}, "usage": {"output_tokens": 5}}),
+        json!({"type": "message_stop"}),
+    ]);
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(sse_response(body))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let model_provider = ModelProviderInfo {
+        name: "anthropic".into(),
+        base_url: Some(format!("{}/v1", server.uri())),
+        env_key: None,
+        env_key_instructions: None,
+        experimental_bearer_token: Some("sk-ant-test".into()),
+        wire_api: WireApi::Messages,
+        query_params: None,
+        http_headers: None,
+        env_http_headers: None,
+        request_max_retries: Some(0),
+        stream_max_retries: Some(0),
+        stream_idle_timeout_ms: Some(2000),
+        requires_openai_auth: false,
+    };
+    let TestCodex { codex, .. } = test_codex()
+        .with_config(move |config| {
+            config.model_provider = model_provider;
+            config.model_max_output_tokens = Some(4096);
+        })
+        .build(&server)
+        .await?;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text { text: "hi".into() }],
+            final_output_json_schema: None,
+        })
+        .await?;
+    let message = wait_for_event(&codex, |ev| matches!(ev, EventMsg::AgentMessage(_))).await;
+    let EventMsg::AgentMessage(message) = message else {
+        unreachable!("waited for an agent message");
+    };
+    assert_eq!(message.message, "Hello from Claude");
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let requests = server.received_requests().await.unwrap_or_default();
+    let request = requests
+        .iter()
+        .find(|request| request.url.path() == "/v1/messages")
+        .expect("messages request");
+    assert_eq!(
+        request
+            .headers
+            .get("x-api-key")
+            .and_then(|value| value.to_str().ok()),
+        Some("sk-ant-test")
+    );
+    assert_eq!(request.headers.get("x-openai-subagent"), None);
+
+    let body: Value = request.body_json()?;
+    assert_eq!(body["max_tokens"], json!(4096));
+    assert_eq!(body["stream"], json!(true));
+    assert!(
+        body["system"]
+            .as_str()
+            .is_some_and(|system| !system.is_empty()),
+        "expected base instructions as the system prompt"
+    );
+    let messages = body["messages"].as_array().expect("messages array");
+    let last = messages.last().expect("at least one message");
+    assert_eq!(last["role"], json!("user"));
+    assert_eq!(
+        last["content"]
+            .as_array()
+            .and_then(|content| content.last()),
+        Some(&json!({"type": "text", "text": "hi"}))
+    );
+    let tools = body["tools"].as_array().expect("tools array");
+    assert!(!tools.is_empty());
+    for tool in tools {
+        assert!(tool["name"].is_string(), "tool without a name: {tool}");
+        assert!(
+            tool["input_schema"].is_object(),
+            "tool without a schema: {tool}"
+        );
+    }
+
+    Ok(())
+}
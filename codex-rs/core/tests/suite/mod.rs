@@ -20,6 +20,7 @@ mod apply_patch_cli;
 #[cfg(not(target_os = "windows"))]
 mod approvals;
 mod auth_refresh;
+mod budget;
 mod cli_stream;
 mod client;
 mod client_websockets;
@@ -33,12 +34,14 @@ mod exec_policy;
 mod fork_thread;
 mod grep_files;
 mod hierarchical_agents;
+mod hooks;
 mod image_rollout;
 mod items;
 mod json_result;
 mod list_dir;
 mod list_models;
 mod live_cli;
+mod messages_api;
 mod model_info_overrides;
 mod model_overrides;
 mod model_tools;
@@ -84,6 +84,7 @@ async fn review_op_emits_lifecycle_and_review_output() {
                     instructions: "Please review my changes".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -204,6 +205,7 @@ async fn review_op_with_plain_text_emits_review_fallback() {
                     instructions: "Plain text review".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -264,6 +266,7 @@ async fn review_filters_agent_message_related_events() {
                     instructions: "Filter streaming events".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -345,6 +348,7 @@ async fn review_does_not_emit_agent_message_on_structured_output() {
                     instructions: "check structured".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -404,6 +408,7 @@ async fn review_uses_custom_review_model_from_config() {
                     instructions: "use custom model".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -524,6 +529,7 @@ async fn review_input_isolated_from_parent_history() {
                     instructions: review_prompt.clone(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -635,6 +641,7 @@ async fn review_history_surfaces_in_parent_session() {
                     instructions: "Start a review".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
@@ -780,6 +787,7 @@ async fn review_uses_overridden_cwd_for_base_branch_merge_base() {
                     branch: "main".to_string(),
                 },
                 user_facing_hint: None,
+                paths: Vec::new(),
             },
         })
         .await
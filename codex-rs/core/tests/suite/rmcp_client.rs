@@ -15,6 +15,7 @@ use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
+use codex_core::protocol::McpToolCallProgressEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::SandboxPolicy;
 use codex_protocol::config_types::ReasoningSummary;
@@ -173,6 +174,347 @@ async fn stdio_server_round_trip() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_tool_call_progress_is_streamed() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+
+    let call_id = "call-progress";
+    let server_name = "rmcp";
+    let tool_name = format!("mcp__{server_name}__progress");
+
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_function_call(call_id, &tool_name, "{\"steps\":2}"),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-1", "rmcp progress tool completed."),
+            responses::ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+    let session_model = fixture.session_configured.model.clone();
+
+    fixture
+        .codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::Text {
+                text: "call the rmcp progress tool".into(),
+            }],
+            final_output_json_schema: None,
+            cwd: fixture.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            model: session_model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+        .await?;
+
+    let mut progress = Vec::new();
+    loop {
+        let event = wait_for_event(&fixture.codex, |ev| {
+            matches!(
+                ev,
+                EventMsg::McpToolCallProgress(_) | EventMsg::McpToolCallEnd(_)
+            )
+        })
+        .await;
+        match event {
+            EventMsg::McpToolCallProgress(update) if update.progress.is_some() => {
+                progress.push(update);
+            }
+            EventMsg::McpToolCallProgress(_) => {}
+            _ => break,
+        }
+    }
+    // Notifications may be handled out of order; compare them sorted.
+    progress.sort_by(|a, b| {
+        a.progress
+            .unwrap_or_default()
+            .total_cmp(&b.progress.unwrap_or_default())
+    });
+    assert_eq!(
+        progress,
+        vec![
+            McpToolCallProgressEvent {
+                call_id: call_id.to_string(),
+                progress: Some(1.0),
+                total: Some(2.0),
+                message: Some("step 1".to_string()),
+            },
+            McpToolCallProgressEvent {
+                call_id: call_id.to_string(),
+                progress: Some(2.0),
+                total: Some(2.0),
+                message: Some("step 2".to_string()),
+            },
+        ]
+    );
+
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_prompt_is_injected_into_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let response_mock = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-1", "Reviewed."),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+
+    let server_name = "rmcp";
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+    let session_model = fixture.session_configured.model.clone();
+
+    fixture
+        .codex
+        .submit(Op::UserTurn {
+            items: vec![UserInput::McpPrompt {
+                server: server_name.to_string(),
+                name: "review".to_string(),
+                arguments: HashMap::from([("topic".to_string(), "the parser".to_string())]),
+            }],
+            final_output_json_schema: None,
+            cwd: fixture.cwd.path().to_path_buf(),
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::ReadOnly,
+            model: session_model,
+            effort: None,
+            summary: ReasoningSummary::Auto,
+        })
+        .await?;
+
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let request = response_mock.single_request();
+    let user_texts = request.message_input_texts("user");
+    assert_eq!(
+        user_texts.last().map(String::as_str),
+        Some("Please review the parser and list concrete problems.")
+    );
+    let empty_user_messages = request
+        .inputs_of_type("message")
+        .into_iter()
+        .filter(|item| {
+            item.get("role").and_then(Value::as_str) == Some("user")
+                && item
+                    .get("content")
+                    .and_then(Value::as_array)
+                    .is_some_and(Vec::is_empty)
+        })
+        .count();
+    assert_eq!(empty_user_messages, 0, "no empty user message is recorded");
+    let assistant_messages: Vec<Value> = request
+        .inputs_of_type("message")
+        .into_iter()
+        .filter(|item| item.get("role").and_then(Value::as_str) == Some("assistant"))
+        .collect();
+    assert_eq!(
+        assistant_messages,
+        vec![json!({
+            "type": "message",
+            "role": "assistant",
+            "content": [{"type": "output_text", "text": "I will review the parser."}],
+        })]
+    );
+
+    Ok(())
+}
+
+fn request_tool_names(request: &responses::ResponsesRequest) -> Vec<String> {
+    request.body_json()["tools"]
+        .as_array()
+        .map(|tools| {
+            tools
+                .iter()
+                .filter_map(|tool| tool.get("name").and_then(Value::as_str))
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+#[serial(mcp_test_value)]
+async fn stdio_server_tool_list_changes_apply_to_next_turn() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = responses::start_mock_server().await;
+    let server_name = "rmcp";
+    let late_tool_name = format!("mcp__{server_name}__late_echo");
+
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp-1"),
+            responses::ev_function_call(
+                "call-1",
+                &format!("mcp__{server_name}__add_tool"),
+                "{\"name\":\"late_echo\"}",
+            ),
+            responses::ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-1", "Registered."),
+            responses::ev_completed("resp-2"),
+        ]),
+    )
+    .await;
+    let second_turn_mock = mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_assistant_message("msg-2", "Done."),
+            responses::ev_completed("resp-3"),
+        ]),
+    )
+    .await;
+
+    let rmcp_test_server_bin = stdio_server_bin()?;
+    let fixture = test_codex()
+        .with_config(move |config| {
+            let mut servers = config.mcp_servers.get().clone();
+            servers.insert(
+                server_name.to_string(),
+                McpServerConfig {
+                    transport: McpServerTransportConfig::Stdio {
+                        command: rmcp_test_server_bin,
+                        args: Vec::new(),
+                        env: None,
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                    enabled: true,
+                    startup_timeout_sec: Some(Duration::from_secs(10)),
+                    tool_timeout_sec: None,
+                    enabled_tools: None,
+                    disabled_tools: None,
+                },
+            );
+            config
+                .mcp_servers
+                .set(servers)
+                .expect("test mcp servers should accept any configuration");
+        })
+        .build(&server)
+        .await?;
+    let session_model = fixture.session_configured.model.clone();
+    let user_turn = |text: &str| Op::UserTurn {
+        items: vec![UserInput::Text { text: text.into() }],
+        final_output_json_schema: None,
+        cwd: fixture.cwd.path().to_path_buf(),
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::ReadOnly,
+        model: session_model.clone(),
+        effort: None,
+        summary: ReasoningSummary::Auto,
+    };
+
+    fixture.codex.submit(user_turn("register a tool")).await?;
+
+    // The refresh runs in the background, so it may land before or after the turn ends.
+    let mut tools_changed = None;
+    let mut turn_complete = false;
+    while tools_changed.is_none() || !turn_complete {
+        match wait_for_event(&fixture.codex, |ev| {
+            matches!(ev, EventMsg::McpToolsChanged(_) | EventMsg::TurnComplete(_))
+        })
+        .await
+        {
+            EventMsg::McpToolsChanged(ev) => tools_changed = Some(ev),
+            _ => turn_complete = true,
+        }
+    }
+    let tools_changed = tools_changed.expect("tools changed event");
+    assert_eq!(tools_changed.server, server_name);
+    assert!(tools_changed.tools.contains(&"late_echo".to_string()));
+
+    fixture.codex.submit(user_turn("use the new tool")).await?;
+    wait_for_event(&fixture.codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    assert!(request_tool_names(&second_turn_mock.single_request()).contains(&late_tool_name));
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 #[serial(mcp_test_value)]
 async fn stdio_image_responses_round_trip() -> anyhow::Result<()> {
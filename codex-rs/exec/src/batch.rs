@@ -0,0 +1,484 @@
+//! `codex exec --batch`: run every line of a JSONL file as its own thread.
+//!
+//! Each input line is either a JSON string (the prompt) or an object:
+//!
+//! ```json
+//! {"id": "pkg-a", "prompt": "Migrate to the new API", "cwd": "packages/a"}
+//! ```
+//!
+//! `id` defaults to the 1-based line number and `cwd` is resolved against the
+//! session working directory. One result line is appended to the output file
+//! as soon as each item finishes, so an interrupted batch can be resumed by
+//! running the same command again: items that already have a `completed`
+//! result are skipped, everything else is run (again).
+
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use codex_core::CodexThread;
+use codex_core::NewThread;
+use codex_core::ThreadManager;
+use codex_core::config::Config;
+use codex_core::protocol::AskForApproval;
+use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::TokenUsage;
+use codex_core::protocol::TurnCompleteEvent;
+use codex_protocol::ThreadId;
+use codex_protocol::approvals::ElicitationAction;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::user_input::UserInput;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::task::JoinSet;
+use tracing::warn;
+
+use crate::BUDGET_EXCEEDED_EXIT_CODE;
+use crate::output_schema::OUTPUT_SCHEMA_EXIT_CODE;
+use crate::output_schema::OutputSchemaValidator;
+use crate::output_schema::retry_prompt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BatchItem {
+    pub(crate) id: String,
+    pub(crate) prompt: String,
+    pub(crate) cwd: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BatchLine {
+    Prompt(String),
+    Item {
+        #[serde(default)]
+        id: Option<String>,
+        prompt: String,
+        #[serde(default)]
+        cwd: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum BatchItemStatus {
+    /// The turn finished without errors (and matched `--output-schema`).
+    Completed,
+    /// The agent reported an error or the thread could not be started.
+    Failed,
+    /// The final message did not match `--output-schema` after all retries.
+    SchemaFailed,
+}
+
+/// One line of the batch output file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BatchItemResult {
+    pub(crate) id: String,
+    pub(crate) status: BatchItemStatus,
+    pub(crate) exit_code: i32,
+    pub(crate) final_message: Option<String>,
+    pub(crate) error: Option<String>,
+    pub(crate) usage: Option<TokenUsage>,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) rollout_path: Option<PathBuf>,
+}
+
+/// Per-turn settings shared by every item in the batch.
+pub(crate) struct BatchTurnSettings {
+    pub(crate) approval_policy: AskForApproval,
+    pub(crate) sandbox_policy: SandboxPolicy,
+    pub(crate) model: String,
+    pub(crate) effort: Option<ReasoningEffort>,
+    pub(crate) summary: ReasoningSummary,
+    pub(crate) output_schema: Option<Value>,
+    pub(crate) output_schema_retries: usize,
+}
+
+pub(crate) struct BatchOptions {
+    pub(crate) input: PathBuf,
+    pub(crate) output: Option<PathBuf>,
+    pub(crate) parallel: usize,
+}
+
+/// Runs the batch and returns `true` when every item completed.
+pub(crate) async fn run_batch(
+    thread_manager: Arc<ThreadManager>,
+    config: Config,
+    settings: BatchTurnSettings,
+    options: BatchOptions,
+) -> anyhow::Result<bool> {
+    let contents = std::fs::read_to_string(&options.input)
+        .with_context(|| format!("failed to read batch file {}", options.input.display()))?;
+    let items = parse_batch(&contents)
+        .with_context(|| format!("invalid batch file {}", options.input.display()))?;
+    let output_path = options
+        .output
+        .unwrap_or_else(|| default_output_path(&options.input));
+    let completed = load_completed_ids(&output_path)?;
+
+    let pending: Vec<BatchItem> = items
+        .into_iter()
+        .filter(|item| !completed.contains(&item.id))
+        .collect();
+    if !completed.is_empty() {
+        eprintln!(
+            "Resuming batch: skipping {} completed item(s) recorded in {}",
+            completed.len(),
+            output_path.display()
+        );
+    }
+    let total = pending.len();
+    eprintln!(
+        "Running {total} item(s) with parallelism {}; writing results to {}",
+        options.parallel,
+        output_path.display()
+    );
+
+    let mut output = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&output_path)
+        .with_context(|| format!("failed to open {}", output_path.display()))?;
+    // A previous run may have been killed mid-line; start on a fresh line so
+    // the truncated record does not swallow the next result.
+    if std::fs::read(&output_path).is_ok_and(|bytes| bytes.last().is_some_and(|b| *b != b'\n')) {
+        writeln!(output)?;
+    }
+
+    let settings = Arc::new(settings);
+    let mut pending = pending.into_iter();
+    let mut running = JoinSet::new();
+    let mut finished = 0;
+    let mut all_completed = true;
+    loop {
+        while running.len() < options.parallel.max(1)
+            && let Some(item) = pending.next()
+        {
+            running.spawn(run_item(
+                Arc::clone(&thread_manager),
+                config.clone(),
+                Arc::clone(&settings),
+                item,
+            ));
+        }
+        let Some(joined) = running.join_next().await else {
+            break;
+        };
+        let result = joined.context("batch item panicked")?;
+        finished += 1;
+        all_completed &= result.status == BatchItemStatus::Completed;
+        eprintln!(
+            "[{finished}/{total}] {}: {}",
+            result.id,
+            match result.status {
+                BatchItemStatus::Completed => "completed",
+                BatchItemStatus::Failed => "failed",
+                BatchItemStatus::SchemaFailed => "output did not match schema",
+            }
+        );
+        let line = serde_json::to_string(&result)?;
+        writeln!(output, "{line}")
+            .and_then(|()| output.flush())
+            .with_context(|| format!("failed to write {}", output_path.display()))?;
+    }
+
+    Ok(all_completed)
+}
+
+pub(crate) fn parse_batch(contents: &str) -> anyhow::Result<Vec<BatchItem>> {
+    let mut items = Vec::new();
+    let mut seen = HashSet::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parsed: BatchLine = serde_json::from_str(line).with_context(|| {
+            format!("line {line_number}: expected a JSON string or an object with a `prompt`")
+        })?;
+        let item = match parsed {
+            BatchLine::Prompt(prompt) => BatchItem {
+                id: line_number.to_string(),
+                prompt,
+                cwd: None,
+            },
+            BatchLine::Item { id, prompt, cwd } => BatchItem {
+                id: id.unwrap_or_else(|| line_number.to_string()),
+                prompt,
+                cwd,
+            },
+        };
+        if item.prompt.trim().is_empty() {
+            anyhow::bail!("line {line_number}: prompt must not be empty");
+        }
+        if !seen.insert(item.id.clone()) {
+            anyhow::bail!("line {line_number}: duplicate id `{}`", item.id);
+        }
+        items.push(item);
+    }
+    Ok(items)
+}
+
+/// `prompts.jsonl` -> `prompts.results.jsonl`.
+fn default_output_path(input: &Path) -> PathBuf {
+    let stem = input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "batch".to_string());
+    input.with_file_name(format!("{stem}.results.jsonl"))
+}
+
+/// Ids that already have a `completed` result in `output_path`.
+fn load_completed_ids(output_path: &Path) -> anyhow::Result<HashSet<String>> {
+    #[derive(Deserialize)]
+    struct RecordedResult {
+        id: String,
+        status: BatchItemStatus,
+    }
+
+    let contents = match std::fs::read_to_string(output_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(HashSet::new()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("failed to read {}", output_path.display()));
+        }
+    };
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<RecordedResult>(line).ok())
+        .filter(|result| result.status == BatchItemStatus::Completed)
+        .map(|result| result.id)
+        .collect())
+}
+
+async fn run_item(
+    thread_manager: Arc<ThreadManager>,
+    mut config: Config,
+    settings: Arc<BatchTurnSettings>,
+    item: BatchItem,
+) -> BatchItemResult {
+    let mut result = BatchItemResult {
+        id: item.id.clone(),
+        status: BatchItemStatus::Failed,
+        exit_code: 1,
+        final_message: None,
+        error: None,
+        usage: None,
+        thread_id: None,
+        rollout_path: None,
+    };
+
+    if let Some(cwd) = &item.cwd {
+        config.cwd = config.cwd.join(cwd);
+    }
+    let cwd = config.cwd.clone();
+    let NewThread {
+        thread_id, thread, ..
+    } = match thread_manager.start_thread(config).await {
+        Ok(new_thread) => new_thread,
+        Err(err) => {
+            result.error = Some(format!("failed to start thread: {err}"));
+            return result;
+        }
+    };
+    result.thread_id = Some(thread_id.to_string());
+    result.rollout_path = Some(thread.rollout_path());
+
+    let mut validator = match settings.output_schema.as_ref() {
+        Some(schema) => match OutputSchemaValidator::new(schema, settings.output_schema_retries) {
+            Ok(validator) => Some(validator),
+            Err(err) => {
+                result.error = Some(format!("output schema is not a valid JSON Schema: {err}"));
+                return result;
+            }
+        },
+        None => None,
+    };
+
+    let user_turn = |text: String| Op::UserTurn {
+        items: vec![UserInput::Text { text }],
+        cwd: cwd.clone(),
+        approval_policy: settings.approval_policy,
+        sandbox_policy: settings.sandbox_policy.clone(),
+        model: settings.model.clone(),
+        effort: settings.effort,
+        summary: settings.summary,
+        final_output_json_schema: settings.output_schema.clone(),
+    };
+
+    if let Err(err) = thread.submit(user_turn(item.prompt.clone())).await {
+        result.error = Some(err.to_string());
+        return result;
+    }
+
+    loop {
+        let event = match thread.next_event().await {
+            Ok(event) => event,
+            Err(err) => {
+                result.error = Some(err.to_string());
+                break;
+            }
+        };
+        match event.msg {
+            EventMsg::Error(ev) => {
+                if ev.codex_error_info == Some(CodexErrorInfo::BudgetExceeded) {
+                    result.exit_code = BUDGET_EXCEEDED_EXIT_CODE;
+                }
+                result.error = Some(ev.message);
+            }
+            EventMsg::TokenCount(ev) => {
+                if let Some(info) = ev.info {
+                    result.usage = Some(info.total_token_usage);
+                }
+            }
+            EventMsg::ElicitationRequest(ev) => {
+                // Nobody can answer elicitations in batch mode.
+                let _ = thread
+                    .submit(Op::ResolveElicitation {
+                        server_name: ev.server_name,
+                        request_id: ev.id,
+                        decision: ElicitationAction::Cancel,
+                    })
+                    .await;
+            }
+            EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }) => {
+                if result.error.is_none()
+                    && let Some(validator) = validator.as_mut()
+                {
+                    let errors = validator.validate(last_agent_message.as_deref());
+                    if !errors.is_empty() {
+                        if validator.take_retry() {
+                            if let Err(err) = thread.submit(user_turn(retry_prompt(&errors))).await
+                            {
+                                result.error = Some(err.to_string());
+                                break;
+                            }
+                            continue;
+                        }
+                        result.status = BatchItemStatus::SchemaFailed;
+                        result.exit_code = OUTPUT_SCHEMA_EXIT_CODE;
+                        result.error = Some(format!(
+                            "Final message does not match the output schema: {}",
+                            errors.join("; ")
+                        ));
+                    }
+                }
+                if result.error.is_none() {
+                    result.status = BatchItemStatus::Completed;
+                    result.exit_code = 0;
+                }
+                result.final_message = last_agent_message;
+                break;
+            }
+            EventMsg::ShutdownComplete => {
+                result.error = Some("thread shut down before the turn completed".to_string());
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    shutdown_thread(&thread_manager, &thread_id, &thread).await;
+    result
+}
+
+async fn shutdown_thread(
+    thread_manager: &ThreadManager,
+    thread_id: &ThreadId,
+    thread: &CodexThread,
+) {
+    if let Err(err) = thread.submit(Op::Shutdown).await {
+        warn!("failed to shut down batch thread {thread_id}: {err}");
+    } else {
+        while let Ok(event) = thread.next_event().await {
+            if matches!(event.msg, EventMsg::ShutdownComplete) {
+                break;
+            }
+        }
+    }
+    thread_manager.remove_thread(thread_id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_strings_and_objects() {
+        let items = parse_batch(
+            r#""Fix the build"
+
+{"id": "pkg-a", "prompt": "Migrate", "cwd": "packages/a"}
+{"prompt": "Migrate again"}
+"#,
+        )
+        .expect("valid batch");
+        assert_eq!(
+            items,
+            vec![
+                BatchItem {
+                    id: "1".to_string(),
+                    prompt: "Fix the build".to_string(),
+                    cwd: None,
+                },
+                BatchItem {
+                    id: "pkg-a".to_string(),
+                    prompt: "Migrate".to_string(),
+                    cwd: Some(PathBuf::from("packages/a")),
+                },
+                BatchItem {
+                    id: "4".to_string(),
+                    prompt: "Migrate again".to_string(),
+                    cwd: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_ids_and_bad_lines() {
+        let err =
+            parse_batch("{\"id\": \"a\", \"prompt\": \"x\"}\n{\"id\": \"a\", \"prompt\": \"y\"}")
+                .expect_err("duplicate id");
+        assert_eq!(err.to_string(), "line 2: duplicate id `a`");
+
+        let err = parse_batch("42").expect_err("not a prompt");
+        assert_eq!(
+            err.to_string(),
+            "line 1: expected a JSON string or an object with a `prompt`"
+        );
+    }
+
+    #[test]
+    fn resume_skips_only_completed_items() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let output = dir.path().join("prompts.results.jsonl");
+        std::fs::write(
+            &output,
+            concat!(
+                "{\"id\":\"a\",\"status\":\"completed\",\"exit_code\":0}\n",
+                "{\"id\":\"b\",\"status\":\"failed\",\"exit_code\":1}\n",
+                "{\"id\":\"c\",\"status\":\"schema_failed\",\"exit_code\":3}\n",
+                "truncated line",
+            ),
+        )
+        .expect("write results");
+
+        assert_eq!(
+            load_completed_ids(&output).expect("load"),
+            HashSet::from(["a".to_string()])
+        );
+        assert_eq!(
+            default_output_path(Path::new("/tmp/prompts.jsonl")),
+            PathBuf::from("/tmp/prompts.results.jsonl")
+        );
+    }
+}
@@ -73,6 +73,42 @@ pub struct Cli {
     #[arg(long = "output-schema", value_name = "FILE")]
     pub output_schema: Option<PathBuf>,
 
+    /// How many times to ask the model to fix a final message that does not
+    /// match `--output-schema` before exiting with status 3.
+    #[arg(
+        long = "output-schema-retries",
+        value_name = "N",
+        default_value_t = 2,
+        requires = "output_schema"
+    )]
+    pub output_schema_retries: usize,
+
+    /// Run every line of this JSONL file as an independent session instead of
+    /// a single prompt. Lines are JSON strings or objects with `prompt` and
+    /// optional `id` and `cwd` fields.
+    #[arg(
+        long = "batch",
+        value_name = "FILE",
+        conflicts_with_all = ["prompt", "images", "last_message_file"]
+    )]
+    pub batch: Option<PathBuf>,
+
+    /// Number of batch items to run concurrently.
+    #[arg(
+        long = "parallel",
+        value_name = "N",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u16).range(1..),
+        requires = "batch"
+    )]
+    pub parallel: u16,
+
+    /// Where to write per-item batch results (JSONL). Defaults to
+    /// `<batch>.results.jsonl`; items already recorded as completed there are
+    /// skipped, so re-running resumes an interrupted batch.
+    #[arg(long = "batch-output", value_name = "FILE", requires = "batch")]
+    pub batch_output: Option<PathBuf>,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 
@@ -140,7 +176,7 @@ pub struct ReviewArgs {
     #[arg(
         long = "uncommitted",
         default_value_t = false,
-        conflicts_with_all = ["base", "commit", "prompt"]
+        conflicts_with_all = ["base", "upstream", "commit", "range", "prompt"]
     )]
     pub uncommitted: bool,
 
@@ -148,18 +184,39 @@ pub struct ReviewArgs {
     #[arg(
         long = "base",
         value_name = "BRANCH",
-        conflicts_with_all = ["uncommitted", "commit", "prompt"]
+        conflicts_with_all = ["uncommitted", "upstream", "commit", "range", "prompt"]
     )]
     pub base: Option<String>,
 
+    /// Review changes against the branch a pull request would target: the
+    /// upstream tracking branch, or the remote's default branch.
+    #[arg(
+        long = "upstream",
+        default_value_t = false,
+        conflicts_with_all = ["uncommitted", "base", "commit", "range", "prompt"]
+    )]
+    pub upstream: bool,
+
     /// Review the changes introduced by a commit.
     #[arg(
         long = "commit",
         value_name = "SHA",
-        conflicts_with_all = ["uncommitted", "base", "prompt"]
+        conflicts_with_all = ["uncommitted", "base", "upstream", "range", "prompt"]
     )]
     pub commit: Option<String>,
 
+    /// Review the commits in a range, e.g. `HEAD~3..HEAD`.
+    #[arg(
+        long = "range",
+        value_name = "FROM..TO",
+        conflicts_with_all = ["uncommitted", "base", "upstream", "commit", "prompt"]
+    )]
+    pub range: Option<String>,
+
+    /// Only review changes to this path. May be repeated.
+    #[arg(long = "path", value_name = "PATH")]
+    pub paths: Vec<String>,
+
     /// Optional commit title to display in the review summary.
     #[arg(long = "title", value_name = "TITLE", requires = "commit")]
     pub commit_title: Option<String>,
@@ -167,6 +224,29 @@ pub struct ReviewArgs {
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
+
+    /// Print the findings in this format instead of the final message.
+    #[arg(long = "format", value_enum, default_value_t = ReviewFormat::Text)]
+    pub format: ReviewFormat,
+
+    /// Write the formatted findings to FILE instead of stdout.
+    #[arg(long = "report-file", value_name = "FILE")]
+    pub report_file: Option<PathBuf>,
+}
+
+/// Output format for review findings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum ReviewFormat {
+    /// Human-readable summary.
+    #[default]
+    Text,
+    /// The review output as JSON.
+    Json,
+    /// SARIF 2.1.0 log, for code-scanning uploads.
+    Sarif,
+    /// Payload for GitHub's "create a review for a pull request" API.
+    Github,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
@@ -14,6 +14,7 @@ use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::McpToolCallProgressEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::SessionConfiguredEvent;
@@ -219,6 +220,15 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 };
                 ts_msg!(self, "{} {}", "mcp startup:".style(self.cyan), joined);
             }
+            EventMsg::McpToolsChanged(update) => {
+                ts_msg!(
+                    self,
+                    "{} {} tools changed ({} available)",
+                    "mcp:".style(self.cyan),
+                    update.server,
+                    update.tools.len()
+                );
+            }
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
@@ -331,6 +341,13 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     format_mcp_invocation(&invocation).style(self.bold),
                 );
             }
+            EventMsg::McpToolCallProgress(progress) => {
+                ts_msg!(
+                    self,
+                    "{}",
+                    format_mcp_progress(&progress).style(self.dimmed)
+                );
+            }
             EventMsg::McpToolCallEnd(tool_call_end_event) => {
                 let is_success = tool_call_end_event.is_success();
                 let McpToolCallEndEvent {
@@ -580,6 +597,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
+            | EventMsg::ListMcpPromptsResponse(_)
             | EventMsg::ListSkillsResponse(_)
             | EventMsg::RawResponseItem(_)
             | EventMsg::UserMessage(_)
@@ -642,6 +660,20 @@ fn format_file_change(change: &FileChange) -> &'static str {
     }
 }
 
+fn format_mcp_progress(progress: &McpToolCallProgressEvent) -> String {
+    let amount = match (progress.progress, progress.total) {
+        (Some(done), Some(total)) => Some(format!("{done}/{total}")),
+        (Some(done), None) => Some(done.to_string()),
+        (None, _) => None,
+    };
+    match (amount, progress.message.as_deref()) {
+        (Some(amount), Some(message)) => format!("progress {amount}: {message}"),
+        (Some(amount), None) => format!("progress {amount}"),
+        (None, Some(message)) => message.to_string(),
+        (None, None) => "progress".to_string(),
+    }
+}
+
 fn format_mcp_invocation(invocation: &McpInvocation) -> String {
     // Build fully-qualified tool name: server.tool
     let fq_tool_name = format!("{}.{}", invocation.server, invocation.tool);
@@ -22,6 +22,7 @@ use crate::exec_events::PatchApplyStatus;
 use crate::exec_events::PatchChangeKind;
 use crate::exec_events::ReasoningItem;
 use crate::exec_events::ThreadErrorEvent;
+use crate::exec_events::ThreadErrorKind;
 use crate::exec_events::ThreadEvent;
 use crate::exec_events::ThreadItem;
 use crate::exec_events::ThreadItemDetails;
@@ -115,8 +116,15 @@ impl EventProcessorWithJsonOutput {
             protocol::EventMsg::TurnStarted(ev) => self.handle_task_started(ev),
             protocol::EventMsg::TurnComplete(_) => self.handle_task_complete(),
             protocol::EventMsg::Error(ev) => {
+                let kind = match ev.codex_error_info {
+                    Some(protocol::CodexErrorInfo::BudgetExceeded) => {
+                        Some(ThreadErrorKind::BudgetExceeded)
+                    }
+                    _ => None,
+                };
                 let error = ThreadErrorEvent {
                     message: ev.message.clone(),
+                    kind,
                 };
                 self.last_critical_error = Some(error.clone());
                 vec![ThreadEvent::Error(error)]
@@ -137,7 +145,10 @@ impl EventProcessorWithJsonOutput {
                     }
                     _ => ev.message.clone(),
                 };
-                vec![ThreadEvent::Error(ThreadErrorEvent { message })]
+                vec![ThreadEvent::Error(ThreadErrorEvent {
+                    message,
+                    kind: None,
+                })]
             }
             protocol::EventMsg::PlanUpdate(ev) => self.handle_plan_update(ev),
             _ => Vec::new(),
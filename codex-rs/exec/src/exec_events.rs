@@ -85,6 +85,18 @@ pub struct ItemUpdatedEvent {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ThreadErrorEvent {
     pub message: String,
+    /// Machine-readable category, set for errors automation may want to
+    /// handle differently from a generic failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub kind: Option<ThreadErrorKind>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreadErrorKind {
+    /// A configured session or turn token/cost budget was exceeded.
+    BudgetExceeded,
 }
 
 /// Canonical representation of a thread item and its domain-specific payload.
@@ -1,18 +1,22 @@
 // - In the default output mode, it is paramount that the only thing written to
-//   stdout is the final message (if any).
+//   stdout is the final message (if any), or the findings report requested
+//   with `review --format`.
 // - In --json mode, stdout must be valid JSONL, one event per line.
 // For both modes, any other output must be written to stderr.
 #![deny(clippy::print_stdout)]
 
+mod batch;
 mod cli;
 mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod output_schema;
 
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
+pub use cli::ReviewFormat;
 use codex_common::oss::ensure_oss_provider_ready;
 use codex_common::oss::get_default_model_for_oss_provider;
 use codex_common::oss::ollama_chat_deprecation_notice;
@@ -31,22 +35,30 @@ use codex_core::config::resolve_oss_provider;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::models_manager::manager::RefreshStrategy;
 use codex_core::protocol::AskForApproval;
+use codex_core::protocol::CodexErrorInfo;
+use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewOutputEvent;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SessionSource;
+use codex_core::protocol::TurnCompleteEvent;
+use codex_core::protocol::WarningEvent;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+pub use output_schema::OUTPUT_SCHEMA_EXIT_CODE;
 use serde_json::Value;
 use std::io::IsTerminal;
 use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Arc;
 use supports_color::Stream;
 use tracing::debug;
 use tracing::error;
@@ -54,11 +66,26 @@ use tracing::info;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::prelude::*;
 
+use crate::batch::BatchOptions;
+use crate::batch::BatchTurnSettings;
+use crate::batch::run_batch;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::output_schema::OutputSchemaValidator;
+use crate::output_schema::retry_prompt;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_thread_path_by_id_str;
+use codex_core::review_export::ReviewedDiff;
+use codex_core::review_export::review_output_to_github_review;
+use codex_core::review_export::review_output_to_sarif;
+use codex_core::review_export::reviewed_commit;
+use codex_core::review_export::reviewed_diff;
+use codex_core::review_format::render_review_output_text;
+
+/// Exit status used when a configured token or cost budget stopped the run,
+/// so automation can tell it apart from other agent failures (`1`).
+pub const BUDGET_EXCEEDED_EXIT_CODE: i32 = 4;
 
 enum InitialOperation {
     UserTurn {
@@ -70,6 +97,15 @@ enum InitialOperation {
     },
 }
 
+/// Findings report requested with `review --format` / `--report-file`.
+struct ReviewReport {
+    format: ReviewFormat,
+    file: Option<PathBuf>,
+    /// What was reviewed; the GitHub format anchors comments to its diff and
+    /// to the reviewed commit.
+    target: ReviewTarget,
+}
+
 pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()> {
     if let Err(err) = set_default_originator("codex_exec".to_string()) {
         tracing::warn!(?err, "Failed to set codex exec originator override {err:?}");
@@ -93,6 +129,10 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
         output_schema: output_schema_path,
+        output_schema_retries,
+        batch: batch_path,
+        parallel,
+        batch_output,
         config_overrides,
     } = cli;
 
@@ -316,6 +356,32 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         .get_default_model(&config.model, &config, RefreshStrategy::OnlineIfUncached)
         .await;
 
+    if let Some(input) = batch_path {
+        if command.is_some() {
+            eprintln!("--batch cannot be combined with a subcommand.");
+            std::process::exit(1);
+        }
+        let settings = BatchTurnSettings {
+            approval_policy: default_approval_policy,
+            sandbox_policy: default_sandbox_policy.clone(),
+            model: default_model,
+            effort: default_effort,
+            summary: default_summary,
+            output_schema: load_output_schema(output_schema_path),
+            output_schema_retries,
+        };
+        let options = BatchOptions {
+            input,
+            output: batch_output,
+            parallel: usize::from(parallel),
+        };
+        let all_completed = run_batch(Arc::new(thread_manager), config, settings, options).await?;
+        if !all_completed {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Handle resume subcommand by resolving a rollout path and using explicit resume API.
     let NewThread {
         thread_id: _,
@@ -334,10 +400,30 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     } else {
         thread_manager.start_thread(config.clone()).await?
     };
+    let mut review_report = None;
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
-            let review_request = build_review_request(review_cli)?;
-            let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
+            let wants_report =
+                review_cli.format != ReviewFormat::Text || review_cli.report_file.is_some();
+            if wants_report && json_mode && review_cli.report_file.is_none() {
+                anyhow::bail!(
+                    "--json already writes events to stdout; use --report-file to write the review report"
+                );
+            }
+            let format = review_cli.format;
+            let report_file = review_cli.report_file.clone();
+            let review_request = build_review_request(review_cli, &default_cwd)?;
+            if wants_report {
+                review_report = Some(ReviewReport {
+                    format,
+                    file: report_file,
+                    target: review_request.target.clone(),
+                });
+            }
+            let summary = codex_core::review_prompts::user_facing_hint_with_paths(
+                &review_request.target,
+                &review_request.paths,
+            );
             (InitialOperation::Review { review_request }, summary)
         }
         (Some(ExecCommand::Resume(args)), root_prompt, imgs) => {
@@ -435,6 +521,22 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         });
     }
 
+    // Validate the final message locally when an output schema was given; the
+    // model does not always honor it.
+    let mut output_schema_validator = match &initial_operation {
+        InitialOperation::UserTurn {
+            output_schema: Some(schema),
+            ..
+        } => match OutputSchemaValidator::new(schema, output_schema_retries) {
+            Ok(validator) => Some((validator, schema.clone())),
+            Err(err) => {
+                eprintln!("Output schema is not a valid JSON Schema: {err}");
+                std::process::exit(1);
+            }
+        },
+        _ => None,
+    };
+
     match initial_operation {
         InitialOperation::UserTurn {
             items,
@@ -443,10 +545,10 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             let task_id = thread
                 .submit(Op::UserTurn {
                     items,
-                    cwd: default_cwd,
+                    cwd: default_cwd.clone(),
                     approval_policy: default_approval_policy,
                     sandbox_policy: default_sandbox_policy.clone(),
-                    model: default_model,
+                    model: default_model.clone(),
                     effort: default_effort,
                     summary: default_summary,
                     final_output_json_schema: output_schema,
@@ -466,6 +568,11 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
+    let mut budget_exceeded = false;
+    // Set when the final message still fails `--output-schema` validation
+    // after all retries.
+    let mut output_schema_failed = false;
+    let mut review_output: Option<ReviewOutputEvent> = None;
     while let Some(event) = rx.recv().await {
         if let EventMsg::ElicitationRequest(ev) = &event.msg {
             // Automatically cancel elicitation requests in exec mode.
@@ -477,10 +584,69 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
                 })
                 .await?;
         }
-        if matches!(event.msg, EventMsg::Error(_)) {
+        if let EventMsg::Error(ev) = &event.msg {
             error_seen = true;
+            budget_exceeded |= ev.codex_error_info == Some(CodexErrorInfo::BudgetExceeded);
+        }
+        if let EventMsg::ExitedReviewMode(ev) = &event.msg {
+            review_output = ev.review_output.clone();
         }
+
+        // Only validate turns the agent finished without errors, so callers
+        // can tell a model that ignored the schema from an agent failure.
+        let mut retry = None;
+        if let EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }) = &event.msg
+            && !error_seen
+            && let Some((validator, schema)) = output_schema_validator.as_mut()
+        {
+            let errors = validator.validate(last_agent_message.as_deref());
+            if !errors.is_empty() {
+                if validator.take_retry() {
+                    retry = Some((errors, schema.clone()));
+                } else {
+                    output_schema_failed = true;
+                    event_processor.process_event(Event {
+                        id: event.id.clone(),
+                        msg: EventMsg::Error(ErrorEvent {
+                            message: format!(
+                                "Final message does not match the output schema:\n- {}",
+                                errors.join("\n- ")
+                            ),
+                            codex_error_info: None,
+                        }),
+                    });
+                }
+            }
+        }
+
+        let event_id = event.id.clone();
         let shutdown: CodexStatus = event_processor.process_event(event);
+        if let Some((errors, schema)) = retry {
+            event_processor.process_event(Event {
+                id: event_id,
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!(
+                        "Final message does not match the output schema ({}); asking the model to fix it.",
+                        errors.join("; ")
+                    ),
+                }),
+            });
+            thread
+                .submit(Op::UserTurn {
+                    items: vec![UserInput::Text {
+                        text: retry_prompt(&errors),
+                    }],
+                    cwd: default_cwd.clone(),
+                    approval_policy: default_approval_policy,
+                    sandbox_policy: default_sandbox_policy.clone(),
+                    model: default_model.clone(),
+                    effort: default_effort,
+                    summary: default_summary,
+                    final_output_json_schema: Some(schema),
+                })
+                .await?;
+            continue;
+        }
         match shutdown {
             CodexStatus::Running => continue,
             CodexStatus::InitiateShutdown => {
@@ -491,7 +657,42 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             }
         }
     }
-    event_processor.print_final_output();
+    // A report on stdout replaces the final message.
+    if !review_report
+        .as_ref()
+        .is_some_and(|report| report.file.is_none())
+    {
+        event_processor.print_final_output();
+    }
+    if let Some(report) = review_report {
+        let Some(output) = review_output else {
+            eprintln!("The review did not produce any findings output; no report was written.");
+            std::process::exit(1);
+        };
+        let root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.clone());
+        let (commit, diff) = match report.format {
+            ReviewFormat::Github => (
+                reviewed_commit(&report.target, &root).await,
+                reviewed_diff(&report.target, &root).await,
+            ),
+            _ => (None, ReviewedDiff::NotADiff),
+        };
+        let rendered =
+            render_review_report(report.format, &output, &root, commit.as_deref(), &diff)?;
+        match report.file {
+            Some(path) => std::fs::write(&path, rendered).map_err(|err| {
+                anyhow::anyhow!("failed to write review report to {}: {err}", path.display())
+            })?,
+            #[allow(clippy::print_stdout)]
+            None => println!("{rendered}"),
+        }
+    }
+    if output_schema_failed {
+        std::process::exit(OUTPUT_SCHEMA_EXIT_CODE);
+    }
+    if budget_exceeded {
+        std::process::exit(BUDGET_EXCEEDED_EXIT_CODE);
+    }
     if error_seen {
         std::process::exit(1);
     }
@@ -499,6 +700,22 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     Ok(())
 }
 
+fn render_review_report(
+    format: ReviewFormat,
+    output: &ReviewOutputEvent,
+    root: &Path,
+    commit: Option<&str>,
+    diff: &ReviewedDiff,
+) -> anyhow::Result<String> {
+    let value = match format {
+        ReviewFormat::Text => return Ok(render_review_output_text(output)),
+        ReviewFormat::Json => serde_json::to_value(output)?,
+        ReviewFormat::Sarif => review_output_to_sarif(output, root),
+        ReviewFormat::Github => review_output_to_github_review(output, root, commit, diff),
+    };
+    Ok(serde_json::to_string_pretty(&value)?)
+}
+
 async fn resolve_resume_path(
     config: &Config,
     args: &crate::cli::ResumeArgs,
@@ -584,16 +801,20 @@ fn resolve_prompt(prompt_arg: Option<String>) -> String {
     }
 }
 
-fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
+fn build_review_request(args: ReviewArgs, cwd: &Path) -> anyhow::Result<ReviewRequest> {
     let target = if args.uncommitted {
         ReviewTarget::UncommittedChanges
     } else if let Some(branch) = args.base {
         ReviewTarget::BaseBranch { branch }
+    } else if args.upstream {
+        codex_core::review_prompts::upstream_review_target(cwd)?
     } else if let Some(sha) = args.commit {
         ReviewTarget::Commit {
             sha,
             title: args.commit_title,
         }
+    } else if let Some(range) = args.range {
+        parse_commit_range(&range)?
     } else if let Some(prompt_arg) = args.prompt {
         let prompt = resolve_prompt(Some(prompt_arg)).trim().to_string();
         if prompt.is_empty() {
@@ -604,13 +825,29 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --upstream, --commit, --range, or provide custom review instructions"
         );
     };
 
     Ok(ReviewRequest {
         target,
         user_facing_hint: None,
+        paths: args.paths,
+    })
+}
+
+/// Parses `FROM..TO` into a commit-range review target.
+fn parse_commit_range(range: &str) -> anyhow::Result<ReviewTarget> {
+    let Some((from, to)) = range.split_once("..") else {
+        anyhow::bail!("--range expects FROM..TO, e.g. HEAD~3..HEAD");
+    };
+    let (from, to) = (from.trim(), to.trim());
+    if from.is_empty() || to.is_empty() || to.starts_with('.') {
+        anyhow::bail!("--range expects FROM..TO, e.g. HEAD~3..HEAD");
+    }
+    Ok(ReviewTarget::CommitRange {
+        from: from.to_string(),
+        to: to.to_string(),
     })
 }
 
@@ -621,18 +858,27 @@ mod tests {
 
     #[test]
     fn builds_uncommitted_review_request() {
-        let request = build_review_request(ReviewArgs {
-            uncommitted: true,
-            base: None,
-            commit: None,
-            commit_title: None,
-            prompt: None,
-        })
+        let request = build_review_request(
+            ReviewArgs {
+                uncommitted: true,
+                base: None,
+                upstream: false,
+                commit: None,
+                range: None,
+                commit_title: None,
+                prompt: None,
+                format: ReviewFormat::Text,
+                report_file: None,
+                paths: Vec::new(),
+            },
+            Path::new("."),
+        )
         .expect("builds uncommitted review request");
 
         let expected = ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            paths: Vec::new(),
         };
 
         assert_eq!(request, expected);
@@ -640,13 +886,21 @@ mod tests {
 
     #[test]
     fn builds_commit_review_request_with_title() {
-        let request = build_review_request(ReviewArgs {
-            uncommitted: false,
-            base: None,
-            commit: Some("123456789".to_string()),
-            commit_title: Some("Add review command".to_string()),
-            prompt: None,
-        })
+        let request = build_review_request(
+            ReviewArgs {
+                uncommitted: false,
+                base: None,
+                upstream: false,
+                commit: Some("123456789".to_string()),
+                range: None,
+                commit_title: Some("Add review command".to_string()),
+                prompt: None,
+                format: ReviewFormat::Text,
+                report_file: None,
+                paths: Vec::new(),
+            },
+            Path::new("."),
+        )
         .expect("builds commit review request");
 
         let expected = ReviewRequest {
@@ -655,6 +909,7 @@ mod tests {
                 title: Some("Add review command".to_string()),
             },
             user_facing_hint: None,
+            paths: Vec::new(),
         };
 
         assert_eq!(request, expected);
@@ -662,13 +917,21 @@ mod tests {
 
     #[test]
     fn builds_custom_review_request_trims_prompt() {
-        let request = build_review_request(ReviewArgs {
-            uncommitted: false,
-            base: None,
-            commit: None,
-            commit_title: None,
-            prompt: Some("  custom review instructions  ".to_string()),
-        })
+        let request = build_review_request(
+            ReviewArgs {
+                uncommitted: false,
+                base: None,
+                upstream: false,
+                commit: None,
+                range: None,
+                commit_title: None,
+                prompt: Some("  custom review instructions  ".to_string()),
+                format: ReviewFormat::Text,
+                report_file: None,
+                paths: Vec::new(),
+            },
+            Path::new("."),
+        )
         .expect("builds custom review request");
 
         let expected = ReviewRequest {
@@ -676,8 +939,42 @@ mod tests {
                 instructions: "custom review instructions".to_string(),
             },
             user_facing_hint: None,
+            paths: Vec::new(),
+        };
+
+        assert_eq!(request, expected);
+    }
+
+    #[test]
+    fn builds_commit_range_review_request_with_paths() {
+        let request = build_review_request(
+            ReviewArgs {
+                uncommitted: false,
+                base: None,
+                upstream: false,
+                commit: None,
+                range: Some("HEAD~3..HEAD".to_string()),
+                commit_title: None,
+                prompt: None,
+                format: ReviewFormat::Text,
+                report_file: None,
+                paths: vec!["core/src".to_string()],
+            },
+            Path::new("."),
+        )
+        .expect("builds commit range review request");
+
+        let expected = ReviewRequest {
+            target: ReviewTarget::CommitRange {
+                from: "HEAD~3".to_string(),
+                to: "HEAD".to_string(),
+            },
+            user_facing_hint: None,
+            paths: vec!["core/src".to_string()],
         };
 
         assert_eq!(request, expected);
+        assert!(parse_commit_range("HEAD~3").is_err());
+        assert!(parse_commit_range("main...feature").is_err());
     }
 }
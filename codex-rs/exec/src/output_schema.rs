@@ -0,0 +1,132 @@
+//! Local validation of the final agent message against `--output-schema`.
+//!
+//! The schema is forwarded to the model as a structured-output constraint,
+//! but providers do not all enforce it (and some only enforce a subset of
+//! JSON Schema), so exec validates the last agent message itself. When it
+//! does not match, the model is asked to fix its answer a bounded number of
+//! times before exec gives up with [`OUTPUT_SCHEMA_EXIT_CODE`].
+
+use serde_json::Value;
+
+/// Exit status used when the final message still does not match the output
+/// schema after all retries. Distinct from `1`, which signals that the agent
+/// itself failed (for example, a model or sandbox error).
+pub const OUTPUT_SCHEMA_EXIT_CODE: i32 = 3;
+
+pub(crate) struct OutputSchemaValidator {
+    validator: jsonschema::Validator,
+    retries_left: usize,
+}
+
+impl OutputSchemaValidator {
+    pub(crate) fn new(schema: &Value, max_retries: usize) -> Result<Self, String> {
+        let validator = jsonschema::validator_for(schema).map_err(|err| err.to_string())?;
+        Ok(Self {
+            validator,
+            retries_left: max_retries,
+        })
+    }
+
+    /// Returns a human-readable description of every validation error, or
+    /// an empty list when `message` matches the schema.
+    pub(crate) fn validate(&self, message: Option<&str>) -> Vec<String> {
+        let Some(message) = message else {
+            return vec!["the agent did not produce a final message".to_string()];
+        };
+        let instance: Value = match serde_json::from_str(message.trim()) {
+            Ok(instance) => instance,
+            Err(err) => return vec![format!("the final message is not valid JSON: {err}")],
+        };
+        self.validator
+            .iter_errors(&instance)
+            .map(|err| {
+                let path = err.instance_path.to_string();
+                if path.is_empty() {
+                    err.to_string()
+                } else {
+                    format!("{path}: {err}")
+                }
+            })
+            .collect()
+    }
+
+    /// Consumes one retry, returning `false` once they are exhausted.
+    pub(crate) fn take_retry(&mut self) -> bool {
+        if self.retries_left == 0 {
+            return false;
+        }
+        self.retries_left -= 1;
+        true
+    }
+}
+
+/// Follow-up prompt asking the model to correct a response that failed
+/// validation.
+pub(crate) fn retry_prompt(errors: &[String]) -> String {
+    let mut prompt =
+        String::from("Your final message does not match the required output JSON schema:\n");
+    for error in errors {
+        prompt.push_str("- ");
+        prompt.push_str(error);
+        prompt.push('\n');
+    }
+    prompt.push_str(
+        "Reply again with only a JSON document that satisfies the schema. Do not redo any work.",
+    );
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn validator() -> OutputSchemaValidator {
+        let schema = json!({
+            "type": "object",
+            "properties": {"answer": {"type": "string"}},
+            "required": ["answer"],
+            "additionalProperties": false,
+        });
+        OutputSchemaValidator::new(&schema, 1).expect("valid schema")
+    }
+
+    #[test]
+    fn accepts_matching_message() {
+        assert_eq!(
+            validator().validate(Some(" {\"answer\": \"42\"}\n")),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn reports_schema_violations_with_paths() {
+        assert_eq!(
+            validator().validate(Some(r#"{"answer": 42, "extra": true}"#)),
+            vec![
+                "/answer: 42 is not of type \"string\"".to_string(),
+                "Additional properties are not allowed ('extra' was unexpected)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_missing_or_non_json_messages() {
+        let validator = validator();
+        assert_eq!(
+            validator.validate(None),
+            vec!["the agent did not produce a final message".to_string()]
+        );
+        let errors = validator.validate(Some("Sure! Here you go."));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("the final message is not valid JSON"));
+    }
+
+    #[test]
+    fn retries_are_bounded() {
+        let mut validator = validator();
+        assert!(validator.take_retry());
+        assert!(!validator.take_retry());
+    }
+}
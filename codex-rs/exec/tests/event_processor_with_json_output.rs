@@ -33,6 +33,7 @@ use codex_exec::exec_events::PatchApplyStatus;
 use codex_exec::exec_events::PatchChangeKind;
 use codex_exec::exec_events::ReasoningItem;
 use codex_exec::exec_events::ThreadErrorEvent;
+use codex_exec::exec_events::ThreadErrorKind;
 use codex_exec::exec_events::ThreadEvent;
 use codex_exec::exec_events::ThreadItem;
 use codex_exec::exec_events::ThreadItemDetails;
@@ -548,10 +549,38 @@ fn error_event_produces_error() {
         out,
         vec![ThreadEvent::Error(ThreadErrorEvent {
             message: "boom".to_string(),
+            kind: None,
         })]
     );
 }
 
+#[test]
+fn budget_exceeded_error_is_tagged_with_its_kind() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let out = ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::Error(ErrorEvent {
+            message: "Token budget exceeded".to_string(),
+            codex_error_info: Some(CodexErrorInfo::BudgetExceeded),
+        }),
+    ));
+    assert_eq!(
+        out,
+        vec![ThreadEvent::Error(ThreadErrorEvent {
+            message: "Token budget exceeded".to_string(),
+            kind: Some(ThreadErrorKind::BudgetExceeded),
+        })]
+    );
+    assert_eq!(
+        serde_json::to_value(&out[0]).unwrap(),
+        json!({
+            "type": "error",
+            "message": "Token budget exceeded",
+            "kind": "budget_exceeded",
+        })
+    );
+}
+
 #[test]
 fn warning_event_produces_error_item() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -589,6 +618,7 @@ fn stream_error_event_produces_error() {
         out,
         vec![ThreadEvent::Error(ThreadErrorEvent {
             message: "retrying".to_string(),
+            kind: None,
         })]
     );
 }
@@ -608,6 +638,7 @@ fn error_followed_by_task_complete_produces_turn_failed() {
         ep.collect_thread_events(&error_event),
         vec![ThreadEvent::Error(ThreadErrorEvent {
             message: "boom".to_string(),
+            kind: None,
         })]
     );
 
@@ -622,6 +653,7 @@ fn error_followed_by_task_complete_produces_turn_failed() {
         vec![ThreadEvent::TurnFailed(TurnFailedEvent {
             error: ThreadErrorEvent {
                 message: "boom".to_string(),
+                kind: None,
             },
         })]
     );
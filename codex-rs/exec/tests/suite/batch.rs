@@ -0,0 +1,121 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+fn read_results(path: &std::path::Path) -> Vec<Value> {
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn batch_runs_each_line_and_resumes() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let batch_path = test.cwd_path().join("prompts.jsonl");
+    std::fs::write(
+        &batch_path,
+        "{\"id\": \"pkg-a\", \"prompt\": \"migrate a\"}\n\"migrate b\"\n",
+    )?;
+
+    let server = responses::start_mock_server().await;
+    let response_mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp1"),
+                responses::ev_assistant_message("m1", "done a"),
+                responses::ev_completed_with_tokens("resp1", 10),
+            ]),
+            responses::sse(vec![
+                responses::ev_response_created("resp2"),
+                responses::ev_assistant_message("m2", "done b"),
+                responses::ev_completed_with_tokens("resp2", 20),
+            ]),
+        ],
+    )
+    .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--batch")
+        .arg(&batch_path)
+        .arg("--parallel")
+        .arg("1")
+        .assert()
+        .success();
+
+    let results_path = test.cwd_path().join("prompts.results.jsonl");
+    let results = read_results(&results_path);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["id"], "pkg-a");
+    assert_eq!(results[0]["status"], "completed");
+    assert_eq!(results[0]["exit_code"], 0);
+    assert_eq!(results[0]["final_message"], "done a");
+    assert_eq!(results[0]["usage"]["input_tokens"], 10);
+    assert_eq!(results[1]["id"], "2");
+    assert_eq!(results[1]["final_message"], "done b");
+    let rollout_path = results[1]["rollout_path"].as_str().expect("rollout path");
+    assert!(std::path::Path::new(rollout_path).exists());
+    assert_eq!(response_mock.requests().len(), 2);
+
+    // Re-running the batch skips items that already completed.
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--batch")
+        .arg(&batch_path)
+        .assert()
+        .success();
+    assert_eq!(read_results(&results_path).len(), 2);
+    assert_eq!(response_mock.requests().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn batch_exits_non_zero_when_an_item_fails() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let batch_path = test.cwd_path().join("prompts.jsonl");
+    std::fs::write(&batch_path, "\"migrate\"\n")?;
+    let results_path = test.cwd_path().join("out.jsonl");
+
+    let server = responses::start_mock_server().await;
+    responses::mount_sse_once(
+        &server,
+        responses::sse(vec![
+            responses::ev_response_created("resp1"),
+            serde_json::json!({
+                "type": "response.failed",
+                "response": {
+                    "id": "resp1",
+                    "error": {"code": "insufficient_quota", "message": "quota"}
+                }
+            }),
+        ]),
+    )
+    .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--batch")
+        .arg(&batch_path)
+        .arg("--batch-output")
+        .arg(&results_path)
+        .assert()
+        .code(1);
+
+    let results = read_results(&results_path);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["status"], "failed");
+    assert_eq!(
+        results[0]["error"],
+        "Quota exceeded. Check your plan and billing details."
+    );
+
+    Ok(())
+}
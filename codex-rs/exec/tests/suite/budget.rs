@@ -0,0 +1,43 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use codex_exec::BUDGET_EXCEEDED_EXIT_CODE;
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use serde_json::Value;
+use serde_json::json;
+
+/// An exceeded budget is reported as a tagged JSONL error and a dedicated
+/// exit status, so automation can tell it apart from other failures.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exits_with_budget_code_when_turn_budget_is_exceeded() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "done"),
+        responses::ev_completed_with_tokens("resp1", 500),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("-c")
+        .arg("max_turn_tokens=100")
+        .arg("--experimental-json")
+        .arg("tell me something")
+        .output()?;
+
+    assert_eq!(output.status.code(), Some(BUDGET_EXCEEDED_EXIT_CODE));
+    let errors: Vec<Value> = String::from_utf8(output.stdout)?
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|event| event["type"] == json!("error"))
+        .collect();
+    assert_eq!(errors.len(), 1, "unexpected errors: {errors:?}");
+    assert_eq!(errors[0]["kind"], json!("budget_exceeded"));
+
+    Ok(())
+}
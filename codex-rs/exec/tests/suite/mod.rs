@@ -2,8 +2,11 @@
 mod add_dir;
 mod apply_patch;
 mod auth_env;
+mod batch;
+mod budget;
 mod originator;
 mod output_schema;
 mod resume;
+mod review_report;
 mod sandbox;
 mod server_error_exit;
@@ -5,6 +5,22 @@ use core_test_support::responses;
 use core_test_support::test_codex_exec::test_codex_exec;
 use serde_json::Value;
 
+fn write_schema(
+    test: &core_test_support::test_codex_exec::TestCodexExecBuilder,
+) -> std::path::PathBuf {
+    let schema = serde_json::json!({
+        "type": "object",
+        "properties": {
+            "answer": { "type": "string" }
+        },
+        "required": ["answer"],
+        "additionalProperties": false
+    });
+    let schema_path = test.cwd_path().join("schema.json");
+    std::fs::write(&schema_path, serde_json::to_vec_pretty(&schema).unwrap()).unwrap();
+    schema_path
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn exec_includes_output_schema_in_request() -> anyhow::Result<()> {
     let test = test_codex_exec();
@@ -24,7 +40,7 @@ async fn exec_includes_output_schema_in_request() -> anyhow::Result<()> {
     let server = responses::start_mock_server().await;
     let body = responses::sse(vec![
         responses::ev_response_created("resp1"),
-        responses::ev_assistant_message("m1", "fixture hello"),
+        responses::ev_assistant_message("m1", r#"{"answer": "fixture hello"}"#),
         responses::ev_completed("resp1"),
     ]);
     let response_mock = responses::mount_sse_once(&server, body).await;
@@ -60,3 +76,96 @@ async fn exec_includes_output_schema_in_request() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_retries_when_final_message_does_not_match_schema() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let schema_path = write_schema(&test);
+
+    let server = responses::start_mock_server().await;
+    let response_mock = responses::mount_sse_sequence(
+        &server,
+        vec![
+            responses::sse(vec![
+                responses::ev_response_created("resp1"),
+                responses::ev_assistant_message("m1", r#"{"answer": 42}"#),
+                responses::ev_completed("resp1"),
+            ]),
+            responses::sse(vec![
+                responses::ev_response_created("resp2"),
+                responses::ev_assistant_message("m2", r#"{"answer": "42"}"#),
+                responses::ev_completed("resp2"),
+            ]),
+        ],
+    )
+    .await;
+
+    test.cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--output-schema")
+        .arg(&schema_path)
+        .arg("what is the answer?")
+        .assert()
+        .success()
+        .stdout(
+            r#"{"answer": "42"}
+"#,
+        );
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    let retry_prompt = requests[1]
+        .message_input_texts("user")
+        .last()
+        .cloned()
+        .expect("retry prompt");
+    assert!(
+        retry_prompt.contains("/answer: 42 is not of type \"string\""),
+        "unexpected retry prompt: {retry_prompt}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn exec_exits_with_dedicated_code_when_schema_validation_fails() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let schema_path = write_schema(&test);
+
+    let server = responses::start_mock_server().await;
+    let body = responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", "not json"),
+        responses::ev_completed("resp1"),
+    ]);
+    responses::mount_sse_once(&server, body).await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("--skip-git-repo-check")
+        .arg("--json")
+        .arg("--output-schema")
+        .arg(&schema_path)
+        .arg("--output-schema-retries")
+        .arg("0")
+        .arg("what is the answer?")
+        .output()?;
+
+    assert_eq!(
+        output.status.code(),
+        Some(codex_exec::OUTPUT_SCHEMA_EXIT_CODE)
+    );
+    let stdout = String::from_utf8(output.stdout)?;
+    let turn_failed = stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .find(|event| event["type"] == "turn.failed")
+        .expect("turn.failed event");
+    let message = turn_failed["error"]["message"].as_str().unwrap_or_default();
+    assert!(
+        message.starts_with("Final message does not match the output schema:"),
+        "unexpected error: {message}"
+    );
+
+    Ok(())
+}
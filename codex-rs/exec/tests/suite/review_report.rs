@@ -0,0 +1,194 @@
+#![cfg(not(target_os = "windows"))]
+#![allow(clippy::expect_used, clippy::unwrap_used)]
+
+use std::path::Path;
+use std::process::Command;
+
+use core_test_support::responses;
+use core_test_support::test_codex_exec::test_codex_exec;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+
+fn git(path: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(path)
+        .status()
+        .expect("run git");
+    assert!(status.success(), "git {args:?} exited with {status}");
+}
+
+/// Commits a 20-line `src/lib.rs` and then edits line 5, so the uncommitted
+/// diff has a single hunk covering lines 2-8 on the new side.
+fn init_repo_with_edit(cwd: &Path) {
+    git(cwd, &["init", "--initial-branch=main"]);
+    git(cwd, &["config", "user.name", "Codex Tests"]);
+    git(cwd, &["config", "user.email", "codex-tests@example.com"]);
+    std::fs::create_dir_all(cwd.join("src")).unwrap();
+    let mut lines: Vec<String> = (1..=20).map(|i| format!("line {i}")).collect();
+    std::fs::write(cwd.join("src/lib.rs"), lines.join("\n") + "\n").unwrap();
+    git(cwd, &["add", "."]);
+    git(cwd, &["commit", "-m", "initial"]);
+    lines[4] = "changed".to_string();
+    std::fs::write(cwd.join("src/lib.rs"), lines.join("\n") + "\n").unwrap();
+}
+
+fn git_output(path: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(path)
+        .output()
+        .expect("run git");
+    assert!(output.status.success(), "git {args:?} failed: {output:?}");
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+fn review_body(file: &Path) -> String {
+    let finding = |title: &str, line: u32| {
+        json!({
+            "title": title,
+            "body": "Details.",
+            "confidence_score": 0.9,
+            "priority": 1,
+            "code_location": {
+                "absolute_file_path": file,
+                "line_range": { "start": line, "end": line },
+            },
+        })
+    };
+    let review = json!({
+        "findings": [finding("[P1] Changed line", 5), finding("[P1] Old code", 18)],
+        "overall_correctness": "patch is incorrect",
+        "overall_explanation": "One bug in the change.",
+        "overall_confidence_score": 0.8,
+    });
+    responses::sse(vec![
+        responses::ev_response_created("resp1"),
+        responses::ev_assistant_message("m1", &review.to_string()),
+        responses::ev_completed("resp1"),
+    ])
+}
+
+/// `--format github --report-file` writes a review whose comments only
+/// target lines in the reviewed diff; other findings go in the body.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn github_report_file_anchors_only_diff_lines() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let cwd = test.cwd_path().canonicalize()?;
+    init_repo_with_edit(&cwd);
+
+    let server = responses::start_mock_server().await;
+    responses::mount_sse_once(&server, review_body(&cwd.join("src/lib.rs"))).await;
+
+    let report_path = test.home_path().join("review.json");
+    test.cmd_with_server(&server)
+        .arg("review")
+        .arg("--uncommitted")
+        .arg("--format")
+        .arg("github")
+        .arg("--report-file")
+        .arg(&report_path)
+        .assert()
+        .success();
+
+    let report: Value = serde_json::from_str(&std::fs::read_to_string(&report_path)?)?;
+    assert_eq!(
+        report["comments"],
+        json!([{
+            "path": "src/lib.rs",
+            "line": 5,
+            "side": "RIGHT",
+            "body": "[P1] Changed line\n\nDetails.",
+        }])
+    );
+    assert_eq!(
+        report["body"],
+        "One bug in the change.\n\nOverall correctness: patch is incorrect\n\nFindings outside the reviewed diff:\n- [P1] Old code — src/lib.rs:18\n  Details."
+    );
+    assert!(report["commit_id"].is_string());
+
+    Ok(())
+}
+
+/// Reviewing the root commit anchors findings on every line it added and
+/// reports that commit, not `HEAD`, as the reviewed revision.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn github_report_for_root_commit_uses_the_reviewed_commit() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let cwd = test.cwd_path().canonicalize()?;
+    init_repo_with_edit(&cwd);
+    git(&cwd, &["commit", "-am", "edit"]);
+    let root_commit = git_output(&cwd, &["rev-parse", "HEAD~1"]);
+
+    let server = responses::start_mock_server().await;
+    responses::mount_sse_once(&server, review_body(&cwd.join("src/lib.rs"))).await;
+
+    let report_path = test.home_path().join("review.json");
+    test.cmd_with_server(&server)
+        .arg("review")
+        .arg("--commit")
+        .arg(&root_commit[..12])
+        .arg("--format")
+        .arg("github")
+        .arg("--report-file")
+        .arg(&report_path)
+        .assert()
+        .success();
+
+    let report: Value = serde_json::from_str(&std::fs::read_to_string(&report_path)?)?;
+    assert_eq!(report["commit_id"], root_commit);
+    let lines: Vec<&Value> = report["comments"]
+        .as_array()
+        .expect("comments")
+        .iter()
+        .map(|comment| &comment["line"])
+        .collect();
+    assert_eq!(lines, vec![&json!(5), &json!(18)]);
+
+    Ok(())
+}
+
+/// `--format sarif` without `--report-file` prints the log to stdout in
+/// place of the final message.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn sarif_format_prints_report_to_stdout() -> anyhow::Result<()> {
+    let test = test_codex_exec();
+    let cwd = test.cwd_path().canonicalize()?;
+    init_repo_with_edit(&cwd);
+
+    let server = responses::start_mock_server().await;
+    responses::mount_sse_once(&server, review_body(&cwd.join("src/lib.rs"))).await;
+
+    let output = test
+        .cmd_with_server(&server)
+        .arg("review")
+        .arg("--uncommitted")
+        .arg("--format")
+        .arg("sarif")
+        .output()?;
+
+    assert!(output.status.success(), "exec failed: {output:?}");
+    let sarif: Value = serde_json::from_slice(&output.stdout)?;
+    let locations: Vec<Value> = sarif["runs"][0]["results"]
+        .as_array()
+        .expect("results")
+        .iter()
+        .map(|result| result["locations"][0]["physicalLocation"].clone())
+        .collect();
+    assert_eq!(
+        locations,
+        vec![
+            json!({
+                "artifactLocation": { "uri": "src/lib.rs", "uriBaseId": "SRCROOT" },
+                "region": { "startLine": 5, "endLine": 5 },
+            }),
+            json!({
+                "artifactLocation": { "uri": "src/lib.rs", "uriBaseId": "SRCROOT" },
+                "region": { "startLine": 18, "endLine": 18 },
+            }),
+        ]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+#![allow(clippy::needless_lifetimes)]
+
+use allocative::Allocative;
+use derive_more::derive::Display;
+use regex_lite::Regex;
+use starlark::any::ProvidesStaticType;
+use starlark::values::AllocValue;
+use starlark::values::Heap;
+use starlark::values::NoSerialize;
+use starlark::values::StarlarkValue;
+use starlark::values::UnpackValue;
+use starlark::values::Value;
+use starlark::values::ValueLike;
+use starlark::values::starlark_value;
+use wildmatch::WildMatch;
+
+use crate::error::Error;
+use crate::error::Result;
+
+/// Matches a single command token by something other than string equality.
+///
+/// Values of this type are created by the `glob(...)` and `regex(...)` policy
+/// builtins and may appear anywhere a literal token is accepted in a pattern.
+#[derive(Clone, Debug, Display, NoSerialize, ProvidesStaticType, Allocative)]
+pub enum ArgMatcher {
+    /// Literal token, used when a list of alternatives mixes strings and matchers.
+    #[display("{_0:?}")]
+    Literal(String),
+
+    /// Shell-style wildcard where `*` matches any run of characters and `?`
+    /// matches exactly one.
+    #[display("glob({source:?})")]
+    Glob {
+        source: String,
+        #[allocative(skip)]
+        compiled: WildMatch,
+    },
+
+    /// Regular expression that must match the entire token.
+    #[display("regex({source:?})")]
+    Regex {
+        source: String,
+        #[allocative(skip)]
+        compiled: Regex,
+    },
+}
+
+impl ArgMatcher {
+    pub fn literal(token: impl Into<String>) -> Self {
+        Self::Literal(token.into())
+    }
+
+    pub fn glob(pattern: &str) -> Result<Self> {
+        if pattern.is_empty() {
+            return Err(Error::InvalidPattern("glob cannot be empty".to_string()));
+        }
+        Ok(Self::Glob {
+            source: pattern.to_string(),
+            compiled: WildMatch::new(pattern),
+        })
+    }
+
+    pub fn regex(pattern: &str) -> Result<Self> {
+        if pattern.is_empty() {
+            return Err(Error::InvalidPattern("regex cannot be empty".to_string()));
+        }
+        let compiled = Regex::new(&format!("^(?:{pattern})$"))
+            .map_err(|err| Error::InvalidPattern(format!("invalid regex `{pattern}`: {err}")))?;
+        Ok(Self::Regex {
+            source: pattern.to_string(),
+            compiled,
+        })
+    }
+
+    pub fn matches(&self, token: &str) -> bool {
+        match self {
+            Self::Literal(expected) => expected == token,
+            Self::Glob { compiled, .. } => compiled.matches(token),
+            Self::Regex { compiled, .. } => compiled.is_match(token),
+        }
+    }
+}
+
+impl PartialEq for ArgMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Literal(lhs), Self::Literal(rhs)) => lhs == rhs,
+            (Self::Glob { source: lhs, .. }, Self::Glob { source: rhs, .. }) => lhs == rhs,
+            (Self::Regex { source: lhs, .. }, Self::Regex { source: rhs, .. }) => lhs == rhs,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for ArgMatcher {}
+
+impl<'v> AllocValue<'v> for ArgMatcher {
+    fn alloc_value(self, heap: &'v Heap) -> Value<'v> {
+        heap.alloc_simple(self)
+    }
+}
+
+#[starlark_value(type = "ArgMatcher")]
+impl<'v> StarlarkValue<'v> for ArgMatcher {
+    type Canonical = ArgMatcher;
+}
+
+impl<'v> UnpackValue<'v> for ArgMatcher {
+    type Error = starlark::Error;
+
+    fn unpack_value_impl(value: Value<'v>) -> starlark::Result<Option<Self>> {
+        Ok(value.downcast_ref::<ArgMatcher>().cloned())
+    }
+}
@@ -7,6 +7,7 @@ use clap::Parser;
 use serde::Serialize;
 
 use crate::Decision;
+use crate::MatchContext;
 use crate::Policy;
 use crate::PolicyParser;
 use crate::RuleMatch;
@@ -22,6 +23,16 @@ pub struct ExecPolicyCheckCommand {
     #[arg(long)]
     pub pretty: bool,
 
+    /// Workspace roots used by `path_rule`s (repeatable). Path rules never match
+    /// when no workspace root is given.
+    #[arg(long = "workspace-root", value_name = "DIR")]
+    pub workspace_roots: Vec<PathBuf>,
+
+    /// Directory relative path arguments are resolved against. Defaults to the
+    /// current directory.
+    #[arg(long, value_name = "DIR")]
+    pub cwd: Option<PathBuf>,
+
     /// Command tokens to check against the policy.
     #[arg(
         value_name = "COMMAND",
@@ -36,13 +47,25 @@ impl ExecPolicyCheckCommand {
     /// Load the policies for this command, evaluate the command, and render JSON output.
     pub fn run(&self) -> Result<()> {
         let policy = load_policies(&self.rules)?;
-        let matched_rules = policy.matches_for_command(&self.command, None);
+        let context = self.match_context()?;
+        let matched_rules = policy.matches_for_command_with_context(&self.command, &context, None);
 
         let json = format_matches_json(&matched_rules, self.pretty)?;
         println!("{json}");
 
         Ok(())
     }
+
+    fn match_context(&self) -> Result<MatchContext> {
+        if self.workspace_roots.is_empty() {
+            return Ok(MatchContext::default());
+        }
+        let cwd = match &self.cwd {
+            Some(cwd) => cwd.clone(),
+            None => std::env::current_dir().context("failed to resolve current directory")?,
+        };
+        Ok(MatchContext::new(cwd, self.workspace_roots.clone()))
+    }
 }
 
 pub fn format_matches_json(matched_rules: &[RuleMatch], pretty: bool) -> Result<String> {
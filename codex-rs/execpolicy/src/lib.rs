@@ -1,17 +1,21 @@
 pub mod amend;
+pub mod arg_matcher;
 pub mod decision;
 pub mod error;
 pub mod execpolicycheck;
+pub mod match_context;
 pub mod parser;
 pub mod policy;
 pub mod rule;
 
 pub use amend::AmendError;
 pub use amend::blocking_append_allow_prefix_rule;
+pub use arg_matcher::ArgMatcher;
 pub use decision::Decision;
 pub use error::Error;
 pub use error::Result;
 pub use execpolicycheck::ExecPolicyCheckCommand;
+pub use match_context::MatchContext;
 pub use parser::PolicyParser;
 pub use policy::Evaluation;
 pub use policy::Policy;
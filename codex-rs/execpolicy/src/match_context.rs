@@ -0,0 +1,121 @@
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Directory used as both cwd and workspace root when validating `match` /
+/// `not_match` examples at load time.
+const EXAMPLE_WORKSPACE_ROOT: &str = "/workspace";
+
+/// Information about where a command runs, consulted by rules that reason
+/// about path arguments (see [`crate::rule::PathRule`]).
+///
+/// Rules that only look at tokens ignore the context entirely, so
+/// [`MatchContext::default()`] is fine when no path rules are in play.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MatchContext {
+    /// Directory relative path arguments are resolved against.
+    pub cwd: Option<PathBuf>,
+    /// Directories path arguments are allowed to reference.
+    pub workspace_roots: Vec<PathBuf>,
+}
+
+impl MatchContext {
+    pub fn new(cwd: PathBuf, workspace_roots: Vec<PathBuf>) -> Self {
+        Self {
+            cwd: Some(cwd),
+            workspace_roots,
+        }
+    }
+
+    pub(crate) fn for_examples() -> Self {
+        Self::new(
+            PathBuf::from(EXAMPLE_WORKSPACE_ROOT),
+            vec![PathBuf::from(EXAMPLE_WORKSPACE_ROOT)],
+        )
+    }
+
+    /// Returns whether `raw` resolves outside every workspace root, or `None`
+    /// when the context does not describe a workspace.
+    ///
+    /// Resolution is purely lexical: symlinks are not followed. Arguments that
+    /// depend on shell expansion (`~`, `$VAR`) cannot be resolved and are
+    /// treated as outside the workspace.
+    pub fn is_outside_workspace(&self, raw: &str) -> Option<bool> {
+        let cwd = self.cwd.as_deref()?;
+        if self.workspace_roots.is_empty() {
+            return None;
+        }
+        if raw.starts_with('~') || raw.starts_with('$') {
+            return Some(true);
+        }
+
+        let resolved = normalize(&cwd.join(raw));
+        Some(
+            !self
+                .workspace_roots
+                .iter()
+                .any(|root| resolved.starts_with(normalize(root))),
+        )
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn workspace_context() -> MatchContext {
+        MatchContext::new(
+            PathBuf::from("/repo/crate"),
+            vec![PathBuf::from("/repo"), PathBuf::from("/tmp/scratch")],
+        )
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_cwd() {
+        let context = workspace_context();
+        assert_eq!(Some(false), context.is_outside_workspace("src/lib.rs"));
+        assert_eq!(Some(false), context.is_outside_workspace("../other"));
+        assert_eq!(Some(true), context.is_outside_workspace("../../etc"));
+    }
+
+    #[test]
+    fn absolute_paths_are_checked_against_every_root() {
+        let context = workspace_context();
+        assert_eq!(
+            Some(false),
+            context.is_outside_workspace("/tmp/scratch/out")
+        );
+        assert_eq!(Some(true), context.is_outside_workspace("/tmp/other"));
+        assert_eq!(
+            Some(true),
+            context.is_outside_workspace("/repo/../etc/passwd")
+        );
+    }
+
+    #[test]
+    fn shell_expansions_are_treated_as_outside() {
+        let context = workspace_context();
+        assert_eq!(Some(true), context.is_outside_workspace("~/.ssh"));
+        assert_eq!(Some(true), context.is_outside_workspace("$HOME/.aws"));
+    }
+
+    #[test]
+    fn missing_workspace_yields_none() {
+        assert_eq!(None, MatchContext::default().is_outside_workspace("/etc"));
+    }
+}
@@ -8,6 +8,7 @@ use starlark::starlark_module;
 use starlark::syntax::AstModule;
 use starlark::syntax::Dialect;
 use starlark::values::Value;
+use starlark::values::ValueLike;
 use starlark::values::list::ListRef;
 use starlark::values::list::UnpackList;
 use starlark::values::none::NoneType;
@@ -15,9 +16,13 @@ use std::cell::RefCell;
 use std::cell::RefMut;
 use std::sync::Arc;
 
+use crate::arg_matcher::ArgMatcher;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::rule::ArgRule;
+use crate::rule::ArgsPattern;
+use crate::rule::PathRule;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
@@ -106,23 +111,40 @@ fn parse_pattern<'v>(pattern: UnpackList<Value<'v>>) -> Result<Vec<PatternToken>
 fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
     if let Some(s) = value.unpack_str() {
         Ok(PatternToken::Single(s.to_string()))
+    } else if let Some(matcher) = value.downcast_ref::<ArgMatcher>() {
+        Ok(PatternToken::Matchers(vec![matcher.clone()]))
     } else if let Some(list) = ListRef::from_value(value) {
-        let tokens: Vec<String> = list
+        let matchers: Vec<ArgMatcher> = list
             .content()
             .iter()
             .map(|value| {
-                value
-                    .unpack_str()
-                    .ok_or_else(|| {
-                        Error::InvalidPattern(format!(
-                            "pattern alternative must be a string (got {})",
-                            value.get_type()
-                        ))
-                    })
-                    .map(str::to_string)
+                if let Some(s) = value.unpack_str() {
+                    Ok(ArgMatcher::literal(s))
+                } else if let Some(matcher) = value.downcast_ref::<ArgMatcher>() {
+                    Ok(matcher.clone())
+                } else {
+                    Err(Error::InvalidPattern(format!(
+                        "pattern alternative must be a string, glob, or regex (got {})",
+                        value.get_type()
+                    )))
+                }
             })
             .collect::<Result<_>>()?;
 
+        if matchers
+            .iter()
+            .any(|matcher| !matches!(matcher, ArgMatcher::Literal(_)))
+        {
+            return Ok(PatternToken::Matchers(matchers));
+        }
+
+        let tokens: Vec<String> = matchers
+            .into_iter()
+            .filter_map(|matcher| match matcher {
+                ArgMatcher::Literal(token) => Some(token),
+                _ => None,
+            })
+            .collect();
         match tokens.as_slice() {
             [] => Err(Error::InvalidPattern(
                 "pattern alternatives cannot be empty".to_string(),
@@ -132,12 +154,73 @@ fn parse_pattern_token<'v>(value: Value<'v>) -> Result<PatternToken> {
         }
     } else {
         Err(Error::InvalidPattern(format!(
-            "pattern element must be a string or list of strings (got {})",
+            "pattern element must be a string, glob, regex, or list of these (got {})",
             value.get_type()
         )))
     }
 }
 
+/// Parses the `program` argument of `arg_rule` / `path_rule`: a string or a list
+/// of string alternatives. Globs and regexes are rejected because rules are
+/// indexed by program name.
+fn parse_programs<'v>(program: Value<'v>) -> Result<Vec<String>> {
+    parse_pattern_token(program)?
+        .alternatives()
+        .map(<[String]>::to_vec)
+        .ok_or_else(|| {
+            Error::InvalidPattern("program must be a literal string or list of strings".to_string())
+        })
+}
+
+fn parse_args<'v>(args: Option<UnpackList<Value<'v>>>) -> Result<Vec<PatternToken>> {
+    args.map(|args| {
+        args.items
+            .into_iter()
+            .map(parse_pattern_token)
+            .collect::<Result<_>>()
+    })
+    .transpose()
+    .map(Option::unwrap_or_default)
+}
+
+fn parse_decision(decision: Option<&str>) -> Result<Decision> {
+    match decision {
+        Some(raw) => Decision::parse(raw),
+        None => Ok(Decision::Allow),
+    }
+}
+
+fn parse_justification(justification: Option<&str>) -> Result<Option<String>> {
+    match justification {
+        Some(raw) if raw.trim().is_empty() => Err(Error::InvalidRule(
+            "justification cannot be empty".to_string(),
+        )),
+        Some(raw) => Ok(Some(raw.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Validates the rule's examples and registers it with the builder.
+fn add_validated_rules<'v>(
+    eval: &mut Evaluator<'v, '_, '_>,
+    rules: Vec<RuleRef>,
+    r#match: Option<UnpackList<Value<'v>>>,
+    not_match: Option<UnpackList<Value<'v>>>,
+) -> Result<()> {
+    let matches: Vec<Vec<String>> = r#match.map(parse_examples).transpose()?.unwrap_or_default();
+    let not_matches: Vec<Vec<String>> = not_match
+        .map(parse_examples)
+        .transpose()?
+        .unwrap_or_default();
+
+    validate_not_match_examples(&rules, &not_matches)?;
+    validate_match_examples(&rules, &matches)?;
+
+    let mut builder = policy_builder(eval);
+    rules.into_iter().for_each(|rule| builder.add_rule(rule));
+    Ok(())
+}
+
 fn parse_examples<'v>(examples: UnpackList<Value<'v>>) -> Result<Vec<Vec<String>>> {
     examples.items.into_iter().map(parse_example).collect()
 }
@@ -215,38 +298,22 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
         justification: Option<&'v str>,
         eval: &mut Evaluator<'v, '_, '_>,
     ) -> anyhow::Result<NoneType> {
-        let decision = match decision {
-            Some(raw) => Decision::parse(raw)?,
-            None => Decision::Allow,
-        };
-
-        let justification = match justification {
-            Some(raw) if raw.trim().is_empty() => {
-                return Err(Error::InvalidRule("justification cannot be empty".to_string()).into());
-            }
-            Some(raw) => Some(raw.to_string()),
-            None => None,
-        };
-
+        let decision = parse_decision(decision)?;
+        let justification = parse_justification(justification)?;
         let pattern_tokens = parse_pattern(pattern)?;
 
-        let matches: Vec<Vec<String>> =
-            r#match.map(parse_examples).transpose()?.unwrap_or_default();
-        let not_matches: Vec<Vec<String>> = not_match
-            .map(parse_examples)
-            .transpose()?
-            .unwrap_or_default();
-
-        let mut builder = policy_builder(eval);
-
         let (first_token, remaining_tokens) = pattern_tokens
             .split_first()
             .ok_or_else(|| Error::InvalidPattern("pattern cannot be empty".to_string()))?;
+        let programs = first_token.alternatives().ok_or_else(|| {
+            Error::InvalidPattern(
+                "first pattern element must be a literal program name".to_string(),
+            )
+        })?;
 
         let rest: Arc<[PatternToken]> = remaining_tokens.to_vec().into();
 
-        let rules: Vec<RuleRef> = first_token
-            .alternatives()
+        let rules: Vec<RuleRef> = programs
             .iter()
             .map(|head| {
                 Arc::new(PrefixRule {
@@ -260,10 +327,82 @@ fn policy_builtins(builder: &mut GlobalsBuilder) {
             })
             .collect();
 
-        validate_not_match_examples(&rules, &not_matches)?;
-        validate_match_examples(&rules, &matches)?;
+        add_validated_rules(eval, rules, r#match, not_match)?;
+        Ok(NoneType)
+    }
 
-        rules.into_iter().for_each(|rule| builder.add_rule(rule));
+    fn arg_rule<'v>(
+        program: Value<'v>,
+        args: UnpackList<Value<'v>>,
+        decision: Option<&'v str>,
+        r#match: Option<UnpackList<Value<'v>>>,
+        not_match: Option<UnpackList<Value<'v>>>,
+        justification: Option<&'v str>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<NoneType> {
+        let decision = parse_decision(decision)?;
+        let justification = parse_justification(justification)?;
+        let programs = parse_programs(program)?;
+        let args: Arc<[PatternToken]> = parse_args(Some(args))?.into();
+        if args.is_empty() {
+            return Err(Error::InvalidPattern("args cannot be empty".to_string()).into());
+        }
+
+        let rules: Vec<RuleRef> = programs
+            .iter()
+            .map(|program| {
+                Arc::new(ArgRule {
+                    pattern: ArgsPattern {
+                        program: Arc::from(program.as_str()),
+                        args: args.clone(),
+                    },
+                    decision,
+                    justification: justification.clone(),
+                }) as RuleRef
+            })
+            .collect();
+
+        add_validated_rules(eval, rules, r#match, not_match)?;
+        Ok(NoneType)
+    }
+
+    fn path_rule<'v>(
+        program: Value<'v>,
+        args: Option<UnpackList<Value<'v>>>,
+        decision: Option<&'v str>,
+        r#match: Option<UnpackList<Value<'v>>>,
+        not_match: Option<UnpackList<Value<'v>>>,
+        justification: Option<&'v str>,
+        eval: &mut Evaluator<'v, '_, '_>,
+    ) -> anyhow::Result<NoneType> {
+        let decision = parse_decision(decision)?;
+        let justification = parse_justification(justification)?;
+        let programs = parse_programs(program)?;
+        let args: Arc<[PatternToken]> = parse_args(args)?.into();
+
+        let rules: Vec<RuleRef> = programs
+            .iter()
+            .map(|program| {
+                Arc::new(PathRule {
+                    pattern: ArgsPattern {
+                        program: Arc::from(program.as_str()),
+                        args: args.clone(),
+                    },
+                    decision,
+                    justification: justification.clone(),
+                }) as RuleRef
+            })
+            .collect();
+
+        add_validated_rules(eval, rules, r#match, not_match)?;
         Ok(NoneType)
     }
+
+    fn glob(pattern: &str) -> anyhow::Result<ArgMatcher> {
+        Ok(ArgMatcher::glob(pattern)?)
+    }
+
+    fn regex(pattern: &str) -> anyhow::Result<ArgMatcher> {
+        Ok(ArgMatcher::regex(pattern)?)
+    }
 }
@@ -1,6 +1,7 @@
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::match_context::MatchContext;
 use crate::rule::PatternToken;
 use crate::rule::PrefixPattern;
 use crate::rule::PrefixRule;
@@ -67,6 +68,22 @@ impl Policy {
         commands: Commands,
         heuristics_fallback: &F,
     ) -> Evaluation
+    where
+        Commands: IntoIterator,
+        Commands::Item: AsRef<[String]>,
+        F: Fn(&[String]) -> Decision,
+    {
+        self.check_multiple_with_context(commands, &MatchContext::default(), heuristics_fallback)
+    }
+
+    /// Like [`Policy::check_multiple`], but resolves path arguments against
+    /// `context` so that `path_rule`s can apply.
+    pub fn check_multiple_with_context<Commands, F>(
+        &self,
+        commands: Commands,
+        context: &MatchContext,
+        heuristics_fallback: &F,
+    ) -> Evaluation
     where
         Commands: IntoIterator,
         Commands::Item: AsRef<[String]>,
@@ -75,7 +92,11 @@ impl Policy {
         let matched_rules: Vec<RuleMatch> = commands
             .into_iter()
             .flat_map(|command| {
-                self.matches_for_command(command.as_ref(), Some(heuristics_fallback))
+                self.matches_for_command_with_context(
+                    command.as_ref(),
+                    context,
+                    Some(heuristics_fallback),
+                )
             })
             .collect();
 
@@ -93,12 +114,28 @@ impl Policy {
         &self,
         cmd: &[String],
         heuristics_fallback: HeuristicsFallback<'_>,
+    ) -> Vec<RuleMatch> {
+        self.matches_for_command_with_context(cmd, &MatchContext::default(), heuristics_fallback)
+    }
+
+    /// Returns matching rules for the given command, resolving path arguments
+    /// against `context`. See [`Policy::matches_for_command`].
+    pub fn matches_for_command_with_context(
+        &self,
+        cmd: &[String],
+        context: &MatchContext,
+        heuristics_fallback: HeuristicsFallback<'_>,
     ) -> Vec<RuleMatch> {
         let matched_rules: Vec<RuleMatch> = match cmd.first() {
             Some(first) => self
                 .rules_by_program
                 .get_vec(first)
-                .map(|rules| rules.iter().filter_map(|rule| rule.matches(cmd)).collect())
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .filter_map(|rule| rule.matches(cmd, context))
+                        .collect()
+                })
                 .unwrap_or_default(),
             None => Vec::new(),
         };
@@ -1,6 +1,8 @@
+use crate::arg_matcher::ArgMatcher;
 use crate::decision::Decision;
 use crate::error::Error;
 use crate::error::Result;
+use crate::match_context::MatchContext;
 use serde::Deserialize;
 use serde::Serialize;
 use shlex::try_join;
@@ -13,6 +15,8 @@ use std::sync::Arc;
 pub enum PatternToken {
     Single(String),
     Alts(Vec<String>),
+    /// Alternatives where at least one entry is a `glob(...)` or `regex(...)` matcher.
+    Matchers(Vec<ArgMatcher>),
 }
 
 impl PatternToken {
@@ -20,13 +24,17 @@ impl PatternToken {
         match self {
             Self::Single(expected) => expected == token,
             Self::Alts(alternatives) => alternatives.iter().any(|alt| alt == token),
+            Self::Matchers(matchers) => matchers.iter().any(|matcher| matcher.matches(token)),
         }
     }
 
-    pub fn alternatives(&self) -> &[String] {
+    /// Returns the literal tokens this pattern accepts, or `None` when it relies on
+    /// glob or regex matchers and therefore cannot be enumerated.
+    pub fn alternatives(&self) -> Option<&[String]> {
         match self {
-            Self::Single(expected) => std::slice::from_ref(expected),
-            Self::Alts(alternatives) => alternatives,
+            Self::Single(expected) => Some(std::slice::from_ref(expected)),
+            Self::Alts(alternatives) => Some(alternatives),
+            Self::Matchers(_) => None,
         }
     }
 }
@@ -56,6 +64,29 @@ impl PrefixPattern {
     }
 }
 
+/// Position-independent argument matcher: every token in `args` must match at
+/// least one argument after the program name, in any order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArgsPattern {
+    pub program: Arc<str>,
+    pub args: Arc<[PatternToken]>,
+}
+
+impl ArgsPattern {
+    /// Returns the argument matched by each token in `args`, in pattern order.
+    pub fn matches_args(&self, cmd: &[String]) -> Option<Vec<String>> {
+        let (program, args) = cmd.split_first()?;
+        if program != self.program.as_ref() {
+            return None;
+        }
+
+        self.args
+            .iter()
+            .map(|pattern_token| args.iter().find(|arg| pattern_token.matches(arg)).cloned())
+            .collect()
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum RuleMatch {
@@ -70,6 +101,23 @@ pub enum RuleMatch {
         #[serde(skip_serializing_if = "Option::is_none")]
         justification: Option<String>,
     },
+    ArgRuleMatch {
+        program: String,
+        #[serde(rename = "matchedArgs")]
+        matched_args: Vec<String>,
+        decision: Decision,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        justification: Option<String>,
+    },
+    PathRuleMatch {
+        program: String,
+        /// Path arguments that resolved outside every workspace root.
+        #[serde(rename = "outsidePaths")]
+        outside_paths: Vec<String>,
+        decision: Decision,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        justification: Option<String>,
+    },
     HeuristicsRuleMatch {
         command: Vec<String>,
         decision: Decision,
@@ -80,9 +128,20 @@ impl RuleMatch {
     pub fn decision(&self) -> Decision {
         match self {
             Self::PrefixRuleMatch { decision, .. } => *decision,
+            Self::ArgRuleMatch { decision, .. } => *decision,
+            Self::PathRuleMatch { decision, .. } => *decision,
             Self::HeuristicsRuleMatch { decision, .. } => *decision,
         }
     }
+
+    pub fn justification(&self) -> Option<&str> {
+        match self {
+            Self::PrefixRuleMatch { justification, .. }
+            | Self::ArgRuleMatch { justification, .. }
+            | Self::PathRuleMatch { justification, .. } => justification.as_deref(),
+            Self::HeuristicsRuleMatch { .. } => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -92,10 +151,28 @@ pub struct PrefixRule {
     pub justification: Option<String>,
 }
 
+/// Matches commands for `program` whose arguments contain every token in `args`,
+/// regardless of position (e.g. `git push --force` as well as `git push origin --force`).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ArgRule {
+    pub pattern: ArgsPattern,
+    pub decision: Decision,
+    pub justification: Option<String>,
+}
+
+/// Matches commands for `program` (optionally constrained by `args`) that reference
+/// at least one path argument outside the workspace roots in the [`MatchContext`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PathRule {
+    pub pattern: ArgsPattern,
+    pub decision: Decision,
+    pub justification: Option<String>,
+}
+
 pub trait Rule: Any + Debug + Send + Sync {
     fn program(&self) -> &str;
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch>;
+    fn matches(&self, cmd: &[String], context: &MatchContext) -> Option<RuleMatch>;
 }
 
 pub type RuleRef = Arc<dyn Rule>;
@@ -105,7 +182,7 @@ impl Rule for PrefixRule {
         self.pattern.first.as_ref()
     }
 
-    fn matches(&self, cmd: &[String]) -> Option<RuleMatch> {
+    fn matches(&self, cmd: &[String], _context: &MatchContext) -> Option<RuleMatch> {
         self.pattern
             .matches_prefix(cmd)
             .map(|matched_prefix| RuleMatch::PrefixRuleMatch {
@@ -116,12 +193,73 @@ impl Rule for PrefixRule {
     }
 }
 
+impl Rule for ArgRule {
+    fn program(&self) -> &str {
+        self.pattern.program.as_ref()
+    }
+
+    fn matches(&self, cmd: &[String], _context: &MatchContext) -> Option<RuleMatch> {
+        self.pattern
+            .matches_args(cmd)
+            .map(|matched_args| RuleMatch::ArgRuleMatch {
+                program: self.pattern.program.to_string(),
+                matched_args,
+                decision: self.decision,
+                justification: self.justification.clone(),
+            })
+    }
+}
+
+impl Rule for PathRule {
+    fn program(&self) -> &str {
+        self.pattern.program.as_ref()
+    }
+
+    fn matches(&self, cmd: &[String], context: &MatchContext) -> Option<RuleMatch> {
+        self.pattern.matches_args(cmd)?;
+
+        let outside_paths: Vec<String> = path_arguments(&cmd[1..])
+            .filter(|path| context.is_outside_workspace(path) == Some(true))
+            .map(str::to_string)
+            .collect();
+        if outside_paths.is_empty() {
+            return None;
+        }
+
+        Some(RuleMatch::PathRuleMatch {
+            program: self.pattern.program.to_string(),
+            outside_paths,
+            decision: self.decision,
+            justification: self.justification.clone(),
+        })
+    }
+}
+
+/// Yields the arguments that may name a path: positional arguments and the value
+/// half of `--flag=value`. Other flags are skipped.
+fn path_arguments(args: &[String]) -> impl Iterator<Item = &str> {
+    args.iter().filter_map(|arg| {
+        if arg.starts_with("--") {
+            arg.split_once('=')
+                .map(|(_, value)| value)
+                .filter(|value| !value.is_empty())
+        } else if arg.starts_with('-') || arg.is_empty() {
+            None
+        } else {
+            Some(arg.as_str())
+        }
+    })
+}
+
 /// Count how many rules match each provided example and error if any example is unmatched.
 pub(crate) fn validate_match_examples(rules: &[RuleRef], matches: &[Vec<String>]) -> Result<()> {
     let mut unmatched_examples = Vec::new();
 
     for example in matches {
-        if rules.iter().any(|rule| rule.matches(example).is_some()) {
+        if rules.iter().any(|rule| {
+            rule.matches(example, &MatchContext::for_examples())
+                .is_some()
+        }) {
             continue;
         }
 
@@ -147,7 +285,10 @@ pub(crate) fn validate_not_match_examples(
     not_matches: &[Vec<String>],
 ) -> Result<()> {
     for example in not_matches {
-        if let Some(rule) = rules.iter().find(|rule| rule.matches(example).is_some()) {
+        if let Some(rule) = rules.iter().find(|rule| {
+            rule.matches(example, &MatchContext::for_examples())
+                .is_some()
+        }) {
             return Err(Error::ExampleDidMatch {
                 rule: format!("{rule:?}"),
                 example: try_join(example.iter().map(String::as_str))
@@ -6,6 +6,7 @@ use anyhow::Result;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Error;
 use codex_execpolicy::Evaluation;
+use codex_execpolicy::MatchContext;
 use codex_execpolicy::Policy;
 use codex_execpolicy::PolicyParser;
 use codex_execpolicy::RuleMatch;
@@ -14,6 +15,7 @@ use codex_execpolicy::rule::PatternToken;
 use codex_execpolicy::rule::PrefixPattern;
 use codex_execpolicy::rule::PrefixRule;
 use pretty_assertions::assert_eq;
+use std::path::PathBuf;
 
 fn tokens(cmd: &[&str]) -> Vec<String> {
     cmd.iter().map(std::string::ToString::to_string).collect()
@@ -551,3 +553,156 @@ fn heuristics_match_is_returned_when_no_policy_matches() {
         evaluation
     );
 }
+
+#[test]
+fn glob_and_regex_tokens_match_prefix_arguments() -> Result<()> {
+    let policy_src = r#"
+prefix_rule(
+    pattern = ["cat", glob("*.pem")],
+    decision = "forbidden",
+    match = ["cat server.pem"],
+    not_match = ["cat server.pem.txt"],
+)
+prefix_rule(
+    pattern = ["cargo", ["check", regex("te?st")]],
+    match = ["cargo check", "cargo tst", "cargo test --all"],
+    not_match = ["cargo testing"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(&tokens(&["cat", "keys/server.pem"]), &allow_all);
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::PrefixRuleMatch {
+                matched_prefix: tokens(&["cat", "keys/server.pem"]),
+                decision: Decision::Forbidden,
+                justification: None,
+            }],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn arg_rule_matches_flags_in_any_position() -> Result<()> {
+    let policy_src = r#"
+arg_rule(
+    program = "git",
+    args = ["push", ["--force", "-f", glob("--force-*")]],
+    decision = "forbidden",
+    justification = "force pushes rewrite shared history",
+    match = [
+        "git push --force",
+        "git push origin main --force",
+        "git -C repo push -f",
+        "git push --force-with-lease",
+    ],
+    not_match = ["git push origin main", "git fetch --force"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+
+    let evaluation = policy.check(
+        &tokens(&["git", "push", "origin", "main", "--force"]),
+        &allow_all,
+    );
+    assert_eq!(
+        Evaluation {
+            decision: Decision::Forbidden,
+            matched_rules: vec![RuleMatch::ArgRuleMatch {
+                program: "git".to_string(),
+                matched_args: tokens(&["push", "--force"]),
+                decision: Decision::Forbidden,
+                justification: Some("force pushes rewrite shared history".to_string()),
+            }],
+        },
+        evaluation
+    );
+    Ok(())
+}
+
+#[test]
+fn path_rule_matches_paths_outside_workspace() -> Result<()> {
+    let policy_src = r#"
+path_rule(
+    program = ["rm", "mv"],
+    decision = "forbidden",
+    match = ["rm -rf /etc", "mv ../../secrets ."],
+    not_match = ["rm -rf target", "rm --force ./build"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    parser.parse("test.rules", policy_src)?;
+    let policy = parser.build();
+    let context = MatchContext::new(
+        PathBuf::from("/repo/app"),
+        vec![PathBuf::from("/repo/app"), PathBuf::from("/tmp")],
+    );
+
+    let command = tokens(&["rm", "-rf", "/tmp/cache", "~/.ssh", "--backup=/var/bak"]);
+    let matched_rules = policy.matches_for_command_with_context(&command, &context, None);
+    assert_eq!(
+        vec![RuleMatch::PathRuleMatch {
+            program: "rm".to_string(),
+            outside_paths: tokens(&["~/.ssh", "/var/bak"]),
+            decision: Decision::Forbidden,
+            justification: None,
+        }],
+        matched_rules
+    );
+
+    let inside = policy.matches_for_command_with_context(
+        &tokens(&["mv", "src/a.rs", "/tmp/a.rs"]),
+        &context,
+        None,
+    );
+    assert_eq!(Vec::<RuleMatch>::new(), inside);
+
+    // Without a workspace, path rules cannot be evaluated and never match.
+    let no_context = policy.matches_for_command(&tokens(&["rm", "-rf", "/etc"]), None);
+    assert_eq!(Vec::<RuleMatch>::new(), no_context);
+    Ok(())
+}
+
+#[test]
+fn first_pattern_token_must_be_literal() {
+    let policy_src = r#"
+prefix_rule(
+    pattern = [glob("git*"), "status"],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("expected parse error");
+    assert!(
+        err.to_string()
+            .contains("first pattern element must be a literal program name"),
+        "unexpected error: {err}"
+    );
+}
+
+#[test]
+fn invalid_regex_is_rejected() {
+    let policy_src = r#"
+arg_rule(
+    program = "git",
+    args = [regex("(unclosed")],
+)
+    "#;
+    let mut parser = PolicyParser::new();
+    let err = parser
+        .parse("test.rules", policy_src)
+        .expect_err("expected parse error");
+    assert!(
+        err.to_string().contains("invalid regex `(unclosed`"),
+        "unexpected error: {err}"
+    );
+}
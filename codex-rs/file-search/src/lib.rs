@@ -1,4 +1,5 @@
 use ignore::WalkBuilder;
+use ignore::WalkParallel;
 use ignore::overrides::OverrideBuilder;
 use nucleo_matcher::Matcher;
 use nucleo_matcher::Utf32Str;
@@ -13,6 +14,7 @@ use std::collections::BinaryHeap;
 use std::num::NonZero;
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
@@ -160,37 +162,12 @@ pub fn run(
         })
         .collect();
 
-    // Use the same tree-walker library that ripgrep uses. We use it directly so
-    // that we can leverage the parallelism it provides.
-    let mut walk_builder = WalkBuilder::new(search_directory);
-    walk_builder
-        .threads(num_walk_builder_threads)
-        // Allow hidden entries.
-        .hidden(false)
-        // Follow symlinks to search their contents.
-        .follow_links(true)
-        // Don't require git to be present to apply to apply git-related ignore rules.
-        .require_git(false);
-    if !respect_gitignore {
-        walk_builder
-            .git_ignore(false)
-            .git_global(false)
-            .git_exclude(false)
-            .ignore(false)
-            .parents(false);
-    }
-
-    if !exclude.is_empty() {
-        let mut override_builder = OverrideBuilder::new(search_directory);
-        for exclude in exclude {
-            // The `!` prefix is used to indicate an exclude pattern.
-            let exclude_pattern = format!("!{exclude}");
-            override_builder.add(&exclude_pattern)?;
-        }
-        let override_matcher = override_builder.build()?;
-        walk_builder.overrides(override_matcher);
-    }
-    let walker = walk_builder.build_parallel();
+    let walker = build_walker(
+        search_directory,
+        exclude,
+        num_walk_builder_threads,
+        respect_gitignore,
+    )?;
 
     // Each worker created by `WalkParallel::run()` will have its own
     // `BestMatchesList` to update.
@@ -221,24 +198,6 @@ pub fn run(
         })
     });
 
-    fn get_file_path<'a>(
-        entry_result: &'a Result<ignore::DirEntry, ignore::Error>,
-        search_directory: &std::path::Path,
-    ) -> Option<&'a str> {
-        let entry = match entry_result {
-            Ok(e) => e,
-            Err(_) => return None,
-        };
-        if entry.file_type().is_some_and(|ft| ft.is_dir()) {
-            return None;
-        }
-        let path = entry.path();
-        match path.strip_prefix(search_directory) {
-            Ok(rel_path) => rel_path.to_str(),
-            Err(_) => None,
-        }
-    }
-
     // If the cancel flag is set, we return early with an empty result.
     if cancel_flag.load(Ordering::Relaxed) {
         return Ok(FileSearchResults {
@@ -307,6 +266,142 @@ pub fn run(
     })
 }
 
+/// Files and directories visited by [`list_files`], as paths relative to the
+/// search directory (unsorted). The search directory itself is listed as `""`.
+#[derive(Debug, Default)]
+pub struct FileList {
+    pub files: Vec<String>,
+    pub dirs: Vec<String>,
+}
+
+/// Lists every file and directory under `search_directory` using the same
+/// walker and ignore rules as [`run`]. Returns an empty list if `cancel_flag`
+/// is set before the walk completes.
+pub fn list_files(
+    search_directory: &Path,
+    exclude: Vec<String>,
+    threads: NonZero<usize>,
+    cancel_flag: Arc<AtomicBool>,
+    respect_gitignore: bool,
+) -> anyhow::Result<FileList> {
+    let walker = build_walker(search_directory, exclude, threads.get(), respect_gitignore)?;
+    let list = Mutex::new(FileList::default());
+    walker.run(|| {
+        const CHECK_INTERVAL: usize = 1024;
+        let mut processed = 0;
+        let cancel = cancel_flag.clone();
+        let list = &list;
+
+        Box::new(move |entry| {
+            if let Ok(entry) = &entry
+                && let Ok(relative) = entry.path().strip_prefix(search_directory)
+                && let Some(relative) = relative.to_str()
+                && let Ok(mut list) = list.lock()
+            {
+                if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+                    list.dirs.push(relative.to_string());
+                } else {
+                    list.files.push(relative.to_string());
+                }
+            }
+
+            processed += 1;
+            if processed % CHECK_INTERVAL == 0 && cancel.load(Ordering::Relaxed) {
+                ignore::WalkState::Quit
+            } else {
+                ignore::WalkState::Continue
+            }
+        })
+    });
+
+    if cancel_flag.load(Ordering::Relaxed) {
+        return Ok(FileList::default());
+    }
+    list.into_inner()
+        .map_err(|_| anyhow::anyhow!("file list lock poisoned"))
+}
+
+/// Scores arbitrary strings against a query with the same fuzzy matching
+/// rules that [`run`] applies to paths.
+pub struct FuzzyScorer {
+    pattern: Pattern,
+    matcher: Matcher,
+    utf32buf: Vec<char>,
+}
+
+impl FuzzyScorer {
+    pub fn new(query: &str) -> Self {
+        Self {
+            pattern: create_pattern(query),
+            matcher: Matcher::new(nucleo_matcher::Config::DEFAULT),
+            utf32buf: Vec::new(),
+        }
+    }
+
+    /// Returns `None` when `haystack` does not match the query.
+    pub fn score(&mut self, haystack: &str) -> Option<u32> {
+        let haystack: Utf32Str<'_> = Utf32Str::new(haystack, &mut self.utf32buf);
+        self.pattern.score(haystack, &mut self.matcher)
+    }
+}
+
+fn build_walker(
+    search_directory: &Path,
+    exclude: Vec<String>,
+    threads: usize,
+    respect_gitignore: bool,
+) -> anyhow::Result<WalkParallel> {
+    // Use the same tree-walker library that ripgrep uses. We use it directly so
+    // that we can leverage the parallelism it provides.
+    let mut walk_builder = WalkBuilder::new(search_directory);
+    walk_builder
+        .threads(threads)
+        // Allow hidden entries.
+        .hidden(false)
+        // Follow symlinks to search their contents.
+        .follow_links(true)
+        // Don't require git to be present to apply to apply git-related ignore rules.
+        .require_git(false);
+    if !respect_gitignore {
+        walk_builder
+            .git_ignore(false)
+            .git_global(false)
+            .git_exclude(false)
+            .ignore(false)
+            .parents(false);
+    }
+
+    if !exclude.is_empty() {
+        let mut override_builder = OverrideBuilder::new(search_directory);
+        for exclude in exclude {
+            // The `!` prefix is used to indicate an exclude pattern.
+            let exclude_pattern = format!("!{exclude}");
+            override_builder.add(&exclude_pattern)?;
+        }
+        let override_matcher = override_builder.build()?;
+        walk_builder.overrides(override_matcher);
+    }
+    Ok(walk_builder.build_parallel())
+}
+
+fn get_file_path<'a>(
+    entry_result: &'a Result<ignore::DirEntry, ignore::Error>,
+    search_directory: &Path,
+) -> Option<&'a str> {
+    let entry = match entry_result {
+        Ok(e) => e,
+        Err(_) => return None,
+    };
+    if entry.file_type().is_some_and(|ft| ft.is_dir()) {
+        return None;
+    }
+    let path = entry.path();
+    match path.strip_prefix(search_directory) {
+        Ok(rel_path) => rel_path.to_str(),
+        Err(_) => None,
+    }
+}
+
 /// Sort matches in-place by descending score, then ascending path.
 fn sort_matches(matches: &mut [(u32, String)]) {
     matches.sort_by(cmp_by_score_desc_then_path_asc::<(u32, String), _, _>(
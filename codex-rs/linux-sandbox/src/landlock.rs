@@ -1,6 +1,8 @@
 use std::collections::BTreeMap;
 use std::path::Path;
 
+use crate::proxy_bridge::confine_network_to_proxy;
+
 use codex_core::error::CodexErr;
 use codex_core::error::Result;
 use codex_core::error::SandboxErr;
@@ -27,47 +29,109 @@ use seccompiler::apply_filter;
 
 /// Apply sandbox policies inside this thread so only the child inherits
 /// them, not the entire CLI process.
+///
+/// When the policy blocks network access and `network_proxy` is set to the
+/// proxy's port and Unix socket, the command runs in a private network
+/// namespace where that port on loopback is bridged to the proxy and nothing
+/// else is reachable, instead of having the network blocked outright.
 pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
+    network_proxy: Option<(u16, &Path)>,
 ) -> Result<()> {
     if !sandbox_policy.has_full_network_access() {
-        install_network_seccomp_filter_on_current_thread()?;
+        match network_proxy {
+            Some((port, socket)) => {
+                confine_network_to_proxy(port, socket)?;
+                install_network_seccomp_filter_on_current_thread(NetworkSeccompMode::ProxyOnly)?;
+            }
+            None => install_network_seccomp_filter_on_current_thread(NetworkSeccompMode::Blocked)?,
+        }
     }
 
-    if !sandbox_policy.has_full_disk_write_access() {
+    let readable_roots = sandbox_policy.get_readable_roots_with_cwd(cwd);
+    if !sandbox_policy.has_full_disk_write_access() || readable_roots.is_some() {
         let writable_roots = sandbox_policy
             .get_writable_roots_with_cwd(cwd)
             .into_iter()
             .map(|writable_root| writable_root.root)
             .collect();
-        install_filesystem_landlock_rules_on_current_thread(writable_roots)?;
+        install_filesystem_landlock_rules_on_current_thread(writable_roots, readable_roots)?;
     }
 
-    // TODO(ragona): Add appropriate restrictions if
-    // `sandbox_policy.has_full_disk_read_access()` is `false`.
-
     Ok(())
 }
 
-/// Installs Landlock file-system rules on the current thread allowing read
-/// access to the entire file-system while restricting write access to
-/// `/dev/null` and the provided list of `writable_roots`.
+/// System paths that stay readable under a restricted-read policy: only what
+/// the dynamic loader, shells, and common tools need to run. `/etc`, `/opt`
+/// and `/run` are deliberately not included wholesale because they hold
+/// credentials and service sockets.
+const SYSTEM_READABLE_ROOTS: &[&str] = &[
+    "/bin",
+    "/sbin",
+    "/usr",
+    "/lib",
+    "/lib32",
+    "/lib64",
+    "/libx32",
+    "/nix/store",
+    "/dev/zero",
+    "/dev/random",
+    "/dev/urandom",
+    "/dev/tty",
+    "/etc/alternatives",
+    "/etc/bash.bashrc",
+    "/etc/ca-certificates",
+    "/etc/environment",
+    "/etc/group",
+    "/etc/hosts",
+    "/etc/inputrc",
+    "/etc/ld.so.cache",
+    "/etc/ld.so.conf",
+    "/etc/ld.so.conf.d",
+    "/etc/localtime",
+    "/etc/nsswitch.conf",
+    "/etc/passwd",
+    "/etc/pki/tls/certs",
+    "/etc/profile",
+    "/etc/profile.d",
+    "/etc/resolv.conf",
+    "/etc/ssl/certs",
+];
+
+/// Installs Landlock file-system rules on the current thread restricting
+/// write access to `/dev/null` and the provided list of `writable_roots`.
+///
+/// Read access covers the entire file-system when `readable_roots` is `None`;
+/// otherwise it is limited to `readable_roots` plus [`SYSTEM_READABLE_ROOTS`].
 ///
 /// # Errors
 /// Returns [`CodexErr::Sandbox`] variants when the ruleset fails to apply.
 fn install_filesystem_landlock_rules_on_current_thread(
     writable_roots: Vec<AbsolutePathBuf>,
+    readable_roots: Option<Vec<AbsolutePathBuf>>,
 ) -> Result<()> {
     let abi = ABI::V5;
     let access_rw = AccessFs::from_all(abi);
     let access_ro = AccessFs::from_read(abi);
 
-    let mut ruleset = Ruleset::default()
+    let ruleset = Ruleset::default()
         .set_compatibility(CompatLevel::BestEffort)
         .handle_access(access_rw)?
-        .create()?
-        .add_rules(landlock::path_beneath_rules(&["/"], access_ro))?
+        .create()?;
+    let ruleset = match readable_roots {
+        None => ruleset.add_rules(landlock::path_beneath_rules(&["/"], access_ro))?,
+        Some(readable_roots) => {
+            let system_roots = SYSTEM_READABLE_ROOTS
+                .iter()
+                .map(Path::new)
+                .filter(|path| path.exists());
+            ruleset
+                .add_rules(landlock::path_beneath_rules(system_roots, access_ro))?
+                .add_rules(landlock::path_beneath_rules(&readable_roots, access_ro))?
+        }
+    };
+    let mut ruleset = ruleset
         .add_rules(landlock::path_beneath_rules(&["/dev/null"], access_rw))?
         .set_no_new_privs(true);
 
@@ -84,9 +148,22 @@ fn install_filesystem_landlock_rules_on_current_thread(
     Ok(())
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum NetworkSeccompMode {
+    /// Block all network sockets other than AF_UNIX.
+    Blocked,
+    /// Additionally allow TCP sockets over IPv4/IPv6 so the command can talk
+    /// to the filtering proxy; the private network namespace ensures the
+    /// proxy bridge is the only thing they can reach.
+    ProxyOnly,
+}
+
 /// Installs a seccomp filter that blocks outbound network access except for
-/// AF_UNIX domain sockets.
-fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(), SandboxErr> {
+/// AF_UNIX domain sockets (and, in [`NetworkSeccompMode::ProxyOnly`], TCP
+/// sockets).
+fn install_network_seccomp_filter_on_current_thread(
+    mode: NetworkSeccompMode,
+) -> std::result::Result<(), SandboxErr> {
     // Build rule map.
     let mut rules: BTreeMap<i64, Vec<SeccompRule>> = BTreeMap::new();
 
@@ -95,23 +172,25 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         rules.insert(nr, vec![]); // empty rule vec = unconditional match
     };
 
-    deny_syscall(libc::SYS_connect);
     deny_syscall(libc::SYS_accept);
     deny_syscall(libc::SYS_accept4);
     deny_syscall(libc::SYS_bind);
     deny_syscall(libc::SYS_listen);
-    deny_syscall(libc::SYS_getpeername);
-    deny_syscall(libc::SYS_getsockname);
-    deny_syscall(libc::SYS_shutdown);
-    deny_syscall(libc::SYS_sendto);
-    deny_syscall(libc::SYS_sendmmsg);
-    // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
-    // with their socketpair + child processes for sub-proc management
-    // deny_syscall(libc::SYS_recvfrom);
-    deny_syscall(libc::SYS_recvmmsg);
-    deny_syscall(libc::SYS_getsockopt);
-    deny_syscall(libc::SYS_setsockopt);
     deny_syscall(libc::SYS_ptrace);
+    if mode == NetworkSeccompMode::Blocked {
+        deny_syscall(libc::SYS_connect);
+        deny_syscall(libc::SYS_getpeername);
+        deny_syscall(libc::SYS_getsockname);
+        deny_syscall(libc::SYS_shutdown);
+        deny_syscall(libc::SYS_sendto);
+        deny_syscall(libc::SYS_sendmmsg);
+        // NOTE: allowing recvfrom allows some tools like: `cargo clippy` to run
+        // with their socketpair + child processes for sub-proc management
+        // deny_syscall(libc::SYS_recvfrom);
+        deny_syscall(libc::SYS_recvmmsg);
+        deny_syscall(libc::SYS_getsockopt);
+        deny_syscall(libc::SYS_setsockopt);
+    }
 
     // For `socket` we allow AF_UNIX (arg0 == AF_UNIX) and deny everything else.
     let unix_only_rule = SeccompRule::new(vec![SeccompCondition::new(
@@ -121,7 +200,11 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
         libc::AF_UNIX as u64,
     )?])?;
 
-    rules.insert(libc::SYS_socket, vec![unix_only_rule.clone()]);
+    let socket_rules = match mode {
+        NetworkSeccompMode::Blocked => vec![unix_only_rule.clone()],
+        NetworkSeccompMode::ProxyOnly => proxy_only_socket_rules()?,
+    };
+    rules.insert(libc::SYS_socket, socket_rules);
     rules.insert(libc::SYS_socketpair, vec![unix_only_rule]); // always deny (Unix can use socketpair but fine, keep open?)
 
     let filter = SeccompFilter::new(
@@ -143,3 +226,35 @@ fn install_network_seccomp_filter_on_current_thread() -> std::result::Result<(),
 
     Ok(())
 }
+
+/// Deny rules for `socket` that allow AF_UNIX plus IPv4/IPv6 stream sockets.
+/// UDP and raw sockets stay blocked so DNS and other datagram traffic cannot
+/// bypass the proxy.
+fn proxy_only_socket_rules() -> std::result::Result<Vec<SeccompRule>, SandboxErr> {
+    // The low bits of `type` hold the socket type; the rest are flags such as
+    // SOCK_CLOEXEC and SOCK_NONBLOCK.
+    const SOCK_TYPE_MASK: u64 = 0xf;
+
+    let mut rules = vec![SeccompRule::new(
+        [libc::AF_UNIX, libc::AF_INET, libc::AF_INET6]
+            .into_iter()
+            .map(|domain| {
+                SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Ne, domain as u64)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?,
+    )?];
+    for domain in [libc::AF_INET, libc::AF_INET6] {
+        for sock_type in [libc::SOCK_DGRAM, libc::SOCK_RAW] {
+            rules.push(SeccompRule::new(vec![
+                SeccompCondition::new(0, SeccompCmpArgLen::Dword, SeccompCmpOp::Eq, domain as u64)?,
+                SeccompCondition::new(
+                    1, // second argument (type)
+                    SeccompCmpArgLen::Dword,
+                    SeccompCmpOp::MaskedEq(SOCK_TYPE_MASK),
+                    sock_type as u64,
+                )?,
+            ])?);
+        }
+    }
+    Ok(rules)
+}
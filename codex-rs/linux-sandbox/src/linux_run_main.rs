@@ -14,6 +14,16 @@ pub struct LandlockCommand {
     #[arg(long = "sandbox-policy")]
     pub sandbox_policy: codex_core::protocol::SandboxPolicy,
 
+    /// Loopback port of the filtering network proxy. When set and the policy
+    /// blocks network access, TCP connections to this port on loopback are
+    /// bridged to `--network-proxy-socket`.
+    #[arg(long = "network-proxy-port", requires = "network_proxy_socket")]
+    pub network_proxy_port: Option<u16>,
+
+    /// Unix socket the filtering network proxy also listens on.
+    #[arg(long = "network-proxy-socket", requires = "network_proxy_port")]
+    pub network_proxy_socket: Option<PathBuf>,
+
     /// Full command args to run under landlock.
     #[arg(trailing_var_arg = true)]
     pub command: Vec<String>,
@@ -23,11 +33,17 @@ pub fn run_main() -> ! {
     let LandlockCommand {
         sandbox_policy_cwd,
         sandbox_policy,
+        network_proxy_port,
+        network_proxy_socket,
         command,
     } = LandlockCommand::parse();
 
-    if let Err(e) = apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd) {
-        panic!("error running landlock: {e:?}");
+    let network_proxy = network_proxy_port.zip(network_proxy_socket.as_deref());
+    if let Err(e) =
+        apply_sandbox_policy_to_current_thread(&sandbox_policy, &sandbox_policy_cwd, network_proxy)
+    {
+        eprintln!("codex-linux-sandbox: failed to set up the sandbox: {e}");
+        std::process::exit(1);
     }
 
     if command.is_empty() {
@@ -0,0 +1,126 @@
+//! Confines a sandboxed command's network access to the filtering proxy.
+//!
+//! The command runs in a private network namespace whose only interface is
+//! loopback, so no outside address is reachable. A bridge process listens on
+//! the proxy's port inside that namespace and forwards every connection to the
+//! proxy's Unix socket, which stays reachable because Unix sockets are
+//! addressed through the file system rather than the network namespace.
+
+use std::fs::File;
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::Shutdown;
+use std::net::TcpListener;
+use std::net::TcpStream;
+use std::os::fd::AsRawFd;
+use std::os::fd::FromRawFd;
+use std::os::fd::OwnedFd;
+use std::os::unix::net::UnixStream;
+use std::path::Path;
+
+/// Moves the current process into new user and network namespaces and starts
+/// a bridge from `127.0.0.1:proxy_port` inside them to `proxy_socket`.
+///
+/// Must be called while the process is still single-threaded and before any
+/// seccomp filter is installed, since the bridge needs `bind`/`accept`.
+pub(crate) fn confine_network_to_proxy(proxy_port: u16, proxy_socket: &Path) -> io::Result<()> {
+    let uid = unsafe { libc::geteuid() };
+    let gid = unsafe { libc::getegid() };
+    if unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET) } != 0 {
+        let err = io::Error::last_os_error();
+        return Err(io::Error::new(
+            err.kind(),
+            format!(
+                "failed to create a private network namespace for the network proxy \
+                 (unprivileged user namespaces may be disabled): {err}"
+            ),
+        ));
+    }
+    // Keep the caller's identity inside the namespace; `setgroups` must be
+    // denied before an unprivileged process may write its gid map.
+    std::fs::write("/proc/self/setgroups", "deny")?;
+    std::fs::write("/proc/self/uid_map", format!("{uid} {uid} 1"))?;
+    std::fs::write("/proc/self/gid_map", format!("{gid} {gid} 1"))?;
+    bring_up_loopback()?;
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, proxy_port))?;
+    let parent = unsafe { libc::getpid() };
+    match unsafe { libc::fork() } {
+        -1 => Err(io::Error::last_os_error()),
+        0 => {
+            run_bridge(parent, listener, proxy_socket);
+            unsafe { libc::_exit(0) }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Sets `IFF_UP` on the namespace's `lo` interface, which starts out down.
+fn bring_up_loopback() -> io::Result<()> {
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let mut request: libc::ifreq = unsafe { std::mem::zeroed() };
+    for (dst, src) in request.ifr_name.iter_mut().zip(b"lo") {
+        *dst = *src as libc::c_char;
+    }
+    unsafe {
+        if libc::ioctl(socket.as_raw_fd(), libc::SIOCGIFFLAGS as _, &mut request) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        request.ifr_ifru.ifru_flags |= libc::IFF_UP as libc::c_short;
+        if libc::ioctl(socket.as_raw_fd(), libc::SIOCSIFFLAGS as _, &request) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Body of the forked bridge process. It runs until the sandboxed command
+/// (the process that forked it) exits.
+fn run_bridge(parent: libc::pid_t, listener: TcpListener, proxy_socket: &Path) {
+    unsafe {
+        if libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL) != 0 || libc::getppid() != parent {
+            return;
+        }
+    }
+    // Do not hold the command's stdio pipes open once the command exits.
+    if let Ok(dev_null) = File::options().read(true).write(true).open("/dev/null") {
+        for fd in 0..=2 {
+            unsafe {
+                libc::dup2(dev_null.as_raw_fd(), fd);
+            }
+        }
+    }
+
+    for client in listener.incoming() {
+        let Ok(client) = client else {
+            continue;
+        };
+        let proxy_socket = proxy_socket.to_path_buf();
+        std::thread::spawn(move || {
+            if let Ok(upstream) = UnixStream::connect(&proxy_socket) {
+                forward(client, upstream);
+            }
+        });
+    }
+}
+
+fn forward(client: TcpStream, upstream: UnixStream) {
+    let (Ok(mut client_read), Ok(mut upstream_read)) = (client.try_clone(), upstream.try_clone())
+    else {
+        return;
+    };
+    let mut client_write = client;
+    let mut upstream_write = upstream;
+    let upload = std::thread::spawn(move || {
+        let _ = io::copy(&mut client_read, &mut upstream_write);
+        let _ = upstream_write.shutdown(Shutdown::Write);
+    });
+    let _ = io::copy(&mut upstream_read, &mut client_write);
+    let _ = client_write.shutdown(Shutdown::Write);
+    let _ = upload.join();
+}
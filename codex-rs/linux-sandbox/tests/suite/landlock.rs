@@ -9,6 +9,7 @@ use codex_core::protocol::SandboxPolicy;
 use codex_core::sandboxing::SandboxPermissions;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 use tempfile::NamedTempFile;
 
@@ -133,6 +134,67 @@ async fn test_timeout() {
     run_cmd(&["sleep", "2"], &[], 50).await;
 }
 
+#[expect(clippy::expect_used, clippy::unwrap_used)]
+async fn run_restricted_read_cmd(cmd: &[&str], readable_roots: &[PathBuf]) -> i32 {
+    let cwd = std::env::current_dir().expect("cwd should exist");
+    let sandbox_cwd = cwd.clone();
+    let params = ExecParams {
+        command: cmd.iter().copied().map(str::to_owned).collect(),
+        cwd,
+        expiration: LONG_TIMEOUT_MS.into(),
+        env: create_env_from_core_vars(),
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        justification: None,
+        arg0: None,
+    };
+
+    let sandbox_policy = SandboxPolicy::RestrictedRead {
+        readable_roots: readable_roots
+            .iter()
+            .map(|p| AbsolutePathBuf::try_from(p.as_path()).unwrap())
+            .collect(),
+        writable_roots: Vec::new(),
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+    };
+    let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
+    let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
+    process_exec_tool_call(
+        params,
+        &sandbox_policy,
+        sandbox_cwd.as_path(),
+        &codex_linux_sandbox_exe,
+        None,
+    )
+    .await
+    .unwrap()
+    .exit_code
+}
+
+#[tokio::test]
+async fn test_restricted_read_limits_reads_to_readable_roots() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let file_path = tmpdir.path().join("secret");
+    std::fs::write(&file_path, "blah").unwrap();
+    let cat = ["cat", file_path.to_str().unwrap()];
+
+    assert_ne!(run_restricted_read_cmd(&cat, &[]).await, 0);
+    assert_eq!(
+        run_restricted_read_cmd(&cat, &[tmpdir.path().to_path_buf()]).await,
+        0
+    );
+    // Programs still run, but system directories that may hold credentials
+    // stay unreadable.
+    assert_eq!(run_restricted_read_cmd(&["true"], &[]).await, 0);
+    if Path::new("/etc/hostname").exists() {
+        assert_ne!(
+            run_restricted_read_cmd(&["cat", "/etc/hostname"], &[]).await,
+            0
+        );
+    }
+}
+
 /// Helper that runs `cmd` under the Linux sandbox and asserts that the command
 /// does NOT succeed (i.e. returns a non‑zero exit code) **unless** the binary
 /// is missing in which case we silently treat it as an accepted skip so the
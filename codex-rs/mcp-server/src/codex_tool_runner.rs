@@ -259,7 +259,9 @@ async fn run_codex_tool_session_inner(
                     EventMsg::AgentReasoningDelta(_) => {
                         // TODO: think how we want to support this in the MCP
                     }
-                    EventMsg::McpStartupUpdate(_) | EventMsg::McpStartupComplete(_) => {
+                    EventMsg::McpStartupUpdate(_)
+                    | EventMsg::McpStartupComplete(_)
+                    | EventMsg::McpToolsChanged(_) => {
                         // Ignored in MCP tool runner.
                     }
                     EventMsg::AgentMessage(AgentMessageEvent { .. }) => {
@@ -272,9 +274,11 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::AgentReasoning(_)
                     | EventMsg::AgentReasoningSectionBreak(_)
                     | EventMsg::McpToolCallBegin(_)
+                    | EventMsg::McpToolCallProgress(_)
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
                     | EventMsg::ListCustomPromptsResponse(_)
+                    | EventMsg::ListMcpPromptsResponse(_)
                     | EventMsg::ListSkillsResponse(_)
                     | EventMsg::ExecCommandBegin(_)
                     | EventMsg::TerminalInteraction(_)
@@ -0,0 +1,103 @@
+/// Hosts that sandboxed commands may reach through the proxy.
+///
+/// An entry matches the host itself and every subdomain, so `crates.io`
+/// admits `index.crates.io` and `static.crates.io`. A leading `*.` is
+/// accepted for readability and means the same thing. IP literals only match
+/// exactly.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DomainAllowlist {
+    domains: Vec<String>,
+}
+
+impl DomainAllowlist {
+    pub fn new<I, S>(domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut normalized: Vec<String> = domains
+            .into_iter()
+            .map(|domain| {
+                let domain = domain.as_ref().trim();
+                normalize_host(domain.strip_prefix("*.").unwrap_or(domain))
+            })
+            .filter(|domain| !domain.is_empty())
+            .collect();
+        normalized.sort();
+        normalized.dedup();
+        Self {
+            domains: normalized,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+
+    pub fn domains(&self) -> &[String] {
+        &self.domains
+    }
+
+    /// Returns whether `host` (as it appears in a request target, possibly
+    /// with IPv6 brackets) may be contacted.
+    pub fn is_allowed(&self, host: &str) -> bool {
+        let host = normalize_host(host);
+        if host.is_empty() {
+            return false;
+        }
+        self.domains.iter().any(|domain| {
+            host == *domain
+                || host
+                    .strip_suffix(domain.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    let host = host
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(host);
+    host.trim_end_matches('.').to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn entries_match_host_and_subdomains() {
+        let allowlist = DomainAllowlist::new(["crates.io", "*.npmjs.org"]);
+        assert!(allowlist.is_allowed("crates.io"));
+        assert!(allowlist.is_allowed("static.crates.io"));
+        assert!(allowlist.is_allowed("registry.npmjs.org"));
+        assert!(allowlist.is_allowed("npmjs.org"));
+        assert!(!allowlist.is_allowed("evilcrates.io"));
+        assert!(!allowlist.is_allowed("crates.io.evil.com"));
+        assert!(!allowlist.is_allowed("example.com"));
+    }
+
+    #[test]
+    fn matching_ignores_case_and_trailing_dots() {
+        let allowlist = DomainAllowlist::new(["Crates.IO."]);
+        assert_eq!(allowlist.domains(), ["crates.io".to_string()]);
+        assert!(allowlist.is_allowed("INDEX.crates.io."));
+    }
+
+    #[test]
+    fn ip_literals_match_exactly() {
+        let allowlist = DomainAllowlist::new(["127.0.0.1", "::1"]);
+        assert!(allowlist.is_allowed("127.0.0.1"));
+        assert!(allowlist.is_allowed("[::1]"));
+        assert!(!allowlist.is_allowed("127.0.0.2"));
+    }
+
+    #[test]
+    fn empty_entries_are_dropped() {
+        let allowlist = DomainAllowlist::new(["", "  ", "."]);
+        assert!(allowlist.is_empty());
+        assert!(!allowlist.is_allowed(""));
+    }
+}
@@ -0,0 +1,13 @@
+//! Local HTTP proxy that lets sandboxed commands reach an allowlist of
+//! domains while every other destination stays blocked.
+//!
+//! The sandbox only permits outbound TCP connections to the proxy's port, so
+//! clients that honor the standard `HTTP_PROXY`/`HTTPS_PROXY` variables (cargo,
+//! npm, pip, curl, ...) keep working for allowlisted registries.
+
+mod allowlist;
+mod proxy;
+
+pub use allowlist::DomainAllowlist;
+pub use proxy::NetworkProxy;
+pub use proxy::proxy_env_vars;
@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+use std::io;
+use std::net::Ipv4Addr;
+use std::net::SocketAddr;
+use std::path::Path;
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tokio::task::JoinHandle;
+
+use crate::allowlist::DomainAllowlist;
+
+/// Upper bound on the size of a request head (request line plus headers).
+const MAX_REQUEST_HEAD_BYTES: usize = 32 * 1024;
+
+/// Environment variables that point HTTP clients at a proxy. Both spellings are
+/// set because tools disagree on which one they read.
+const PROXY_ENV_VARS: &[&str] = &[
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+    "npm_config_proxy",
+    "npm_config_https_proxy",
+];
+
+/// Variables that would let a client skip the proxy for some hosts.
+const NO_PROXY_ENV_VARS: &[&str] = &["NO_PROXY", "no_proxy"];
+
+/// Local HTTP proxy that only forwards requests to allowlisted hosts.
+///
+/// Supports `CONNECT` tunnels (used for HTTPS) and plain HTTP requests in
+/// absolute form. Anything else, and any request for a host that is not on the
+/// allowlist, is answered with an error status and never leaves the machine.
+/// The accept loop stops when the proxy is dropped.
+///
+/// On Unix the proxy also listens on a socket file in a private temporary
+/// directory. Unlike the loopback port, that path stays reachable from a
+/// sandbox that runs in its own network namespace.
+#[derive(Debug)]
+pub struct NetworkProxy {
+    addr: SocketAddr,
+    allowlist: Arc<DomainAllowlist>,
+    accept_task: JoinHandle<()>,
+    #[cfg(unix)]
+    unix_socket: UnixSocket,
+}
+
+#[cfg(unix)]
+#[derive(Debug)]
+struct UnixSocket {
+    path: PathBuf,
+    accept_task: JoinHandle<()>,
+    /// Keeps the directory holding the socket alive; removed on drop.
+    _dir: tempfile::TempDir,
+}
+
+impl NetworkProxy {
+    /// Starts the proxy on an ephemeral loopback port.
+    pub async fn start(allowlist: DomainAllowlist) -> io::Result<Self> {
+        Self::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0)), allowlist).await
+    }
+
+    pub async fn bind(addr: SocketAddr, allowlist: DomainAllowlist) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let allowlist = Arc::new(allowlist);
+        #[cfg(unix)]
+        let unix_socket = {
+            let dir = tempfile::Builder::new()
+                .prefix("codex-network-proxy-")
+                .tempdir()?;
+            let path = dir.path().join("proxy.sock");
+            let listener = UnixListener::bind(&path)?;
+            UnixSocket {
+                path,
+                accept_task: tokio::spawn(accept_unix_loop(listener, Arc::clone(&allowlist))),
+                _dir: dir,
+            }
+        };
+        let accept_task = tokio::spawn(accept_loop(listener, Arc::clone(&allowlist)));
+        Ok(Self {
+            addr,
+            allowlist,
+            accept_task,
+            #[cfg(unix)]
+            unix_socket,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Path of the Unix socket that serves the same proxy as [`Self::addr`];
+    /// `None` on platforms without Unix sockets.
+    pub fn unix_socket_path(&self) -> Option<&Path> {
+        #[cfg(unix)]
+        {
+            Some(&self.unix_socket.path)
+        }
+        #[cfg(not(unix))]
+        {
+            None
+        }
+    }
+
+    pub fn allowlist(&self) -> &DomainAllowlist {
+        &self.allowlist
+    }
+
+    /// Environment to apply to a child process so its HTTP clients go
+    /// through this proxy.
+    pub fn env_vars(&self) -> HashMap<String, String> {
+        proxy_env_vars(self.addr)
+    }
+}
+
+impl Drop for NetworkProxy {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        #[cfg(unix)]
+        self.unix_socket.accept_task.abort();
+    }
+}
+
+/// Environment that routes HTTP clients through the proxy listening on
+/// `addr` and clears any `NO_PROXY` exemptions.
+pub fn proxy_env_vars(addr: SocketAddr) -> HashMap<String, String> {
+    let url = format!("http://{addr}");
+    PROXY_ENV_VARS
+        .iter()
+        .map(|name| (name.to_string(), url.clone()))
+        .chain(
+            NO_PROXY_ENV_VARS
+                .iter()
+                .map(|name| (name.to_string(), String::new())),
+        )
+        .collect()
+}
+
+async fn accept_loop(listener: TcpListener, allowlist: Arc<DomainAllowlist>) {
+    loop {
+        let (client, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(err) => {
+                tracing::warn!("network proxy failed to accept connection: {err}");
+                continue;
+            }
+        };
+        let allowlist = Arc::clone(&allowlist);
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(client, &allowlist).await {
+                tracing::debug!("network proxy connection from {peer} failed: {err}");
+            }
+        });
+    }
+}
+
+#[cfg(unix)]
+async fn accept_unix_loop(listener: UnixListener, allowlist: Arc<DomainAllowlist>) {
+    loop {
+        let client = match listener.accept().await {
+            Ok((client, _)) => client,
+            Err(err) => {
+                tracing::warn!("network proxy failed to accept unix connection: {err}");
+                continue;
+            }
+        };
+        let allowlist = Arc::clone(&allowlist);
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(client, &allowlist).await {
+                tracing::debug!("network proxy unix connection failed: {err}");
+            }
+        });
+    }
+}
+
+async fn handle_connection<S>(mut client: S, allowlist: &DomainAllowlist) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (head, leftover) = match read_request_head(&mut client).await? {
+        Some(read) => read,
+        None => return Ok(()),
+    };
+    let request = match ProxyRequest::parse(&head) {
+        Ok(request) => request,
+        Err(reason) => {
+            return write_error(&mut client, "400 Bad Request", reason).await;
+        }
+    };
+
+    if !allowlist.is_allowed(&request.host) {
+        tracing::info!("network proxy blocked request for {}", request.host);
+        let reason = format!("{} is not on the network allowlist", request.host);
+        return write_error(&mut client, "403 Forbidden", &reason).await;
+    }
+
+    let mut upstream = match TcpStream::connect((request.host.as_str(), request.port)).await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            let reason = format!("failed to connect to {}: {err}", request.host);
+            return write_error(&mut client, "502 Bad Gateway", &reason).await;
+        }
+    };
+
+    match request.forward_head {
+        None => {
+            client
+                .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                .await?;
+        }
+        Some(forward_head) => upstream.write_all(forward_head.as_bytes()).await?,
+    }
+    upstream.write_all(&leftover).await?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Reads up to and including the blank line that ends the request head.
+/// Returns the head and any bytes the client sent after it, or `None` if the
+/// client hung up before sending anything.
+async fn read_request_head<S>(client: &mut S) -> io::Result<Option<(String, Vec<u8>)>>
+where
+    S: AsyncRead + Unpin,
+{
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = client.read(&mut chunk).await?;
+        if read == 0 {
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "connection closed before end of request head",
+            ));
+        }
+        buf.extend_from_slice(&chunk[..read]);
+        if let Some(end) = find_head_end(&buf) {
+            let leftover = buf.split_off(end);
+            let head = String::from_utf8(buf)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "non-UTF-8 head"))?;
+            return Ok(Some((head, leftover)));
+        }
+        if buf.len() > MAX_REQUEST_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request head too large",
+            ));
+        }
+    }
+}
+
+fn find_head_end(buf: &[u8]) -> Option<usize> {
+    buf.windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .map(|pos| pos + 4)
+}
+
+async fn write_error<S>(client: &mut S, status: &str, reason: &str) -> io::Result<()>
+where
+    S: AsyncWrite + Unpin,
+{
+    let body = format!("{reason}\n");
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    client.write_all(response.as_bytes()).await?;
+    client.shutdown().await
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ProxyRequest {
+    host: String,
+    port: u16,
+    /// Request head to send upstream for plain HTTP requests; `None` for
+    /// `CONNECT` tunnels.
+    forward_head: Option<String>,
+}
+
+impl ProxyRequest {
+    fn parse(head: &str) -> Result<Self, &'static str> {
+        let mut lines = head.split("\r\n");
+        let request_line = lines.next().ok_or("missing request line")?;
+        let mut parts = request_line.split(' ');
+        let (Some(method), Some(target), Some(version), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err("malformed request line");
+        };
+
+        if method.eq_ignore_ascii_case("CONNECT") {
+            let (host, port) = split_authority(target, None)?;
+            return Ok(Self {
+                host,
+                port,
+                forward_head: None,
+            });
+        }
+
+        let rest = target
+            .strip_prefix("http://")
+            .ok_or("only CONNECT and absolute http:// requests are supported")?;
+        let (authority, path) = match rest.find('/') {
+            Some(idx) => rest.split_at(idx),
+            None => (rest, "/"),
+        };
+        let (host, port) = split_authority(authority, Some(80))?;
+
+        // Force one request per connection so a client cannot reuse an
+        // approved connection to reach a different host.
+        let mut forward_head = format!("{method} {path} {version}\r\n");
+        for header in lines.filter(|line| !line.is_empty()) {
+            let name = header.split(':').next().unwrap_or_default().trim();
+            if name.eq_ignore_ascii_case("connection")
+                || name.eq_ignore_ascii_case("proxy-connection")
+                || name.eq_ignore_ascii_case("proxy-authorization")
+            {
+                continue;
+            }
+            forward_head.push_str(header);
+            forward_head.push_str("\r\n");
+        }
+        forward_head.push_str("Connection: close\r\n\r\n");
+
+        Ok(Self {
+            host,
+            port,
+            forward_head: Some(forward_head),
+        })
+    }
+}
+
+/// Splits `host:port` (IPv6 hosts in brackets), falling back to
+/// `default_port` when no port is given.
+fn split_authority(
+    authority: &str,
+    default_port: Option<u16>,
+) -> Result<(String, u16), &'static str> {
+    let (host, port) = if let Some(rest) = authority.strip_prefix('[') {
+        let (host, after) = rest.split_once(']').ok_or("malformed IPv6 host")?;
+        (host, after.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    if host.is_empty() {
+        return Err("missing host");
+    }
+    let port = match port {
+        Some(port) => port.parse().map_err(|_| "invalid port")?,
+        None => default_port.ok_or("missing port")?,
+    };
+    Ok((host.to_string(), port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_connect_request() {
+        let request =
+            ProxyRequest::parse("CONNECT index.crates.io:443 HTTP/1.1\r\nHost: x\r\n\r\n").unwrap();
+        assert_eq!(
+            request,
+            ProxyRequest {
+                host: "index.crates.io".to_string(),
+                port: 443,
+                forward_head: None,
+            }
+        );
+    }
+
+    #[test]
+    fn rewrites_absolute_form_requests_to_origin_form() {
+        let request = ProxyRequest::parse(
+            "GET http://example.com/a/b?c=d HTTP/1.1\r\nHost: example.com\r\nProxy-Connection: keep-alive\r\nConnection: keep-alive\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(
+            request,
+            ProxyRequest {
+                host: "example.com".to_string(),
+                port: 80,
+                forward_head: Some(
+                    "GET /a/b?c=d HTTP/1.1\r\nHost: example.com\r\nConnection: close\r\n\r\n"
+                        .to_string()
+                ),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_ipv6_authority() {
+        assert_eq!(
+            split_authority("[::1]:8080", None),
+            Ok(("::1".to_string(), 8080))
+        );
+        assert_eq!(
+            split_authority("[::1]", Some(80)),
+            Ok(("::1".to_string(), 80))
+        );
+    }
+
+    #[test]
+    fn rejects_origin_form_and_missing_ports() {
+        assert_eq!(
+            ProxyRequest::parse("GET / HTTP/1.1\r\n\r\n"),
+            Err("only CONNECT and absolute http:// requests are supported")
+        );
+        assert_eq!(
+            ProxyRequest::parse("CONNECT example.com HTTP/1.1\r\n\r\n"),
+            Err("missing port")
+        );
+    }
+}
@@ -0,0 +1,183 @@
+use std::net::SocketAddr;
+
+use codex_network_proxy::DomainAllowlist;
+use codex_network_proxy::NetworkProxy;
+use pretty_assertions::assert_eq;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+
+/// Stand-in upstream server that answers every connection with a fixed HTTP
+/// response after echoing back the request head it received in the body.
+#[expect(clippy::unwrap_used)]
+async fn spawn_upstream() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            tokio::spawn(async move {
+                let mut received = Vec::new();
+                let mut chunk = [0u8; 1024];
+                while !received.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let read = stream.read(&mut chunk).await.unwrap();
+                    if read == 0 {
+                        return;
+                    }
+                    received.extend_from_slice(&chunk[..read]);
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    received.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.write_all(&received).await.unwrap();
+                stream.shutdown().await.unwrap();
+            });
+        }
+    });
+    addr
+}
+
+#[expect(clippy::unwrap_used)]
+async fn roundtrip(proxy: &NetworkProxy, request: &str) -> String {
+    let mut client = TcpStream::connect(proxy.addr()).await.unwrap();
+    client.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn forwards_plain_http_to_allowlisted_host() {
+    let upstream = spawn_upstream().await;
+    let proxy = NetworkProxy::start(DomainAllowlist::new(["localhost"]))
+        .await
+        .unwrap();
+
+    let port = upstream.port();
+    let response = roundtrip(
+        &proxy,
+        &format!(
+            "GET http://localhost:{port}/index HTTP/1.1\r\nHost: localhost:{port}\r\nProxy-Connection: keep-alive\r\n\r\n"
+        ),
+    )
+    .await;
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    let (_, echoed) = response.split_once("\r\n\r\n").unwrap();
+    assert_eq!(
+        echoed,
+        format!("GET /index HTTP/1.1\r\nHost: localhost:{port}\r\nConnection: close\r\n\r\n")
+    );
+}
+
+#[tokio::test]
+async fn tunnels_connect_to_allowlisted_host() {
+    let upstream = spawn_upstream().await;
+    let proxy = NetworkProxy::start(DomainAllowlist::new(["localhost"]))
+        .await
+        .unwrap();
+
+    let port = upstream.port();
+    let mut client = TcpStream::connect(proxy.addr()).await.unwrap();
+    client
+        .write_all(format!("CONNECT localhost:{port} HTTP/1.1\r\n\r\n").as_bytes())
+        .await
+        .unwrap();
+    let established = b"HTTP/1.1 200 Connection Established\r\n\r\n";
+    let mut buf = vec![0u8; established.len()];
+    client.read_exact(&mut buf).await.unwrap();
+    assert_eq!(buf, established);
+
+    // Everything after the CONNECT handshake is relayed verbatim.
+    client
+        .write_all(b"GET /tunneled HTTP/1.1\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).await.unwrap();
+    assert!(
+        response.ends_with("GET /tunneled HTTP/1.1\r\n\r\n"),
+        "{response}"
+    );
+}
+
+#[tokio::test]
+async fn blocks_hosts_missing_from_allowlist() {
+    let upstream = spawn_upstream().await;
+    let proxy = NetworkProxy::start(DomainAllowlist::new(["crates.io"]))
+        .await
+        .unwrap();
+
+    let port = upstream.port();
+    let connect = roundtrip(
+        &proxy,
+        &format!("CONNECT 127.0.0.1:{port} HTTP/1.1\r\n\r\n"),
+    )
+    .await;
+    assert!(
+        connect.starts_with("HTTP/1.1 403 Forbidden\r\n"),
+        "{connect}"
+    );
+    assert!(connect.ends_with("127.0.0.1 is not on the network allowlist\n"));
+
+    let plain = roundtrip(
+        &proxy,
+        &format!("GET http://localhost:{port}/ HTTP/1.1\r\n\r\n"),
+    )
+    .await;
+    assert!(plain.starts_with("HTTP/1.1 403 Forbidden\r\n"), "{plain}");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn serves_the_same_proxy_over_its_unix_socket() {
+    let upstream = spawn_upstream().await;
+    let proxy = NetworkProxy::start(DomainAllowlist::new(["localhost"]))
+        .await
+        .unwrap();
+
+    let port = upstream.port();
+    let mut client = tokio::net::UnixStream::connect(proxy.unix_socket_path().unwrap())
+        .await
+        .unwrap();
+    client
+        .write_all(format!("GET http://localhost:{port}/unix HTTP/1.1\r\n\r\n").as_bytes())
+        .await
+        .unwrap();
+    let mut response = String::new();
+    client.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+    assert!(response.ends_with("GET /unix HTTP/1.1\r\nConnection: close\r\n\r\n"));
+
+    let socket_dir = proxy
+        .unix_socket_path()
+        .and_then(std::path::Path::parent)
+        .unwrap()
+        .to_path_buf();
+    drop(proxy);
+    assert!(!socket_dir.exists());
+}
+
+#[tokio::test]
+async fn rejects_requests_that_are_not_proxy_requests() {
+    let proxy = NetworkProxy::start(DomainAllowlist::new(["localhost"]))
+        .await
+        .unwrap();
+
+    let response = roundtrip(&proxy, "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+    assert!(
+        response.starts_with("HTTP/1.1 400 Bad Request\r\n"),
+        "{response}"
+    );
+}
+
+#[test]
+fn env_vars_point_clients_at_proxy() {
+    let env = codex_network_proxy::proxy_env_vars("127.0.0.1:3128".parse().unwrap());
+    assert_eq!(env["HTTPS_PROXY"], "http://127.0.0.1:3128");
+    assert_eq!(env["http_proxy"], "http://127.0.0.1:3128");
+    assert_eq!(env["NO_PROXY"], "");
+}
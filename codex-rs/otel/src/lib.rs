@@ -8,6 +8,7 @@ mod otlp;
 use crate::metrics::MetricsClient;
 use crate::metrics::MetricsConfig;
 use crate::metrics::MetricsError;
+use crate::metrics::PrometheusRegistry;
 use crate::metrics::Result as MetricsResult;
 use crate::metrics::timer::Timer;
 use crate::metrics::validation::validate_tag_key;
@@ -43,6 +44,7 @@ pub struct OtelManager {
     pub(crate) metadata: OtelEventMetadata,
     pub(crate) metrics: Option<MetricsClient>,
     pub(crate) metrics_use_metadata_tags: bool,
+    pub(crate) prometheus: Option<PrometheusRegistry>,
 }
 
 impl OtelManager {
@@ -64,6 +66,11 @@ impl OtelManager {
         self
     }
 
+    pub fn with_prometheus(mut self, registry: PrometheusRegistry) -> Self {
+        self.prometheus = Some(registry);
+        self
+    }
+
     pub fn with_metrics_config(self, config: MetricsConfig) -> MetricsResult<Self> {
         let metrics = MetricsClient::new(config)?;
         Ok(self.with_metrics(metrics))
@@ -77,6 +84,8 @@ impl OtelManager {
     }
 
     pub fn counter(&self, name: &str, inc: i64, tags: &[(&str, &str)]) {
+        self.prometheus_counter(name, inc, tags);
+
         let res: MetricsResult<()> = (|| {
             let Some(metrics) = &self.metrics else {
                 return Ok(());
@@ -129,6 +138,41 @@ impl OtelManager {
         metrics.start_timer(name, &tags)
     }
 
+    /// Increase a counter on the Prometheus endpoint only.
+    pub(crate) fn prometheus_counter(&self, name: &str, inc: i64, tags: &[(&str, &str)]) {
+        let Some(prometheus) = &self.prometheus else {
+            return;
+        };
+        match self.tags_with_metadata(tags) {
+            Ok(tags) => prometheus.counter(name, inc.max(0) as u64, &tags),
+            Err(e) => tracing::warn!("metrics counter [{name}] failed: {e}"),
+        }
+    }
+
+    /// Add `delta` to a gauge. Gauges are only exposed on the Prometheus
+    /// endpoint; OTLP metrics have no equivalent here.
+    pub fn gauge_add(&self, name: &str, delta: i64, tags: &[(&str, &str)]) {
+        let Some(prometheus) = &self.prometheus else {
+            return;
+        };
+        match self.tags_with_metadata(tags) {
+            Ok(tags) => prometheus.gauge_add(name, delta as f64, &tags),
+            Err(e) => tracing::warn!("metrics gauge [{name}] failed: {e}"),
+        }
+    }
+
+    /// Record a latency in a Prometheus histogram (in seconds). Not exported
+    /// over OTLP; use [`OtelManager::record_duration`] for that.
+    pub fn observe_latency(&self, name: &str, duration: Duration, tags: &[(&str, &str)]) {
+        let Some(prometheus) = &self.prometheus else {
+            return;
+        };
+        match self.tags_with_metadata(tags) {
+            Ok(tags) => prometheus.observe_duration(name, duration, &tags),
+            Err(e) => tracing::warn!("metrics latency [{name}] failed: {e}"),
+        }
+    }
+
     pub fn shutdown_metrics(&self) -> MetricsResult<()> {
         let Some(metrics) = &self.metrics else {
             return Ok(());
@@ -1,6 +1,7 @@
 mod client;
 mod config;
 mod error;
+mod prometheus;
 pub(crate) mod timer;
 pub(crate) mod validation;
 
@@ -9,6 +10,8 @@ pub use crate::metrics::config::MetricsConfig;
 pub use crate::metrics::config::MetricsExporter;
 pub use crate::metrics::error::MetricsError;
 pub use crate::metrics::error::Result;
+pub use crate::metrics::prometheus::PrometheusRegistry;
+pub use crate::metrics::prometheus::PrometheusServer;
 use std::sync::OnceLock;
 
 static GLOBAL_METRICS: OnceLock<MetricsClient> = OnceLock::new();
@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::thread;
+use std::time::Duration;
+use tiny_http::Header;
+use tiny_http::Method;
+use tiny_http::Response;
+use tiny_http::Server;
+use tracing::debug;
+use tracing::warn;
+
+/// Bucket upper bounds (in seconds) used for every histogram. They cover both
+/// sub-second tool calls and approvals that wait on a human for minutes.
+const HISTOGRAM_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+static GLOBAL_PROMETHEUS: OnceLock<PrometheusRegistry> = OnceLock::new();
+
+type Labels = Vec<(String, String)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Histogram => "histogram",
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Series {
+    Value(f64),
+    Histogram {
+        buckets: Vec<u64>,
+        sum: f64,
+        count: u64,
+    },
+}
+
+#[derive(Debug)]
+struct Family {
+    kind: MetricKind,
+    series: BTreeMap<Labels, Series>,
+}
+
+/// In-process metrics registry rendered in the Prometheus text exposition
+/// format. Unlike [`crate::metrics::MetricsClient`], nothing is pushed: the
+/// current values are read whenever `/metrics` is scraped.
+#[derive(Clone, Debug, Default)]
+pub struct PrometheusRegistry {
+    families: Arc<Mutex<BTreeMap<String, Family>>>,
+}
+
+impl PrometheusRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install this registry as the process-wide registry picked up by every
+    /// `OtelManager` created afterwards. Only the first call has an effect.
+    pub fn install_global(&self) {
+        let _ = GLOBAL_PROMETHEUS.set(self.clone());
+    }
+
+    /// The process-wide registry, if a Prometheus endpoint was started.
+    pub fn global() -> Option<Self> {
+        GLOBAL_PROMETHEUS.get().cloned()
+    }
+
+    /// Increase a counter by `inc`.
+    pub fn counter(&self, name: &str, inc: u64, labels: &[(&str, &str)]) {
+        self.update(name, MetricKind::Counter, labels, |series| {
+            if let Series::Value(value) = series {
+                *value += inc as f64;
+            }
+        });
+    }
+
+    /// Add `delta` (which may be negative) to a gauge.
+    pub fn gauge_add(&self, name: &str, delta: f64, labels: &[(&str, &str)]) {
+        self.update(name, MetricKind::Gauge, labels, |series| {
+            if let Series::Value(value) = series {
+                *value += delta;
+            }
+        });
+    }
+
+    /// Set a gauge to `value`.
+    pub fn gauge_set(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.update(name, MetricKind::Gauge, labels, |series| {
+            if let Series::Value(current) = series {
+                *current = value;
+            }
+        });
+    }
+
+    /// Record a duration in a histogram measured in seconds.
+    pub fn observe_duration(&self, name: &str, duration: Duration, labels: &[(&str, &str)]) {
+        let seconds = duration.as_secs_f64();
+        self.update(name, MetricKind::Histogram, labels, |series| {
+            if let Series::Histogram {
+                buckets,
+                sum,
+                count,
+            } = series
+            {
+                for (bucket, bound) in buckets.iter_mut().zip(HISTOGRAM_BUCKETS) {
+                    if seconds <= *bound {
+                        *bucket += 1;
+                    }
+                }
+                *sum += seconds;
+                *count += 1;
+            }
+        });
+    }
+
+    /// Render every metric in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let families = self
+            .families
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut out = String::new();
+        for (name, family) in families.iter() {
+            let _ = writeln!(out, "# TYPE {name} {}", family.kind.as_str());
+            for (labels, series) in &family.series {
+                match series {
+                    Series::Value(value) => {
+                        let _ = writeln!(out, "{name}{} {value}", format_labels(labels, None));
+                    }
+                    Series::Histogram {
+                        buckets,
+                        sum,
+                        count,
+                    } => {
+                        for (bucket, bound) in buckets.iter().zip(HISTOGRAM_BUCKETS) {
+                            let le = bound.to_string();
+                            let _ = writeln!(
+                                out,
+                                "{name}_bucket{} {bucket}",
+                                format_labels(labels, Some(&le))
+                            );
+                        }
+                        let _ = writeln!(
+                            out,
+                            "{name}_bucket{} {count}",
+                            format_labels(labels, Some("+Inf"))
+                        );
+                        let _ = writeln!(out, "{name}_sum{} {sum}", format_labels(labels, None));
+                        let _ =
+                            writeln!(out, "{name}_count{} {count}", format_labels(labels, None));
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn update(
+        &self,
+        name: &str,
+        kind: MetricKind,
+        labels: &[(&str, &str)],
+        apply: impl FnOnce(&mut Series),
+    ) {
+        let name = sanitize_name(name);
+        // Later labels win, matching how `MetricsClient` merges tags.
+        let labels: Labels = labels
+            .iter()
+            .map(|(key, value)| (sanitize_name(key), (*value).to_string()))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .collect();
+
+        let mut families = self
+            .families
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let family = families.entry(name.clone()).or_insert_with(|| Family {
+            kind,
+            series: BTreeMap::new(),
+        });
+        if family.kind != kind {
+            warn!(
+                "prometheus metric {name} is a {} and cannot be recorded as a {}",
+                family.kind.as_str(),
+                kind.as_str()
+            );
+            return;
+        }
+        let series = family.series.entry(labels).or_insert_with(|| match kind {
+            MetricKind::Counter | MetricKind::Gauge => Series::Value(0.0),
+            MetricKind::Histogram => Series::Histogram {
+                buckets: vec![0; HISTOGRAM_BUCKETS.len()],
+                sum: 0.0,
+                count: 0,
+            },
+        });
+        apply(series);
+    }
+}
+
+/// HTTP server exposing a [`PrometheusRegistry`] at `GET /metrics`.
+///
+/// The server runs on a dedicated thread and stops when dropped.
+pub struct PrometheusServer {
+    server: Arc<Server>,
+    local_addr: SocketAddr,
+}
+
+impl PrometheusServer {
+    pub fn start(addr: SocketAddr, registry: PrometheusRegistry) -> io::Result<Self> {
+        let server = Server::http(addr).map_err(io::Error::other)?;
+        let local_addr = server.server_addr().to_ip().unwrap_or(addr);
+        let server = Arc::new(server);
+
+        let worker = Arc::clone(&server);
+        thread::Builder::new()
+            .name("prometheus-metrics".to_string())
+            .spawn(move || {
+                while let Ok(request) = worker.recv() {
+                    let response = if *request.method() == Method::Get
+                        && request.url().split('?').next() == Some("/metrics")
+                    {
+                        let mut response = Response::from_string(registry.render());
+                        if let Ok(header) =
+                            Header::from_bytes(&b"Content-Type"[..], CONTENT_TYPE.as_bytes())
+                        {
+                            response.add_header(header);
+                        }
+                        response
+                    } else {
+                        Response::from_string("Not Found").with_status_code(404)
+                    };
+                    if let Err(err) = request.respond(response) {
+                        debug!("failed to respond to metrics scrape: {err}");
+                    }
+                }
+            })?;
+
+        Ok(Self { server, local_addr })
+    }
+
+    /// Address the server is listening on (useful when binding port 0).
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for PrometheusServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+    }
+}
+
+/// Map a Codex metric or tag name (`codex.tool.call`) onto the Prometheus
+/// name charset (`codex_tool_call`).
+fn sanitize_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+fn format_labels(labels: &[(String, String)], le: Option<&str>) -> String {
+    if labels.is_empty() && le.is_none() {
+        return String::new();
+    }
+    let mut parts: Vec<String> = labels
+        .iter()
+        .map(|(key, value)| format!("{key}=\"{}\"", escape_label_value(value)))
+        .collect();
+    if let Some(le) = le {
+        parts.push(format!("le=\"{le}\""));
+    }
+    format!("{{{}}}", parts.join(","))
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
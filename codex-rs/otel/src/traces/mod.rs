@@ -1 +1,2 @@
 pub mod otel_manager;
+pub mod turn_spans;
@@ -1,3 +1,4 @@
+use crate::metrics::PrometheusRegistry;
 use crate::otel_provider::traceparent_context_from_env;
 use chrono::SecondsFormat;
 use chrono::Utc;
@@ -56,6 +57,7 @@ impl OtelManager {
             },
             metrics: crate::metrics::global(),
             metrics_use_metadata_tags: true,
+            prometheus: PrometheusRegistry::global(),
         }
     }
 
@@ -299,6 +301,18 @@ impl OtelManager {
         reasoning_token_count: Option<i64>,
         tool_token_count: i64,
     ) {
+        let model = self.metadata.model.as_str();
+        for (kind, count) in [
+            ("input", Some(input_token_count)),
+            ("output", Some(output_token_count)),
+            ("cached", cached_token_count),
+            ("reasoning", reasoning_token_count),
+            ("tool", Some(tool_token_count)),
+        ] {
+            if let Some(count) = count {
+                self.prometheus_counter("codex.tokens", count, &[("model", model), ("type", kind)]);
+            }
+        }
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.sse_event",
@@ -352,6 +366,18 @@ impl OtelManager {
         );
     }
 
+    /// Record how long a tool call waited for an approval decision.
+    pub fn approval_wait(&self, tool_name: &str, decision: &ReviewDecision, waited: Duration) {
+        self.observe_latency(
+            "codex.approval.wait_duration_seconds",
+            waited,
+            &[
+                ("tool", tool_name),
+                ("decision", decision.to_opaque_string()),
+            ],
+        );
+    }
+
     pub fn tool_decision(
         &self,
         tool_name: &str,
@@ -446,6 +472,11 @@ impl OtelManager {
             1,
             &[("tool", tool_name), ("success", success_str)],
         );
+        self.observe_latency(
+            "codex.tool.call.duration_seconds",
+            duration,
+            &[("tool", tool_name), ("success", success_str)],
+        );
         tracing::event!(
             tracing::Level::INFO,
             event.name = "codex.tool_result",
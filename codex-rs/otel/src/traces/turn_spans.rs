@@ -0,0 +1,105 @@
+//! Spans that make up the trace tree of a single turn.
+//!
+//! Every turn gets one `codex.turn` root span. Model sampling, tool calls,
+//! approval waits, sandboxed executions, MCP calls and compactions are
+//! recorded beneath it, and each span carries the same `thread.id` and
+//! `turn.id` attributes so a slow turn can be broken down in a trace viewer.
+
+use codex_protocol::protocol::ReviewDecision;
+use tracing::Span;
+use tracing::field;
+use tracing::info_span;
+
+use crate::OtelManager;
+
+impl OtelManager {
+    /// Root span for one turn. Continues the trace from `TRACEPARENT` when it
+    /// is set.
+    pub fn turn_span(&self, turn_id: &str, kind: &str) -> Span {
+        let span = info_span!(
+            "codex.turn",
+            thread.id = %self.metadata.conversation_id,
+            turn.id = turn_id,
+            turn.kind = kind,
+            model = %self.metadata.model,
+        );
+        self.apply_traceparent_parent(&span);
+        span
+    }
+
+    /// Span covering one tool call, from dispatch until its output is ready.
+    /// `success` and `aborted` are recorded once the call finishes.
+    pub fn tool_call_span(&self, turn_id: &str, tool_name: &str, call_id: &str) -> Span {
+        info_span!(
+            "codex.tool_call",
+            thread.id = %self.metadata.conversation_id,
+            turn.id = turn_id,
+            tool.name = tool_name,
+            call.id = call_id,
+            success = field::Empty,
+            aborted = false,
+        )
+    }
+
+    /// Span covering the wait for a user decision on a tool call.
+    pub fn approval_span(&self, turn_id: &str, tool_name: &str, call_id: &str) -> Span {
+        info_span!(
+            "codex.approval",
+            thread.id = %self.metadata.conversation_id,
+            turn.id = turn_id,
+            tool.name = tool_name,
+            call.id = call_id,
+            approval.decision = field::Empty,
+        )
+    }
+
+    pub fn record_approval_decision(span: &Span, decision: &ReviewDecision) {
+        span.record("approval.decision", decision.to_opaque_string());
+    }
+
+    /// Span covering one attempt to run a command under `sandbox`. Commands
+    /// that finish record `exit_code` on the span.
+    pub fn exec_span(
+        &self,
+        turn_id: &str,
+        tool_name: &str,
+        call_id: &str,
+        sandbox: &str,
+        escalated: bool,
+    ) -> Span {
+        info_span!(
+            "codex.exec",
+            thread.id = %self.metadata.conversation_id,
+            turn.id = turn_id,
+            tool.name = tool_name,
+            call.id = call_id,
+            sandbox = sandbox,
+            escalated = escalated,
+            exit_code = field::Empty,
+        )
+    }
+
+    /// Span covering a request to an MCP server.
+    pub fn mcp_call_span(&self, turn_id: &str, server: &str, tool: &str, call_id: &str) -> Span {
+        info_span!(
+            "codex.mcp_call",
+            thread.id = %self.metadata.conversation_id,
+            turn.id = turn_id,
+            mcp.server = server,
+            tool.name = tool,
+            call.id = call_id,
+            success = field::Empty,
+        )
+    }
+
+    /// Span covering a history compaction, run either `local`ly or `remote`ly.
+    pub fn compaction_span(&self, turn_id: &str, mode: &str, strategy: &str) -> Span {
+        info_span!(
+            "codex.compaction",
+            thread.id = %self.metadata.conversation_id,
+            turn.id = turn_id,
+            compaction.mode = mode,
+            compaction.strategy = strategy,
+        )
+    }
+}
@@ -1,5 +1,6 @@
 mod manager_metrics;
 mod otlp_http_loopback;
+mod prometheus;
 mod send;
 mod timing;
 mod validation;
@@ -1,17 +1,29 @@
 use codex_otel::config::OtelExporter;
 use codex_otel::config::OtelHttpProtocol;
+use codex_otel::config::OtelSettings;
 use codex_otel::metrics::MetricsClient;
 use codex_otel::metrics::MetricsConfig;
 use codex_otel::metrics::Result;
+use codex_otel::otel_provider::OtelProvider;
+use codex_otel::traces::otel_manager::OtelManager;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SessionSource;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::io::Read as _;
 use std::io::Write as _;
+use std::net::SocketAddr;
 use std::net::TcpListener;
 use std::net::TcpStream;
+use std::path::PathBuf;
 use std::sync::mpsc;
 use std::thread;
 use std::time::Duration;
 use std::time::Instant;
+use tracing_subscriber::layer::SubscriberExt;
 
 struct CapturedRequest {
     path: String,
@@ -128,8 +140,13 @@ fn write_http_response(stream: &mut TcpStream, status: &str) -> std::io::Result<
     stream.flush()
 }
 
-#[test]
-fn otlp_http_exporter_sends_metrics_to_collector() -> Result<()> {
+/// Starts a stand-in OTLP collector that accepts every request for a few
+/// seconds and then hands back what it captured.
+fn spawn_collector() -> (
+    SocketAddr,
+    thread::JoinHandle<()>,
+    mpsc::Receiver<Vec<CapturedRequest>>,
+) {
     let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
     let addr = listener.local_addr().expect("local_addr");
     listener.set_nonblocking(true).expect("set_nonblocking");
@@ -161,6 +178,12 @@ fn otlp_http_exporter_sends_metrics_to_collector() -> Result<()> {
 
         let _ = tx.send(captured);
     });
+    (addr, server, rx)
+}
+
+#[test]
+fn otlp_http_exporter_sends_metrics_to_collector() -> Result<()> {
+    let (addr, server, rx) = spawn_collector();
 
     let metrics = MetricsClient::new(MetricsConfig::otlp(
         "test",
@@ -212,3 +235,158 @@ fn otlp_http_exporter_sends_metrics_to_collector() -> Result<()> {
 
     Ok(())
 }
+
+#[derive(Debug)]
+struct ExportedSpan {
+    span_id: String,
+    parent_span_id: String,
+    attributes: BTreeMap<String, String>,
+}
+
+/// Flattens OTLP/JSON trace export bodies into spans keyed by name.
+fn exported_spans(requests: &[&CapturedRequest]) -> BTreeMap<String, ExportedSpan> {
+    let mut spans = BTreeMap::new();
+    for request in requests {
+        let body: Value = serde_json::from_slice(&request.body).expect("trace export is json");
+        let resource_spans = body["resourceSpans"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        for scope_spans in resource_spans
+            .iter()
+            .filter_map(|resource| resource["scopeSpans"].as_array())
+            .flatten()
+        {
+            for span in scope_spans["spans"].as_array().into_iter().flatten() {
+                let attributes = span["attributes"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(|attribute| {
+                        let key = attribute["key"].as_str().unwrap_or_default().to_string();
+                        let value = attribute["value"]
+                            .as_object()
+                            .and_then(|value| value.values().next())
+                            .map(|value| match value {
+                                Value::String(text) => text.clone(),
+                                other => other.to_string(),
+                            })
+                            .unwrap_or_default();
+                        (key, value)
+                    })
+                    .collect();
+                spans.insert(
+                    span["name"].as_str().unwrap_or_default().to_string(),
+                    ExportedSpan {
+                        span_id: span["spanId"].as_str().unwrap_or_default().to_string(),
+                        parent_span_id: span["parentSpanId"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .to_string(),
+                        attributes,
+                    },
+                );
+            }
+        }
+    }
+    spans
+}
+
+#[test]
+fn otlp_http_exporter_sends_turn_span_tree_to_collector() {
+    let (addr, server, rx) = spawn_collector();
+
+    let provider = OtelProvider::from(&OtelSettings {
+        environment: "test".to_string(),
+        service_name: "codex-cli".to_string(),
+        service_version: env!("CARGO_PKG_VERSION").to_string(),
+        codex_home: PathBuf::from("."),
+        exporter: OtelExporter::None,
+        trace_exporter: OtelExporter::OtlpHttp {
+            endpoint: format!("http://{addr}/v1/traces"),
+            headers: HashMap::new(),
+            protocol: OtelHttpProtocol::Json,
+            tls: None,
+        },
+        metrics_exporter: OtelExporter::None,
+    })
+    .expect("otel provider")
+    .expect("trace exporter enabled");
+    let subscriber = tracing_subscriber::registry().with(provider.tracing_layer());
+
+    let thread_id = ThreadId::new();
+    let manager = OtelManager::new(
+        thread_id,
+        "gpt-5.1",
+        "gpt-5.1",
+        None,
+        None,
+        None,
+        false,
+        "test".to_string(),
+        SessionSource::Exec,
+    );
+    tracing::subscriber::with_default(subscriber, || {
+        let turn = manager.turn_span("turn-1", "regular");
+        let _turn = turn.enter();
+        let tool_call = manager.tool_call_span("turn-1", "shell", "call-1");
+        let _tool_call = tool_call.enter();
+
+        let approval = manager.approval_span("turn-1", "shell", "call-1");
+        OtelManager::record_approval_decision(&approval, &ReviewDecision::ApprovedForSession);
+        drop(approval);
+
+        let exec = manager.exec_span("turn-1", "shell", "call-1", "linux_seccomp", false);
+        exec.record("exit_code", 2);
+        drop(exec);
+
+        tool_call.record("success", false);
+    });
+    provider.shutdown();
+
+    server.join().expect("server join");
+    let captured = rx.recv_timeout(Duration::from_secs(1)).expect("captured");
+    let requests: Vec<&CapturedRequest> = captured
+        .iter()
+        .filter(|req| req.path == "/v1/traces")
+        .collect();
+    assert!(!requests.is_empty(), "missing /v1/traces request");
+
+    let spans = exported_spans(&requests);
+    let span = |name: &str| {
+        spans
+            .get(name)
+            .unwrap_or_else(|| panic!("missing span {name}; got {:?}", spans.keys()))
+    };
+    let turn = span("codex.turn");
+    let tool_call = span("codex.tool_call");
+    let approval = span("codex.approval");
+    let exec = span("codex.exec");
+
+    assert_eq!(tool_call.parent_span_id, turn.span_id);
+    assert_eq!(approval.parent_span_id, tool_call.span_id);
+    assert_eq!(exec.parent_span_id, tool_call.span_id);
+
+    let thread_id = thread_id.to_string();
+    for exported in [turn, tool_call, approval, exec] {
+        assert_eq!(
+            exported.attributes.get("thread.id"),
+            Some(&thread_id),
+            "{exported:?}"
+        );
+        assert_eq!(
+            exported.attributes.get("turn.id").map(String::as_str),
+            Some("turn-1")
+        );
+    }
+    let attribute = |span: &ExportedSpan, key: &str| span.attributes.get(key).cloned();
+    assert_eq!(attribute(turn, "turn.kind").as_deref(), Some("regular"));
+    assert_eq!(attribute(tool_call, "tool.name").as_deref(), Some("shell"));
+    assert_eq!(attribute(tool_call, "success").as_deref(), Some("false"));
+    assert_eq!(
+        attribute(approval, "approval.decision").as_deref(),
+        Some("approved_for_session")
+    );
+    assert_eq!(attribute(exec, "sandbox").as_deref(), Some("linux_seccomp"));
+    assert_eq!(attribute(exec, "exit_code").as_deref(), Some("2"));
+}
@@ -0,0 +1,166 @@
+use codex_app_server_protocol::AuthMode;
+use codex_otel::OtelManager;
+use codex_otel::metrics::PrometheusRegistry;
+use codex_otel::metrics::PrometheusServer;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SessionSource;
+use pretty_assertions::assert_eq;
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpStream;
+use std::time::Duration;
+
+fn manager_with_registry(registry: &PrometheusRegistry) -> OtelManager {
+    OtelManager::new(
+        ThreadId::new(),
+        "gpt-5.1",
+        "gpt-5.1",
+        None,
+        None,
+        Some(AuthMode::ApiKey),
+        true,
+        "tty".to_string(),
+        SessionSource::Cli,
+    )
+    .with_prometheus(registry.clone())
+}
+
+#[test]
+fn registry_renders_counters_gauges_and_histograms() {
+    let registry = PrometheusRegistry::new();
+    registry.counter(
+        "codex.tool.call",
+        3,
+        &[("tool", "shell"), ("success", "true")],
+    );
+    registry.counter(
+        "codex.tool.call",
+        2,
+        &[("success", "true"), ("tool", "shell")],
+    );
+    registry.gauge_add("codex.turns.in_flight", 2.0, &[]);
+    registry.gauge_add("codex.turns.in_flight", -1.0, &[]);
+    registry.gauge_set("codex.mcp.server.up", 0.0, &[("server", "do\"cs")]);
+    registry.observe_duration(
+        "codex.tool.call.duration_seconds",
+        Duration::from_millis(300),
+        &[("tool", "shell")],
+    );
+
+    let rendered = registry.render();
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    for expected in [
+        "# TYPE codex_tool_call counter",
+        "codex_tool_call{success=\"true\",tool=\"shell\"} 5",
+        "# TYPE codex_turns_in_flight gauge",
+        "codex_turns_in_flight 1",
+        "codex_mcp_server_up{server=\"do\\\"cs\"} 0",
+        "# TYPE codex_tool_call_duration_seconds histogram",
+        "codex_tool_call_duration_seconds_bucket{tool=\"shell\",le=\"0.25\"} 0",
+        "codex_tool_call_duration_seconds_bucket{tool=\"shell\",le=\"0.5\"} 1",
+        "codex_tool_call_duration_seconds_bucket{tool=\"shell\",le=\"+Inf\"} 1",
+        "codex_tool_call_duration_seconds_count{tool=\"shell\"} 1",
+    ] {
+        assert!(
+            lines.contains(&expected),
+            "missing `{expected}` in:\n{rendered}"
+        );
+    }
+}
+
+#[test]
+fn registry_ignores_samples_of_a_different_kind() {
+    let registry = PrometheusRegistry::new();
+    registry.counter("codex.turns", 1, &[]);
+    registry.gauge_set("codex.turns", 5.0, &[]);
+
+    assert_eq!(
+        registry.render(),
+        "# TYPE codex_turns counter\ncodex_turns 1\n"
+    );
+}
+
+#[test]
+fn manager_records_tokens_latencies_and_counters() {
+    let registry = PrometheusRegistry::new();
+    let manager = manager_with_registry(&registry);
+
+    manager.sse_event_completed(100, 20, Some(80), None, 0);
+    manager.tool_result("shell", "call-1", "{}", Duration::from_secs(2), true, "ok");
+    manager.approval_wait(
+        "shell",
+        &ReviewDecision::ApprovedForSession,
+        Duration::from_secs(45),
+    );
+    manager.gauge_add("codex.turns.in_flight", 1, &[("kind", "regular")]);
+
+    let rendered = registry.render();
+    let lines: Vec<&str> = rendered.lines().collect();
+    let version = env!("CARGO_PKG_VERSION");
+
+    for expected in [
+        format!(
+            "codex_tokens{{app_version=\"{version}\",auth_mode=\"ApiKey\",model=\"gpt-5.1\",type=\"input\"}} 100"
+        ),
+        format!(
+            "codex_tokens{{app_version=\"{version}\",auth_mode=\"ApiKey\",model=\"gpt-5.1\",type=\"cached\"}} 80"
+        ),
+        format!(
+            "codex_tool_call{{app_version=\"{version}\",auth_mode=\"ApiKey\",model=\"gpt-5.1\",success=\"true\",tool=\"shell\"}} 1"
+        ),
+        format!(
+            "codex_tool_call_duration_seconds_count{{app_version=\"{version}\",auth_mode=\"ApiKey\",model=\"gpt-5.1\",success=\"true\",tool=\"shell\"}} 1"
+        ),
+        format!(
+            "codex_approval_wait_duration_seconds_bucket{{app_version=\"{version}\",auth_mode=\"ApiKey\",decision=\"approved_for_session\",model=\"gpt-5.1\",tool=\"shell\",le=\"30\"}} 0"
+        ),
+        format!(
+            "codex_approval_wait_duration_seconds_bucket{{app_version=\"{version}\",auth_mode=\"ApiKey\",decision=\"approved_for_session\",model=\"gpt-5.1\",tool=\"shell\",le=\"60\"}} 1"
+        ),
+        format!(
+            "codex_turns_in_flight{{app_version=\"{version}\",auth_mode=\"ApiKey\",kind=\"regular\",model=\"gpt-5.1\"}} 1"
+        ),
+    ] {
+        assert!(
+            lines.contains(&expected.as_str()),
+            "missing `{expected}` in:\n{rendered}"
+        );
+    }
+    assert!(
+        !rendered.contains("type=\"reasoning\""),
+        "absent token counts should not be recorded:\n{rendered}"
+    );
+}
+
+#[test]
+fn server_serves_metrics_over_http() -> std::io::Result<()> {
+    let registry = PrometheusRegistry::new();
+    registry.gauge_set("codex.threads.active", 2.0, &[]);
+    let server = PrometheusServer::start("127.0.0.1:0".parse().expect("addr"), registry)?;
+
+    let get = |path: &str| -> std::io::Result<String> {
+        let mut stream = TcpStream::connect(server.local_addr())?;
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        write!(
+            stream,
+            "GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n"
+        )?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response)?;
+        Ok(response)
+    };
+
+    let metrics = get("/metrics")?;
+    assert!(metrics.starts_with("HTTP/1.1 200"), "{metrics}");
+    assert!(
+        metrics.contains("text/plain; version=0.0.4"),
+        "missing content type:\n{metrics}"
+    );
+    assert!(metrics.ends_with("codex_threads_active 2\n"), "{metrics}");
+
+    let missing = get("/other")?;
+    assert!(missing.starts_with("HTTP/1.1 404"), "{missing}");
+    Ok(())
+}
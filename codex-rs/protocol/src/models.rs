@@ -17,6 +17,7 @@ use crate::protocol::SandboxPolicy;
 use crate::protocol::WritableRoot;
 use crate::user_input::UserInput;
 use codex_git::GhostCommit;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_image::error::ImageProcessingError;
 use schemars::JsonSchema;
 
@@ -185,6 +186,8 @@ const SANDBOX_MODE_DANGER_FULL_ACCESS: &str =
 const SANDBOX_MODE_WORKSPACE_WRITE: &str =
     include_str!("prompts/permissions/sandbox_mode/workspace_write.md");
 const SANDBOX_MODE_READ_ONLY: &str = include_str!("prompts/permissions/sandbox_mode/read_only.md");
+const SANDBOX_MODE_RESTRICTED_READ: &str =
+    include_str!("prompts/permissions/sandbox_mode/restricted_read.md");
 
 impl DeveloperInstructions {
     pub fn new<T: Into<String>>(text: T) -> Self {
@@ -220,13 +223,19 @@ impl DeveloperInstructions {
                 let roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
                 (SandboxMode::WorkspaceWrite, Some(roots))
             }
+            SandboxPolicy::RestrictedRead { .. } => {
+                let roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
+                (SandboxMode::RestrictedRead, Some(roots))
+            }
         };
+        let readable_roots = sandbox_policy.get_readable_roots_with_cwd(cwd);
 
         DeveloperInstructions::from_permissions_with_network(
             sandbox_mode,
             network_access,
             approval_policy,
             writable_roots,
+            readable_roots,
         )
     }
 
@@ -235,6 +244,7 @@ impl DeveloperInstructions {
         network_access: NetworkAccess,
         approval_policy: AskForApproval,
         writable_roots: Option<Vec<WritableRoot>>,
+        readable_roots: Option<Vec<AbsolutePathBuf>>,
     ) -> Self {
         let start_tag = DeveloperInstructions::new("<permissions instructions>");
         let end_tag = DeveloperInstructions::new("</permissions instructions>");
@@ -245,9 +255,27 @@ impl DeveloperInstructions {
             ))
             .concat(DeveloperInstructions::from(approval_policy))
             .concat(DeveloperInstructions::from_writable_roots(writable_roots))
+            .concat(DeveloperInstructions::from_readable_roots(readable_roots))
             .concat(end_tag)
     }
 
+    fn from_readable_roots(readable_roots: Option<Vec<AbsolutePathBuf>>) -> Self {
+        let Some(roots) = readable_roots else {
+            return DeveloperInstructions::new("");
+        };
+
+        let roots_list: Vec<String> = roots
+            .iter()
+            .map(|root| format!("`{}`", root.to_string_lossy()))
+            .collect();
+        let text = match roots_list.as_slice() {
+            [] => " No directories are readable beyond system directories.".to_string(),
+            [root] => format!(" The readable root is {root}."),
+            _ => format!(" The readable roots are {}.", roots_list.join(", ")),
+        };
+        DeveloperInstructions::new(text)
+    }
+
     fn from_writable_roots(writable_roots: Option<Vec<WritableRoot>>) -> Self {
         let Some(roots) = writable_roots else {
             return DeveloperInstructions::new("");
@@ -274,6 +302,7 @@ impl DeveloperInstructions {
             SandboxMode::DangerFullAccess => SANDBOX_MODE_DANGER_FULL_ACCESS.trim_end(),
             SandboxMode::WorkspaceWrite => SANDBOX_MODE_WORKSPACE_WRITE.trim_end(),
             SandboxMode::ReadOnly => SANDBOX_MODE_READ_ONLY.trim_end(),
+            SandboxMode::RestrictedRead => SANDBOX_MODE_RESTRICTED_READ.trim_end(),
         };
         let text = template.replace("{network_access}", &network_access.to_string());
 
@@ -297,7 +326,9 @@ impl From<SandboxMode> for DeveloperInstructions {
     fn from(mode: SandboxMode) -> Self {
         let network_access = match mode {
             SandboxMode::DangerFullAccess => NetworkAccess::Enabled,
-            SandboxMode::WorkspaceWrite | SandboxMode::ReadOnly => NetworkAccess::Restricted,
+            SandboxMode::WorkspaceWrite | SandboxMode::ReadOnly | SandboxMode::RestrictedRead => {
+                NetworkAccess::Restricted
+            }
         };
 
         DeveloperInstructions::sandbox_text(mode, network_access)
@@ -565,6 +596,7 @@ impl From<Vec<UserInput>> for ResponseInputItem {
                         local_image_content_items_with_label_number(&path, Some(image_index))
                     }
                     UserInput::Skill { .. } => Vec::new(), // Skill bodies are injected later in core
+                    UserInput::McpPrompt { .. } => Vec::new(), // Prompt messages are injected later in core
                 })
                 .collect::<Vec<ContentItem>>(),
         }
@@ -573,7 +605,7 @@ impl From<Vec<UserInput>> for ResponseInputItem {
 
 /// If the `name` of a `ResponseItem::FunctionCall` is either `container.exec`
 /// or `shell`, the `arguments` field should deserialize to this struct.
-#[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 pub struct ShellToolCallParams {
     pub command: Vec<String>,
     pub workdir: Option<String>,
@@ -817,6 +849,7 @@ mod tests {
             NetworkAccess::Enabled,
             AskForApproval::OnRequest,
             None,
+            None,
         );
 
         let text = instructions.into_text();
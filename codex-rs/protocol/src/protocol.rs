@@ -25,6 +25,7 @@ use crate::plan_tool::UpdatePlanArgs;
 use crate::user_input::UserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use mcp_types::CallToolResult;
+use mcp_types::Prompt as McpPrompt;
 use mcp_types::RequestId;
 use mcp_types::Resource as McpResource;
 use mcp_types::ResourceTemplate as McpResourceTemplate;
@@ -199,6 +200,10 @@ pub enum Op {
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
+    /// Request the list of prompts published by the configured MCP servers.
+    /// Reply is delivered via `EventMsg::ListMcpPromptsResponse`.
+    ListMcpPrompts,
+
     /// Request the list of skills for the provided `cwd` values or the session default.
     ListSkills {
         /// Working directories to scope repo skills discovery.
@@ -352,6 +357,39 @@ pub enum SandboxPolicy {
         #[serde(default)]
         exclude_slash_tmp: bool,
     },
+
+    /// Same as `WorkspaceWrite` but additionally restricts disk reads to
+    /// `readable_roots`, the writable roots (including cwd) and the system
+    /// directories needed to run programs. Read restrictions are enforced by
+    /// the Linux and macOS sandboxes; Windows rejects this mode.
+    #[serde(rename = "restricted-read")]
+    RestrictedRead {
+        /// Additional folders (beyond the writable roots) that should be
+        /// readable from within the sandbox.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        readable_roots: Vec<AbsolutePathBuf>,
+
+        /// Additional folders (beyond cwd and possibly TMPDIR) that should be
+        /// writable from within the sandbox.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        writable_roots: Vec<AbsolutePathBuf>,
+
+        /// When set to `true`, outbound network access is allowed. `false` by
+        /// default.
+        #[serde(default)]
+        network_access: bool,
+
+        /// When set to `true`, will NOT include the per-user `TMPDIR`
+        /// environment variable among the default writable roots. Defaults to
+        /// `false`.
+        #[serde(default)]
+        exclude_tmpdir_env_var: bool,
+
+        /// When set to `true`, will NOT include the `/tmp` among the default
+        /// writable roots on UNIX. Defaults to `false`.
+        #[serde(default)]
+        exclude_slash_tmp: bool,
+    },
 }
 
 /// A writable root path accompanied by a list of subpaths that should remain
@@ -411,9 +449,14 @@ impl SandboxPolicy {
         }
     }
 
-    /// Always returns `true`; restricting read access is not supported.
     pub fn has_full_disk_read_access(&self) -> bool {
-        true
+        match self {
+            SandboxPolicy::DangerFullAccess => true,
+            SandboxPolicy::ExternalSandbox { .. } => true,
+            SandboxPolicy::ReadOnly => true,
+            SandboxPolicy::WorkspaceWrite { .. } => true,
+            SandboxPolicy::RestrictedRead { .. } => false,
+        }
     }
 
     pub fn has_full_disk_write_access(&self) -> bool {
@@ -422,6 +465,7 @@ impl SandboxPolicy {
             SandboxPolicy::ExternalSandbox { .. } => true,
             SandboxPolicy::ReadOnly => false,
             SandboxPolicy::WorkspaceWrite { .. } => false,
+            SandboxPolicy::RestrictedRead { .. } => false,
         }
     }
 
@@ -431,6 +475,28 @@ impl SandboxPolicy {
             SandboxPolicy::ExternalSandbox { network_access } => network_access.is_enabled(),
             SandboxPolicy::ReadOnly => false,
             SandboxPolicy::WorkspaceWrite { network_access, .. } => *network_access,
+            SandboxPolicy::RestrictedRead { network_access, .. } => *network_access,
+        }
+    }
+
+    /// Returns the list of readable roots (tailored to the current working
+    /// directory), or `None` when the policy grants read access to the entire
+    /// file-system. Writable roots are always readable.
+    pub fn get_readable_roots_with_cwd(&self, cwd: &Path) -> Option<Vec<AbsolutePathBuf>> {
+        match self {
+            SandboxPolicy::DangerFullAccess
+            | SandboxPolicy::ExternalSandbox { .. }
+            | SandboxPolicy::ReadOnly
+            | SandboxPolicy::WorkspaceWrite { .. } => None,
+            SandboxPolicy::RestrictedRead { readable_roots, .. } => {
+                let mut roots = readable_roots.clone();
+                for writable_root in self.get_writable_roots_with_cwd(cwd) {
+                    if !roots.contains(&writable_root.root) {
+                        roots.push(writable_root.root);
+                    }
+                }
+                Some(roots)
+            }
         }
     }
 
@@ -446,7 +512,13 @@ impl SandboxPolicy {
                 writable_roots,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
-                network_access: _,
+                ..
+            }
+            | SandboxPolicy::RestrictedRead {
+                writable_roots,
+                exclude_tmpdir_env_var,
+                exclude_slash_tmp,
+                ..
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<AbsolutePathBuf> = writable_roots.clone();
@@ -606,8 +678,14 @@ pub enum EventMsg {
     /// Aggregate MCP startup completion summary.
     McpStartupComplete(McpStartupCompleteEvent),
 
+    /// An MCP server announced a new tool list; it applies from the next turn.
+    McpToolsChanged(McpToolsChangedEvent),
+
     McpToolCallBegin(McpToolCallBeginEvent),
 
+    /// Progress or a log message reported by the server while an MCP tool call runs.
+    McpToolCallProgress(McpToolCallProgressEvent),
+
     McpToolCallEnd(McpToolCallEndEvent),
 
     WebSearchBegin(WebSearchBeginEvent),
@@ -666,6 +744,9 @@ pub enum EventMsg {
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
+    /// List of prompts published by MCP servers.
+    ListMcpPromptsResponse(ListMcpPromptsResponseEvent),
+
     /// List of skills available to the agent.
     ListSkillsResponse(ListSkillsResponseEvent),
 
@@ -742,6 +823,8 @@ pub enum CodexErrorInfo {
         http_status_code: Option<u16>,
     },
     ThreadRollbackFailed,
+    /// A configured session or turn token/cost budget was exceeded.
+    BudgetExceeded,
     Other,
 }
 
@@ -1165,6 +1248,20 @@ pub struct McpToolCallBeginEvent {
     pub invocation: McpInvocation,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct McpToolCallProgressEvent {
+    /// Identifier of the McpToolCallBegin this update belongs to.
+    pub call_id: String,
+    /// Progress so far, absent for plain log messages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<f64>,
+    /// Total progress expected, when the server knows it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
 pub struct McpToolCallEndEvent {
     /// Identifier for the corresponding McpToolCallBegin that finished.
@@ -1441,6 +1538,12 @@ pub enum ReviewTarget {
         title: Option<String>,
     },
 
+    /// Review the changes in the commit range `from..to`: commits reachable
+    /// from `to` but not from `from`.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommitRange { from: String, to: String },
+
     /// Arbitrary instructions provided by the user.
     #[serde(rename_all = "camelCase")]
     #[ts(rename_all = "camelCase")]
@@ -1454,6 +1557,10 @@ pub struct ReviewRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[ts(optional)]
     pub user_facing_hint: Option<String>,
+    /// Restrict the review to these paths (files or directories, relative to
+    /// the working directory). When empty, all changes are reviewed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
 }
 
 /// Structured review result produced by a child review session.
@@ -1758,6 +1865,15 @@ pub struct McpStartupFailure {
     pub error: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpToolsChangedEvent {
+    /// Server whose tool list changed.
+    pub server: String,
+    /// Names of the tools now available from the server, after
+    /// `enabled_tools`/`disabled_tools` filtering.
+    pub tools: Vec<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -1786,6 +1902,13 @@ pub struct ListCustomPromptsResponseEvent {
     pub custom_prompts: Vec<CustomPrompt>,
 }
 
+/// Response payload for `Op::ListMcpPrompts`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ListMcpPromptsResponseEvent {
+    /// Known prompts grouped by server name.
+    pub prompts: std::collections::HashMap<String, Vec<McpPrompt>>,
+}
+
 /// Response payload for `Op::ListSkills`.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ListSkillsResponseEvent {
@@ -1882,6 +2005,19 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has reviewed this patch hunk by hunk and approved only part of
+    /// it. `changes` (the accepted hunks, possibly edited by the user) is
+    /// applied instead of the proposed patch.
+    ApprovedPartially {
+        changes: HashMap<PathBuf, FileChange>,
+        /// Hunks that were not applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        rejected_hunks: Vec<PatchHunk>,
+        /// Hunks the user edited before applying, as applied.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        edited_hunks: Vec<PatchHunk>,
+    },
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -1900,13 +2036,14 @@ impl ReviewDecision {
             ReviewDecision::Approved => "approved",
             ReviewDecision::ApprovedExecpolicyAmendment { .. } => "approved_with_amendment",
             ReviewDecision::ApprovedForSession => "approved_for_session",
+            ReviewDecision::ApprovedPartially { .. } => "approved_partially",
             ReviewDecision::Denied => "denied",
             ReviewDecision::Abort => "abort",
         }
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type")]
 pub enum FileChange {
@@ -1922,6 +2059,15 @@ pub enum FileChange {
     },
 }
 
+/// A single hunk of a proposed file change, as reviewed by the user.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct PatchHunk {
+    pub path: PathBuf,
+    /// The hunk as a unified diff starting with its `@@` header. For added or
+    /// deleted files this is the whole file.
+    pub diff: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct Chunk {
     /// 1-based line index of the first line in the original file
@@ -1968,6 +2114,31 @@ mod tests {
         assert!(enabled.has_full_network_access());
     }
 
+    #[test]
+    fn restricted_read_includes_writable_roots_in_readable_roots() -> Result<()> {
+        let cwd = tempfile::tempdir()?;
+        let docs = AbsolutePathBuf::from_absolute_path("/opt/team-docs")?;
+        let policy = SandboxPolicy::RestrictedRead {
+            readable_roots: vec![docs.clone()],
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        assert!(!policy.has_full_disk_read_access());
+        assert!(!policy.has_full_disk_write_access());
+        assert_eq!(
+            Some(vec![docs, AbsolutePathBuf::from_absolute_path(cwd.path())?]),
+            policy.get_readable_roots_with_cwd(cwd.path())
+        );
+        assert_eq!(
+            None,
+            SandboxPolicy::new_workspace_write_policy().get_readable_roots_with_cwd(cwd.path())
+        );
+        Ok(())
+    }
+
     #[test]
     fn item_started_event_from_web_search_emits_begin_event() {
         let event = ItemStartedEvent {
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -27,4 +29,13 @@ pub enum UserInput {
         name: String,
         path: std::path::PathBuf,
     },
+
+    /// MCP prompt selected by the user (server + prompt name + arguments).
+    /// Core renders it with `prompts/get` and injects the resulting messages.
+    McpPrompt {
+        server: String,
+        name: String,
+        #[serde(default)]
+        arguments: HashMap<String, String>,
+    },
 }
@@ -1,17 +1,32 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
 use rmcp::ErrorData as McpError;
 use rmcp::ServiceExt;
 use rmcp::handler::server::ServerHandler;
 use rmcp::model::CallToolRequestParam;
 use rmcp::model::CallToolResult;
+use rmcp::model::Content;
+use rmcp::model::CreateMessageRequestParam;
+use rmcp::model::GetPromptRequestParam;
+use rmcp::model::GetPromptResult;
 use rmcp::model::JsonObject;
+use rmcp::model::ListPromptsResult;
 use rmcp::model::ListResourceTemplatesResult;
 use rmcp::model::ListResourcesResult;
 use rmcp::model::ListToolsResult;
+use rmcp::model::LoggingLevel;
+use rmcp::model::LoggingMessageNotificationParam;
 use rmcp::model::PaginatedRequestParam;
+use rmcp::model::ProgressNotificationParam;
+use rmcp::model::Prompt;
+use rmcp::model::PromptArgument;
+use rmcp::model::PromptMessage;
+use rmcp::model::PromptMessageRole;
 use rmcp::model::RawResource;
 use rmcp::model::RawResourceTemplate;
 use rmcp::model::ReadResourceRequestParam;
@@ -19,6 +34,8 @@ use rmcp::model::ReadResourceResult;
 use rmcp::model::Resource;
 use rmcp::model::ResourceContents;
 use rmcp::model::ResourceTemplate;
+use rmcp::model::Role;
+use rmcp::model::SamplingMessage;
 use rmcp::model::ServerCapabilities;
 use rmcp::model::ServerInfo;
 use rmcp::model::Tool;
@@ -28,9 +45,12 @@ use tokio::task;
 
 #[derive(Clone)]
 struct TestToolServer {
-    tools: Arc<Vec<Tool>>,
+    tools: Arc<RwLock<Vec<Tool>>>,
     resources: Arc<Vec<Resource>>,
     resource_templates: Arc<Vec<ResourceTemplate>>,
+    prompts: Arc<Vec<Prompt>>,
+    /// Number of `wait` calls the client cancelled with `notifications/cancelled`.
+    cancelled_waits: Arc<AtomicUsize>,
 }
 
 const MEMO_URI: &str = "memo://codex/example-note";
@@ -40,13 +60,25 @@ pub fn stdio() -> (tokio::io::Stdin, tokio::io::Stdout) {
 }
 impl TestToolServer {
     fn new() -> Self {
-        let tools = vec![Self::echo_tool(), Self::image_tool()];
+        let tools = vec![
+            Self::echo_tool(),
+            Self::image_tool(),
+            Self::add_tool_tool(),
+            Self::roots_tool(),
+            Self::sample_tool(),
+            Self::progress_tool(),
+            Self::wait_tool(),
+            Self::cancelled_waits_tool(),
+        ];
         let resources = vec![Self::memo_resource()];
         let resource_templates = vec![Self::memo_template()];
+        let prompts = vec![Self::review_prompt()];
         Self {
-            tools: Arc::new(tools),
+            tools: Arc::new(RwLock::new(tools)),
             resources: Arc::new(resources),
             resource_templates: Arc::new(resource_templates),
+            prompts: Arc::new(prompts),
+            cancelled_waits: Arc::new(AtomicUsize::new(0)),
         }
     }
 
@@ -86,6 +118,133 @@ impl TestToolServer {
         )
     }
 
+    /// Registers another echo-style tool at runtime and announces it with
+    /// `notifications/tools/list_changed`, like servers that load plugins lazily.
+    fn add_tool_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" }
+            },
+            "required": ["name"],
+            "additionalProperties": false
+        }))
+        .expect("add_tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("add_tool"),
+            Cow::Borrowed("Register a new tool and notify the client that the tool list changed."),
+            Arc::new(schema),
+        )
+    }
+
+    /// Asks the client for its roots via `roots/list` and returns their URIs.
+    fn roots_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("roots tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("roots"),
+            Cow::Borrowed("Return the URIs of the client's roots, one per line."),
+            Arc::new(schema),
+        )
+    }
+
+    /// Asks the client to run the prompt via `sampling/createMessage`.
+    fn sample_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "prompt": { "type": "string" }
+            },
+            "required": ["prompt"],
+            "additionalProperties": false
+        }))
+        .expect("sample tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("sample"),
+            Cow::Borrowed("Ask the client's model to answer the prompt and return its reply."),
+            Arc::new(schema),
+        )
+    }
+
+    /// Reports `steps` progress notifications and one log message before returning.
+    fn progress_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {
+                "steps": { "type": "integer" }
+            },
+            "required": ["steps"],
+            "additionalProperties": false
+        }))
+        .expect("progress tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("progress"),
+            Cow::Borrowed("Report progress for the given number of steps, then finish."),
+            Arc::new(schema),
+        )
+    }
+
+    /// Never finishes on its own; counts how often the client cancels it.
+    fn wait_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("wait tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("wait"),
+            Cow::Borrowed("Wait until the client cancels the call."),
+            Arc::new(schema),
+        )
+    }
+
+    fn cancelled_waits_tool() -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("cancelled_waits tool schema should deserialize");
+
+        Tool::new(
+            Cow::Borrowed("cancelled_waits"),
+            Cow::Borrowed("Return how many wait calls were cancelled by the client."),
+            Arc::new(schema),
+        )
+    }
+
+    fn late_tool(name: String) -> Tool {
+        #[expect(clippy::expect_used)]
+        let schema: JsonObject = serde_json::from_value(json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }))
+        .expect("late tool schema should deserialize");
+
+        Tool::new(
+            Cow::Owned(name),
+            Cow::Borrowed("A tool registered after startup."),
+            Arc::new(schema),
+        )
+    }
+
     fn memo_resource() -> Resource {
         let raw = RawResource {
             uri: MEMO_URI.to_string(),
@@ -113,11 +272,39 @@ impl TestToolServer {
         ResourceTemplate::new(raw, None)
     }
 
+    fn review_prompt() -> Prompt {
+        Prompt::new(
+            "review",
+            Some("Ask for a focused review of the given topic."),
+            Some(vec![PromptArgument {
+                name: "topic".to_string(),
+                title: None,
+                description: Some("What to review.".to_string()),
+                required: Some(true),
+            }]),
+        )
+    }
+
     fn memo_text() -> &'static str {
         MEMO_CONTENT
     }
 }
 
+#[derive(Deserialize)]
+struct AddToolArgs {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct ProgressArgs {
+    steps: u32,
+}
+
+#[derive(Deserialize)]
+struct SampleArgs {
+    prompt: String,
+}
+
 #[derive(Deserialize)]
 struct EchoArgs {
     message: String,
@@ -132,6 +319,8 @@ impl ServerHandler for TestToolServer {
                 .enable_tools()
                 .enable_tool_list_changed()
                 .enable_resources()
+                .enable_prompts()
+                .enable_logging()
                 .build(),
             ..ServerInfo::default()
         }
@@ -142,10 +331,14 @@ impl ServerHandler for TestToolServer {
         _request: Option<PaginatedRequestParam>,
         _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> impl std::future::Future<Output = Result<ListToolsResult, McpError>> + Send + '_ {
-        let tools = self.tools.clone();
+        let tools = self
+            .tools
+            .read()
+            .map(|tools| tools.clone())
+            .unwrap_or_default();
         async move {
             Ok(ListToolsResult {
-                tools: (*tools).clone(),
+                tools,
                 next_cursor: None,
                 meta: None,
             })
@@ -201,12 +394,167 @@ impl ServerHandler for TestToolServer {
         }
     }
 
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        Ok(ListPromptsResult {
+            prompts: (*self.prompts).clone(),
+            next_cursor: None,
+            meta: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParam { name, arguments }: GetPromptRequestParam,
+        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        if name != "review" {
+            return Err(McpError::invalid_params(
+                format!("unknown prompt: {name}"),
+                None,
+            ));
+        }
+        let topic = arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get("topic"))
+            .and_then(|topic| topic.as_str())
+            .ok_or_else(|| McpError::invalid_params("missing required argument: topic", None))?;
+
+        Ok(GetPromptResult {
+            description: Some("Focused review".to_string()),
+            messages: vec![
+                PromptMessage::new_text(
+                    PromptMessageRole::User,
+                    format!("Please review {topic} and list concrete problems."),
+                ),
+                PromptMessage::new_text(
+                    PromptMessageRole::Assistant,
+                    format!("I will review {topic}."),
+                ),
+            ],
+        })
+    }
+
     async fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
+        context: rmcp::service::RequestContext<rmcp::service::RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         match request.name.as_ref() {
+            "add_tool" => {
+                let args: AddToolArgs = serde_json::from_value(serde_json::Value::Object(
+                    request.arguments.unwrap_or_default().into_iter().collect(),
+                ))
+                .map_err(|err| McpError::invalid_params(err.to_string(), None))?;
+                self.tools
+                    .write()
+                    .map_err(|_| McpError::internal_error("tool list lock poisoned", None))?
+                    .push(Self::late_tool(args.name.clone()));
+                context
+                    .peer
+                    .notify_tool_list_changed()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+
+                Ok(CallToolResult::success(vec![rmcp::model::Content::text(
+                    format!("registered {}", args.name),
+                )]))
+            }
+            "roots" => {
+                let roots = context
+                    .peer
+                    .list_roots()
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let uris: Vec<String> = roots.roots.into_iter().map(|root| root.uri).collect();
+
+                Ok(CallToolResult::success(vec![Content::text(
+                    uris.join("\n"),
+                )]))
+            }
+            "progress" => {
+                let args: ProgressArgs = serde_json::from_value(serde_json::Value::Object(
+                    request.arguments.unwrap_or_default().into_iter().collect(),
+                ))
+                .map_err(|err| McpError::invalid_params(err.to_string(), None))?;
+                if let Some(progress_token) = context.meta.get_progress_token() {
+                    for step in 1..=args.steps {
+                        context
+                            .peer
+                            .notify_progress(ProgressNotificationParam {
+                                progress_token: progress_token.clone(),
+                                progress: f64::from(step),
+                                total: Some(f64::from(args.steps)),
+                                message: Some(format!("step {step}")),
+                            })
+                            .await
+                            .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                    }
+                }
+                context
+                    .peer
+                    .notify_logging_message(LoggingMessageNotificationParam {
+                        level: LoggingLevel::Info,
+                        logger: None,
+                        data: json!("progress finished"),
+                    })
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                // rmcp handles notifications on spawned tasks, so give the client a
+                // moment to process them before the response completes the call.
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "completed {} steps",
+                    args.steps
+                ))]))
+            }
+            "wait" => {
+                context.ct.cancelled().await;
+                self.cancelled_waits.fetch_add(1, Ordering::SeqCst);
+                Err(McpError::internal_error("wait cancelled", None))
+            }
+            "cancelled_waits" => Ok(CallToolResult::success(vec![Content::text(
+                self.cancelled_waits.load(Ordering::SeqCst).to_string(),
+            )])),
+            "sample" => {
+                let args: SampleArgs = serde_json::from_value(serde_json::Value::Object(
+                    request.arguments.unwrap_or_default().into_iter().collect(),
+                ))
+                .map_err(|err| McpError::invalid_params(err.to_string(), None))?;
+                let result = context
+                    .peer
+                    .create_message(CreateMessageRequestParam {
+                        messages: vec![SamplingMessage {
+                            role: Role::User,
+                            content: Content::text(args.prompt),
+                        }],
+                        model_preferences: None,
+                        system_prompt: None,
+                        include_context: None,
+                        temperature: None,
+                        max_tokens: 256,
+                        stop_sequences: None,
+                        metadata: None,
+                    })
+                    .await
+                    .map_err(|err| McpError::internal_error(err.to_string(), None))?;
+                let reply = result
+                    .message
+                    .content
+                    .raw
+                    .as_text()
+                    .map(|text| text.text.clone())
+                    .unwrap_or_default();
+
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "{}: {reply}",
+                    result.model
+                ))]))
+            }
             "echo" => {
                 let args: EchoArgs = match request.arguments {
                     Some(arguments) => serde_json::from_value(serde_json::Value::Object(
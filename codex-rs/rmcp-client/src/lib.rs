@@ -22,5 +22,10 @@ pub use perform_oauth_login::perform_oauth_login_return_url;
 pub use rmcp::model::ElicitationAction;
 pub use rmcp_client::Elicitation;
 pub use rmcp_client::ElicitationResponse;
+pub use rmcp_client::ListChanged;
+pub use rmcp_client::ListRoots;
 pub use rmcp_client::RmcpClient;
 pub use rmcp_client::SendElicitation;
+pub use rmcp_client::SendSampling;
+pub use rmcp_client::ToolCallProgress;
+pub use rmcp_client::ToolCallProgressSender;
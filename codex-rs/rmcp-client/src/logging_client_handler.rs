@@ -6,6 +6,10 @@ use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientInfo;
 use rmcp::model::CreateElicitationRequestParam;
 use rmcp::model::CreateElicitationResult;
+use rmcp::model::CreateMessageRequestMethod;
+use rmcp::model::CreateMessageRequestParam;
+use rmcp::model::CreateMessageResult;
+use rmcp::model::ListRootsResult;
 use rmcp::model::LoggingLevel;
 use rmcp::model::LoggingMessageNotificationParam;
 use rmcp::model::ProgressNotificationParam;
@@ -13,26 +17,90 @@ use rmcp::model::RequestId;
 use rmcp::model::ResourceUpdatedNotificationParam;
 use rmcp::service::NotificationContext;
 use rmcp::service::RequestContext;
+use tokio::sync::broadcast;
 use tracing::debug;
 use tracing::error;
 use tracing::info;
 use tracing::warn;
 
+use crate::rmcp_client::InFlightToolCalls;
+use crate::rmcp_client::ListChanged;
+use crate::rmcp_client::ListRoots;
 use crate::rmcp_client::SendElicitation;
+use crate::rmcp_client::SendSampling;
+use crate::rmcp_client::ToolCallProgress;
+use crate::utils::convert_to_mcp;
+use crate::utils::convert_to_rmcp;
 
 #[derive(Clone)]
 pub(crate) struct LoggingClientHandler {
     client_info: ClientInfo,
     send_elicitation: Arc<SendElicitation>,
+    send_sampling: Option<Arc<SendSampling>>,
+    list_roots: Option<Arc<ListRoots>>,
+    list_changed_tx: broadcast::Sender<ListChanged>,
+    in_flight_tool_calls: InFlightToolCalls,
 }
 
 impl LoggingClientHandler {
-    pub(crate) fn new(client_info: ClientInfo, send_elicitation: SendElicitation) -> Self {
+    pub(crate) fn new(
+        client_info: ClientInfo,
+        send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        list_roots: Option<ListRoots>,
+        list_changed_tx: broadcast::Sender<ListChanged>,
+        in_flight_tool_calls: InFlightToolCalls,
+    ) -> Self {
         Self {
             client_info,
             send_elicitation: Arc::new(send_elicitation),
+            send_sampling: send_sampling.map(Arc::new),
+            list_roots: list_roots.map(Arc::new),
+            list_changed_tx,
+            in_flight_tool_calls,
         }
     }
+
+    fn notify_list_changed(&self, kind: ListChanged) {
+        // Sending only fails when nobody is subscribed, which is fine.
+        let _ = self.list_changed_tx.send(kind);
+    }
+
+    /// Forwards a progress notification to the tool call that owns its token.
+    fn forward_progress(&self, params: &ProgressNotificationParam) {
+        let Ok(calls) = self.in_flight_tool_calls.lock() else {
+            return;
+        };
+        if let Some(progress_tx) = calls.get(&params.progress_token) {
+            let _ = progress_tx.send(ToolCallProgress {
+                progress: Some(params.progress),
+                total: params.total,
+                message: params.message.clone(),
+            });
+        }
+    }
+
+    /// Log messages are not tied to a request, so they are shown on every tool
+    /// call currently running on this server. Debug messages are only logged.
+    fn forward_log_message(&self, message: String) {
+        let Ok(calls) = self.in_flight_tool_calls.lock() else {
+            return;
+        };
+        for progress_tx in calls.values() {
+            let _ = progress_tx.send(ToolCallProgress {
+                progress: None,
+                total: None,
+                message: Some(message.clone()),
+            });
+        }
+    }
+}
+
+fn to_mcp_request_id(id: RequestId) -> mcp_types::RequestId {
+    match id {
+        RequestId::String(id) => mcp_types::RequestId::String(id.to_string()),
+        RequestId::Number(id) => mcp_types::RequestId::Integer(id),
+    }
 }
 
 impl ClientHandler for LoggingClientHandler {
@@ -41,12 +109,41 @@ impl ClientHandler for LoggingClientHandler {
         request: CreateElicitationRequestParam,
         context: RequestContext<RoleClient>,
     ) -> Result<CreateElicitationResult, rmcp::ErrorData> {
-        let id = match context.id {
-            RequestId::String(id) => mcp_types::RequestId::String(id.to_string()),
-            RequestId::Number(id) => mcp_types::RequestId::Integer(id),
+        (self.send_elicitation)(to_mcp_request_id(context.id), request)
+            .await
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+        context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, rmcp::ErrorData> {
+        let Some(send_sampling) = &self.send_sampling else {
+            return Err(rmcp::ErrorData::method_not_found::<
+                CreateMessageRequestMethod,
+            >());
         };
-        (self.send_elicitation)(id, request)
+        let params = convert_to_mcp(params)
+            .map_err(|err| rmcp::ErrorData::invalid_params(err.to_string(), None))?;
+        let result = send_sampling(to_mcp_request_id(context.id), params)
             .await
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))?;
+        convert_to_rmcp(result)
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
+    }
+
+    async fn list_roots(
+        &self,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<ListRootsResult, rmcp::ErrorData> {
+        let Some(list_roots) = &self.list_roots else {
+            return Ok(ListRootsResult::default());
+        };
+        let roots = list_roots()
+            .await
+            .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))?;
+        convert_to_rmcp(mcp_types::ListRootsResult { roots })
             .map_err(|err| rmcp::ErrorData::internal_error(err.to_string(), None))
     }
 
@@ -70,6 +167,7 @@ impl ClientHandler for LoggingClientHandler {
             "MCP server progress notification (token: {:?}, progress: {}, total: {:?}, message: {:?})",
             params.progress_token, params.progress, params.total, params.message
         );
+        self.forward_progress(&params);
     }
 
     async fn on_resource_updated(
@@ -82,14 +180,17 @@ impl ClientHandler for LoggingClientHandler {
 
     async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server resource list changed");
+        self.notify_list_changed(ListChanged::Resources);
     }
 
     async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server tool list changed");
+        self.notify_list_changed(ListChanged::Tools);
     }
 
     async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
         info!("MCP server prompt list changed");
+        self.notify_list_changed(ListChanged::Prompts);
     }
 
     fn get_info(&self) -> ClientInfo {
@@ -107,6 +208,12 @@ impl ClientHandler for LoggingClientHandler {
             data,
         } = params;
         let logger = logger.as_deref();
+        if level != LoggingLevel::Debug {
+            self.forward_log_message(match data.as_str() {
+                Some(text) => text.to_string(),
+                None => data.to_string(),
+            });
+        }
         match level {
             LoggingLevel::Emergency
             | LoggingLevel::Alert
@@ -4,6 +4,8 @@ use std::io;
 use std::path::PathBuf;
 use std::process::Stdio;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -12,8 +14,14 @@ use futures::FutureExt;
 use futures::future::BoxFuture;
 use mcp_types::CallToolRequestParams;
 use mcp_types::CallToolResult;
+use mcp_types::CreateMessageRequestParams;
+use mcp_types::CreateMessageResult;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
 use mcp_types::InitializeRequestParams;
 use mcp_types::InitializeResult;
+use mcp_types::ListPromptsRequestParams;
+use mcp_types::ListPromptsResult;
 use mcp_types::ListResourceTemplatesRequestParams;
 use mcp_types::ListResourceTemplatesResult;
 use mcp_types::ListResourcesRequestParams;
@@ -23,8 +31,11 @@ use mcp_types::ListToolsResult;
 use mcp_types::ReadResourceRequestParams;
 use mcp_types::ReadResourceResult;
 use mcp_types::RequestId;
+use mcp_types::Root;
 use reqwest::header::HeaderMap;
+use rmcp::model::CallToolRequest;
 use rmcp::model::CallToolRequestParam;
+use rmcp::model::CancelledNotificationParam;
 use rmcp::model::ClientNotification;
 use rmcp::model::ClientRequest;
 use rmcp::model::CreateElicitationRequestParam;
@@ -32,12 +43,20 @@ use rmcp::model::CreateElicitationResult;
 use rmcp::model::CustomNotification;
 use rmcp::model::CustomRequest;
 use rmcp::model::Extensions;
+use rmcp::model::GetPromptRequestParam;
 use rmcp::model::InitializeRequestParam;
+use rmcp::model::Meta;
+use rmcp::model::NumberOrString;
 use rmcp::model::PaginatedRequestParam;
+use rmcp::model::ProgressToken;
 use rmcp::model::ReadResourceRequestParam;
 use rmcp::model::ServerResult;
+use rmcp::service::Peer;
+use rmcp::service::PeerRequestOptions;
+use rmcp::service::RequestHandle;
 use rmcp::service::RoleClient;
 use rmcp::service::RunningService;
+use rmcp::service::ServiceError;
 use rmcp::service::{self};
 use rmcp::transport::StreamableHttpClientTransport;
 use rmcp::transport::auth::AuthClient;
@@ -48,6 +67,8 @@ use tokio::io::AsyncBufReadExt;
 use tokio::io::BufReader;
 use tokio::process::Command;
 use tokio::sync::Mutex;
+use tokio::sync::broadcast;
+use tokio::sync::mpsc;
 use tokio::time;
 use tracing::info;
 use tracing::warn;
@@ -95,10 +116,100 @@ pub type SendElicitation = Box<
     dyn Fn(RequestId, Elicitation) -> BoxFuture<'static, Result<ElicitationResponse>> + Send + Sync,
 >;
 
+/// Interface for answering `sampling/createMessage` requests from the server.
+pub type SendSampling = Box<
+    dyn Fn(RequestId, CreateMessageRequestParams) -> BoxFuture<'static, Result<CreateMessageResult>>
+        + Send
+        + Sync,
+>;
+
+/// Interface for answering `roots/list` requests from the server.
+pub type ListRoots = Box<dyn Fn() -> BoxFuture<'static, Result<Vec<Root>>> + Send + Sync>;
+
+/// A `notifications/*/list_changed` notification received from the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListChanged {
+    Tools,
+    Resources,
+    Prompts,
+}
+
+/// Only the latest notifications matter to subscribers, so a small buffer is
+/// enough; lagging receivers should simply re-fetch.
+const LIST_CHANGED_CHANNEL_CAPACITY: usize = 16;
+
+/// An update received while a `tools/call` request is in flight: either a
+/// `notifications/progress` for the call, or a `notifications/message` log
+/// entry from the same server, which carries only a message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolCallProgress {
+    pub progress: Option<f64>,
+    pub total: Option<f64>,
+    pub message: Option<String>,
+}
+
+pub type ToolCallProgressSender = mpsc::UnboundedSender<ToolCallProgress>;
+
+/// Progress subscribers for in-flight tool calls, keyed by the progress token
+/// sent with each request.
+pub(crate) type InFlightToolCalls =
+    Arc<std::sync::Mutex<HashMap<ProgressToken, ToolCallProgressSender>>>;
+
+/// Removes a tool call's progress subscriber when the call finishes.
+struct InFlightToolCallGuard {
+    calls: InFlightToolCalls,
+    token: ProgressToken,
+}
+
+impl Drop for InFlightToolCallGuard {
+    fn drop(&mut self) {
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.remove(&self.token);
+        }
+    }
+}
+
+/// Sends `notifications/cancelled` for a request whose future is dropped (or
+/// times out) before the server answered, e.g. when the user interrupts a turn.
+struct CancelOnDrop {
+    peer: Peer<RoleClient>,
+    request_id: Option<rmcp::model::RequestId>,
+}
+
+impl CancelOnDrop {
+    fn disarm(&mut self) {
+        self.request_id = None;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        let Some(request_id) = self.request_id.take() else {
+            return;
+        };
+        let peer = self.peer.clone();
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        runtime.spawn(async move {
+            let params = CancelledNotificationParam {
+                request_id,
+                reason: Some("cancelled by the client".to_string()),
+            };
+            if let Err(err) = peer.notify_cancelled(params).await {
+                warn!("failed to send notifications/cancelled: {err}");
+            }
+        });
+    }
+}
+
 /// MCP client implemented on top of the official `rmcp` SDK.
 /// https://github.com/modelcontextprotocol/rust-sdk
 pub struct RmcpClient {
     state: Mutex<ClientState>,
+    list_changed_tx: broadcast::Sender<ListChanged>,
+    in_flight_tool_calls: InFlightToolCalls,
+    next_progress_token: AtomicU64,
 }
 
 impl RmcpClient {
@@ -151,11 +262,7 @@ impl RmcpClient {
             });
         }
 
-        Ok(Self {
-            state: Mutex::new(ClientState::Connecting {
-                transport: Some(PendingTransport::ChildProcess(transport)),
-            }),
-        })
+        Ok(Self::new(PendingTransport::ChildProcess(transport)))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -205,23 +312,49 @@ impl RmcpClient {
             let transport = StreamableHttpClientTransport::with_client(http_client, http_config);
             PendingTransport::StreamableHttp { transport }
         };
-        Ok(Self {
+        Ok(Self::new(transport))
+    }
+
+    fn new(transport: PendingTransport) -> Self {
+        let (list_changed_tx, _) = broadcast::channel(LIST_CHANGED_CHANNEL_CAPACITY);
+        Self {
             state: Mutex::new(ClientState::Connecting {
                 transport: Some(transport),
             }),
-        })
+            list_changed_tx,
+            in_flight_tool_calls: Arc::default(),
+            next_progress_token: AtomicU64::new(0),
+        }
+    }
+
+    /// Subscribe to `list_changed` notifications sent by the server. Subscribe
+    /// before [`RmcpClient::initialize`] to avoid missing early notifications.
+    pub fn subscribe_list_changed(&self) -> broadcast::Receiver<ListChanged> {
+        self.list_changed_tx.subscribe()
     }
 
     /// Perform the initialization handshake with the MCP server.
     /// https://modelcontextprotocol.io/specification/2025-06-18/basic/lifecycle#initialization
+    ///
+    /// `send_sampling` and `list_roots` answer the corresponding server
+    /// requests; pass `None` when the matching capability is not advertised.
     pub async fn initialize(
         &self,
         params: InitializeRequestParams,
         timeout: Option<Duration>,
         send_elicitation: SendElicitation,
+        send_sampling: Option<SendSampling>,
+        list_roots: Option<ListRoots>,
     ) -> Result<InitializeResult> {
         let rmcp_params: InitializeRequestParam = convert_to_rmcp(params.clone())?;
-        let client_handler = LoggingClientHandler::new(rmcp_params, send_elicitation);
+        let client_handler = LoggingClientHandler::new(
+            rmcp_params,
+            send_elicitation,
+            send_sampling,
+            list_roots,
+            self.list_changed_tx.clone(),
+            Arc::clone(&self.in_flight_tool_calls),
+        );
 
         let (transport, oauth_persistor) = {
             let mut guard = self.state.lock().await;
@@ -350,23 +483,118 @@ impl RmcpClient {
         Ok(converted)
     }
 
+    pub async fn list_prompts(
+        &self,
+        params: Option<ListPromptsRequestParams>,
+        timeout: Option<Duration>,
+    ) -> Result<ListPromptsResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let rmcp_params = params
+            .map(convert_to_rmcp::<_, PaginatedRequestParam>)
+            .transpose()?;
+
+        let fut = service.list_prompts(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/list").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
+    pub async fn get_prompt(
+        &self,
+        params: GetPromptRequestParams,
+        timeout: Option<Duration>,
+    ) -> Result<GetPromptResult> {
+        self.refresh_oauth_if_needed().await;
+        let service = self.service().await?;
+        let rmcp_params: GetPromptRequestParam = convert_to_rmcp(params)?;
+        let fut = service.get_prompt(rmcp_params);
+        let result = run_with_timeout(fut, timeout, "prompts/get").await?;
+        let converted = convert_to_mcp(result)?;
+        self.persist_oauth_tokens().await;
+        Ok(converted)
+    }
+
     pub async fn call_tool(
         &self,
         name: String,
         arguments: Option<serde_json::Value>,
         timeout: Option<Duration>,
+    ) -> Result<CallToolResult> {
+        self.call_tool_with_progress(name, arguments, timeout, None)
+            .await
+    }
+
+    /// Like [`RmcpClient::call_tool`], but forwards progress and log
+    /// notifications received while the call is in flight to `progress_tx`.
+    ///
+    /// If the returned future is dropped or times out before the server
+    /// responds, the request is cancelled with `notifications/cancelled`.
+    pub async fn call_tool_with_progress(
+        &self,
+        name: String,
+        arguments: Option<serde_json::Value>,
+        timeout: Option<Duration>,
+        progress_tx: Option<ToolCallProgressSender>,
     ) -> Result<CallToolResult> {
         self.refresh_oauth_if_needed().await;
         let service = self.service().await?;
         let params = CallToolRequestParams { arguments, name };
         let rmcp_params: CallToolRequestParam = convert_to_rmcp(params)?;
-        let fut = service.call_tool(rmcp_params);
-        let rmcp_result = run_with_timeout(fut, timeout, "tools/call").await?;
+
+        let progress_token = self.next_progress_token();
+        let _in_flight_guard = match progress_tx {
+            Some(progress_tx) => {
+                self.in_flight_tool_calls
+                    .lock()
+                    .map_err(|_| anyhow!("in-flight tool call lock poisoned"))?
+                    .insert(progress_token.clone(), progress_tx);
+                Some(InFlightToolCallGuard {
+                    calls: Arc::clone(&self.in_flight_tool_calls),
+                    token: progress_token.clone(),
+                })
+            }
+            None => None,
+        };
+        // Registered before sending, so our own token (rather than the one
+        // rmcp generates) is the one the server echoes back.
+        let mut meta = Meta::new();
+        meta.set_progress_token(progress_token);
+        let RequestHandle { rx, peer, id, .. } = service
+            .peer()
+            .send_cancellable_request(
+                ClientRequest::CallToolRequest(CallToolRequest::new(rmcp_params)),
+                PeerRequestOptions {
+                    timeout: None,
+                    meta: Some(meta),
+                },
+            )
+            .await?;
+        let mut cancel_on_drop = CancelOnDrop {
+            peer,
+            request_id: Some(id),
+        };
+
+        let fut = async move { rx.await.map_err(|_| ServiceError::TransportClosed)? };
+        let response = run_with_timeout(fut, timeout, "tools/call").await?;
+        cancel_on_drop.disarm();
+        let rmcp_result = match response {
+            ServerResult::CallToolResult(result) => result,
+            _ => return Err(anyhow!("tools/call failed: unexpected response")),
+        };
         let converted = convert_call_tool_result(rmcp_result)?;
         self.persist_oauth_tokens().await;
         Ok(converted)
     }
 
+    fn next_progress_token(&self) -> ProgressToken {
+        let n = self.next_progress_token.fetch_add(1, Ordering::Relaxed);
+        ProgressToken(NumberOrString::String(
+            format!("codex-tool-call-{n}").into(),
+        ))
+    }
+
     pub async fn send_custom_notification(
         &self,
         method: &str,
@@ -383,6 +611,13 @@ impl RmcpClient {
         Ok(())
     }
 
+    /// Tell the server that the result of `roots/list` has changed.
+    pub async fn notify_roots_list_changed(&self) -> Result<()> {
+        let service: Arc<RunningService<RoleClient, LoggingClientHandler>> = self.service().await?;
+        service.peer().notify_roots_list_changed().await?;
+        Ok(())
+    }
+
     pub async fn send_custom_request(
         &self,
         method: &str,
@@ -0,0 +1,90 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::ListChanged;
+use codex_rmcp_client::RmcpClient;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use mcp_types::ClientCapabilities;
+use mcp_types::Implementation;
+use mcp_types::InitializeRequestParams;
+use serde_json::json;
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: None,
+            sampling: None,
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp list_changed test".into()),
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_string(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rmcp_client_reports_tool_list_changes() -> anyhow::Result<()> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+    let mut list_changed = client.subscribe_list_changed();
+
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Accept,
+                        content: Some(json!({})),
+                    })
+                }
+                .boxed()
+            }),
+            None,
+            None,
+        )
+        .await?;
+
+    let before = client
+        .list_tools(None, Some(Duration::from_secs(5)))
+        .await?;
+    assert!(!before.tools.iter().any(|tool| tool.name == "late_echo"));
+
+    client
+        .call_tool(
+            "add_tool".to_string(),
+            Some(json!({ "name": "late_echo" })),
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+
+    let notification = tokio::time::timeout(Duration::from_secs(5), list_changed.recv()).await??;
+    assert_eq!(notification, ListChanged::Tools);
+
+    let after = client
+        .list_tools(None, Some(Duration::from_secs(5)))
+        .await?;
+    assert!(after.tools.iter().any(|tool| tool.name == "late_echo"));
+
+    Ok(())
+}
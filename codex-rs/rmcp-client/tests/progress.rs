@@ -0,0 +1,153 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::RmcpClient;
+use codex_rmcp_client::ToolCallProgress;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use mcp_types::ClientCapabilities;
+use mcp_types::ContentBlock;
+use mcp_types::Implementation;
+use mcp_types::InitializeRequestParams;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use tokio::sync::mpsc;
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: None,
+            sampling: None,
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp progress test".into()),
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_string(),
+    }
+}
+
+async fn initialized_client() -> anyhow::Result<RmcpClient> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Accept,
+                        content: Some(json!({})),
+                    })
+                }
+                .boxed()
+            }),
+            None,
+            None,
+        )
+        .await?;
+    Ok(client)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rmcp_client_forwards_tool_call_progress() -> anyhow::Result<()> {
+    let client = initialized_client().await?;
+    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+
+    client
+        .call_tool_with_progress(
+            "progress".to_string(),
+            Some(json!({ "steps": 2 })),
+            Some(Duration::from_secs(5)),
+            Some(progress_tx),
+        )
+        .await?;
+
+    // rmcp dispatches each notification on its own task, so arrival order is
+    // not guaranteed.
+    let mut updates = Vec::new();
+    while let Ok(update) = progress_rx.try_recv() {
+        updates.push(update);
+    }
+    let expected = [
+        ToolCallProgress {
+            progress: Some(1.0),
+            total: Some(2.0),
+            message: Some("step 1".to_string()),
+        },
+        ToolCallProgress {
+            progress: Some(2.0),
+            total: Some(2.0),
+            message: Some("step 2".to_string()),
+        },
+        ToolCallProgress {
+            progress: None,
+            total: None,
+            message: Some("progress finished".to_string()),
+        },
+    ];
+    assert_eq!(updates.len(), expected.len(), "updates: {updates:?}");
+    for update in &expected {
+        assert!(
+            updates.contains(update),
+            "missing {update:?} in {updates:?}"
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn dropping_a_tool_call_cancels_it_on_the_server() -> anyhow::Result<()> {
+    let client = initialized_client().await?;
+
+    let dropped = tokio::time::timeout(
+        Duration::from_millis(200),
+        client.call_tool("wait".to_string(), None, None),
+    )
+    .await;
+    assert!(dropped.is_err(), "wait should only finish when cancelled");
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+    loop {
+        let result = client
+            .call_tool(
+                "cancelled_waits".to_string(),
+                None,
+                Some(Duration::from_secs(5)),
+            )
+            .await?;
+        let cancelled = result
+            .content
+            .iter()
+            .any(|block| matches!(block, ContentBlock::TextContent(text) if text.text == "1"));
+        if cancelled {
+            break;
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "server never saw notifications/cancelled"
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+
+    Ok(())
+}
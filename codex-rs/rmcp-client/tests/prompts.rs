@@ -0,0 +1,142 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::RmcpClient;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use mcp_types::ClientCapabilities;
+use mcp_types::ContentBlock;
+use mcp_types::GetPromptRequestParams;
+use mcp_types::GetPromptResult;
+use mcp_types::Implementation;
+use mcp_types::InitializeRequestParams;
+use mcp_types::ListPromptsResult;
+use mcp_types::Prompt;
+use mcp_types::PromptArgument;
+use mcp_types::PromptMessage;
+use mcp_types::Role;
+use mcp_types::TextContent;
+use serde_json::json;
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: None,
+            sampling: None,
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp prompt test".into()),
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_string(),
+    }
+}
+
+fn text_message(role: Role, text: &str) -> PromptMessage {
+    PromptMessage {
+        content: ContentBlock::TextContent(TextContent {
+            annotations: None,
+            text: text.to_string(),
+            r#type: "text".to_string(),
+        }),
+        role,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rmcp_client_can_list_and_get_prompts() -> anyhow::Result<()> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Accept,
+                        content: Some(json!({})),
+                    })
+                }
+                .boxed()
+            }),
+            None,
+            None,
+        )
+        .await?;
+
+    let list = client
+        .list_prompts(None, Some(Duration::from_secs(5)))
+        .await?;
+    assert_eq!(
+        list,
+        ListPromptsResult {
+            next_cursor: None,
+            prompts: vec![Prompt {
+                arguments: Some(vec![PromptArgument {
+                    description: Some("What to review.".to_string()),
+                    name: "topic".to_string(),
+                    required: Some(true),
+                    title: None,
+                }]),
+                description: Some("Ask for a focused review of the given topic.".to_string()),
+                name: "review".to_string(),
+                title: None,
+            }],
+        }
+    );
+
+    let prompt = client
+        .get_prompt(
+            GetPromptRequestParams {
+                arguments: Some(json!({ "topic": "the parser" })),
+                name: "review".to_string(),
+            },
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    assert_eq!(
+        prompt,
+        GetPromptResult {
+            description: Some("Focused review".to_string()),
+            messages: vec![
+                text_message(
+                    Role::User,
+                    "Please review the parser and list concrete problems."
+                ),
+                text_message(Role::Assistant, "I will review the parser."),
+            ],
+        }
+    );
+
+    let missing_argument = client
+        .get_prompt(
+            GetPromptRequestParams {
+                arguments: None,
+                name: "review".to_string(),
+            },
+            Some(Duration::from_secs(5)),
+        )
+        .await;
+    assert!(missing_argument.is_err());
+
+    Ok(())
+}
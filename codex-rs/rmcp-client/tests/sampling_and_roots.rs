@@ -0,0 +1,130 @@
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_rmcp_client::ElicitationAction;
+use codex_rmcp_client::ElicitationResponse;
+use codex_rmcp_client::RmcpClient;
+use codex_utils_cargo_bin::CargoBinError;
+use futures::FutureExt as _;
+use mcp_types::ClientCapabilities;
+use mcp_types::ClientCapabilitiesRoots;
+use mcp_types::ContentBlock;
+use mcp_types::CreateMessageResult;
+use mcp_types::CreateMessageResultContent;
+use mcp_types::Implementation;
+use mcp_types::InitializeRequestParams;
+use mcp_types::Role;
+use mcp_types::Root;
+use mcp_types::SamplingMessageContent;
+use mcp_types::TextContent;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+fn stdio_server_bin() -> Result<PathBuf, CargoBinError> {
+    codex_utils_cargo_bin::cargo_bin("test_stdio_server")
+}
+
+fn init_params() -> InitializeRequestParams {
+    InitializeRequestParams {
+        capabilities: ClientCapabilities {
+            experimental: None,
+            roots: Some(ClientCapabilitiesRoots {
+                list_changed: Some(true),
+            }),
+            sampling: Some(json!({})),
+            elicitation: Some(json!({})),
+        },
+        client_info: Implementation {
+            name: "codex-test".into(),
+            version: "0.0.0-test".into(),
+            title: Some("Codex rmcp sampling and roots test".into()),
+            user_agent: None,
+        },
+        protocol_version: mcp_types::MCP_SCHEMA_VERSION.to_string(),
+    }
+}
+
+fn text_of(content: &[ContentBlock]) -> Vec<String> {
+    content
+        .iter()
+        .filter_map(|block| match block {
+            ContentBlock::TextContent(text) => Some(text.text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn rmcp_client_answers_sampling_and_roots_requests() -> anyhow::Result<()> {
+    let client = RmcpClient::new_stdio_client(
+        stdio_server_bin()?.into(),
+        Vec::<OsString>::new(),
+        None,
+        &[],
+        None,
+    )
+    .await?;
+
+    client
+        .initialize(
+            init_params(),
+            Some(Duration::from_secs(5)),
+            Box::new(|_, _| {
+                async {
+                    Ok(ElicitationResponse {
+                        action: ElicitationAction::Accept,
+                        content: Some(json!({})),
+                    })
+                }
+                .boxed()
+            }),
+            Some(Box::new(|_, params| {
+                async move {
+                    let prompt = match params.messages.first().map(|message| &message.content) {
+                        Some(SamplingMessageContent::TextContent(text)) => text.text.clone(),
+                        _ => String::new(),
+                    };
+                    Ok(CreateMessageResult {
+                        content: CreateMessageResultContent::TextContent(TextContent {
+                            annotations: None,
+                            text: format!("echo {prompt}"),
+                            r#type: "text".to_string(),
+                        }),
+                        model: "test-model".to_string(),
+                        role: Role::Assistant,
+                        stop_reason: Some("endTurn".to_string()),
+                    })
+                }
+                .boxed()
+            })),
+            Some(Box::new(|| {
+                async {
+                    Ok(vec![Root {
+                        name: Some("workspace".to_string()),
+                        uri: "file:///workspace".to_string(),
+                    }])
+                }
+                .boxed()
+            })),
+        )
+        .await?;
+
+    let roots = client
+        .call_tool("roots".to_string(), None, Some(Duration::from_secs(5)))
+        .await?;
+    assert_eq!(text_of(&roots.content), vec!["file:///workspace"]);
+
+    let sample = client
+        .call_tool(
+            "sample".to_string(),
+            Some(json!({ "prompt": "hello" })),
+            Some(Duration::from_secs(5)),
+        )
+        .await?;
+    assert_eq!(text_of(&sample.content), vec!["test-model: echo hello"]);
+
+    client.notify_roots_list_changed().await?;
+
+    Ok(())
+}
@@ -14,6 +14,7 @@ pub fn add_dir_warning_message(
 
     match sandbox_policy {
         SandboxPolicy::WorkspaceWrite { .. }
+        | SandboxPolicy::RestrictedRead { .. }
         | SandboxPolicy::DangerFullAccess
         | SandboxPolicy::ExternalSandbox { .. } => None,
         SandboxPolicy::ReadOnly => Some(format_warning(additional_dirs)),
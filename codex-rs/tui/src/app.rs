@@ -881,6 +881,21 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::OpenDiffViewer(unified_diff) => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_diff_viewer(
+                    &unified_diff,
+                    &self.config.cwd,
+                    self.app_event_tx.clone(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::DiffFileReverted {
+                path,
+                remaining_diff,
+            } => {
+                self.chat_widget.on_diff_file_reverted(path, remaining_diff);
+            }
             AppEvent::StartFileSearch(query) => {
                 if !query.is_empty() {
                     self.file_search.on_user_query(query);
@@ -935,6 +950,9 @@ impl App {
                     self.launch_external_editor(tui).await;
                 }
             }
+            AppEvent::EditPatchHunk { seed } => {
+                self.edit_patch_hunk(tui, seed).await;
+            }
             AppEvent::OpenWindowsSandboxEnablePrompt { preset } => {
                 self.chat_widget.open_windows_sandbox_enable_prompt(preset);
             }
@@ -1411,6 +1429,28 @@ impl App {
         tui.frame_requester().schedule_frame();
     }
 
+    async fn edit_patch_hunk(&mut self, tui: &mut tui::Tui, seed: String) {
+        let edited = match external_editor::resolve_editor_command() {
+            Ok(editor_cmd) => tui
+                .with_restored(tui::RestoreMode::KeepRaw, || async {
+                    external_editor::run_editor_with_suffix(&seed, &editor_cmd, ".diff").await
+                })
+                .await
+                .map_err(|err| format!("Failed to open editor: {err}")),
+            Err(external_editor::EditorError::MissingEditor) => {
+                Err("Cannot open external editor: set $VISUAL or $EDITOR".to_string())
+            }
+            Err(err) => Err(format!("Failed to open editor: {err}")),
+        };
+        match edited {
+            Ok(text) => self.chat_widget.apply_patch_hunk_edit(text),
+            Err(message) => self
+                .chat_widget
+                .add_to_history(history_cell::new_error_event(message)),
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
     fn request_external_editor_launch(&mut self, tui: &mut tui::Tui) {
         self.chat_widget
             .set_external_editor_state(ExternalEditorState::Requested);
@@ -70,6 +70,16 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Open the full-screen viewer for the latest turn's unified diff.
+    OpenDiffViewer(String),
+
+    /// A file's changes were reverted from the diff viewer. `remaining_diff`
+    /// is the turn diff without that file.
+    DiffFileReverted {
+        path: String,
+        remaining_diff: String,
+    },
+
     InsertHistoryCell(Box<dyn HistoryCell>),
 
     StartCommitAnimation,
@@ -213,6 +223,11 @@ pub(crate) enum AppEvent {
 
     /// Launch the external editor after a normal draw has completed.
     LaunchExternalEditor,
+
+    /// Open the external editor on a patch hunk under hunk-by-hunk review.
+    EditPatchHunk {
+        seed: String,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -8,6 +8,8 @@ use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
+use crate::bottom_pane::patch_hunk_review::HunkReviewAction;
+use crate::bottom_pane::patch_hunk_review::PatchHunkReview;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
@@ -69,6 +71,8 @@ pub(crate) struct ApprovalOverlay {
     current_complete: bool,
     done: bool,
     features: Features,
+    /// Set while the user steps through the hunks of the current patch.
+    hunk_review: Option<PatchHunkReview>,
 }
 
 impl ApprovalOverlay {
@@ -83,6 +87,7 @@ impl ApprovalOverlay {
             current_complete: false,
             done: false,
             features,
+            hunk_review: None,
         };
         view.set_current(request);
         view
@@ -97,6 +102,7 @@ impl ApprovalOverlay {
         let ApprovalRequestState { variant, header } = ApprovalRequestState::from(request);
         self.current_variant = Some(variant.clone());
         self.current_complete = false;
+        self.hunk_review = None;
         let (options, params) = Self::build_options(variant, header, &self.features);
         self.options = options;
         self.list = ListSelectionView::new(params, self.app_event_tx.clone());
@@ -174,6 +180,13 @@ impl ApprovalOverlay {
                 (ApprovalVariant::ApplyPatch { id, .. }, ApprovalDecision::Review(decision)) => {
                     self.handle_patch_decision(id, decision.clone());
                 }
+                (
+                    ApprovalVariant::ApplyPatch { cwd, changes, .. },
+                    ApprovalDecision::ReviewHunks,
+                ) => {
+                    self.hunk_review = Some(PatchHunkReview::new(changes, cwd.clone()));
+                    return;
+                }
                 (
                     ApprovalVariant::McpElicitation {
                         server_name,
@@ -207,6 +220,25 @@ impl ApprovalOverlay {
         }));
     }
 
+    fn handle_hunk_review_action(&mut self, action: HunkReviewAction) {
+        match action {
+            HunkReviewAction::None => {}
+            HunkReviewAction::Edit(seed) => {
+                self.app_event_tx.send(AppEvent::EditPatchHunk { seed });
+            }
+            HunkReviewAction::Finished => {
+                if let Some(review) = self.hunk_review.take()
+                    && let Some(ApprovalVariant::ApplyPatch { id, .. }) = &self.current_variant
+                {
+                    self.handle_patch_decision(id, review.decision());
+                    self.current_complete = true;
+                    self.advance_queue();
+                }
+            }
+            HunkReviewAction::Cancel => self.hunk_review = None,
+        }
+    }
+
     fn handle_elicitation_decision(
         &self,
         server_name: &str,
@@ -263,6 +295,11 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if let Some(review) = self.hunk_review.as_mut() {
+            let action = review.handle_key_event(key_event);
+            self.handle_hunk_review_action(action);
+            return;
+        }
         if self.try_handle_shortcut(&key_event) {
             return;
         }
@@ -298,6 +335,7 @@ impl BottomPaneView for ApprovalOverlay {
                 }
             }
         }
+        self.hunk_review = None;
         self.queue.clear();
         self.done = true;
         CancellationEvent::Handled
@@ -314,15 +352,28 @@ impl BottomPaneView for ApprovalOverlay {
         self.enqueue_request(request);
         None
     }
+
+    fn apply_patch_hunk_edit(&mut self, edited: String) {
+        if let Some(review) = self.hunk_review.as_mut() {
+            let action = review.apply_edit(edited);
+            self.handle_hunk_review_action(action);
+        }
+    }
 }
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        match &self.hunk_review {
+            Some(review) => review.desired_height(width),
+            None => self.list.desired_height(width),
+        }
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        match &self.hunk_review {
+            Some(review) => review.render(area, buf),
+            None => self.list.render(area, buf),
+        }
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
@@ -380,9 +431,9 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     ));
                     header.push(Box::new(Line::from("")));
                 }
-                header.push(DiffSummary::new(changes, cwd).into());
+                header.push(DiffSummary::new(changes.clone(), cwd.clone()).into());
                 Self {
-                    variant: ApprovalVariant::ApplyPatch { id },
+                    variant: ApprovalVariant::ApplyPatch { id, cwd, changes },
                     header: Box::new(ColumnRenderable::with(header)),
                 }
             }
@@ -418,6 +469,8 @@ enum ApprovalVariant {
     },
     ApplyPatch {
         id: String,
+        cwd: PathBuf,
+        changes: HashMap<PathBuf, FileChange>,
     },
     McpElicitation {
         server_name: String,
@@ -428,6 +481,8 @@ enum ApprovalVariant {
 #[derive(Clone)]
 enum ApprovalDecision {
     Review(ReviewDecision),
+    /// Step through the hunks of a patch before deciding.
+    ReviewHunks,
     McpElicitation(ElicitationAction),
 }
 
@@ -504,6 +559,12 @@ fn patch_options() -> Vec<ApprovalOption> {
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
         },
+        ApprovalOption {
+            label: "Review each hunk".to_string(),
+            decision: ApprovalDecision::ReviewHunks,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('h'))],
+        },
         ApprovalOption {
             label: "No, and tell Codex what to do differently".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Abort),
@@ -724,4 +785,57 @@ mod tests {
         }
         assert_eq!(decision, Some(ReviewDecision::Approved));
     }
+
+    #[test]
+    fn hunk_review_sends_partial_approval() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let changes = HashMap::from([(
+            PathBuf::from("/repo/a.txt"),
+            FileChange::Update {
+                unified_diff: "@@ -1 +1 @@\n-one\n+uno\n@@ -9 +9 @@\n-nine\n+nueve\n".to_string(),
+                move_path: None,
+            },
+        )]);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::ApplyPatch {
+                id: "patch".to_string(),
+                reason: None,
+                cwd: PathBuf::from("/repo"),
+                changes,
+            },
+            tx,
+            Features::with_defaults(),
+        );
+
+        for c in ['h', 'y', 'n'] {
+            view.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+
+        assert!(view.is_complete());
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::PatchApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+                break;
+            }
+        }
+        assert_eq!(
+            decision,
+            Some(ReviewDecision::ApprovedPartially {
+                changes: HashMap::from([(
+                    PathBuf::from("/repo/a.txt"),
+                    FileChange::Update {
+                        unified_diff: "@@ -1 +1 @@\n-one\n+uno\n".to_string(),
+                        move_path: None,
+                    },
+                )]),
+                rejected_hunks: vec![codex_core::protocol::PatchHunk {
+                    path: PathBuf::from("/repo/a.txt"),
+                    diff: "@@ -9 +9 @@\n-nine\n+nueve\n".to_string(),
+                }],
+                edited_hunks: Vec::new(),
+            })
+        );
+    }
 }
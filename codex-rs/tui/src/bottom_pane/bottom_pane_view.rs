@@ -34,4 +34,7 @@ pub(crate) trait BottomPaneView: Renderable {
     ) -> Option<ApprovalRequest> {
         Some(request)
     }
+
+    /// Receive the text of a patch hunk the user edited in their editor.
+    fn apply_patch_hunk_edit(&mut self, _edited: String) {}
 }
@@ -87,6 +87,8 @@ use super::footer::footer_height;
 use super::footer::render_footer;
 use super::footer::reset_mode_after_activity;
 use super::footer::toggle_shortcut_mode;
+use super::mcp_prompts::McpPromptCommand;
+use super::mcp_prompts::parse_mcp_prompt_invocation;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
 use super::skill_popup::SkillPopup;
@@ -187,6 +189,7 @@ pub(crate) struct ChatComposer {
     // When true, disables paste-burst logic and inserts characters immediately.
     disable_paste_burst: bool,
     custom_prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     footer_mode: FooterMode,
     footer_hint_override: Option<Vec<(String, String)>>,
     context_window_percent: Option<i64>,
@@ -238,6 +241,7 @@ impl ChatComposer {
             paste_burst: PasteBurst::default(),
             disable_paste_burst: false,
             custom_prompts: Vec::new(),
+            mcp_prompts: Vec::new(),
             footer_mode: FooterMode::ShortcutSummary,
             footer_hint_override: None,
             context_window_percent: None,
@@ -719,6 +723,16 @@ impl ChatComposer {
                                 }
                             }
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                let command = format!("/{}", prompt.command_name());
+                                if !first_line.trim_start().starts_with(&command) {
+                                    let (text, cursor) = prompt.command_with_arg_placeholders();
+                                    self.textarea.set_text(&text);
+                                    cursor_target = Some(cursor);
+                                }
+                            }
+                        }
                     }
                     if let Some(pos) = cursor_target {
                         self.textarea.set_cursor(pos);
@@ -772,6 +786,21 @@ impl ChatComposer {
                             }
                             return (InputResult::None, true);
                         }
+                        CommandItem::McpPrompt(idx) => {
+                            if let Some(prompt) = popup.mcp_prompt(idx) {
+                                let command = format!("/{}", prompt.command_name());
+                                let typed = first_line.trim_start().starts_with(&command);
+                                if !typed && prompt.has_required_arguments() {
+                                    let (text, cursor) = prompt.command_with_arg_placeholders();
+                                    self.textarea.set_text(&text);
+                                    self.textarea.set_cursor(cursor);
+                                    return (InputResult::None, true);
+                                }
+                                if !typed {
+                                    self.textarea.set_text(&command);
+                                }
+                            }
+                        }
                     }
                 }
                 // Fallback to default newline handling if no command selected.
@@ -1331,7 +1360,11 @@ impl ChatComposer {
                             .any(|prompt| prompt.name == prompt_name)
                     })
                     .unwrap_or(false);
-                if !is_builtin && !is_known_prompt {
+                let is_mcp_prompt = self
+                    .mcp_prompts
+                    .iter()
+                    .any(|prompt| prompt.command_name() == name);
+                if !is_builtin && !is_known_prompt && !is_mcp_prompt {
                     let message = format!(
                         r#"Unrecognized command '/{name}'. Type "/" for a list of supported commands."#
                     );
@@ -1345,6 +1378,16 @@ impl ChatComposer {
             }
         }
 
+        // MCP prompts are rendered by core; only validate their arguments here.
+        if let Err(err) = parse_mcp_prompt_invocation(&text, &self.mcp_prompts) {
+            self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                history_cell::new_error_event(err.user_message()),
+            )));
+            self.textarea.set_text(&original_input);
+            self.textarea.set_cursor(original_input.len());
+            return None;
+        }
+
         let expanded_prompt = match expand_custom_prompt(&text, &self.custom_prompts) {
             Ok(expanded) => expanded,
             Err(err) => {
@@ -1907,7 +1950,7 @@ impl ChatComposer {
     }
 
     /// Heuristic for whether the typed slash command looks like a valid
-    /// prefix for any known command (built-in or custom prompt).
+    /// prefix for any known command (built-in, custom prompt or MCP prompt).
     /// Empty names only count when there is no extra content after the '/'.
     fn looks_like_slash_prefix(&self, name: &str, rest_after_name: &str) -> bool {
         if name.is_empty() {
@@ -1929,6 +1972,10 @@ impl ChatComposer {
         self.custom_prompts
             .iter()
             .any(|p| fuzzy_match(&format!("{prompt_prefix}{}", p.name), name).is_some())
+            || self
+                .mcp_prompts
+                .iter()
+                .any(|p| fuzzy_match(&p.command_name(), name).is_some())
     }
 
     /// Synchronize `self.command_popup` with the current text in the
@@ -1974,6 +2021,7 @@ impl ChatComposer {
                     let skills_enabled = self.skills_enabled();
                     let mut command_popup =
                         CommandPopup::new(self.custom_prompts.clone(), skills_enabled);
+                    command_popup.set_mcp_prompts(self.mcp_prompts.clone());
                     command_popup.on_composer_text_change(first_line.to_string());
                     self.active_popup = ActivePopup::Command(command_popup);
                 }
@@ -1988,6 +2036,17 @@ impl ChatComposer {
         }
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = prompts.clone();
+        if let ActivePopup::Command(popup) = &mut self.active_popup {
+            popup.set_mcp_prompts(prompts);
+        }
+    }
+
+    pub(crate) fn mcp_prompts(&self) -> &[McpPromptCommand] {
+        &self.mcp_prompts
+    }
+
     /// Synchronize `self.file_search_popup` with the current text in the textarea.
     /// Note this is only called when self.active_popup is NOT Command.
     fn sync_file_search_popup(&mut self, query: String) {
@@ -3306,7 +3365,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "model")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/mo'")
                 }
                 None => panic!("no selected command for '/mo'"),
@@ -3363,7 +3422,7 @@ mod tests {
                 Some(CommandItem::Builtin(cmd)) => {
                     assert_eq!(cmd.command(), "resume")
                 }
-                Some(CommandItem::UserPrompt(_)) => {
+                Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                     panic!("unexpected prompt selected for '/res'")
                 }
                 None => panic!("no selected command for '/res'"),
@@ -4172,6 +4231,78 @@ mod tests {
         assert!(composer.textarea.is_empty());
     }
 
+    fn review_mcp_prompt() -> McpPromptCommand {
+        McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: mcp_types::Prompt {
+                arguments: Some(vec![mcp_types::PromptArgument {
+                    description: None,
+                    name: "topic".to_string(),
+                    required: Some(true),
+                    title: None,
+                }]),
+                description: None,
+                name: "review".to_string(),
+                title: None,
+            },
+        }
+    }
+
+    #[test]
+    fn mcp_prompt_submission_keeps_invocation_text() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_steer_enabled(true);
+        composer.set_mcp_prompts(vec![review_mcp_prompt()]);
+
+        composer
+            .textarea
+            .set_text("/mcp:docs:review topic=\"the parser\"");
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            InputResult::Submitted("/mcp:docs:review topic=\"the parser\"".to_string()),
+            result
+        );
+        assert!(composer.textarea.is_empty());
+    }
+
+    #[test]
+    fn selecting_mcp_prompt_with_required_args_inserts_placeholders() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_steer_enabled(true);
+        composer.set_mcp_prompts(vec![review_mcp_prompt()]);
+
+        type_chars_humanlike(
+            &mut composer,
+            &['/', 'm', 'c', 'p', ':', 'd', 'o', 'c', 's'],
+        );
+
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(InputResult::None, result);
+        assert_eq!(composer.textarea.text(), "/mcp:docs:review topic=\"\"");
+        assert_eq!(composer.textarea.cursor(), 24);
+    }
+
     #[test]
     fn custom_prompt_submission_accepts_quoted_values() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -2,6 +2,7 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::widgets::WidgetRef;
 
+use super::mcp_prompts::McpPromptCommand;
 use super::popup_consts::MAX_POPUP_ROWS;
 use super::scroll_state::ScrollState;
 use super::selection_popup_common::GenericDisplayRow;
@@ -22,18 +23,22 @@ fn windows_degraded_sandbox_active() -> bool {
         && !codex_core::is_windows_elevated_sandbox_enabled()
 }
 
-/// A selectable item in the popup: either a built-in command or a user prompt.
+/// A selectable item in the popup: a built-in command, a user prompt, or a
+/// prompt published by an MCP server.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum CommandItem {
     Builtin(SlashCommand),
     // Index into `prompts`
     UserPrompt(usize),
+    // Index into `mcp_prompts`
+    McpPrompt(usize),
 }
 
 pub(crate) struct CommandPopup {
     command_filter: String,
     builtins: Vec<(&'static str, SlashCommand)>,
     prompts: Vec<CustomPrompt>,
+    mcp_prompts: Vec<McpPromptCommand>,
     state: ScrollState,
 }
 
@@ -53,6 +58,7 @@ impl CommandPopup {
             command_filter: String::new(),
             builtins,
             prompts,
+            mcp_prompts: Vec::new(),
             state: ScrollState::new(),
         }
     }
@@ -72,6 +78,14 @@ impl CommandPopup {
         self.prompts.get(idx)
     }
 
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.mcp_prompts = prompts;
+    }
+
+    pub(crate) fn mcp_prompt(&self, idx: usize) -> Option<&McpPromptCommand> {
+        self.mcp_prompts.get(idx)
+    }
+
     /// Update the filter string based on the current composer text. The text
     /// passed in is expected to start with a leading '/'. Everything after the
     /// *first* '/" on the *first* line becomes the active filter that is used
@@ -127,6 +141,10 @@ impl CommandPopup {
             for idx in 0..self.prompts.len() {
                 out.push((CommandItem::UserPrompt(idx), None, 0));
             }
+            // MCP prompts last, already sorted by server and name.
+            for idx in 0..self.mcp_prompts.len() {
+                out.push((CommandItem::McpPrompt(idx), None, 0));
+            }
             return out;
         }
 
@@ -144,16 +162,23 @@ impl CommandPopup {
                 out.push((CommandItem::UserPrompt(idx), Some(indices), score));
             }
         }
+        for (idx, p) in self.mcp_prompts.iter().enumerate() {
+            if let Some((indices, score)) = fuzzy_match(&p.command_name(), filter) {
+                out.push((CommandItem::McpPrompt(idx), Some(indices), score));
+            }
+        }
         // When filtering, sort by ascending score and then by name for stability.
         out.sort_by(|a, b| {
             a.2.cmp(&b.2).then_with(|| {
                 let an = match a.0 {
                     CommandItem::Builtin(c) => c.command(),
                     CommandItem::UserPrompt(i) => &self.prompts[i].name,
+                    CommandItem::McpPrompt(i) => &self.mcp_prompts[i].prompt.name,
                 };
                 let bn = match b.0 {
                     CommandItem::Builtin(c) => c.command(),
                     CommandItem::UserPrompt(i) => &self.prompts[i].name,
+                    CommandItem::McpPrompt(i) => &self.mcp_prompts[i].prompt.name,
                 };
                 an.cmp(bn)
             })
@@ -187,6 +212,10 @@ impl CommandPopup {
                             description,
                         )
                     }
+                    CommandItem::McpPrompt(i) => {
+                        let prompt = &self.mcp_prompts[i];
+                        (format!("/{}", prompt.command_name()), prompt.description())
+                    }
                 };
                 GenericDisplayRow {
                     name,
@@ -255,7 +284,7 @@ mod tests {
         let matches = popup.filtered_items();
         let has_init = matches.iter().any(|item| match item {
             CommandItem::Builtin(cmd) => cmd.command() == "init",
-            CommandItem::UserPrompt(_) => false,
+            CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => false,
         });
         assert!(
             has_init,
@@ -273,7 +302,9 @@ mod tests {
         let selected = popup.selected_item();
         match selected {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "init"),
-            Some(CommandItem::UserPrompt(_)) => panic!("unexpected prompt selected for '/init'"),
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
+                panic!("unexpected prompt selected for '/init'")
+            }
             None => panic!("expected a selected command for exact match"),
         }
     }
@@ -285,7 +316,7 @@ mod tests {
         let matches = popup.filtered_items();
         match matches.first() {
             Some(CommandItem::Builtin(cmd)) => assert_eq!(cmd.command(), "model"),
-            Some(CommandItem::UserPrompt(_)) => {
+            Some(CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_)) => {
                 panic!("unexpected prompt ranked before '/model' for '/mo'")
             }
             None => panic!("expected at least one match for '/mo'"),
@@ -384,6 +415,30 @@ mod tests {
         assert_eq!(description, Some("send saved prompt"));
     }
 
+    #[test]
+    fn mcp_prompts_are_listed_with_server_prefix() {
+        let mut popup = CommandPopup::new(Vec::new(), false);
+        popup.set_mcp_prompts(vec![McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: mcp_types::Prompt {
+                arguments: None,
+                description: Some("Ask for a focused review.".to_string()),
+                name: "review".to_string(),
+                title: None,
+            },
+        }]);
+        popup.on_composer_text_change("/mcp:docs".to_string());
+
+        assert_eq!(popup.selected_item(), Some(CommandItem::McpPrompt(0)));
+        let rows = popup.rows_from_matches(vec![(CommandItem::McpPrompt(0), None, 0)]);
+        let row = rows.first().expect("mcp prompt row");
+        assert_eq!(row.name, "/mcp:docs:review");
+        assert_eq!(
+            row.description.as_deref(),
+            Some("Ask for a focused review.")
+        );
+    }
+
     #[test]
     fn fuzzy_filter_matches_subsequence_for_ac() {
         let mut popup = CommandPopup::new(Vec::new(), false);
@@ -394,7 +449,7 @@ mod tests {
             .into_iter()
             .filter_map(|item| match item {
                 CommandItem::Builtin(cmd) => Some(cmd.command()),
-                CommandItem::UserPrompt(_) => None,
+                CommandItem::UserPrompt(_) | CommandItem::McpPrompt(_) => None,
             })
             .collect();
         assert!(
@@ -0,0 +1,212 @@
+//! MCP prompts offered as `/mcp:<server>:<prompt> key=value …` slash commands.
+//!
+//! Unlike saved prompts, MCP prompts are not expanded locally: the composer
+//! only validates the arguments, and the chat widget forwards the invocation
+//! to core as a `UserInput::McpPrompt`, which renders it with `prompts/get`.
+
+use std::collections::HashMap;
+
+use mcp_types::Prompt;
+
+use crate::bottom_pane::prompt_args::PromptExpansionError;
+use crate::bottom_pane::prompt_args::parse_prompt_inputs;
+use crate::bottom_pane::prompt_args::parse_slash_name;
+
+/// Prefix used for MCP prompts in the slash popup: `/mcp:<server>:<prompt>`.
+pub(crate) const MCP_PROMPT_CMD_PREFIX: &str = "mcp";
+
+/// A prompt published by an MCP server.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct McpPromptCommand {
+    pub(crate) server: String,
+    pub(crate) prompt: Prompt,
+}
+
+impl McpPromptCommand {
+    /// Flattens the per-server prompt lists, sorted by server then prompt name.
+    pub(crate) fn from_server_prompts(prompts: HashMap<String, Vec<Prompt>>) -> Vec<Self> {
+        let mut commands: Vec<Self> = prompts
+            .into_iter()
+            .flat_map(|(server, prompts)| {
+                prompts.into_iter().map(move |prompt| Self {
+                    server: server.clone(),
+                    prompt,
+                })
+            })
+            .collect();
+        commands.sort_by(|a, b| {
+            a.server
+                .cmp(&b.server)
+                .then_with(|| a.prompt.name.cmp(&b.prompt.name))
+        });
+        commands
+    }
+
+    /// Command name without the leading slash, e.g. `mcp:docs:review`.
+    pub(crate) fn command_name(&self) -> String {
+        format!(
+            "{MCP_PROMPT_CMD_PREFIX}:{}:{}",
+            self.server, self.prompt.name
+        )
+    }
+
+    pub(crate) fn description(&self) -> String {
+        self.prompt
+            .description
+            .clone()
+            .or_else(|| self.prompt.title.clone())
+            .unwrap_or_else(|| format!("send prompt from MCP server {}", self.server))
+    }
+
+    fn required_arguments(&self) -> Vec<String> {
+        self.prompt
+            .arguments
+            .iter()
+            .flatten()
+            .filter(|argument| argument.required.unwrap_or(false))
+            .map(|argument| argument.name.clone())
+            .collect()
+    }
+
+    /// Command text with an empty `name=""` placeholder for every required
+    /// argument, and the cursor position inside the first placeholder.
+    pub(crate) fn command_with_arg_placeholders(&self) -> (String, usize) {
+        let mut text = format!("/{}", self.command_name());
+        let mut cursor = text.len();
+        for (i, argument) in self.required_arguments().iter().enumerate() {
+            text.push_str(&format!(" {argument}=\"\""));
+            if i == 0 {
+                cursor = text.len() - 1;
+            }
+        }
+        (text, cursor)
+    }
+
+    pub(crate) fn has_required_arguments(&self) -> bool {
+        !self.required_arguments().is_empty()
+    }
+}
+
+/// An MCP prompt the user asked to run.
+#[derive(Debug, PartialEq)]
+pub(crate) struct McpPromptInvocation {
+    pub(crate) server: String,
+    pub(crate) name: String,
+    pub(crate) arguments: HashMap<String, String>,
+}
+
+/// Parses a message of the form `/mcp:<server>:<prompt> key=value …`.
+///
+/// Returns `Ok(None)` when the text does not name a known MCP prompt, and an
+/// error when the arguments cannot be parsed or a required one is missing.
+pub(crate) fn parse_mcp_prompt_invocation(
+    text: &str,
+    prompts: &[McpPromptCommand],
+) -> Result<Option<McpPromptInvocation>, PromptExpansionError> {
+    let Some((name, rest)) = parse_slash_name(text) else {
+        return Ok(None);
+    };
+    let Some(command) = prompts.iter().find(|p| p.command_name() == name) else {
+        return Ok(None);
+    };
+
+    let command_text = format!("/{name}");
+    let arguments = parse_prompt_inputs(rest).map_err(|error| PromptExpansionError::Args {
+        command: command_text.clone(),
+        error,
+    })?;
+    let missing: Vec<String> = command
+        .required_arguments()
+        .into_iter()
+        .filter(|argument| !arguments.contains_key(argument))
+        .collect();
+    if !missing.is_empty() {
+        return Err(PromptExpansionError::MissingArgs {
+            command: command_text,
+            missing,
+        });
+    }
+
+    Ok(Some(McpPromptInvocation {
+        server: command.server.clone(),
+        name: command.prompt.name.clone(),
+        arguments,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::PromptArgument;
+    use pretty_assertions::assert_eq;
+
+    fn review_prompt() -> McpPromptCommand {
+        McpPromptCommand {
+            server: "docs".to_string(),
+            prompt: Prompt {
+                arguments: Some(vec![
+                    PromptArgument {
+                        description: None,
+                        name: "topic".to_string(),
+                        required: Some(true),
+                        title: None,
+                    },
+                    PromptArgument {
+                        description: None,
+                        name: "depth".to_string(),
+                        required: None,
+                        title: None,
+                    },
+                ]),
+                description: None,
+                name: "review".to_string(),
+                title: None,
+            },
+        }
+    }
+
+    #[test]
+    fn parses_known_prompt_with_arguments() {
+        let invocation = parse_mcp_prompt_invocation(
+            "/mcp:docs:review topic=\"the parser\" depth=2",
+            &[review_prompt()],
+        )
+        .expect("valid invocation");
+        assert_eq!(
+            invocation,
+            Some(McpPromptInvocation {
+                server: "docs".to_string(),
+                name: "review".to_string(),
+                arguments: HashMap::from([
+                    ("topic".to_string(), "the parser".to_string()),
+                    ("depth".to_string(), "2".to_string()),
+                ]),
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_prompts() {
+        let invocation = parse_mcp_prompt_invocation("/mcp:docs:other topic=x", &[review_prompt()])
+            .expect("not an error");
+        assert_eq!(invocation, None);
+    }
+
+    #[test]
+    fn reports_missing_required_arguments() {
+        let err = parse_mcp_prompt_invocation("/mcp:docs:review depth=2", &[review_prompt()])
+            .expect_err("topic is required");
+        assert_eq!(
+            err.user_message(),
+            "Missing required args for /mcp:docs:review: topic. Provide as key=value (quote values with spaces)."
+        );
+    }
+
+    #[test]
+    fn placeholders_cover_required_arguments_only() {
+        assert_eq!(
+            review_prompt().command_with_arg_placeholders(),
+            ("/mcp:docs:review topic=\"\"".to_string(), 24)
+        );
+    }
+}
@@ -30,6 +30,7 @@ mod experimental_features_view;
 mod file_search_popup;
 mod footer;
 mod list_selection_view;
+mod mcp_prompts;
 mod prompt_args;
 mod skill_popup;
 pub(crate) use list_selection_view::SelectionViewParams;
@@ -38,6 +39,7 @@ pub(crate) use feedback_view::feedback_disabled_params;
 pub(crate) use feedback_view::feedback_selection_params;
 pub(crate) use feedback_view::feedback_upload_consent_params;
 mod paste_burst;
+mod patch_hunk_review;
 pub mod popup_consts;
 mod queued_user_messages;
 mod scroll_state;
@@ -55,6 +57,8 @@ pub(crate) enum CancellationEvent {
 pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
+pub(crate) use mcp_prompts::McpPromptCommand;
+pub(crate) use mcp_prompts::parse_mcp_prompt_invocation;
 
 use crate::status_indicator_widget::StatusIndicatorWidget;
 pub(crate) use experimental_features_view::BetaFeatureItem;
@@ -298,6 +302,18 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Forward an edited patch hunk to the approval overlay that asked for it.
+    pub(crate) fn apply_patch_hunk_edit(&mut self, edited: String) {
+        if let Some(view) = self.view_stack.last_mut() {
+            view.apply_patch_hunk_edit(edited);
+            if view.is_complete() {
+                self.view_stack.pop();
+                self.on_active_view_complete();
+            }
+        }
+        self.request_redraw();
+    }
+
     pub(crate) fn set_footer_hint_override(&mut self, items: Option<Vec<(String, String)>>) {
         self.composer.set_footer_hint_override(items);
         self.request_redraw();
@@ -443,6 +459,16 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Update MCP server prompts available for the slash popup.
+    pub(crate) fn set_mcp_prompts(&mut self, prompts: Vec<McpPromptCommand>) {
+        self.composer.set_mcp_prompts(prompts);
+        self.request_redraw();
+    }
+
+    pub(crate) fn mcp_prompts(&self) -> &[McpPromptCommand] {
+        self.composer.mcp_prompts()
+    }
+
     pub(crate) fn composer_is_empty(&self) -> bool {
         self.composer.is_empty()
     }
@@ -0,0 +1,497 @@
+//! Hunk-by-hunk review of a proposed patch, shown inside the approval
+//! overlay. Each hunk can be accepted, rejected or edited in `$VISUAL` /
+//! `$EDITOR`; the result is sent back as a single [`ReviewDecision`].
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_core::protocol::FileChange;
+use codex_core::protocol::PatchHunk;
+use codex_core::protocol::ReviewDecision;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Wrap;
+
+use crate::diff_render::DiffSummary;
+use crate::key_hint;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+
+const EDIT_INSTRUCTIONS: &str = "\
+# Edit the hunk above, then save and close the editor.
+# To drop a '+' line, delete it. To keep a '-' line, replace its '-' with ' '.
+# Do not change context (' ') or '-' lines otherwise.
+# Lines starting with '#' are removed; an empty hunk cancels the edit.
+";
+
+/// What the overlay should do after a key press during hunk review.
+#[derive(Debug, PartialEq)]
+pub(crate) enum HunkReviewAction {
+    None,
+    /// Open the editor seeded with this text; the result comes back through
+    /// [`PatchHunkReview::apply_edit`].
+    Edit(String),
+    /// Every hunk has a decision.
+    Finished,
+    /// Leave hunk review and return to the approval options.
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HunkDecision {
+    Accept,
+    Reject,
+}
+
+struct ReviewHunk {
+    path: PathBuf,
+    /// This hunk alone as a change to `path`. For updates, `unified_diff`
+    /// holds the hunk as it will be applied (edited, if the user edited it).
+    change: FileChange,
+    /// The hunk as proposed by the model, for updates.
+    proposed: Option<String>,
+    decision: Option<HunkDecision>,
+}
+
+impl ReviewHunk {
+    fn diff(&self) -> &str {
+        match &self.change {
+            FileChange::Update { unified_diff, .. } => unified_diff,
+            FileChange::Add { content } | FileChange::Delete { content } => content,
+        }
+    }
+
+    fn is_edited(&self) -> bool {
+        self.proposed
+            .as_deref()
+            .is_some_and(|proposed| proposed != self.diff())
+    }
+
+    /// The hunk as reported back to the model.
+    fn to_patch_hunk(&self, diff: &str) -> PatchHunk {
+        let diff = match &self.change {
+            FileChange::Add { content } => prefix_lines(content, '+'),
+            FileChange::Delete { content } => prefix_lines(content, '-'),
+            FileChange::Update { .. } => diff.to_string(),
+        };
+        PatchHunk {
+            path: self.path.clone(),
+            diff,
+        }
+    }
+}
+
+pub(crate) struct PatchHunkReview {
+    cwd: PathBuf,
+    hunks: Vec<ReviewHunk>,
+    current: usize,
+    error: Option<String>,
+    view: ColumnRenderable<'static>,
+}
+
+impl PatchHunkReview {
+    pub(crate) fn new(changes: &HashMap<PathBuf, FileChange>, cwd: PathBuf) -> Self {
+        let mut paths: Vec<&PathBuf> = changes.keys().collect();
+        paths.sort();
+
+        let mut hunks = Vec::new();
+        for path in paths {
+            match &changes[path] {
+                FileChange::Update {
+                    unified_diff,
+                    move_path,
+                } => {
+                    let mut file_hunks = split_hunks(unified_diff);
+                    if file_hunks.is_empty() {
+                        // A pure rename still needs a decision.
+                        file_hunks.push(String::new());
+                    }
+                    hunks.extend(file_hunks.into_iter().map(|hunk| ReviewHunk {
+                        path: path.clone(),
+                        change: FileChange::Update {
+                            unified_diff: hunk.clone(),
+                            move_path: move_path.clone(),
+                        },
+                        proposed: Some(hunk),
+                        decision: None,
+                    }));
+                }
+                change => hunks.push(ReviewHunk {
+                    path: path.clone(),
+                    change: change.clone(),
+                    proposed: None,
+                    decision: None,
+                }),
+            }
+        }
+
+        let mut review = Self {
+            cwd,
+            hunks,
+            current: 0,
+            error: None,
+            view: ColumnRenderable::new(),
+        };
+        review.refresh_view();
+        review
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) -> HunkReviewAction {
+        if key_event.kind != KeyEventKind::Press {
+            return HunkReviewAction::None;
+        }
+        let action = match key_event.code {
+            KeyCode::Char('y') => self.decide(HunkDecision::Accept),
+            KeyCode::Char('n') => self.decide(HunkDecision::Reject),
+            KeyCode::Char('e') => match self.hunks.get(self.current) {
+                Some(hunk) if hunk.proposed.as_deref().is_some_and(|p| !p.is_empty()) => {
+                    HunkReviewAction::Edit(format!("{}\n{EDIT_INSTRUCTIONS}", hunk.diff()))
+                }
+                _ => {
+                    self.error = Some("Only hunks of modified files can be edited.".to_string());
+                    HunkReviewAction::None
+                }
+            },
+            KeyCode::Char('b') | KeyCode::Left => {
+                self.current = self.current.saturating_sub(1);
+                self.error = None;
+                HunkReviewAction::None
+            }
+            KeyCode::Char('o') => HunkReviewAction::Cancel,
+            _ => return HunkReviewAction::None,
+        };
+        self.refresh_view();
+        action
+    }
+
+    /// Replaces the current hunk with the user's edit and accepts it. The edit
+    /// may only change added lines, so the hunk still applies where proposed.
+    pub(crate) fn apply_edit(&mut self, edited: String) -> HunkReviewAction {
+        let Some(hunk) = self.hunks.get_mut(self.current) else {
+            return HunkReviewAction::None;
+        };
+        let Some(proposed) = hunk.proposed.as_deref() else {
+            return HunkReviewAction::None;
+        };
+        let action = match normalize_edited_hunk(proposed, &edited) {
+            Ok(None) => {
+                self.error = None;
+                HunkReviewAction::None
+            }
+            Ok(Some(diff)) => {
+                if let FileChange::Update { unified_diff, .. } = &mut hunk.change {
+                    *unified_diff = diff;
+                }
+                self.decide(HunkDecision::Accept)
+            }
+            Err(err) => {
+                self.error = Some(err);
+                HunkReviewAction::None
+            }
+        };
+        self.refresh_view();
+        action
+    }
+
+    fn decide(&mut self, decision: HunkDecision) -> HunkReviewAction {
+        let Some(hunk) = self.hunks.get_mut(self.current) else {
+            return HunkReviewAction::None;
+        };
+        hunk.decision = Some(decision);
+        self.error = None;
+        match self.hunks.iter().position(|hunk| hunk.decision.is_none()) {
+            Some(next) => {
+                self.current = next;
+                HunkReviewAction::None
+            }
+            None => HunkReviewAction::Finished,
+        }
+    }
+
+    /// The decision to send once every hunk has been reviewed.
+    pub(crate) fn decision(&self) -> ReviewDecision {
+        let all_accepted = self
+            .hunks
+            .iter()
+            .all(|hunk| hunk.decision == Some(HunkDecision::Accept) && !hunk.is_edited());
+        if all_accepted {
+            return ReviewDecision::Approved;
+        }
+
+        let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
+        let mut rejected_hunks = Vec::new();
+        let mut edited_hunks = Vec::new();
+        for hunk in &self.hunks {
+            if hunk.decision != Some(HunkDecision::Accept) {
+                rejected_hunks.push(hunk.to_patch_hunk(hunk.proposed.as_deref().unwrap_or("")));
+                continue;
+            }
+            if hunk.is_edited() {
+                edited_hunks.push(hunk.to_patch_hunk(hunk.diff()));
+            }
+            match (changes.get_mut(&hunk.path), &hunk.change) {
+                (
+                    Some(FileChange::Update { unified_diff, .. }),
+                    FileChange::Update {
+                        unified_diff: diff, ..
+                    },
+                ) => unified_diff.push_str(diff),
+                _ => {
+                    changes.insert(hunk.path.clone(), hunk.change.clone());
+                }
+            }
+        }
+        ReviewDecision::ApprovedPartially {
+            changes,
+            rejected_hunks,
+            edited_hunks,
+        }
+    }
+
+    fn refresh_view(&mut self) {
+        let total = self.hunks.len();
+        let mut view = ColumnRenderable::new();
+        view.push(Line::from(
+            format!("Review hunk {} of {total}", (self.current + 1).min(total)).bold(),
+        ));
+        view.push(Line::from(""));
+        if let Some(hunk) = self.hunks.get(self.current) {
+            let changes = HashMap::from([(hunk.path.clone(), hunk.change.clone())]);
+            let diff: Box<dyn Renderable> = DiffSummary::new(changes, self.cwd.clone()).into();
+            view.push(diff);
+            if let Some(decision) = hunk.decision {
+                let label = match decision {
+                    HunkDecision::Accept if hunk.is_edited() => "Accepted (edited)",
+                    HunkDecision::Accept => "Accepted",
+                    HunkDecision::Reject => "Rejected",
+                };
+                view.push(Line::from(label.dim()));
+            }
+        }
+        if let Some(error) = &self.error {
+            view.push(Line::from(""));
+            view.push(Paragraph::new(Line::from(error.clone().red())).wrap(Wrap { trim: false }));
+        }
+        view.push(Line::from(""));
+        view.push(Line::from(vec![
+            key_hint::plain(KeyCode::Char('y')).into(),
+            " accept  ".into(),
+            key_hint::plain(KeyCode::Char('n')).into(),
+            " reject  ".into(),
+            key_hint::plain(KeyCode::Char('e')).into(),
+            " edit  ".into(),
+            key_hint::plain(KeyCode::Char('b')).into(),
+            " back  ".into(),
+            key_hint::plain(KeyCode::Char('o')).into(),
+            " all options  ".into(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " cancel".into(),
+        ]));
+        self.view = view;
+    }
+}
+
+impl Renderable for PatchHunkReview {
+    fn desired_height(&self, width: u16) -> u16 {
+        self.view.desired_height(width)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.view.render(area, buf);
+    }
+}
+
+/// Splits a unified diff into its hunks, each starting with its `@@` header.
+fn split_hunks(unified_diff: &str) -> Vec<String> {
+    let mut hunks: Vec<String> = Vec::new();
+    for line in unified_diff.lines() {
+        if line.starts_with("@@") {
+            hunks.push(String::new());
+        }
+        if let Some(hunk) = hunks.last_mut() {
+            hunk.push_str(line);
+            hunk.push('\n');
+        }
+    }
+    hunks
+}
+
+fn prefix_lines(content: &str, prefix: char) -> String {
+    content
+        .lines()
+        .map(|line| format!("{prefix}{line}\n"))
+        .collect()
+}
+
+/// Old side of a hunk body: its context and removed lines.
+fn old_side<'a>(body: &[&'a str]) -> Vec<&'a str> {
+    body.iter()
+        .filter(|line| !line.starts_with('+'))
+        .map(|line| line.get(1..).unwrap_or_default())
+        .collect()
+}
+
+/// Validates an edited hunk against the proposed one and rewrites its header
+/// to match the edited body. Returns `Ok(None)` when the edit was emptied.
+fn normalize_edited_hunk(proposed: &str, edited: &str) -> Result<Option<String>, String> {
+    let mut proposed_lines = proposed.lines();
+    let header = proposed_lines.next().unwrap_or_default();
+    let proposed_body: Vec<&str> = proposed_lines.filter(|l| !l.starts_with('\\')).collect();
+
+    let mut edited_body: Vec<&str> = edited
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.starts_with("@@"))
+        .filter(|line| !line.starts_with('\\'))
+        .collect();
+    while edited_body.last().is_some_and(|line| line.is_empty()) {
+        edited_body.pop();
+    }
+    if edited_body.is_empty() {
+        return Ok(None);
+    }
+    if let Some(line) = edited_body
+        .iter()
+        .find(|line| !line.is_empty() && !line.starts_with([' ', '-', '+']))
+    {
+        return Err(format!(
+            "Edited hunk has a line without a ' ', '-' or '+' prefix: {line}"
+        ));
+    }
+    if old_side(&edited_body) != old_side(&proposed_body) {
+        return Err(
+            "Edited hunk changes lines of the original file; only '+' lines may be edited."
+                .to_string(),
+        );
+    }
+
+    let (old_start, new_start) = parse_hunk_starts(header)
+        .ok_or_else(|| format!("Cannot edit a hunk with header `{header}`"))?;
+    let old_len = old_side(&edited_body).len();
+    let new_len = edited_body
+        .iter()
+        .filter(|line| !line.starts_with('-'))
+        .count();
+    let mut hunk = format!("@@ -{old_start},{old_len} +{new_start},{new_len} @@\n");
+    for line in edited_body {
+        hunk.push_str(if line.is_empty() { " " } else { line });
+        hunk.push('\n');
+    }
+    Ok(Some(hunk))
+}
+
+/// Start lines of the old and new side from a `@@ -a,b +c,d @@` header.
+fn parse_hunk_starts(header: &str) -> Option<(usize, usize)> {
+    let mut tokens = header.strip_prefix("@@")?.split_whitespace();
+    let start = |token: Option<&str>, sign: char| -> Option<usize> {
+        token?.strip_prefix(sign)?.split(',').next()?.parse().ok()
+    };
+    let old_start = start(tokens.next(), '-')?;
+    let new_start = start(tokens.next(), '+')?;
+    Some((old_start, new_start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn changes() -> HashMap<PathBuf, FileChange> {
+        HashMap::from([
+            (
+                PathBuf::from("/repo/a.txt"),
+                FileChange::Update {
+                    unified_diff: "@@ -1,2 +1,2 @@\n-one\n+uno\n two\n@@ -9 +9 @@\n-nine\n+nueve\n"
+                        .to_string(),
+                    move_path: None,
+                },
+            ),
+            (
+                PathBuf::from("/repo/b.txt"),
+                FileChange::Add {
+                    content: "new\n".to_string(),
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn accepting_every_hunk_approves_the_patch() {
+        let mut review = PatchHunkReview::new(&changes(), PathBuf::from("/repo"));
+        assert_eq!(review.handle_key_event(key('y')), HunkReviewAction::None);
+        assert_eq!(review.handle_key_event(key('y')), HunkReviewAction::None);
+        assert_eq!(
+            review.handle_key_event(key('y')),
+            HunkReviewAction::Finished
+        );
+        assert_eq!(review.decision(), ReviewDecision::Approved);
+    }
+
+    #[test]
+    fn rejected_and_edited_hunks_are_reported() {
+        let mut review = PatchHunkReview::new(&changes(), PathBuf::from("/repo"));
+        review.handle_key_event(key('n'));
+        let HunkReviewAction::Edit(seed) = review.handle_key_event(key('e')) else {
+            panic!("expected an edit");
+        };
+        assert!(seed.starts_with("@@ -9 +9 @@\n-nine\n+nueve\n"));
+        let edited = seed.replace("+nueve", "+neun\n+nine");
+        assert_eq!(review.apply_edit(edited), HunkReviewAction::None);
+        assert_eq!(
+            review.handle_key_event(key('y')),
+            HunkReviewAction::Finished
+        );
+
+        assert_eq!(
+            review.decision(),
+            ReviewDecision::ApprovedPartially {
+                changes: HashMap::from([
+                    (
+                        PathBuf::from("/repo/a.txt"),
+                        FileChange::Update {
+                            unified_diff: "@@ -9,1 +9,2 @@\n-nine\n+neun\n+nine\n".to_string(),
+                            move_path: None,
+                        },
+                    ),
+                    (
+                        PathBuf::from("/repo/b.txt"),
+                        FileChange::Add {
+                            content: "new\n".to_string(),
+                        },
+                    ),
+                ]),
+                rejected_hunks: vec![PatchHunk {
+                    path: PathBuf::from("/repo/a.txt"),
+                    diff: "@@ -1,2 +1,2 @@\n-one\n+uno\n two\n".to_string(),
+                }],
+                edited_hunks: vec![PatchHunk {
+                    path: PathBuf::from("/repo/a.txt"),
+                    diff: "@@ -9,1 +9,2 @@\n-nine\n+neun\n+nine\n".to_string(),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn edits_may_not_change_the_original_lines() {
+        let mut review = PatchHunkReview::new(&changes(), PathBuf::from("/repo"));
+        let HunkReviewAction::Edit(seed) = review.handle_key_event(key('e')) else {
+            panic!("expected an edit");
+        };
+        let action = review.apply_edit(seed.replace("-one", "-eins"));
+
+        assert_eq!(action, HunkReviewAction::None);
+        assert!(review.error.is_some());
+        assert_eq!(review.hunks[0].decision, None);
+    }
+}
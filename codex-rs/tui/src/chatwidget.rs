@@ -36,6 +36,7 @@ use codex_core::features::FEATURES;
 use codex_core::features::Feature;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
+use codex_core::memory::MemoryStore;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use codex_core::protocol::AgentMessageDeltaEvent;
@@ -57,6 +58,7 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
+use codex_core::protocol::ListMcpPromptsResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
 use codex_core::protocol::McpStartupCompleteEvent;
@@ -64,6 +66,8 @@ use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::McpToolCallProgressEvent;
+use codex_core::protocol::McpToolsChangedEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::RateLimitSnapshot;
@@ -118,10 +122,12 @@ use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::ExperimentalFeaturesView;
 use crate::bottom_pane::InputResult;
+use crate::bottom_pane::McpPromptCommand;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
+use crate::bottom_pane::parse_mcp_prompt_invocation;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::diff_render::display_path_for;
@@ -419,6 +425,10 @@ pub(crate) struct ChatWidget {
     feedback: codex_feedback::CodexFeedback,
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
+    // Unified diff of the latest turn's file changes, shown by `/changes`.
+    last_turn_diff: Option<String>,
+    // Notes for the model sent ahead of the next user message.
+    pending_model_notes: Vec<String>,
     external_editor_state: ExternalEditorState,
 }
 
@@ -880,11 +890,25 @@ impl ChatWidget {
         }
 
         self.mcp_startup_status = None;
+        // Servers are now connected, so their prompts can be offered as slash commands.
+        self.submit_op(Op::ListMcpPrompts);
         self.update_task_running_state();
         self.maybe_send_next_queued_input();
         self.request_redraw();
     }
 
+    fn on_mcp_tools_changed(&mut self, ev: McpToolsChangedEvent) {
+        self.add_to_history(history_cell::new_info_event(
+            format!(
+                "MCP server `{}` updated its tools ({} available)",
+                ev.server,
+                ev.tools.len()
+            ),
+            Some("Changes apply from the next turn.".to_string()),
+        ));
+        self.request_redraw();
+    }
+
     /// Handle a turn aborted due to user interrupt (Esc).
     /// When there are queued user messages, restore them into the composer
     /// separated by newlines rather than auto‑submitting the next one.
@@ -1114,6 +1138,24 @@ impl ChatWidget {
         self.defer_or_handle(|q| q.push_mcp_begin(ev), |s| s.handle_mcp_begin_now(ev2));
     }
 
+    /// Progress only updates the running call's cell; updates that arrive
+    /// while its begin event is still deferred are dropped.
+    fn on_mcp_tool_call_progress(&mut self, ev: McpToolCallProgressEvent) {
+        let Some(cell) = self
+            .active_cell
+            .as_mut()
+            .and_then(|cell| cell.as_any_mut().downcast_mut::<McpToolCallCell>())
+        else {
+            return;
+        };
+        if cell.call_id() != ev.call_id {
+            return;
+        }
+        cell.update_progress(ev.progress, ev.total, ev.message);
+        self.bump_active_cell_revision();
+        self.request_redraw();
+    }
+
     fn on_mcp_tool_call_end(&mut self, ev: McpToolCallEndEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(|q| q.push_mcp_end(ev), |s| s.handle_mcp_end_now(ev2));
@@ -1147,6 +1189,17 @@ impl ChatWidget {
 
     fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
+        self.last_turn_diff = (!unified_diff.is_empty()).then_some(unified_diff);
+    }
+
+    /// A file was reverted from the diff viewer: drop it from `/changes` and
+    /// let the model know with the next message, so it does not assume its
+    /// edits are still there.
+    pub(crate) fn on_diff_file_reverted(&mut self, path: String, remaining_diff: String) {
+        self.last_turn_diff = (!remaining_diff.is_empty()).then_some(remaining_diff);
+        self.pending_model_notes.push(format!(
+            "The user reverted your changes to {path}; it is back to its state before the last turn."
+        ));
     }
 
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
@@ -1575,6 +1628,8 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            last_turn_diff: None,
+            pending_model_notes: Vec::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -1666,6 +1721,8 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            last_turn_diff: None,
+            pending_model_notes: Vec::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -1794,6 +1851,11 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    pub(crate) fn apply_patch_hunk_edit(&mut self, edited: String) {
+        self.bottom_pane.apply_patch_hunk_edit(edited);
+        self.request_redraw();
+    }
+
     pub(crate) fn external_editor_state(&self) -> ExternalEditorState {
         self.external_editor_state
     }
@@ -1942,6 +2004,17 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Changes => {
+                if let Some(unified_diff) = &self.last_turn_diff {
+                    self.app_event_tx
+                        .send(AppEvent::OpenDiffViewer(unified_diff.clone()));
+                } else {
+                    self.add_info_message(
+                        "No changes have been made in this session yet.".to_string(),
+                        None,
+                    );
+                }
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
@@ -1957,6 +2030,9 @@ impl ChatWidget {
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
+            SlashCommand::Memories => {
+                self.add_memories_output();
+            }
             SlashCommand::Rollout => {
                 if let Some(path) = self.rollout_path() {
                     self.add_info_message(
@@ -2028,6 +2104,7 @@ impl ChatWidget {
                             instructions: trimmed.to_string(),
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 });
             }
@@ -2148,21 +2225,37 @@ impl ChatWidget {
             return;
         }
 
+        for note in self.pending_model_notes.drain(..) {
+            items.push(UserInput::Text { text: note });
+        }
+
         for path in image_paths {
             items.push(UserInput::LocalImage { path });
         }
 
-        if !text.is_empty() {
-            items.push(UserInput::Text { text: text.clone() });
-        }
+        // The composer already validated the arguments; core renders the prompt,
+        // so the slash command text itself is not sent to the model.
+        if let Ok(Some(invocation)) =
+            parse_mcp_prompt_invocation(&text, self.bottom_pane.mcp_prompts())
+        {
+            items.push(UserInput::McpPrompt {
+                server: invocation.server,
+                name: invocation.name,
+                arguments: invocation.arguments,
+            });
+        } else {
+            if !text.is_empty() {
+                items.push(UserInput::Text { text: text.clone() });
+            }
 
-        if let Some(skills) = self.bottom_pane.skills() {
-            let skill_mentions = find_skill_mentions(&text, skills);
-            for skill in skill_mentions {
-                items.push(UserInput::Skill {
-                    name: skill.name.clone(),
-                    path: skill.path.clone(),
-                });
+            if let Some(skills) = self.bottom_pane.skills() {
+                let skill_mentions = find_skill_mentions(&text, skills);
+                for skill in skill_mentions {
+                    items.push(UserInput::Skill {
+                        name: skill.name.clone(),
+                        path: skill.path.clone(),
+                    });
+                }
             }
         }
 
@@ -2261,6 +2354,7 @@ impl ChatWidget {
             EventMsg::Error(ErrorEvent { message, .. }) => self.on_error(message),
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
+            EventMsg::McpToolsChanged(ev) => self.on_mcp_tools_changed(ev),
             EventMsg::TurnAborted(ev) => match ev.reason {
                 TurnAbortReason::Interrupted => {
                     self.on_interrupted_turn(ev.reason);
@@ -2291,12 +2385,14 @@ impl ChatWidget {
             EventMsg::ExecCommandEnd(ev) => self.on_exec_command_end(ev),
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
+            EventMsg::McpToolCallProgress(ev) => self.on_mcp_tool_call_progress(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::ListMcpPromptsResponse(ev) => self.on_list_mcp_prompts(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
             EventMsg::SkillsUpdateAvailable => {
                 self.submit_op(Op::ListSkills {
@@ -3780,6 +3876,17 @@ impl ChatWidget {
         }
     }
 
+    pub(crate) fn add_memories_output(&mut self) {
+        let store = MemoryStore::for_project(&self.config.codex_home, &self.config.cwd);
+        match store.list() {
+            Ok(memories) => self.add_to_history(history_cell::new_memories_output(
+                store.project(),
+                &memories,
+            )),
+            Err(err) => self.add_error_message(format!("Failed to read memories: {err:#}")),
+        }
+    }
+
     /// Forward file-search results to the bottom pane.
     pub(crate) fn apply_file_search_result(&mut self, query: String, matches: Vec<FileMatch>) {
         self.bottom_pane.on_file_search_result(query, matches);
@@ -3856,6 +3963,12 @@ impl ChatWidget {
         self.bottom_pane.set_custom_prompts(ev.custom_prompts);
     }
 
+    fn on_list_mcp_prompts(&mut self, ev: ListMcpPromptsResponseEvent) {
+        let prompts = McpPromptCommand::from_server_prompts(ev.prompts);
+        debug!("received {} MCP prompts", prompts.len());
+        self.bottom_pane.set_mcp_prompts(prompts);
+    }
+
     fn on_list_skills(&mut self, ev: ListSkillsResponseEvent) {
         self.set_skills_from_response(&ev);
     }
@@ -3883,6 +3996,7 @@ impl ChatWidget {
                     review_request: ReviewRequest {
                         target: ReviewTarget::UncommittedChanges,
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             })],
@@ -3938,6 +4052,7 @@ impl ChatWidget {
                                 branch: branch.clone(),
                             },
                             user_facing_hint: None,
+                            paths: Vec::new(),
                         },
                     }));
                 })],
@@ -3976,6 +4091,7 @@ impl ChatWidget {
                                 title: Some(subject.clone()),
                             },
                             user_facing_hint: None,
+                            paths: Vec::new(),
                         },
                     }));
                 })],
@@ -4012,6 +4128,7 @@ impl ChatWidget {
                             instructions: trimmed,
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             }),
@@ -4258,6 +4375,7 @@ pub(crate) fn show_review_commit_picker_with_entries(
                             title: Some(subject.clone()),
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             })],
@@ -189,6 +189,7 @@ async fn entered_review_mode_uses_request_hint() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            paths: Vec::new(),
         }),
     });
 
@@ -208,6 +209,7 @@ async fn entered_review_mode_defaults_to_current_changes_banner() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            paths: Vec::new(),
         }),
     });
 
@@ -242,6 +244,7 @@ async fn review_restores_context_window_indicator() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            paths: Vec::new(),
         }),
     });
 
@@ -435,6 +438,8 @@ async fn make_chatwidget_manual(
         last_rendered_width: std::cell::Cell::new(None),
         feedback: codex_feedback::CodexFeedback::new(),
         current_rollout_path: None,
+        last_turn_diff: None,
+        pending_model_notes: Vec::new(),
         external_editor_state: ExternalEditorState::Closed,
     };
     (widget, rx, op_rx)
@@ -1118,6 +1123,72 @@ async fn ctrl_c_shutdown_ignores_caps_lock() {
     }
 }
 
+#[tokio::test]
+async fn mcp_prompt_submission_sends_only_the_prompt_item() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.bottom_pane.set_mcp_prompts(vec![McpPromptCommand {
+        server: "docs".to_string(),
+        prompt: mcp_types::Prompt {
+            arguments: Some(vec![mcp_types::PromptArgument {
+                description: None,
+                name: "topic".to_string(),
+                required: Some(true),
+                title: None,
+            }]),
+            description: None,
+            name: "review".to_string(),
+            title: None,
+        },
+    }]);
+
+    chat.submit_user_message(UserMessage {
+        text: "/mcp:docs:review topic=\"the parser\"".to_string(),
+        image_paths: Vec::new(),
+    });
+
+    match op_rx.try_recv() {
+        Ok(Op::UserInput { items, .. }) => assert_eq!(
+            items,
+            vec![UserInput::McpPrompt {
+                server: "docs".to_string(),
+                name: "review".to_string(),
+                arguments: HashMap::from([("topic".to_string(), "the parser".to_string())]),
+            }]
+        ),
+        other => panic!("expected Op::UserInput, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn reverted_file_updates_changes_and_notifies_model() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.last_turn_diff = Some("diff --git a/a.rs b/a.rs\n".to_string());
+
+    chat.on_diff_file_reverted("a.rs".to_string(), String::new());
+    assert_eq!(chat.last_turn_diff, None);
+
+    chat.submit_user_message(UserMessage {
+        text: "keep going".to_string(),
+        image_paths: Vec::new(),
+    });
+    match op_rx.try_recv() {
+        Ok(Op::UserInput { items, .. }) => assert_eq!(
+            items,
+            vec![
+                UserInput::Text {
+                    text: "The user reverted your changes to a.rs; it is back to its state before the last turn."
+                        .to_string(),
+                },
+                UserInput::Text {
+                    text: "keep going".to_string(),
+                },
+            ]
+        ),
+        other => panic!("expected Op::UserInput, got {other:?}"),
+    }
+    assert!(chat.pending_model_notes.is_empty());
+}
+
 #[tokio::test]
 async fn ctrl_c_cleared_prompt_is_recoverable_via_history() {
     let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
@@ -1765,6 +1836,7 @@ async fn custom_prompt_submit_sends_review_op() {
                         instructions: "please audit dependencies".to_string(),
                     },
                     user_facing_hint: None,
+                    paths: Vec::new(),
                 }
             );
         }
@@ -3915,6 +3987,7 @@ async fn review_queues_user_messages_snapshot() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: Some("current changes".to_string()),
+            paths: Vec::new(),
         }),
     });
     let _ = drain_insert_history(&mut rx);
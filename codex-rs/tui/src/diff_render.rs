@@ -12,12 +12,17 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::color::blend;
 use crate::exec_command::relativize_to_home;
 use crate::render::Insets;
+use crate::render::highlight::Language;
+use crate::render::highlight::highlight_code_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
+use crate::terminal_palette::best_color;
+use crate::terminal_palette::default_bg;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::protocol::FileChange;
 
@@ -186,22 +191,31 @@ fn render_changes_block(rows: Vec<Row>, wrap_cols: usize, cwd: &Path) -> Vec<RtL
         }
 
         let mut lines = vec![];
-        render_change(&r.change, &mut lines, wrap_cols - 4);
+        let language = Language::from_path(r.move_path.as_ref().unwrap_or(&r.path));
+        render_change(&r.change, language, &mut lines, wrap_cols - 4);
         out.extend(prefix_lines(lines, "    ".into(), "    ".into()));
     }
 
     out
 }
 
-fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usize) {
+fn render_change(
+    change: &FileChange,
+    language: Option<Language>,
+    out: &mut Vec<RtLine<'static>>,
+    width: usize,
+) {
     match change {
         FileChange::Add { content } => {
             let line_number_width = line_number_width(content.lines().count());
+            let highlighted =
+                language.and_then(|language| highlight_code_to_lines(content, language));
             for (i, raw) in content.lines().enumerate() {
                 out.extend(push_wrapped_diff_line(
                     i + 1,
                     DiffLineType::Insert,
                     raw,
+                    highlighted.as_ref().and_then(|lines| lines.get(i)),
                     width,
                     line_number_width,
                 ));
@@ -209,11 +223,14 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
         }
         FileChange::Delete { content } => {
             let line_number_width = line_number_width(content.lines().count());
+            let highlighted =
+                language.and_then(|language| highlight_code_to_lines(content, language));
             for (i, raw) in content.lines().enumerate() {
                 out.extend(push_wrapped_diff_line(
                     i + 1,
                     DiffLineType::Delete,
                     raw,
+                    highlighted.as_ref().and_then(|lines| lines.get(i)),
                     width,
                     line_number_width,
                 ));
@@ -253,6 +270,17 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                     }
                     is_first_hunk = false;
 
+                    let (old_highlighted, new_highlighted) = highlight_hunk(h, language);
+                    let old_syntax = |ln: usize| {
+                        old_highlighted
+                            .as_ref()
+                            .and_then(|lines| lines.get(ln - h.old_range().start()))
+                    };
+                    let new_syntax = |ln: usize| {
+                        new_highlighted
+                            .as_ref()
+                            .and_then(|lines| lines.get(ln - h.new_range().start()))
+                    };
                     let mut old_ln = h.old_range().start();
                     let mut new_ln = h.new_range().start();
                     for l in h.lines() {
@@ -263,6 +291,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Insert,
                                     s,
+                                    new_syntax(new_ln),
                                     width,
                                     line_number_width,
                                 ));
@@ -274,6 +303,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     old_ln,
                                     DiffLineType::Delete,
                                     s,
+                                    old_syntax(old_ln),
                                     width,
                                     line_number_width,
                                 ));
@@ -285,6 +315,7 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
                                     new_ln,
                                     DiffLineType::Context,
                                     s,
+                                    new_syntax(new_ln),
                                     width,
                                     line_number_width,
                                 ));
@@ -299,6 +330,40 @@ fn render_change(change: &FileChange, out: &mut Vec<RtLine<'static>>, width: usi
     }
 }
 
+/// Highlights the old and new side of a hunk separately so multi-line
+/// constructs (strings, block comments) keep their context. Line `i` of each
+/// side corresponds to the `i`-th line of that side in the hunk.
+fn highlight_hunk(
+    hunk: &Hunk<'_, str>,
+    language: Option<Language>,
+) -> (Option<Vec<RtLine<'static>>>, Option<Vec<RtLine<'static>>>) {
+    let Some(language) = language else {
+        return (None, None);
+    };
+    let mut old = String::new();
+    let mut new = String::new();
+    for l in hunk.lines() {
+        let (text, to_old, to_new) = match l {
+            diffy::Line::Insert(text) => (text, false, true),
+            diffy::Line::Delete(text) => (text, true, false),
+            diffy::Line::Context(text) => (text, true, true),
+        };
+        let text = text.trim_end_matches('\n');
+        if to_old {
+            old.push_str(text);
+            old.push('\n');
+        }
+        if to_new {
+            new.push_str(text);
+            new.push('\n');
+        }
+    }
+    (
+        highlight_code_to_lines(&old, language),
+        highlight_code_to_lines(&new, language),
+    )
+}
+
 /// Format a path for display relative to the current working directory when
 /// possible, keeping output stable in jj/no-`.git` workspaces (e.g. image
 /// tool calls should show `example.png` instead of an absolute path).
@@ -346,11 +411,11 @@ fn push_wrapped_diff_line(
     line_number: usize,
     kind: DiffLineType,
     text: &str,
+    syntax: Option<&RtLine<'static>>,
     width: usize,
     line_number_width: usize,
 ) -> Vec<RtLine<'static>> {
     let ln_str = line_number.to_string();
-    let mut remaining_text: &str = text;
 
     // Reserve a fixed number of spaces (equal to the widest line number plus a
     // trailing spacer) so the sign column stays aligned across the diff block.
@@ -358,11 +423,30 @@ fn push_wrapped_diff_line(
     let prefix_cols = gutter_width + 1;
 
     let mut first = true;
-    let (sign_char, line_style) = match kind {
+    let (sign_char, sign_style) = match kind {
         DiffLineType::Insert => ('+', style_add()),
         DiffLineType::Delete => ('-', style_del()),
         DiffLineType::Context => (' ', style_context()),
     };
+    // Syntax colors sit underneath the add/delete style: with a known terminal
+    // background the line is tinted and tokens keep their colors, otherwise the
+    // add/delete color wins and only syntax modifiers (e.g. dim comments) show.
+    let highlighted = syntax_spans(syntax, text);
+    let line_style = match highlighted {
+        Some(_) => style_highlighted_line(&kind),
+        None => sign_style,
+    };
+    let sign_style = line_style.patch(sign_style);
+    let mut remaining_spans: Vec<RtSpan<'static>> = match highlighted {
+        Some(spans) => spans
+            .into_iter()
+            .map(|span| {
+                let style = span.style.patch(line_style);
+                span.style(style)
+            })
+            .collect(),
+        None => vec![RtSpan::styled(text.to_string(), line_style)],
+    };
     let mut lines: Vec<RtLine<'static>> = Vec::new();
 
     loop {
@@ -370,39 +454,73 @@ fn push_wrapped_diff_line(
         // compute how many columns are available after the prefix, then split
         // at a UTF-8 character boundary so this row's chunk fits exactly.
         let available_content_cols = width.saturating_sub(prefix_cols + 1).max(1);
-        let split_at_byte_index = remaining_text
-            .char_indices()
-            .nth(available_content_cols)
-            .map(|(i, _)| i)
-            .unwrap_or_else(|| remaining_text.len());
-        let (chunk, rest) = remaining_text.split_at(split_at_byte_index);
-        remaining_text = rest;
+        let (chunk, rest) = split_spans_at(remaining_spans, available_content_cols);
+        remaining_spans = rest;
 
         if first {
             // Build gutter (right-aligned line number plus spacer) as a dimmed span
             let gutter = format!("{ln_str:>gutter_width$} ");
-            // Content with a sign ('+'/'-'/' ') styled per diff kind
-            let content = format!("{sign_char}{chunk}");
-            lines.push(RtLine::from(vec![
+            // Content follows a sign ('+'/'-'/' ') styled per diff kind
+            let mut spans = vec![
                 RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(content, line_style),
-            ]));
+                RtSpan::styled(sign_char.to_string(), sign_style),
+            ];
+            spans.extend(chunk);
+            lines.push(RtLine::from(spans));
             first = false;
         } else {
             // Continuation lines keep a space for the sign column so content aligns
             let gutter = format!("{:gutter_width$}  ", "");
-            lines.push(RtLine::from(vec![
-                RtSpan::styled(gutter, style_gutter()),
-                RtSpan::styled(chunk.to_string(), line_style),
-            ]));
+            let mut spans = vec![RtSpan::styled(gutter, style_gutter())];
+            spans.extend(chunk);
+            lines.push(RtLine::from(spans));
         }
-        if remaining_text.is_empty() {
+        if remaining_spans.is_empty() {
             break;
         }
     }
     lines
 }
 
+/// The highlighted spans of `syntax` when they reproduce `text` exactly.
+fn syntax_spans(syntax: Option<&RtLine<'static>>, text: &str) -> Option<Vec<RtSpan<'static>>> {
+    let syntax = syntax?;
+    let content: String = syntax.spans.iter().map(|s| s.content.as_ref()).collect();
+    (content == text).then(|| syntax.spans.clone())
+}
+
+/// Splits `spans` after `max_chars` characters, keeping each span's style.
+fn split_spans_at(
+    spans: Vec<RtSpan<'static>>,
+    max_chars: usize,
+) -> (Vec<RtSpan<'static>>, Vec<RtSpan<'static>>) {
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    let mut remaining = max_chars;
+    for span in spans {
+        if span.content.is_empty() {
+            continue;
+        }
+        if !tail.is_empty() || remaining == 0 {
+            tail.push(span);
+            continue;
+        }
+        match span.content.char_indices().nth(remaining) {
+            Some((split_at, _)) => {
+                let (left, right) = span.content.split_at(split_at);
+                head.push(RtSpan::styled(left.to_string(), span.style));
+                tail.push(RtSpan::styled(right.to_string(), span.style));
+                remaining = 0;
+            }
+            None => {
+                remaining -= span.content.chars().count();
+                head.push(span);
+            }
+        }
+    }
+    (head, tail)
+}
+
 fn line_number_width(max_line_number: usize) -> usize {
     if max_line_number == 0 {
         1
@@ -427,6 +545,19 @@ fn style_del() -> Style {
     Style::default().fg(Color::Red)
 }
 
+/// Style layered over syntax-highlighted content of a diff line.
+fn style_highlighted_line(kind: &DiffLineType) -> Style {
+    let (base, tint) = match kind {
+        DiffLineType::Insert => (style_add(), (0, 255, 0)),
+        DiffLineType::Delete => (style_del(), (255, 0, 0)),
+        DiffLineType::Context => return style_context(),
+    };
+    match default_bg() {
+        Some(bg) => Style::default().bg(best_color(blend(tint, bg, 0.15))),
+        None => base,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -497,8 +628,14 @@ mod tests {
         let long_line = "this is a very long line that should wrap across multiple terminal columns and continue";
 
         // Call the wrapping function directly so we can precisely control the width
-        let lines =
-            push_wrapped_diff_line(1, DiffLineType::Insert, long_line, 80, line_number_width(1));
+        let lines = push_wrapped_diff_line(
+            1,
+            DiffLineType::Insert,
+            long_line,
+            None,
+            80,
+            line_number_width(1),
+        );
 
         // Render into a small terminal to capture the visual layout
         snapshot_lines("wrap_behavior_insert", lines, 90, 8);
@@ -694,4 +831,39 @@ mod tests {
 
         snapshot_lines("apply_update_block_relativizes_path", lines, 80, 10);
     }
+
+    #[test]
+    fn update_block_highlights_syntax_under_diff_styles() {
+        let original = "fn one() {}\nlet a = 1;\n";
+        let modified = "fn one() {}\nlet a = \"two\";\n";
+        let patch = diffy::create_patch(original, modified).to_string();
+
+        let mut changes: HashMap<PathBuf, FileChange> = HashMap::new();
+        changes.insert(
+            PathBuf::from("lib.rs"),
+            FileChange::Update {
+                unified_diff: patch,
+                move_path: None,
+            },
+        );
+
+        let lines = diff_summary_for_tests(&changes);
+        let span = |text: &str| {
+            lines
+                .iter()
+                .flat_map(|line| line.spans.iter())
+                .find(|span| span.content == text)
+                .unwrap_or_else(|| panic!("missing span `{text}`"))
+                .clone()
+        };
+
+        // Context lines take the syntax colors directly.
+        assert_eq!(span("fn").style.fg, Some(Color::Magenta));
+        // Without a known terminal background the add/delete color stays on
+        // top of the syntax colors.
+        assert_eq!(span("\"two\"").style.fg, Some(Color::Green));
+        assert_eq!(span("1").style.fg, Some(Color::Red));
+
+        snapshot_lines_text("apply_update_block_syntax_highlighted_text", &lines);
+    }
 }
@@ -0,0 +1,1148 @@
+//! Full-screen viewer for the unified diff produced by a turn.
+//!
+//! Opened with `/changes`, it lists the files touched by the latest turn next
+//! to the selected file's diff, rendered side by side or unified with
+//! word-level highlighting of changed lines. A single file's changes can be
+//! reverted from the viewer.
+
+use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_core::git_info::get_git_repo_root;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Widget;
+use ratatui::widgets::WidgetRef;
+use unicode_width::UnicodeWidthChar;
+use unicode_width::UnicodeWidthStr;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::history_cell;
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::pager_overlay::render_key_hints;
+use crate::tui;
+use crate::tui::TuiEvent;
+
+const KEY_UP: KeyBinding = key_hint::plain(KeyCode::Up);
+const KEY_DOWN: KeyBinding = key_hint::plain(KeyCode::Down);
+const KEY_K: KeyBinding = key_hint::plain(KeyCode::Char('k'));
+const KEY_J: KeyBinding = key_hint::plain(KeyCode::Char('j'));
+const KEY_LEFT: KeyBinding = key_hint::plain(KeyCode::Left);
+const KEY_RIGHT: KeyBinding = key_hint::plain(KeyCode::Right);
+const KEY_H: KeyBinding = key_hint::plain(KeyCode::Char('h'));
+const KEY_L: KeyBinding = key_hint::plain(KeyCode::Char('l'));
+const KEY_PAGE_UP: KeyBinding = key_hint::plain(KeyCode::PageUp);
+const KEY_PAGE_DOWN: KeyBinding = key_hint::plain(KeyCode::PageDown);
+const KEY_SPACE: KeyBinding = key_hint::plain(KeyCode::Char(' '));
+const KEY_HOME: KeyBinding = key_hint::plain(KeyCode::Home);
+const KEY_END: KeyBinding = key_hint::plain(KeyCode::End);
+const KEY_TAB: KeyBinding = key_hint::plain(KeyCode::Tab);
+const KEY_BACK_TAB: KeyBinding = key_hint::plain(KeyCode::BackTab);
+const KEY_SHIFT_BACK_TAB: KeyBinding = key_hint::shift(KeyCode::BackTab);
+const KEY_N: KeyBinding = key_hint::plain(KeyCode::Char('n'));
+const KEY_P: KeyBinding = key_hint::plain(KeyCode::Char('p'));
+const KEY_S: KeyBinding = key_hint::plain(KeyCode::Char('s'));
+const KEY_R: KeyBinding = key_hint::plain(KeyCode::Char('r'));
+const KEY_Q: KeyBinding = key_hint::plain(KeyCode::Char('q'));
+const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
+const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+
+const NAVIGATION_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
+    (&[KEY_UP, KEY_DOWN], "to scroll"),
+    (&[KEY_LEFT, KEY_RIGHT], "to pan"),
+    (&[KEY_N, KEY_P], "to change file"),
+];
+
+const ACTION_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
+    (&[KEY_S], "to toggle side-by-side"),
+    (&[KEY_R], "to revert file"),
+    (&[KEY_Q], "to quit"),
+];
+
+/// Columns moved per horizontal pan step.
+const PAN_STEP: usize = 8;
+
+/// Token-pair budget for the word diff; longer line pairs are shown without
+/// intra-line highlighting.
+const MAX_WORD_DIFF_CELLS: usize = 40_000;
+
+/// The file list is hidden when the terminal is narrower than this.
+const MIN_WIDTH_FOR_FILE_LIST: u16 = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiffLayout {
+    SideBySide,
+    Unified,
+}
+
+/// One file section of a `diff --git` style unified diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DiffFile {
+    /// Path before the turn; `None` for added files.
+    old_path: Option<String>,
+    /// Path after the turn; `None` for deleted files.
+    new_path: Option<String>,
+    /// The file's section of the diff, starting at its `diff --git` line.
+    patch: String,
+    binary: bool,
+    added: usize,
+    removed: usize,
+}
+
+impl DiffFile {
+    fn display_path(&self) -> String {
+        match (&self.old_path, &self.new_path) {
+            (Some(old), Some(new)) if old != new => format!("{old} → {new}"),
+            (_, Some(path)) | (Some(path), None) => path.clone(),
+            (None, None) => String::new(),
+        }
+    }
+
+    fn status(&self) -> char {
+        match (&self.old_path, &self.new_path) {
+            (None, _) => 'A',
+            (_, None) => 'D',
+            (Some(old), Some(new)) if old != new => 'R',
+            _ => 'M',
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    Context,
+    Delete,
+    Insert,
+}
+
+/// One side of a diff line, split into runs flagged `true` where they differ
+/// from the paired line on the other side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SideLine {
+    number: usize,
+    kind: LineKind,
+    runs: Vec<(String, bool)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DiffBlock {
+    /// Hunk headers and notes such as "Binary file changed".
+    Header(String),
+    Context {
+        old: usize,
+        new: usize,
+        text: String,
+    },
+    /// A run of deleted lines followed by the inserted lines replacing them.
+    Change {
+        deleted: Vec<SideLine>,
+        inserted: Vec<SideLine>,
+    },
+}
+
+pub(crate) struct DiffViewer {
+    files: Vec<DiffFile>,
+    selected: usize,
+    blocks: Vec<DiffBlock>,
+    layout: DiffLayout,
+    scroll: usize,
+    hscroll: usize,
+    /// Height of the diff pane at the last render, used for paging.
+    page_height: usize,
+    /// Index of the file whose revert is awaiting confirmation.
+    pending_revert: Option<usize>,
+    status: Option<Line<'static>>,
+    /// Directory the diff paths are relative to.
+    root: PathBuf,
+    app_event_tx: AppEventSender,
+    is_done: bool,
+}
+
+impl DiffViewer {
+    pub(crate) fn new(unified_diff: &str, cwd: &Path, app_event_tx: AppEventSender) -> Self {
+        let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+        let mut viewer = Self {
+            files: parse_diff_files(unified_diff),
+            selected: 0,
+            blocks: Vec::new(),
+            layout: DiffLayout::SideBySide,
+            scroll: 0,
+            hscroll: 0,
+            page_height: 1,
+            pending_revert: None,
+            status: None,
+            root,
+            app_event_tx,
+            is_done: false,
+        };
+        viewer.select(0);
+        viewer
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                self.handle_key_event(key_event);
+                tui.frame_requester()
+                    .schedule_frame_in(Duration::from_millis(16));
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        let pending_revert = self.pending_revert.take();
+        if pending_revert.is_some() {
+            self.status = None;
+        }
+        match key_event {
+            e if KEY_Q.is_press(e) || KEY_ESC.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                self.is_done = true;
+            }
+            e if KEY_UP.is_press(e) || KEY_K.is_press(e) => {
+                self.scroll = self.scroll.saturating_sub(1);
+            }
+            e if KEY_DOWN.is_press(e) || KEY_J.is_press(e) => {
+                self.scroll = self.scroll.saturating_add(1);
+            }
+            e if KEY_PAGE_UP.is_press(e) => {
+                self.scroll = self.scroll.saturating_sub(self.page_height);
+            }
+            e if KEY_PAGE_DOWN.is_press(e) || KEY_SPACE.is_press(e) => {
+                self.scroll = self.scroll.saturating_add(self.page_height);
+            }
+            e if KEY_HOME.is_press(e) => {
+                self.scroll = 0;
+            }
+            e if KEY_END.is_press(e) => {
+                self.scroll = usize::MAX;
+            }
+            e if KEY_LEFT.is_press(e) || KEY_H.is_press(e) => {
+                self.hscroll = self.hscroll.saturating_sub(PAN_STEP);
+            }
+            e if KEY_RIGHT.is_press(e) || KEY_L.is_press(e) => {
+                self.hscroll = self.hscroll.saturating_add(PAN_STEP);
+            }
+            e if KEY_TAB.is_press(e) || KEY_N.is_press(e) => {
+                if !self.files.is_empty() {
+                    self.select((self.selected + 1) % self.files.len());
+                }
+            }
+            e if KEY_BACK_TAB.is_press(e)
+                || KEY_SHIFT_BACK_TAB.is_press(e)
+                || KEY_P.is_press(e) =>
+            {
+                if !self.files.is_empty() {
+                    let len = self.files.len();
+                    self.select((self.selected + len - 1) % len);
+                }
+            }
+            e if KEY_S.is_press(e) => {
+                self.layout = match self.layout {
+                    DiffLayout::SideBySide => DiffLayout::Unified,
+                    DiffLayout::Unified => DiffLayout::SideBySide,
+                };
+            }
+            e if KEY_R.is_press(e) => {
+                if pending_revert == Some(self.selected) {
+                    self.revert_selected();
+                } else if let Some(file) = self.files.get(self.selected) {
+                    self.pending_revert = Some(self.selected);
+                    self.status = Some(Line::from(vec![
+                        "Press ".into(),
+                        "r".bold(),
+                        format!(" again to revert {}", file.display_path()).into(),
+                    ]));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.scroll = 0;
+        self.hscroll = 0;
+        self.blocks = self.files.get(index).map(diff_blocks).unwrap_or_default();
+    }
+
+    fn revert_selected(&mut self) {
+        let Some(file) = self.files.get(self.selected) else {
+            return;
+        };
+        let path = file.display_path();
+        match revert_file(file, &self.root) {
+            Ok(()) => {
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    history_cell::new_info_event(format!("Reverted changes to {path}"), None),
+                )));
+                self.status = Some(Line::from(format!("Reverted {path}")).green());
+                self.files.remove(self.selected);
+                self.select(self.selected.min(self.files.len().saturating_sub(1)));
+                self.app_event_tx.send(AppEvent::DiffFileReverted {
+                    path,
+                    remaining_diff: self.files.iter().map(|file| file.patch.as_str()).collect(),
+                });
+            }
+            Err(err) => {
+                self.status = Some(Line::from(format!("Could not revert {path}: {err}")).red());
+            }
+        }
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        if area.height < 5 {
+            return;
+        }
+
+        let header = Rect::new(area.x, area.y, area.width, 1);
+        Span::from("/ ".repeat(area.width as usize / 2))
+            .dim()
+            .render_ref(header, buf);
+        "/ C H A N G E S".dim().render_ref(header, buf);
+
+        let body = Rect::new(area.x, area.y + 1, area.width, area.height - 5);
+        let status = Rect::new(area.x, body.bottom(), area.width, 1);
+        let separator = Rect::new(area.x, status.bottom(), area.width, 1);
+        let hints = Rect::new(area.x, separator.bottom(), area.width, 1);
+        let action_hints = Rect::new(area.x, hints.bottom(), area.width, 1);
+
+        if self.files.is_empty() {
+            Line::from(" No changes to show.".dim()).render_ref(body, buf);
+        } else if area.width >= MIN_WIDTH_FOR_FILE_LIST {
+            let list_width = (area.width / 4).clamp(20, 40);
+            let list = Rect::new(body.x, body.y, list_width, body.height);
+            self.render_file_list(list, buf);
+            for y in body.y..body.bottom() {
+                buf[(body.x + list_width, y)]
+                    .set_symbol("│")
+                    .set_style(Style::default().dim());
+            }
+            let diff = Rect::new(
+                body.x + list_width + 1,
+                body.y,
+                body.width - list_width - 1,
+                body.height,
+            );
+            self.render_diff(diff, buf);
+        } else {
+            self.render_diff(body, buf);
+        }
+
+        if let Some(line) = &self.status {
+            let mut spans = vec![Span::from(" ")];
+            spans.extend(line.spans.iter().cloned());
+            Line::from(spans).style(line.style).render_ref(status, buf);
+        }
+        Span::from("─".repeat(separator.width as usize))
+            .dim()
+            .render_ref(separator, buf);
+        render_key_hints(hints, buf, NAVIGATION_KEY_HINTS);
+        render_key_hints(action_hints, buf, ACTION_KEY_HINTS);
+    }
+
+    fn render_file_list(&self, area: Rect, buf: &mut Buffer) {
+        let height = area.height as usize;
+        let offset = (self.selected + 1).saturating_sub(height);
+        for (row, (index, file)) in self
+            .files
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .enumerate()
+        {
+            let stats = format!(" +{} -{}", file.added, file.removed);
+            let path_width = (area.width as usize).saturating_sub(stats.width() + 3);
+            let path = truncate_start(&file.display_path(), path_width);
+            let padding = path_width.saturating_sub(path.width());
+            let mut line = Line::from(vec![
+                format!(" {} ", file.status()).dim(),
+                path.into(),
+                " ".repeat(padding).into(),
+                format!(" +{}", file.added).green(),
+                format!(" -{}", file.removed).red(),
+            ]);
+            if index == self.selected {
+                line = line.patch_style(Style::default().add_modifier(Modifier::REVERSED));
+            }
+            let rect = Rect::new(area.x, area.y + row as u16, area.width, 1);
+            line.render_ref(rect, buf);
+        }
+    }
+
+    fn render_diff(&mut self, area: Rect, buf: &mut Buffer) {
+        let Some(file) = self.files.get(self.selected) else {
+            return;
+        };
+        let title = Line::from(vec![
+            " ".into(),
+            file.display_path().bold(),
+            format!(" (+{} -{})", file.added, file.removed).dim(),
+        ]);
+        title.render_ref(Rect::new(area.x, area.y, area.width, 1), buf);
+
+        let content = Rect::new(
+            area.x,
+            area.y + 1,
+            area.width,
+            area.height.saturating_sub(1),
+        );
+        let lines = self.diff_lines(content.width as usize);
+        self.page_height = (content.height as usize).max(1);
+        self.scroll = self
+            .scroll
+            .min(lines.len().saturating_sub(content.height as usize));
+        for (row, line) in lines
+            .iter()
+            .skip(self.scroll)
+            .take(content.height as usize)
+            .enumerate()
+        {
+            let rect = Rect::new(content.x, content.y + row as u16, content.width, 1);
+            line.render_ref(rect, buf);
+        }
+    }
+
+    fn diff_lines(&self, width: usize) -> Vec<Line<'static>> {
+        let number_width = self
+            .blocks
+            .iter()
+            .filter_map(|block| match block {
+                DiffBlock::Header(_) => None,
+                DiffBlock::Context { old, new, .. } => Some(*old.max(new)),
+                DiffBlock::Change { deleted, inserted } => {
+                    deleted.iter().chain(inserted).map(|line| line.number).max()
+                }
+            })
+            .max()
+            .unwrap_or(0)
+            .to_string()
+            .len();
+        let mut lines = Vec::new();
+        for block in &self.blocks {
+            match block {
+                DiffBlock::Header(text) => {
+                    lines.push(Line::from(truncate_end(text, width)).cyan().dim());
+                }
+                DiffBlock::Context { old, new, text } => {
+                    let old = SideLine {
+                        number: *old,
+                        kind: LineKind::Context,
+                        runs: vec![(text.clone(), false)],
+                    };
+                    let new = SideLine {
+                        number: *new,
+                        ..old.clone()
+                    };
+                    lines.push(self.row(Some(&old), Some(&new), number_width, width));
+                }
+                DiffBlock::Change { deleted, inserted } => match self.layout {
+                    DiffLayout::SideBySide => {
+                        for i in 0..deleted.len().max(inserted.len()) {
+                            lines.push(self.row(
+                                deleted.get(i),
+                                inserted.get(i),
+                                number_width,
+                                width,
+                            ));
+                        }
+                    }
+                    DiffLayout::Unified => {
+                        for line in deleted {
+                            lines.push(self.row(Some(line), None, number_width, width));
+                        }
+                        for line in inserted {
+                            lines.push(self.row(None, Some(line), number_width, width));
+                        }
+                    }
+                },
+            }
+        }
+        lines
+    }
+
+    /// Renders one row of the diff pane from the old and/or new side of a line.
+    fn row(
+        &self,
+        old: Option<&SideLine>,
+        new: Option<&SideLine>,
+        number_width: usize,
+        width: usize,
+    ) -> Line<'static> {
+        match self.layout {
+            DiffLayout::SideBySide => {
+                let half = width.saturating_sub(1) / 2;
+                let mut spans = side_spans(old, number_width, self.hscroll, half);
+                spans.push("│".dim());
+                spans.extend(side_spans(
+                    new,
+                    number_width,
+                    self.hscroll,
+                    width.saturating_sub(half + 1),
+                ));
+                Line::from(spans)
+            }
+            DiffLayout::Unified => {
+                let number = |line: Option<&SideLine>| {
+                    line.map(|line| line.number.to_string()).unwrap_or_default()
+                };
+                let gutter = format!(
+                    "{:>number_width$} {:>number_width$} ",
+                    number(old),
+                    number(new)
+                );
+                let text_width = width.saturating_sub(gutter.width());
+                let mut spans = vec![gutter.dim()];
+                // Context rows carry the same text on both sides.
+                if let Some(line) = old.or(new) {
+                    spans.extend(line_spans(line, self.hscroll, text_width));
+                }
+                Line::from(spans)
+            }
+        }
+    }
+}
+
+/// Spans for one side of a side-by-side row, padded to exactly `width` columns.
+fn side_spans(
+    line: Option<&SideLine>,
+    number_width: usize,
+    hscroll: usize,
+    width: usize,
+) -> Vec<Span<'static>> {
+    let Some(line) = line else {
+        return vec![" ".repeat(width).into()];
+    };
+    let gutter = truncate_end(&format!("{:>number_width$} ", line.number), width);
+    let text_width = width.saturating_sub(gutter.width());
+    let mut spans = vec![gutter.dim()];
+    spans.extend(line_spans(line, hscroll, text_width));
+    let used: usize = spans.iter().map(Span::width).sum();
+    if used < width {
+        spans.push(" ".repeat(width - used).into());
+    }
+    spans
+}
+
+/// The sign and text of a diff line, panned by `hscroll` columns and clipped
+/// to `width` columns.
+fn line_spans(line: &SideLine, hscroll: usize, width: usize) -> Vec<Span<'static>> {
+    let (sign, style) = match line.kind {
+        LineKind::Context => (' ', Style::default()),
+        LineKind::Delete => ('-', Style::default().red()),
+        LineKind::Insert => ('+', Style::default().green()),
+    };
+    if width == 0 {
+        return Vec::new();
+    }
+    let mut spans = vec![Span::styled(sign.to_string(), style)];
+    let mut skip = hscroll;
+    let mut remaining = width - 1;
+    for (text, changed) in &line.runs {
+        let mut visible = String::new();
+        for ch in text.chars() {
+            let ch_width = ch.width().unwrap_or(0);
+            if skip > 0 {
+                skip = skip.saturating_sub(ch_width.max(1));
+                continue;
+            }
+            if ch_width > remaining {
+                remaining = 0;
+                break;
+            }
+            remaining -= ch_width;
+            visible.push(ch);
+        }
+        if !visible.is_empty() {
+            let style = if *changed {
+                style.add_modifier(Modifier::REVERSED)
+            } else {
+                style
+            };
+            spans.push(Span::styled(visible, style));
+        }
+        if remaining == 0 {
+            break;
+        }
+    }
+    spans
+}
+
+fn truncate_end(text: &str, width: usize) -> String {
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width > width {
+            break;
+        }
+        used += ch_width;
+        out.push(ch);
+    }
+    out
+}
+
+/// Keeps the end of `text`, which holds the file name, prefixing "…" when
+/// it does not fit.
+fn truncate_start(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut tail: Vec<char> = Vec::new();
+    let mut used = 1;
+    for ch in text.chars().rev() {
+        let ch_width = ch.width().unwrap_or(0);
+        if used + ch_width > width {
+            break;
+        }
+        used += ch_width;
+        tail.push(ch);
+    }
+    std::iter::once('…').chain(tail.into_iter().rev()).collect()
+}
+
+/// Splits a turn diff into its per-file sections.
+fn parse_diff_files(unified_diff: &str) -> Vec<DiffFile> {
+    let mut sections: Vec<String> = Vec::new();
+    for line in unified_diff.split_inclusive('\n') {
+        if line.starts_with("diff --git ") || sections.is_empty() {
+            sections.push(String::new());
+        }
+        if let Some(section) = sections.last_mut() {
+            section.push_str(line);
+        }
+    }
+    sections.into_iter().filter_map(parse_diff_file).collect()
+}
+
+fn parse_diff_file(patch: String) -> Option<DiffFile> {
+    let git_paths = patch
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("diff --git "))
+        .and_then(|paths| paths.split_once(" b/"))
+        .map(|(old, new)| {
+            (
+                old.strip_prefix("a/").unwrap_or(old).to_string(),
+                new.to_string(),
+            )
+        });
+    let mut old_path = None;
+    let mut new_path = None;
+    let mut saw_file_headers = false;
+    let mut new_file = false;
+    let mut deleted_file = false;
+    let mut binary = false;
+    let mut added = 0;
+    let mut removed = 0;
+    let mut in_hunk = false;
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            in_hunk = true;
+        } else if in_hunk {
+            if line.starts_with('+') {
+                added += 1;
+            } else if line.starts_with('-') {
+                removed += 1;
+            }
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            old_path = header_path(path, "a/");
+            saw_file_headers = true;
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            new_path = header_path(path, "b/");
+            saw_file_headers = true;
+        } else if line.starts_with("new file mode") {
+            new_file = true;
+        } else if line.starts_with("deleted file mode") {
+            deleted_file = true;
+        } else if line.starts_with("Binary files") {
+            binary = true;
+        }
+    }
+    if !saw_file_headers && let Some((old, new)) = git_paths {
+        old_path = (!new_file).then_some(old);
+        new_path = (!deleted_file).then_some(new);
+    }
+    if old_path.is_none() && new_path.is_none() {
+        return None;
+    }
+    Some(DiffFile {
+        old_path,
+        new_path,
+        patch,
+        binary,
+        added,
+        removed,
+    })
+}
+
+fn header_path(path: &str, prefix: &str) -> Option<String> {
+    let path = path.trim_end();
+    if path == "/dev/null" {
+        return None;
+    }
+    Some(path.strip_prefix(prefix).unwrap_or(path).to_string())
+}
+
+fn diff_blocks(file: &DiffFile) -> Vec<DiffBlock> {
+    if file.binary {
+        return vec![DiffBlock::Header("Binary file changed".to_string())];
+    }
+    let patch = match diffy::Patch::from_str(&file.patch) {
+        Ok(patch) => patch,
+        Err(err) => return vec![DiffBlock::Header(format!("Unable to parse diff: {err}"))],
+    };
+    let mut blocks = Vec::new();
+    for hunk in patch.hunks() {
+        let old_range = hunk.old_range();
+        let new_range = hunk.new_range();
+        blocks.push(DiffBlock::Header(format!(
+            "@@ -{old_range} +{new_range} @@"
+        )));
+        let mut old_line = old_range.start();
+        let mut new_line = new_range.start();
+        let mut deleted = Vec::new();
+        let mut inserted = Vec::new();
+        for line in hunk.lines() {
+            match line {
+                diffy::Line::Delete(text) => {
+                    deleted.push((old_line, display_text(text)));
+                    old_line += 1;
+                }
+                diffy::Line::Insert(text) => {
+                    inserted.push((new_line, display_text(text)));
+                    new_line += 1;
+                }
+                diffy::Line::Context(text) => {
+                    push_change(&mut blocks, &mut deleted, &mut inserted);
+                    blocks.push(DiffBlock::Context {
+                        old: old_line,
+                        new: new_line,
+                        text: display_text(text),
+                    });
+                    old_line += 1;
+                    new_line += 1;
+                }
+            }
+        }
+        push_change(&mut blocks, &mut deleted, &mut inserted);
+    }
+    if blocks.is_empty() {
+        blocks.push(DiffBlock::Header("No content changes".to_string()));
+    }
+    blocks
+}
+
+fn display_text(text: &str) -> String {
+    text.trim_end_matches('\n').replace('\t', "    ")
+}
+
+/// Pairs the pending deleted and inserted lines row by row, highlighting the
+/// words that differ within each pair.
+fn push_change(
+    blocks: &mut Vec<DiffBlock>,
+    deleted: &mut Vec<(usize, String)>,
+    inserted: &mut Vec<(usize, String)>,
+) {
+    if deleted.is_empty() && inserted.is_empty() {
+        return;
+    }
+    let mut deleted_lines = Vec::new();
+    let mut inserted_lines = Vec::new();
+    for i in 0..deleted.len().max(inserted.len()) {
+        let (old_runs, new_runs) = match (deleted.get(i), inserted.get(i)) {
+            (Some((_, old)), Some((_, new))) => word_diff(old, new),
+            (Some((_, old)), None) => (vec![(old.clone(), false)], Vec::new()),
+            (None, Some((_, new))) => (Vec::new(), vec![(new.clone(), false)]),
+            (None, None) => (Vec::new(), Vec::new()),
+        };
+        if let Some((number, _)) = deleted.get(i) {
+            deleted_lines.push(SideLine {
+                number: *number,
+                kind: LineKind::Delete,
+                runs: old_runs,
+            });
+        }
+        if let Some((number, _)) = inserted.get(i) {
+            inserted_lines.push(SideLine {
+                number: *number,
+                kind: LineKind::Insert,
+                runs: new_runs,
+            });
+        }
+    }
+    deleted.clear();
+    inserted.clear();
+    blocks.push(DiffBlock::Change {
+        deleted: deleted_lines,
+        inserted: inserted_lines,
+    });
+}
+
+/// Splits a line into identifier runs, whitespace runs and single other
+/// characters.
+fn tokenize(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, ch)) = chars.next() {
+        let same_class = |next: char| {
+            ((ch.is_alphanumeric() || ch == '_') && (next.is_alphanumeric() || next == '_'))
+                || (ch.is_whitespace() && next.is_whitespace())
+        };
+        match chars.peek() {
+            Some((_, next)) if same_class(*next) => {}
+            Some((next_i, _)) => {
+                tokens.push(&text[start..*next_i]);
+                start = *next_i;
+            }
+            None => tokens.push(&text[start..i + ch.len_utf8()]),
+        }
+    }
+    tokens
+}
+
+/// Word-level diff of a deleted line against the inserted line replacing it.
+/// Runs flagged `true` are not part of the longest common token subsequence.
+fn word_diff(old: &str, new: &str) -> (Vec<(String, bool)>, Vec<(String, bool)>) {
+    let unflagged = || {
+        (
+            vec![(old.to_string(), false)],
+            vec![(new.to_string(), false)],
+        )
+    };
+    let a = tokenize(old);
+    let b = tokenize(new);
+    if a.is_empty() || b.is_empty() || a.len() * b.len() > MAX_WORD_DIFF_CELLS {
+        return unflagged();
+    }
+
+    let cols = b.len() + 1;
+    let mut lcs = vec![0usize; (a.len() + 1) * cols];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i * cols + j] = if a[i] == b[j] {
+                lcs[(i + 1) * cols + j + 1] + 1
+            } else {
+                lcs[(i + 1) * cols + j].max(lcs[i * cols + j + 1])
+            };
+        }
+    }
+
+    let mut a_common = vec![false; a.len()];
+    let mut b_common = vec![false; b.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            a_common[i] = true;
+            b_common[j] = true;
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * cols + j] >= lcs[i * cols + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    // Lines sharing nothing but whitespace are unrelated; highlighting every
+    // word would only add noise.
+    let shares_words = a
+        .iter()
+        .zip(&a_common)
+        .any(|(token, common)| *common && !token.trim().is_empty());
+    if !shares_words {
+        return unflagged();
+    }
+    (runs(&a, &a_common), runs(&b, &b_common))
+}
+
+fn runs(tokens: &[&str], common: &[bool]) -> Vec<(String, bool)> {
+    let mut runs: Vec<(String, bool)> = Vec::new();
+    for (token, common) in tokens.iter().zip(common) {
+        let changed = !common;
+        match runs.last_mut() {
+            Some((text, flag)) if *flag == changed => text.push_str(token),
+            _ => runs.push((token.to_string(), changed)),
+        }
+    }
+    runs
+}
+
+/// Restores the file to its state before the turn by reversing its diff.
+fn revert_file(file: &DiffFile, root: &Path) -> std::result::Result<(), String> {
+    if file.binary {
+        return Err("binary changes cannot be reverted from the viewer".to_string());
+    }
+    let patch = diffy::Patch::from_str(&file.patch).map_err(|err| err.to_string())?;
+    match (&file.old_path, &file.new_path) {
+        (None, Some(new)) => {
+            // Only delete the file if it still holds what the turn added.
+            let new_path = root.join(new);
+            let current = std::fs::read_to_string(&new_path)
+                .map_err(|err| format!("{}: {err}", new_path.display()))?;
+            if current != side_content(&patch, LineKind::Insert) {
+                return Err("the file has changed since the turn".to_string());
+            }
+            remove_file(&new_path)
+        }
+        (Some(old), None) => write_file(&root.join(old), &side_content(&patch, LineKind::Delete)),
+        (Some(old), Some(new)) => {
+            let new_path = root.join(new);
+            let current = std::fs::read_to_string(&new_path)
+                .map_err(|err| format!("{}: {err}", new_path.display()))?;
+            let original = diffy::apply(&current, &patch.reverse())
+                .map_err(|_| "the file has changed since the turn".to_string())?;
+            let old_path = root.join(old);
+            write_file(&old_path, &original)?;
+            if old_path != new_path {
+                remove_file(&new_path)?;
+            }
+            Ok(())
+        }
+        (None, None) => Ok(()),
+    }
+}
+
+/// Text of one side of `patch`: context lines plus the lines of `kind`.
+fn side_content(patch: &diffy::Patch<'_, str>, kind: LineKind) -> String {
+    patch
+        .hunks()
+        .iter()
+        .flat_map(diffy::Hunk::lines)
+        .filter_map(|line| match (line, kind) {
+            (diffy::Line::Context(text), _)
+            | (diffy::Line::Delete(text), LineKind::Delete)
+            | (diffy::Line::Insert(text), LineKind::Insert) => Some(*text),
+            _ => None,
+        })
+        .collect()
+}
+
+fn write_file(path: &Path, content: &str) -> std::result::Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| format!("{}: {err}", parent.display()))?;
+    }
+    std::fs::write(path, content).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+fn remove_file(path: &Path) -> std::result::Result<(), String> {
+    std::fs::remove_file(path).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    const TURN_DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    let total = add(1, 2);
++    let total = add(1, 3);
+ }
+diff --git a/notes.txt b/notes.txt
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/notes.txt
+@@ -0,0 +1 @@
++hello
+diff --git a/logo.png b/logo.png
+index 4444444..5555555
+Binary files differ
+";
+
+    fn new_viewer(
+        diff: &str,
+        cwd: &Path,
+    ) -> (DiffViewer, tokio::sync::mpsc::UnboundedReceiver<AppEvent>) {
+        let (tx, rx) = unbounded_channel();
+        (DiffViewer::new(diff, cwd, AppEventSender::new(tx)), rx)
+    }
+
+    #[test]
+    fn parses_files_from_turn_diff() {
+        let files = parse_diff_files(TURN_DIFF);
+        let summary: Vec<_> = files
+            .iter()
+            .map(|file| {
+                (
+                    file.status(),
+                    file.display_path(),
+                    file.added,
+                    file.removed,
+                    file.binary,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ('M', "src/lib.rs".to_string(), 1, 1, false),
+                ('A', "notes.txt".to_string(), 1, 0, false),
+                ('M', "logo.png".to_string(), 0, 0, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn word_diff_flags_only_changed_tokens() {
+        let (old, new) = word_diff("    let total = add(1, 2);", "    let total = add(1, 3);");
+        assert_eq!(
+            old,
+            vec![
+                ("    let total = add(1, ".to_string(), false),
+                ("2".to_string(), true),
+                (");".to_string(), false),
+            ]
+        );
+        assert_eq!(
+            new,
+            vec![
+                ("    let total = add(1, ".to_string(), false),
+                ("3".to_string(), true),
+                (");".to_string(), false),
+            ]
+        );
+
+        let (old, _) = word_diff("alpha beta", "gamma delta");
+        assert_eq!(old, vec![("alpha beta".to_string(), false)]);
+    }
+
+    #[test]
+    fn revert_restores_modified_and_removes_added_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("src")).expect("mkdir");
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "fn main() {\n    let total = add(1, 3);\n}\n",
+        )
+        .expect("write lib.rs");
+        std::fs::write(dir.path().join("notes.txt"), "hello\n").expect("write notes");
+
+        let (mut viewer, mut rx) = new_viewer(TURN_DIFF, dir.path());
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('r')));
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('r')));
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("src/lib.rs")).expect("read lib.rs"),
+            "fn main() {\n    let total = add(1, 2);\n}\n"
+        );
+        assert!(matches!(rx.try_recv(), Ok(AppEvent::InsertHistoryCell(_))));
+        match rx.try_recv() {
+            Ok(AppEvent::DiffFileReverted {
+                path,
+                remaining_diff,
+            }) => {
+                assert_eq!(path, "src/lib.rs");
+                assert_eq!(
+                    remaining_diff,
+                    TURN_DIFF[TURN_DIFF.find("diff --git a/notes.txt").expect("notes")..]
+                );
+            }
+            other => panic!("expected DiffFileReverted, got {other:?}"),
+        }
+        assert_eq!(viewer.files.len(), 2);
+
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('r')));
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('r')));
+        assert!(!dir.path().join("notes.txt").exists());
+
+        // A single press only asks for confirmation.
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('r')));
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('j')));
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('r')));
+        assert_eq!(viewer.files.len(), 1);
+        assert_eq!(viewer.pending_revert, Some(0));
+    }
+
+    #[test]
+    fn revert_keeps_added_file_edited_after_the_turn() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("notes.txt"), "hello\nmore notes\n").expect("write notes");
+
+        let (mut viewer, mut rx) = new_viewer(TURN_DIFF, dir.path());
+        viewer.select(1);
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('r')));
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('r')));
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("notes.txt")).expect("read notes"),
+            "hello\nmore notes\n"
+        );
+        assert_eq!(viewer.files.len(), 3);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn renders_file_list_and_side_by_side_diff() {
+        let (mut viewer, _rx) = new_viewer(TURN_DIFF, Path::new("/nonexistent"));
+        let area = Rect::new(0, 0, 100, 12);
+        let mut buf = Buffer::empty(area);
+        viewer.render(area, &mut buf);
+        let rows: Vec<String> = (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect();
+
+        assert!(rows[1].starts_with(" M src/lib.rs"), "{rows:?}");
+        assert!(rows[2].starts_with(" A notes.txt"), "{rows:?}");
+        assert!(rows[1].contains("│ src/lib.rs (+1 -1)"), "{rows:?}");
+        let change = rows
+            .iter()
+            .find(|row| row.contains("-    let total"))
+            .expect("change row");
+        assert!(
+            change.contains("│2 +    let total = add(1, 3);"),
+            "{rows:?}"
+        );
+
+        viewer.handle_key_event(KeyEvent::from(KeyCode::Char('s')));
+        let mut buf = Buffer::empty(area);
+        viewer.render(area, &mut buf);
+        let rendered: String = (0..area.height)
+            .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+            .map(|pos| buf[pos].symbol().to_string())
+            .collect();
+        assert!(rendered.contains("2   -    let total = add(1, 2);"));
+        assert!(rendered.contains("  2 +    let total = add(1, 3);"));
+    }
+}
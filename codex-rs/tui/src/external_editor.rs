@@ -52,12 +52,22 @@ pub(crate) fn resolve_editor_command() -> std::result::Result<Vec<String>, Edito
 
 /// Write `seed` to a temp file, launch the editor command, and return the updated content.
 pub(crate) async fn run_editor(seed: &str, editor_cmd: &[String]) -> Result<String> {
+    run_editor_with_suffix(seed, editor_cmd, ".md").await
+}
+
+/// Like [`run_editor`], with a temp file ending in `suffix` so the editor
+/// picks a matching syntax mode.
+pub(crate) async fn run_editor_with_suffix(
+    seed: &str,
+    editor_cmd: &[String],
+    suffix: &str,
+) -> Result<String> {
     if editor_cmd.is_empty() {
         return Err(Report::msg("editor command is empty"));
     }
 
     // Convert to TempPath immediately so no file handle stays open on Windows.
-    let temp_path = Builder::new().suffix(".md").tempfile()?.into_temp_path();
+    let temp_path = Builder::new().suffix(suffix).tempfile()?.into_temp_path();
     fs::write(&temp_path, seed)?;
 
     let mut cmd = {
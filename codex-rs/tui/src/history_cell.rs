@@ -40,6 +40,7 @@ use base64::Engine;
 use codex_common::format_env_display::format_env_display;
 use codex_core::config::Config;
 use codex_core::config::types::McpServerTransportConfig;
+use codex_core::memory::Memory;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
@@ -682,7 +683,7 @@ pub fn new_approval_decision_cell(
     use codex_core::protocol::ReviewDecision::*;
 
     let (symbol, summary): (Span<'static>, Vec<Span<'static>>) = match decision {
-        Approved => {
+        Approved | ApprovedPartially { .. } => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
                 "✔ ".green(),
@@ -1137,9 +1138,15 @@ pub(crate) struct McpToolCallCell {
     start_time: Instant,
     duration: Option<Duration>,
     result: Option<Result<mcp_types::CallToolResult, String>>,
+    /// Latest `(progress, total)` reported by the server while the call runs.
+    progress: Option<(f64, Option<f64>)>,
+    progress_message: Option<String>,
     animations_enabled: bool,
 }
 
+/// Width of the progress bar shown while an MCP tool call reports a total.
+const MCP_PROGRESS_BAR_WIDTH: usize = 20;
+
 impl McpToolCallCell {
     pub(crate) fn new(
         call_id: String,
@@ -1152,6 +1159,8 @@ impl McpToolCallCell {
             start_time: Instant::now(),
             duration: None,
             result: None,
+            progress: None,
+            progress_message: None,
             animations_enabled,
         }
     }
@@ -1160,6 +1169,49 @@ impl McpToolCallCell {
         &self.call_id
     }
 
+    /// Records a progress update. Updates without a message keep the previous
+    /// one, so a log line stays visible while the counter advances. Progress
+    /// never moves backwards, even if notifications arrive out of order.
+    pub(crate) fn update_progress(
+        &mut self,
+        progress: Option<f64>,
+        total: Option<f64>,
+        message: Option<String>,
+    ) {
+        if let Some(progress) = progress
+            && self.progress.is_none_or(|(current, _)| progress >= current)
+        {
+            self.progress = Some((progress, total));
+        }
+        if message.is_some() {
+            self.progress_message = message;
+        }
+    }
+
+    fn progress_line(&self) -> Option<Line<'static>> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        match self.progress {
+            Some((done, Some(total))) if total > 0.0 => {
+                let ratio = (done / total).clamp(0.0, 1.0);
+                let filled = (ratio * MCP_PROGRESS_BAR_WIDTH as f64).round() as usize;
+                spans.push("[".dim());
+                spans.push("█".repeat(filled).cyan());
+                spans.push("░".repeat(MCP_PROGRESS_BAR_WIDTH - filled).dim());
+                spans.push("] ".dim());
+                spans.push(format!("{done}/{total}").dim());
+            }
+            Some((done, _)) => spans.push(format!("{done}").dim()),
+            None => {}
+        }
+        if let Some(message) = &self.progress_message {
+            if !spans.is_empty() {
+                spans.push(" ".into());
+            }
+            spans.push(message.clone().dim());
+        }
+        (!spans.is_empty()).then(|| Line::from(spans))
+    }
+
     pub(crate) fn complete(
         &mut self,
         duration: Duration,
@@ -1249,6 +1301,18 @@ impl HistoryCell for McpToolCallCell {
         // Reserve four columns for the tree prefix ("  └ "/"    ") and ensure the wrapper still has at least one cell to work with.
         let detail_wrap_width = (width as usize).saturating_sub(4).max(1);
 
+        if self.result.is_none()
+            && let Some(progress_line) = self.progress_line()
+        {
+            let wrapped = word_wrap_line(
+                &progress_line,
+                RtOptions::new(detail_wrap_width)
+                    .initial_indent("".into())
+                    .subsequent_indent("    ".into()),
+            );
+            detail_lines.extend(wrapped.iter().map(line_to_static));
+        }
+
         if let Some(result) = &self.result {
             match result {
                 Ok(mcp_types::CallToolResult { content, .. }) => {
@@ -1415,6 +1479,47 @@ pub(crate) fn empty_mcp_output() -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
+/// Render the memories saved for `project`, oldest first.
+pub(crate) fn new_memories_output(project: &Path, memories: &[Memory]) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        "/memories".magenta().into(),
+        "".into(),
+        vec!["🧠  ".into(), "Memories".bold()].into(),
+        vec!["  Project: ".dim(), project.display().to_string().into()].into(),
+        "".into(),
+    ];
+
+    if memories.is_empty() {
+        lines.push("  • No memories saved for this project.".italic().into());
+        lines.push(
+            "    Enable the `memory` feature to let Codex save them with `remember`."
+                .dim()
+                .into(),
+        );
+        return PlainHistoryCell { lines };
+    }
+
+    for memory in memories {
+        lines.push(
+            vec![
+                "  • ".into(),
+                memory.id.clone().cyan(),
+                " ".into(),
+                memory.text.clone().into(),
+            ]
+            .into(),
+        );
+    }
+    lines.push("".into());
+    lines.push(
+        "  Edit or delete with `codex memory edit|delete <id>`."
+            .dim()
+            .into(),
+    );
+
+    PlainHistoryCell { lines }
+}
+
 /// Render MCP tools grouped by connection using the fully-qualified tool names.
 pub(crate) fn new_mcp_tools_output(
     config: &Config,
@@ -1904,6 +2009,43 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn memories_output_lists_ids_and_text() {
+        let memories = vec![
+            Memory {
+                id: "1a2b3c4d".to_string(),
+                text: "Run `just fmt` before committing".to_string(),
+                updated_at: "2025-01-01T00:00:00Z".to_string(),
+            },
+            Memory {
+                id: "5e6f7a8b".to_string(),
+                text: "Snapshot tests use insta".to_string(),
+                updated_at: "2025-01-02T00:00:00Z".to_string(),
+            },
+        ];
+        let cell = new_memories_output(Path::new("/work/repo"), &memories);
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "/memories",
+                "",
+                "🧠  Memories",
+                "  Project: /work/repo",
+                "",
+                "  • 1a2b3c4d Run `just fmt` before committing",
+                "  • 5e6f7a8b Snapshot tests use insta",
+                "",
+                "  Edit or delete with `codex memory edit|delete <id>`.",
+            ],
+        );
+
+        let empty = new_memories_output(Path::new("/work/repo"), &[]);
+        assert_eq!(
+            render_lines(&empty.display_lines(80))[5],
+            "  • No memories saved for this project."
+        );
+    }
+
     #[tokio::test]
     async fn mcp_tools_output_masks_sensitive_values() {
         let mut config = test_config().await;
@@ -2085,6 +2227,22 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn active_mcp_tool_call_shows_progress_bar_and_message() {
+        let invocation = McpInvocation {
+            server: "search".into(),
+            tool: "index".into(),
+            arguments: None,
+        };
+
+        let mut cell = new_active_mcp_tool_call("call-1".into(), invocation, false);
+        cell.update_progress(Some(1.0), Some(4.0), Some("indexing".to_string()));
+        cell.update_progress(Some(2.0), Some(4.0), None);
+        let rendered = render_lines(&cell.display_lines(80));
+
+        assert_eq!(rendered[1], "  └ [██████████░░░░░░░░░░] 2/4 indexing");
+    }
+
     #[test]
     fn completed_mcp_tool_call_success_snapshot() {
         let invocation = McpInvocation {
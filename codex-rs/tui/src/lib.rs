@@ -48,6 +48,7 @@ mod clipboard_paste;
 mod color;
 pub mod custom_terminal;
 mod diff_render;
+mod diff_viewer;
 mod exec_cell;
 mod exec_command;
 mod external_editor;
@@ -572,6 +573,7 @@ async fn run_ratatui_app(
 
     let use_alt_screen = determine_alt_screen_mode(no_alt_screen, config.tui_alternate_screen);
     tui.set_alt_screen_enabled(use_alt_screen);
+    render::highlight::set_syntax_theme(config.tui_syntax_theme);
 
     let app_result = App::run(
         &mut tui,
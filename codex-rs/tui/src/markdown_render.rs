@@ -1,3 +1,5 @@
+use crate::render::highlight::Language;
+use crate::render::highlight::highlight_code_to_lines;
 use crate::render::line_utils::line_to_static;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
@@ -99,6 +101,9 @@ where
     pending_marker_line: bool,
     in_paragraph: bool,
     in_code_block: bool,
+    /// Source of the current fenced code block when its language can be
+    /// highlighted; it is rendered as a whole when the block ends.
+    highlighted_code_block: Option<(Language, String)>,
     wrap_width: Option<usize>,
     current_line_content: Option<Line<'static>>,
     current_initial_indent: Vec<Span<'static>>,
@@ -124,6 +129,7 @@ where
             pending_marker_line: false,
             in_paragraph: false,
             in_code_block: false,
+            highlighted_code_block: None,
             wrap_width,
             current_line_content: None,
             current_initial_indent: Vec::new(),
@@ -278,6 +284,10 @@ where
             self.push_line(Line::default());
         }
         self.pending_marker_line = false;
+        if let Some((_, code)) = self.highlighted_code_block.as_mut() {
+            code.push_str(&text);
+            return;
+        }
         if self.in_code_block && !self.needs_newline {
             let has_content = self
                 .current_line_content
@@ -394,12 +404,16 @@ where
         self.needs_newline = false;
     }
 
-    fn start_codeblock(&mut self, _lang: Option<String>, indent: Option<Span<'static>>) {
+    fn start_codeblock(&mut self, lang: Option<String>, indent: Option<Span<'static>>) {
         self.flush_current_line();
         if !self.text.lines.is_empty() {
             self.push_blank_line();
         }
         self.in_code_block = true;
+        self.highlighted_code_block = lang
+            .as_deref()
+            .and_then(Language::from_fence_info)
+            .map(|language| (language, String::new()));
         self.indent_stack.push(IndentContext::new(
             vec![indent.unwrap_or_default()],
             None,
@@ -409,11 +423,39 @@ where
     }
 
     fn end_codeblock(&mut self) {
+        if let Some((language, code)) = self.highlighted_code_block.take() {
+            self.push_highlighted_code(language, &code);
+        }
         self.needs_newline = true;
         self.in_code_block = false;
         self.indent_stack.pop();
     }
 
+    fn push_highlighted_code(&mut self, language: Language, code: &str) {
+        if code.is_empty() {
+            return;
+        }
+        let code = code.strip_suffix('\n').unwrap_or(code);
+        let lines = highlight_code_to_lines(code, language).unwrap_or_else(|| {
+            code.split('\n')
+                .map(|line| line.to_string().into())
+                .collect()
+        });
+        for (i, line) in lines.into_iter().enumerate() {
+            if self.needs_newline {
+                self.push_line(Line::default());
+                self.needs_newline = false;
+            }
+            if i > 0 {
+                self.push_line(Line::default());
+            }
+            for span in line.spans {
+                self.push_span(span);
+            }
+        }
+        self.needs_newline = false;
+    }
+
     fn push_inline_style(&mut self, style: Style) {
         let current = self.inline_styles.last().copied().unwrap_or_default();
         let merged = current.patch(style);
@@ -1,4 +1,5 @@
 use pretty_assertions::assert_eq;
+use ratatui::style::Color;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
@@ -652,8 +653,22 @@ fn link() {
 }
 
 #[test]
-fn code_block_unhighlighted() {
+fn code_block_highlighted() {
     let text = render_markdown_text("```rust\nfn main() {}\n```\n");
+    assert_eq!(text.lines.len(), 1);
+    let spans = &text.lines[0].spans;
+    let content: String = spans.iter().map(|s| s.content.as_ref()).collect();
+    assert_eq!(content, "fn main() {}");
+    let keyword = spans
+        .iter()
+        .find(|s| s.content == "fn")
+        .expect("fn keyword span");
+    assert_eq!(keyword.style.fg, Some(Color::Magenta));
+}
+
+#[test]
+fn code_block_unknown_language_unhighlighted() {
+    let text = render_markdown_text("```text\nfn main() {}\n```\n");
     let expected = Text::from_iter([Line::from_iter(["", "fn main() {}"])]);
     assert_eq!(text, expected);
 }
@@ -16,10 +16,13 @@
 //! mutates in place or when its transcript output is time-dependent.
 
 use std::io::Result;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::app_event_sender::AppEventSender;
 use crate::chatwidget::ActiveCellTranscriptKey;
+use crate::diff_viewer::DiffViewer;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
@@ -49,6 +52,7 @@ use ratatui::widgets::Wrap;
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    Diff(DiffViewer),
 }
 
 impl Overlay {
@@ -67,10 +71,19 @@ impl Overlay {
         Self::Static(StaticOverlay::with_renderables(renderables, title))
     }
 
+    pub(crate) fn new_diff_viewer(
+        unified_diff: &str,
+        cwd: &Path,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        Self::Diff(DiffViewer::new(unified_diff, cwd, app_event_tx))
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::Diff(o) => o.handle_event(tui, event),
         }
     }
 
@@ -78,6 +91,7 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::Diff(o) => o.is_done(),
         }
     }
 }
@@ -110,7 +124,7 @@ const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
 ];
 
 // Render a single line of key hints from (key(s), description) pairs.
-fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
+pub(crate) fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)]) {
     let mut spans: Vec<Span<'static>> = vec![" ".into()];
     let mut first = true;
     for (keys, desc) in pairs {
@@ -1,8 +1,14 @@
+use crate::color::is_light;
+use crate::terminal_palette::default_bg;
+use codex_core::config::types::SyntaxTheme;
+use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
+use std::path::Path;
 use std::sync::OnceLock;
+use std::sync::RwLock;
 use tree_sitter_highlight::Highlight;
 use tree_sitter_highlight::HighlightConfiguration;
 use tree_sitter_highlight::HighlightEvent;
@@ -109,40 +115,318 @@ fn push_segment(lines: &mut Vec<Line<'static>>, segment: &str, style: Option<Sty
 /// bash highlight query. The highlighter is streamed so multi-line content is
 /// split into `Line`s while preserving style boundaries.
 pub(crate) fn highlight_bash_to_lines(script: &str) -> Vec<Line<'static>> {
+    highlight_to_lines(highlight_config(), script, |highlight| {
+        highlight_for(highlight).style()
+    })
+    .unwrap_or_else(|| vec![script.to_string().into()])
+}
+
+fn highlight_to_lines(
+    config: &HighlightConfiguration,
+    source: &str,
+    style_for: impl Fn(Highlight) -> Style,
+) -> Option<Vec<Line<'static>>> {
     let mut highlighter = Highlighter::new();
-    let iterator =
-        match highlighter.highlight(highlight_config(), script.as_bytes(), None, |_| None) {
-            Ok(iter) => iter,
-            Err(_) => return vec![script.to_string().into()],
-        };
+    let iterator = highlighter
+        .highlight(config, source.as_bytes(), None, |_| None)
+        .ok()?;
 
     let mut lines: Vec<Line<'static>> = vec![Line::from("")];
     let mut highlight_stack: Vec<Highlight> = Vec::new();
 
     for event in iterator {
-        match event {
-            Ok(HighlightEvent::HighlightStart(highlight)) => highlight_stack.push(highlight),
-            Ok(HighlightEvent::HighlightEnd) => {
+        match event.ok()? {
+            HighlightEvent::HighlightStart(highlight) => highlight_stack.push(highlight),
+            HighlightEvent::HighlightEnd => {
                 highlight_stack.pop();
             }
-            Ok(HighlightEvent::Source { start, end }) => {
+            HighlightEvent::Source { start, end } => {
                 if start == end {
                     continue;
                 }
-                let style = highlight_stack.last().map(|h| highlight_for(*h).style());
-                push_segment(&mut lines, &script[start..end], style);
+                let style = highlight_stack.last().map(|h| style_for(*h));
+                push_segment(&mut lines, &source[start..end], style);
             }
-            Err(_) => return vec![script.to_string().into()],
         }
     }
 
-    if lines.is_empty() {
-        vec![Line::from("")]
-    } else {
-        lines
+    Some(lines)
+}
+
+/// Sources larger than this are left plain to keep rendering responsive.
+const MAX_HIGHLIGHT_BYTES: usize = 256 * 1024;
+
+static SYNTAX_THEME: RwLock<SyntaxTheme> = RwLock::new(SyntaxTheme::Auto);
+
+/// Sets the theme used by [`highlight_code_to_lines`] (`tui.syntax_theme`).
+pub(crate) fn set_syntax_theme(theme: SyntaxTheme) {
+    if let Ok(mut current) = SYNTAX_THEME.write() {
+        *current = theme;
+    }
+}
+
+/// Languages that fenced code blocks and diffs can be highlighted as.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Language {
+    Bash,
+    Go,
+    Java,
+    JavaScript,
+    Json,
+    Python,
+    Rust,
+    Tsx,
+    TypeScript,
+}
+
+impl Language {
+    const COUNT: usize = 9;
+
+    /// Language named by a Markdown fence info string, e.g. `rust` or
+    /// `py title="example"`.
+    pub(crate) fn from_fence_info(info: &str) -> Option<Self> {
+        let name = info
+            .trim()
+            .trim_start_matches(['{', '.'])
+            .split(|c: char| c.is_whitespace() || c == ',' || c == '}')
+            .next()?;
+        Self::from_name(name)
+    }
+
+    /// Language of a file, judged by its extension.
+    pub(crate) fn from_path(path: &Path) -> Option<Self> {
+        Self::from_name(path.extension()?.to_str()?)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let language = match name.to_ascii_lowercase().as_str() {
+            "bash" | "sh" | "shell" | "zsh" => Self::Bash,
+            "go" | "golang" => Self::Go,
+            "java" => Self::Java,
+            "javascript" | "js" | "jsx" | "mjs" | "cjs" => Self::JavaScript,
+            "json" | "jsonc" => Self::Json,
+            "python" | "py" | "python3" | "pyi" => Self::Python,
+            "rust" | "rs" => Self::Rust,
+            "tsx" => Self::Tsx,
+            "typescript" | "ts" | "mts" | "cts" => Self::TypeScript,
+            _ => return None,
+        };
+        Some(language)
+    }
+
+    fn highlight_config(self) -> Option<&'static HighlightConfiguration> {
+        static CONFIGS: [OnceLock<Option<HighlightConfiguration>>; Language::COUNT] =
+            [const { OnceLock::new() }; Language::COUNT];
+        CONFIGS[self as usize]
+            .get_or_init(|| {
+                let mut config = self.load_highlight_config()?;
+                config.configure(syntax_highlight_names());
+                Some(config)
+            })
+            .as_ref()
+    }
+
+    fn load_highlight_config(self) -> Option<HighlightConfiguration> {
+        // TypeScript and TSX extend the JavaScript grammar and only ship the
+        // queries for their own nodes, so the JavaScript queries come first.
+        let javascript = tree_sitter_javascript::HIGHLIGHT_QUERY;
+        let jsx = tree_sitter_javascript::JSX_HIGHLIGHT_QUERY;
+        let typescript = tree_sitter_typescript::HIGHLIGHTS_QUERY;
+        let (language, name, query) = match self {
+            Self::Bash => (
+                tree_sitter_bash::LANGUAGE.into(),
+                "bash",
+                tree_sitter_bash::HIGHLIGHT_QUERY.to_string(),
+            ),
+            Self::Go => (
+                tree_sitter_go::LANGUAGE.into(),
+                "go",
+                tree_sitter_go::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Java => (
+                tree_sitter_java::LANGUAGE.into(),
+                "java",
+                tree_sitter_java::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::JavaScript => (
+                tree_sitter_javascript::LANGUAGE.into(),
+                "javascript",
+                format!("{jsx}\n{javascript}"),
+            ),
+            Self::Json => (
+                tree_sitter_json::LANGUAGE.into(),
+                "json",
+                tree_sitter_json::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Python => (
+                tree_sitter_python::LANGUAGE.into(),
+                "python",
+                tree_sitter_python::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Rust => (
+                tree_sitter_rust::LANGUAGE.into(),
+                "rust",
+                tree_sitter_rust::HIGHLIGHTS_QUERY.to_string(),
+            ),
+            Self::Tsx => (
+                tree_sitter_typescript::LANGUAGE_TSX.into(),
+                "tsx",
+                format!("{jsx}\n{javascript}\n{typescript}"),
+            ),
+            Self::TypeScript => (
+                tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+                "typescript",
+                format!("{javascript}\n{typescript}"),
+            ),
+        };
+        HighlightConfiguration::new(language, name, &query, "", "").ok()
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Palette {
+    Dark,
+    Light,
+}
+
+fn current_palette() -> Option<Palette> {
+    let theme = SYNTAX_THEME.read().map(|theme| *theme).unwrap_or_default();
+    match theme {
+        SyntaxTheme::Auto => match default_bg() {
+            Some(bg) if is_light(bg) => Some(Palette::Light),
+            _ => Some(Palette::Dark),
+        },
+        SyntaxTheme::Dark => Some(Palette::Dark),
+        SyntaxTheme::Light => Some(Palette::Light),
+        SyntaxTheme::None => None,
+    }
+}
+
+// Capture names shared by the grammars' highlight queries. More specific
+// captures (`function.method`, `string.special`, ...) fall back to these.
+#[derive(Copy, Clone)]
+enum SyntaxHighlight {
+    Attribute,
+    Comment,
+    Constant,
+    Constructor,
+    Escape,
+    Function,
+    Keyword,
+    Label,
+    Number,
+    String,
+    Tag,
+    Type,
+    VariableBuiltin,
+}
+
+impl SyntaxHighlight {
+    const ALL: [Self; 13] = [
+        Self::Attribute,
+        Self::Comment,
+        Self::Constant,
+        Self::Constructor,
+        Self::Escape,
+        Self::Function,
+        Self::Keyword,
+        Self::Label,
+        Self::Number,
+        Self::String,
+        Self::Tag,
+        Self::Type,
+        Self::VariableBuiltin,
+    ];
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Attribute => "attribute",
+            Self::Comment => "comment",
+            Self::Constant => "constant",
+            Self::Constructor => "constructor",
+            Self::Escape => "escape",
+            Self::Function => "function",
+            Self::Keyword => "keyword",
+            Self::Label => "label",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Tag => "tag",
+            Self::Type => "type",
+            Self::VariableBuiltin => "variable.builtin",
+        }
+    }
+
+    // Only ANSI colors, so the terminal's own palette decides the exact shade.
+    fn style(self, palette: Palette) -> Style {
+        let dark = palette == Palette::Dark;
+        let fg = match self {
+            Self::Comment => return Style::default().dim().italic(),
+            Self::Keyword => Color::Magenta,
+            Self::VariableBuiltin => {
+                if dark {
+                    Color::LightMagenta
+                } else {
+                    Color::Magenta
+                }
+            }
+            Self::String => Color::Green,
+            Self::Escape | Self::Attribute | Self::Label | Self::Tag => Color::Cyan,
+            Self::Function => {
+                if dark {
+                    Color::LightBlue
+                } else {
+                    Color::Blue
+                }
+            }
+            Self::Type | Self::Constructor => {
+                if dark {
+                    Color::LightCyan
+                } else {
+                    Color::Cyan
+                }
+            }
+            Self::Constant | Self::Number => {
+                if dark {
+                    Color::LightYellow
+                } else {
+                    Color::Red
+                }
+            }
+        };
+        Style::default().fg(fg)
+    }
+}
+
+fn syntax_highlight_names() -> &'static [&'static str] {
+    static NAMES: OnceLock<[&'static str; SyntaxHighlight::ALL.len()]> = OnceLock::new();
+    NAMES
+        .get_or_init(|| SyntaxHighlight::ALL.map(SyntaxHighlight::as_str))
+        .as_slice()
+}
+
+/// Syntax-highlight `code` with the configured `tui.syntax_theme`, one `Line`
+/// per source line. Returns `None` when highlighting is disabled or fails so
+/// callers can keep their plain rendering.
+pub(crate) fn highlight_code_to_lines(
+    code: &str,
+    language: Language,
+) -> Option<Vec<Line<'static>>> {
+    highlight_code_with_palette(code, language, current_palette()?)
+}
+
+fn highlight_code_with_palette(
+    code: &str,
+    language: Language,
+    palette: Palette,
+) -> Option<Vec<Line<'static>>> {
+    if code.len() > MAX_HIGHLIGHT_BYTES {
+        return None;
+    }
+    highlight_to_lines(language.highlight_config()?, code, |highlight| {
+        SyntaxHighlight::ALL[highlight.0].style(palette)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,4 +517,95 @@ mod tests {
         let body_style = body_style.expect("missing heredoc span");
         assert!(body_style.add_modifier.contains(Modifier::DIM));
     }
+
+    fn styled_tokens(lines: &[Line<'static>]) -> Vec<(String, Style)> {
+        lines
+            .iter()
+            .flat_map(|l| l.spans.iter())
+            .map(|sp| (sp.content.trim().to_string(), sp.style))
+            .filter(|(token, _)| !token.is_empty())
+            .collect()
+    }
+
+    fn style_of(lines: &[Line<'static>], token: &str) -> Style {
+        styled_tokens(lines)
+            .into_iter()
+            .find(|(t, _)| t == token)
+            .map(|(_, style)| style)
+            .unwrap_or_else(|| panic!("missing token `{token}`"))
+    }
+
+    #[test]
+    fn detects_languages_from_fences_and_paths() {
+        assert_eq!(Language::from_fence_info("rust"), Some(Language::Rust));
+        assert_eq!(
+            Language::from_fence_info("Python title=\"x\""),
+            Some(Language::Python)
+        );
+        assert_eq!(
+            Language::from_fence_info("rust,no_run"),
+            Some(Language::Rust)
+        );
+        assert_eq!(
+            Language::from_fence_info("{.ts}"),
+            Some(Language::TypeScript)
+        );
+        assert_eq!(Language::from_fence_info("md"), None);
+        assert_eq!(Language::from_fence_info(""), None);
+        assert_eq!(
+            Language::from_path(Path::new("src/lib.rs")),
+            Some(Language::Rust)
+        );
+        assert_eq!(
+            Language::from_path(Path::new("app/View.TSX")),
+            Some(Language::Tsx)
+        );
+        assert_eq!(Language::from_path(Path::new("Makefile")), None);
+    }
+
+    #[test]
+    fn every_language_highlights() {
+        let samples = [
+            (Language::Bash, "echo \"hi\""),
+            (Language::Go, "func main() { return }"),
+            (Language::Java, "class A { void f() { return; } }"),
+            (Language::JavaScript, "const a = \"hi\";"),
+            (Language::Json, "{\"a\": \"hi\"}"),
+            (Language::Python, "def f():\n    return \"hi\""),
+            (Language::Rust, "fn main() { let s = \"hi\"; }"),
+            (Language::Tsx, "const a: string = \"hi\";"),
+            (Language::TypeScript, "const a: string = \"hi\";"),
+        ];
+        for (language, code) in samples {
+            let lines = highlight_code_with_palette(code, language, Palette::Dark)
+                .unwrap_or_else(|| panic!("{language:?} failed to highlight"));
+            assert_eq!(reconstructed(&lines), code, "{language:?}");
+            assert!(
+                styled_tokens(&lines)
+                    .iter()
+                    .any(|(_, style)| *style != Style::default()),
+                "{language:?} produced no highlights"
+            );
+        }
+    }
+
+    #[test]
+    fn highlights_rust_tokens_per_palette() {
+        let code = "// note\nfn main() {\n    let s = \"hi\";\n}";
+        let dark = highlight_code_with_palette(code, Language::Rust, Palette::Dark)
+            .expect("rust highlights");
+        assert_eq!(dark.len(), 4);
+        assert_eq!(style_of(&dark, "fn").fg, Some(Color::Magenta));
+        assert_eq!(style_of(&dark, "main").fg, Some(Color::LightBlue));
+        assert_eq!(style_of(&dark, "\"hi\"").fg, Some(Color::Green));
+        assert!(
+            style_of(&dark, "// note")
+                .add_modifier
+                .contains(Modifier::DIM)
+        );
+
+        let light = highlight_code_with_palette(code, Language::Rust, Palette::Light)
+            .expect("rust highlights");
+        assert_eq!(style_of(&light, "main").fg, Some(Color::Blue));
+    }
 }
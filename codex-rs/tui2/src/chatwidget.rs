@@ -1843,6 +1843,7 @@ impl ChatWidget {
                             instructions: trimmed.to_string(),
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 });
             }
@@ -2070,12 +2071,14 @@ impl ChatWidget {
             EventMsg::ExecCommandEnd(ev) => self.on_exec_command_end(ev),
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
+            EventMsg::McpToolCallProgress(_) => {}
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
+            EventMsg::ListMcpPromptsResponse(_) | EventMsg::McpToolsChanged(_) => {}
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
             EventMsg::SkillsUpdateAvailable => {
                 self.submit_op(Op::ListSkills {
@@ -3614,6 +3617,7 @@ impl ChatWidget {
                     review_request: ReviewRequest {
                         target: ReviewTarget::UncommittedChanges,
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             })],
@@ -3669,6 +3673,7 @@ impl ChatWidget {
                                 branch: branch.clone(),
                             },
                             user_facing_hint: None,
+                            paths: Vec::new(),
                         },
                     }));
                 })],
@@ -3707,6 +3712,7 @@ impl ChatWidget {
                                 title: Some(subject.clone()),
                             },
                             user_facing_hint: None,
+                            paths: Vec::new(),
                         },
                     }));
                 })],
@@ -3743,6 +3749,7 @@ impl ChatWidget {
                             instructions: trimmed,
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             }),
@@ -3989,6 +3996,7 @@ pub(crate) fn show_review_commit_picker_with_entries(
                             title: Some(subject.clone()),
                         },
                         user_facing_hint: None,
+                        paths: Vec::new(),
                     },
                 }));
             })],
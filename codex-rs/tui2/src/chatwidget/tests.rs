@@ -178,6 +178,7 @@ async fn entered_review_mode_uses_request_hint() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            paths: Vec::new(),
         }),
     });
 
@@ -197,6 +198,7 @@ async fn entered_review_mode_defaults_to_current_changes_banner() {
         msg: EventMsg::EnteredReviewMode(ReviewRequest {
             target: ReviewTarget::UncommittedChanges,
             user_facing_hint: None,
+            paths: Vec::new(),
         }),
     });
 
@@ -231,6 +233,7 @@ async fn review_restores_context_window_indicator() {
                 branch: "feature".to_string(),
             },
             user_facing_hint: Some("feature branch".to_string()),
+            paths: Vec::new(),
         }),
     });
 
@@ -1531,6 +1534,7 @@ async fn custom_prompt_submit_sends_review_op() {
                         instructions: "please audit dependencies".to_string(),
                     },
                     user_facing_hint: None,
+                    paths: Vec::new(),
                 }
             );
         }
@@ -657,7 +657,7 @@ pub fn new_approval_decision_cell(
     use codex_core::protocol::ReviewDecision::*;
 
     let (symbol, summary): (Span<'static>, Vec<Span<'static>>) = match decision {
-        Approved => {
+        Approved | ApprovedPartially { .. } => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
                 "✔ ".green(),
@@ -47,6 +47,61 @@ pub fn merge_base_with_head(
     Ok(Some(merge_base))
 }
 
+/// Returns the branch a pull request from the current branch would target, for
+/// use as the base of a review.
+///
+/// This is the upstream tracking branch of `HEAD` (e.g. the parent branch in a
+/// stack of branches). When the upstream is just the remote copy of the
+/// current branch, or no upstream is configured, the remote's default branch
+/// (`origin/HEAD`) is used instead. Returns `Ok(None)` when neither exists.
+pub fn upstream_base_branch(repo_path: &Path) -> Result<Option<String>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+
+    let current = git_stdout_if_ok(
+        repo_root.as_path(),
+        &["symbolic-ref", "--quiet", "--short", "HEAD"],
+    )?;
+    let upstream = git_stdout_if_ok(
+        repo_root.as_path(),
+        &[
+            "rev-parse",
+            "--abbrev-ref",
+            "--symbolic-full-name",
+            "@{upstream}",
+        ],
+    )?;
+    if let Some(upstream) = upstream {
+        let tracks_current = current.as_deref().is_some_and(|current| {
+            upstream == current || upstream.ends_with(&format!("/{current}"))
+        });
+        if !tracks_current {
+            return Ok(Some(upstream));
+        }
+    }
+
+    git_stdout_if_ok(
+        repo_root.as_path(),
+        &[
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            "refs/remotes/origin/HEAD",
+        ],
+    )
+}
+
+/// Runs git and returns its trimmed stdout, or `None` when the command fails
+/// or prints nothing.
+fn git_stdout_if_ok(repo_root: &Path, args: &[&str]) -> Result<Option<String>, GitToolingError> {
+    match run_git_for_stdout(repo_root, args, None) {
+        Ok(value) if value.is_empty() => Ok(None),
+        Ok(value) => Ok(Some(value)),
+        Err(GitToolingError::GitCommand { .. }) => Ok(None),
+        Err(other) => Err(other),
+    }
+}
+
 fn resolve_branch_ref(repo_root: &Path, branch: &str) -> Result<Option<String>, GitToolingError> {
     let rev = run_git_for_stdout(
         repo_root,
@@ -119,6 +174,7 @@ fn resolve_upstream_if_remote_ahead(
 #[cfg(test)]
 mod tests {
     use super::merge_base_with_head;
+    use super::upstream_base_branch;
     use crate::GitToolingError;
     use pretty_assertions::assert_eq;
     use std::path::Path;
@@ -238,6 +294,41 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn upstream_base_prefers_parent_branch_then_remote_default() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path().join("repo");
+        let remote = temp.path().join("remote.git");
+        std::fs::create_dir_all(&repo)?;
+        std::fs::create_dir_all(&remote)?;
+
+        run_git_in(&remote, &["init", "--bare", "--initial-branch=main"]);
+        init_test_repo(&repo);
+        std::fs::write(repo.join("base.txt"), "base\n")?;
+        run_git_in(&repo, &["add", "base.txt"]);
+        commit(&repo, "base commit");
+        run_git_in(
+            &repo,
+            &["remote", "add", "origin", remote.to_str().unwrap()],
+        );
+        run_git_in(&repo, &["push", "-u", "origin", "main"]);
+        run_git_in(&repo, &["remote", "set-head", "origin", "main"]);
+
+        // A branch stacked on a local parent targets that parent.
+        run_git_in(&repo, &["checkout", "-b", "stacked", "--track", "main"]);
+        assert_eq!(upstream_base_branch(&repo)?, Some("main".to_string()));
+
+        // A branch that only tracks its own remote copy targets the default branch.
+        run_git_in(&repo, &["checkout", "-b", "feature"]);
+        run_git_in(&repo, &["push", "-u", "origin", "feature"]);
+        assert_eq!(
+            upstream_base_branch(&repo)?,
+            Some("origin/main".to_string())
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn merge_base_returns_none_when_branch_missing() -> Result<(), GitToolingError> {
         let temp = tempdir()?;
@@ -15,6 +15,7 @@ pub use apply::extract_paths_from_patch;
 pub use apply::parse_git_apply_output;
 pub use apply::stage_paths;
 pub use branch::merge_base_with_head;
+pub use branch::upstream_base_branch;
 pub use errors::GitToolingError;
 pub use ghost_commits::CreateGhostCommitOptions;
 pub use ghost_commits::GhostSnapshotConfig;
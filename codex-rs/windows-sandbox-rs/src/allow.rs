@@ -35,10 +35,16 @@ pub fn compute_allow_paths(
         SandboxPolicy::WorkspaceWrite {
             exclude_tmpdir_env_var: false,
             ..
+        } | SandboxPolicy::RestrictedRead {
+            exclude_tmpdir_env_var: false,
+            ..
         }
     );
 
-    if matches!(policy, SandboxPolicy::WorkspaceWrite { .. }) {
+    if matches!(
+        policy,
+        SandboxPolicy::WorkspaceWrite { .. } | SandboxPolicy::RestrictedRead { .. }
+    ) {
         let add_writable_root =
             |root: PathBuf,
              policy_cwd: &Path,
@@ -65,7 +71,9 @@ pub fn compute_allow_paths(
             &mut add_deny_path,
         );
 
-        if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = policy {
+        if let SandboxPolicy::WorkspaceWrite { writable_roots, .. }
+        | SandboxPolicy::RestrictedRead { writable_roots, .. } = policy
+        {
             for root in writable_roots {
                 add_writable_root(
                     root.clone().into(),
@@ -238,6 +238,9 @@ mod windows_impl {
         ) {
             anyhow::bail!("DangerFullAccess and ExternalSandbox are not supported for sandboxing")
         }
+        if matches!(&policy, SandboxPolicy::RestrictedRead { .. }) {
+            anyhow::bail!("RestrictedRead is not supported by the Windows sandbox")
+        }
         let caps = load_or_create_cap_sids(codex_home)?;
         let (psid_to_use, cap_sid_str) = match &policy {
             SandboxPolicy::ReadOnly => (
@@ -248,8 +251,10 @@ mod windows_impl {
                 unsafe { convert_string_sid_to_sid(&caps.workspace).unwrap() },
                 caps.workspace.clone(),
             ),
-            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
-                unreachable!("DangerFullAccess handled above")
+            SandboxPolicy::DangerFullAccess
+            | SandboxPolicy::ExternalSandbox { .. }
+            | SandboxPolicy::RestrictedRead { .. } => {
+                unreachable!("unsupported policies handled above")
             }
         };
 
@@ -209,6 +209,9 @@ mod windows_impl {
         ) {
             anyhow::bail!("DangerFullAccess and ExternalSandbox are not supported for sandboxing")
         }
+        if matches!(&policy, SandboxPolicy::RestrictedRead { .. }) {
+            anyhow::bail!("RestrictedRead is not supported by the Windows sandbox")
+        }
         let caps = load_or_create_cap_sids(codex_home)?;
         let (h_token, psid_to_use): (HANDLE, *mut c_void) = unsafe {
             match &policy {
@@ -220,8 +223,10 @@ mod windows_impl {
                     let psid = convert_string_sid_to_sid(&caps.workspace).unwrap();
                     super::token::create_workspace_write_token_with_cap(psid)?
                 }
-                SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
-                    unreachable!("DangerFullAccess handled above")
+                SandboxPolicy::DangerFullAccess
+                | SandboxPolicy::ExternalSandbox { .. }
+                | SandboxPolicy::RestrictedRead { .. } => {
+                    unreachable!("unsupported policies handled above")
                 }
             }
         };
@@ -18,6 +18,9 @@ pub fn parse_policy(value: &str) -> Result<SandboxPolicy> {
                     "DangerFullAccess and ExternalSandbox are not supported for sandboxing"
                 );
             }
+            if matches!(parsed, SandboxPolicy::RestrictedRead { .. }) {
+                anyhow::bail!("RestrictedRead is not supported by the Windows sandbox");
+            }
             Ok(parsed)
         }
     }
@@ -50,6 +53,22 @@ mod tests {
             .contains("DangerFullAccess and ExternalSandbox are not supported"));
     }
 
+    #[test]
+    fn rejects_restricted_read_json() {
+        let payload = serde_json::to_string(&SandboxPolicy::RestrictedRead {
+            readable_roots: Vec::new(),
+            writable_roots: Vec::new(),
+            network_access: false,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        })
+        .unwrap();
+        let err = parse_policy(&payload).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("RestrictedRead is not supported by the Windows sandbox"));
+    }
+
     #[test]
     fn parses_read_only_policy() {
         assert_eq!(parse_policy("read-only").unwrap(), SandboxPolicy::ReadOnly);
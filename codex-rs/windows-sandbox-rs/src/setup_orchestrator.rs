@@ -211,7 +211,9 @@ pub(crate) fn gather_read_roots(command_cwd: &Path, policy: &SandboxPolicy) -> V
         roots.push(PathBuf::from(up));
     }
     roots.push(command_cwd.to_path_buf());
-    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = policy {
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. }
+    | SandboxPolicy::RestrictedRead { writable_roots, .. } = policy
+    {
         for root in writable_roots {
             roots.push(root.to_path_buf());
         }
@@ -227,7 +229,10 @@ pub(crate) fn gather_write_roots(
 ) -> Vec<PathBuf> {
     let mut roots: Vec<PathBuf> = Vec::new();
     // Always include the command CWD for workspace-write.
-    if matches!(policy, SandboxPolicy::WorkspaceWrite { .. }) {
+    if matches!(
+        policy,
+        SandboxPolicy::WorkspaceWrite { .. } | SandboxPolicy::RestrictedRead { .. }
+    ) {
         roots.push(command_cwd.to_path_buf());
     }
     let AllowDenyPaths { allow, .. } =
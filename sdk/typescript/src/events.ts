@@ -57,15 +57,21 @@ export type ItemCompletedEvent = {
   item: ThreadItem;
 };
 
+/** Machine-readable category of a {@link ThreadError}. */
+export type ThreadErrorKind = "budget_exceeded";
+
 /** Fatal error emitted by the stream. */
 export type ThreadError = {
   message: string;
+  /** Set for errors automation may want to handle specially, such as an exceeded token or cost budget. */
+  kind?: ThreadErrorKind;
 };
 
 /** Represents an unrecoverable error emitted directly by the event stream. */
 export type ThreadErrorEvent = {
   type: "error";
   message: string;
+  kind?: ThreadErrorKind;
 };
 
 /** Top-level JSONL events emitted by codex exec. */
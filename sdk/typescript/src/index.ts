@@ -9,6 +9,7 @@ export type {
   ItemCompletedEvent,
   ThreadError,
   ThreadErrorEvent,
+  ThreadErrorKind,
   Usage,
 } from "./events";
 export type {